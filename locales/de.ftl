@@ -0,0 +1,71 @@
+# German messages for Hercules.
+# Copy this file to <config dir>/locales/<language>.ftl to add or adjust a translation.
+
+# Panel headings
+heading-cpu = CPU-AUSLASTUNG
+heading-memory = SPEICHERNUTZUNG
+heading-disk = FESTPLATTENNUTZUNG
+heading-network = NETZWERKNUTZUNG
+heading-processes = TOP-PROZESSE
+heading-disk-io = DATENTRÄGER-E/A
+heading-journal = JOURNAL
+heading-traffic = DATENVERKEHR
+heading-file-descriptors = DATEIDESKRIPTOREN
+heading-latency = LATENZ
+heading-child-processes = KINDPROZESSE
+heading-environment = UMGEBUNG
+heading-alerts = WARNUNGEN
+heading-watchdog = WATCHDOG
+heading-uptime = BETRIEBSZEIT
+heading-numa = NUMA-KNOTEN
+heading-connectivity = KONNEKTIVITÄT
+heading-run-summary = LAUFZUSAMMENFASSUNG
+heading-firewall = FIREWALL
+heading-kernel-log = KERNEL-LOG
+heading-connections = NETZWERKVERBINDUNGEN
+heading-listening-ports = OFFENE PORTS
+heading-thermal-summary = TEMPERATURÜBERSICHT
+heading-vpn = VPN-TUNNEL
+heading-growth = PFADWACHSTUM
+heading-tls = TLS-ZERTIFIKATE
+heading-containers = CONTAINER
+heading-lan-devices = GERÄTE IM LAN
+heading-port-audit = PORT-SELBSTPRÜFUNG
+heading-speed-test = GESCHWINDIGKEITSTEST
+heading-cpu-benchmark = CPU-BENCHMARK
+heading-disk-benchmark = FESTPLATTEN-BENCHMARK
+heading-camera = KAMERA
+heading-usb = USB-GERÄTE
+heading-peripherals = BLUETOOTH-GERÄTE
+heading-audio = AUDIO
+heading-displays = BILDSCHIRME
+heading-boost = TURBO-BOOST
+heading-fan = LÜFTER
+
+# CPU panel
+cpu-global-usage = CPU-Auslastung gesamt
+cpu-stolen = +{ $percent }% vom Hypervisor entzogen
+cpu-throttled = GEDROSSELT
+cpu-under-voltage = Unterspannungsereignisse seit dem Start
+cpu-processor = Prozessor
+cpu-average = im Mittel
+cpu-environment = Umgebung
+cpu-steal-time = Steal-Zeit
+cpu-load-average = Durchschnittslast
+cpu-tasks = Tasks
+cpu-tasks-running = laufend
+cpu-tasks-blocked = blockiert
+cpu-context-switches = Kontextwechsel
+cpu-core = Kern
+
+# Memory panel
+memory-memory = Arbeitsspeicher
+memory-swap = Auslagerung
+memory-used = belegt
+
+# Shared labels
+usage-history = Verlauf
+
+# Alerts panel
+alerts-none = Keine aktiven Warnungen
+alerts-resolved = Kürzlich behoben
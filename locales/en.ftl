@@ -0,0 +1,72 @@
+# English messages for Hercules: panel headings and the CPU, memory and alerts
+# labels. Other output is not translated.
+# Copy this file to <config dir>/locales/<language>.ftl to add or adjust a translation.
+
+# Panel headings
+heading-cpu = CPU USAGE
+heading-memory = MEMORY USAGE
+heading-disk = DISK USAGE
+heading-network = NETWORK USAGE
+heading-processes = TOP PROCESSES
+heading-disk-io = DISK I/O
+heading-journal = JOURNAL
+heading-traffic = TRAFFIC BREAKDOWN
+heading-file-descriptors = FILE DESCRIPTORS
+heading-latency = LATENCY
+heading-child-processes = CHILD PROCESSES
+heading-environment = ENVIRONMENT
+heading-alerts = ALERTS
+heading-watchdog = WATCHDOG
+heading-uptime = UPTIME
+heading-numa = NUMA NODES
+heading-connectivity = CONNECTIVITY
+heading-run-summary = RUN SUMMARY
+heading-firewall = FIREWALL
+heading-kernel-log = KERNEL LOG
+heading-connections = NETWORK CONNECTIONS
+heading-listening-ports = LISTENING PORTS
+heading-thermal-summary = THERMAL SUMMARY
+heading-vpn = VPN TUNNELS
+heading-growth = PATH GROWTH
+heading-tls = TLS CERTIFICATES
+heading-containers = CONTAINERS
+heading-lan-devices = LAN DEVICES
+heading-port-audit = PORT SELF-AUDIT
+heading-speed-test = SPEED TEST
+heading-cpu-benchmark = CPU BENCHMARK
+heading-disk-benchmark = DISK BENCHMARK
+heading-camera = CAMERA
+heading-usb = USB DEVICES
+heading-peripherals = BLUETOOTH PERIPHERALS
+heading-audio = AUDIO
+heading-displays = DISPLAYS
+heading-boost = TURBO BOOST
+heading-fan = FAN
+
+# CPU panel
+cpu-global-usage = Global CPU Usage
+cpu-stolen = +{ $percent }% stolen by hypervisor
+cpu-throttled = THROTTLED
+cpu-under-voltage = Under-voltage events since boot
+cpu-processor = Processor
+cpu-average = average
+cpu-environment = Environment
+cpu-steal-time = Steal Time
+cpu-load-average = Load Average
+cpu-tasks = Tasks
+cpu-tasks-running = running
+cpu-tasks-blocked = blocked
+cpu-context-switches = Context Switches
+cpu-core = Core
+
+# Memory panel
+memory-memory = Memory
+memory-swap = Swap
+memory-used = used
+
+# Shared labels
+usage-history = Usage History
+
+# Alerts panel
+alerts-none = No active alerts
+alerts-resolved = Recently resolved
@@ -0,0 +1,71 @@
+# Spanish messages for Hercules.
+# Copy this file to <config dir>/locales/<language>.ftl to add or adjust a translation.
+
+# Panel headings
+heading-cpu = USO DE CPU
+heading-memory = USO DE MEMORIA
+heading-disk = USO DE DISCO
+heading-network = USO DE RED
+heading-processes = PROCESOS PRINCIPALES
+heading-disk-io = E/S DE DISCO
+heading-journal = DIARIO
+heading-traffic = DESGLOSE DEL TRÁFICO
+heading-file-descriptors = DESCRIPTORES DE ARCHIVO
+heading-latency = LATENCIA
+heading-child-processes = PROCESOS HIJOS
+heading-environment = ENTORNO
+heading-alerts = ALERTAS
+heading-watchdog = WATCHDOG
+heading-uptime = TIEMPO ACTIVO
+heading-numa = NODOS NUMA
+heading-connectivity = CONECTIVIDAD
+heading-run-summary = RESUMEN DE LA EJECUCIÓN
+heading-firewall = CORTAFUEGOS
+heading-kernel-log = REGISTRO DEL KERNEL
+heading-connections = CONEXIONES DE RED
+heading-listening-ports = PUERTOS A LA ESCUCHA
+heading-thermal-summary = RESUMEN TÉRMICO
+heading-vpn = TÚNELES VPN
+heading-growth = CRECIMIENTO DE RUTAS
+heading-tls = CERTIFICADOS TLS
+heading-containers = CONTENEDORES
+heading-lan-devices = DISPOSITIVOS EN LA LAN
+heading-port-audit = AUTOAUDITORÍA DE PUERTOS
+heading-speed-test = PRUEBA DE VELOCIDAD
+heading-cpu-benchmark = PRUEBA DE RENDIMIENTO DE CPU
+heading-disk-benchmark = PRUEBA DE RENDIMIENTO DE DISCO
+heading-camera = CÁMARA
+heading-usb = DISPOSITIVOS USB
+heading-peripherals = PERIFÉRICOS BLUETOOTH
+heading-audio = AUDIO
+heading-displays = PANTALLAS
+heading-boost = TURBO BOOST
+heading-fan = VENTILADOR
+
+# CPU panel
+cpu-global-usage = Uso global de CPU
+cpu-stolen = +{ $percent }% robado por el hipervisor
+cpu-throttled = LIMITADA
+cpu-under-voltage = Eventos de subtensión desde el arranque
+cpu-processor = Procesador
+cpu-average = de media
+cpu-environment = Entorno
+cpu-steal-time = Tiempo robado
+cpu-load-average = Carga media
+cpu-tasks = Tareas
+cpu-tasks-running = en ejecución
+cpu-tasks-blocked = bloqueadas
+cpu-context-switches = Cambios de contexto
+cpu-core = Núcleo
+
+# Memory panel
+memory-memory = Memoria
+memory-swap = Intercambio
+memory-used = en uso
+
+# Shared labels
+usage-history = Historial de uso
+
+# Alerts panel
+alerts-none = No hay alertas activas
+alerts-resolved = Resueltas recientemente
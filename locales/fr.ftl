@@ -0,0 +1,71 @@
+# French messages for Hercules.
+# Copy this file to <config dir>/locales/<language>.ftl to add or adjust a translation.
+
+# Panel headings
+heading-cpu = UTILISATION CPU
+heading-memory = UTILISATION MÉMOIRE
+heading-disk = UTILISATION DISQUE
+heading-network = UTILISATION RÉSEAU
+heading-processes = PRINCIPAUX PROCESSUS
+heading-disk-io = E/S DISQUE
+heading-journal = JOURNAL
+heading-traffic = RÉPARTITION DU TRAFIC
+heading-file-descriptors = DESCRIPTEURS DE FICHIERS
+heading-latency = LATENCE
+heading-child-processes = PROCESSUS ENFANTS
+heading-environment = ENVIRONNEMENT
+heading-alerts = ALERTES
+heading-watchdog = WATCHDOG
+heading-uptime = DISPONIBILITÉ
+heading-numa = NŒUDS NUMA
+heading-connectivity = CONNECTIVITÉ
+heading-run-summary = RÉSUMÉ DE L'EXÉCUTION
+heading-firewall = PARE-FEU
+heading-kernel-log = JOURNAL DU NOYAU
+heading-connections = CONNEXIONS RÉSEAU
+heading-listening-ports = PORTS EN ÉCOUTE
+heading-thermal-summary = RÉSUMÉ THERMIQUE
+heading-vpn = TUNNELS VPN
+heading-growth = CROISSANCE DES CHEMINS
+heading-tls = CERTIFICATS TLS
+heading-containers = CONTENEURS
+heading-lan-devices = APPAREILS DU RÉSEAU LOCAL
+heading-port-audit = AUTO-AUDIT DES PORTS
+heading-speed-test = TEST DE DÉBIT
+heading-cpu-benchmark = BENCHMARK CPU
+heading-disk-benchmark = BENCHMARK DISQUE
+heading-camera = CAMÉRA
+heading-usb = PÉRIPHÉRIQUES USB
+heading-peripherals = PÉRIPHÉRIQUES BLUETOOTH
+heading-audio = AUDIO
+heading-displays = ÉCRANS
+heading-boost = TURBO BOOST
+heading-fan = VENTILATEUR
+
+# CPU panel
+cpu-global-usage = Utilisation CPU globale
+cpu-stolen = +{ $percent }% volés par l'hyperviseur
+cpu-throttled = BRIDÉ
+cpu-under-voltage = Sous-tensions depuis le démarrage
+cpu-processor = Processeur
+cpu-average = en moyenne
+cpu-environment = Environnement
+cpu-steal-time = Temps volé
+cpu-load-average = Charge moyenne
+cpu-tasks = Tâches
+cpu-tasks-running = en cours
+cpu-tasks-blocked = bloquées
+cpu-context-switches = Changements de contexte
+cpu-core = Cœur
+
+# Memory panel
+memory-memory = Mémoire
+memory-swap = Swap
+memory-used = utilisés
+
+# Shared labels
+usage-history = Historique
+
+# Alerts panel
+alerts-none = Aucune alerte active
+alerts-resolved = Résolues récemment
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::units::STANDARD_GRAVITY;
+
+// Readings the state is judged over
+const WINDOW: Duration = Duration::from_secs(2);
+// Spread of |a| below which the sensor is still, in m/s²
+const STILL_SPREAD: f32 = 0.2;
+// Rise above 1 g that counts as a step, and the dip back that re-arms it
+const STEP_RISE: f32 = 1.2;
+const STEP_REARM: f32 = 0.3;
+// Walking cadence is about 1-3 steps a second
+const STEP_MIN_GAP: Duration = Duration::from_millis(250);
+const WALKING_MIN_STEPS: usize = 2;
+// Swings around the mean faster than this are vibration rather than gait
+const VIBRATION_CROSSINGS_PER_SEC: f32 = 6.0;
+// A jolt of more than this over 1 g is an impact, shown for IMPACT_HOLD
+const IMPACT_THRESHOLD: f32 = 2.5 * STANDARD_GRAVITY as f32;
+const IMPACT_HOLD: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MotionState {
+    #[default]
+    Still,
+    Walking,
+    Vibrating,
+    // Moving, but neither rhythmic nor fast enough to say more
+    Moving,
+    Impact,
+}
+
+impl MotionState {
+    pub fn name(self) -> &'static str {
+        match self {
+            MotionState::Still => "still",
+            MotionState::Walking => "walking",
+            MotionState::Vibrating => "vibrating",
+            MotionState::Moving => "moving",
+            MotionState::Impact => "impact",
+        }
+    }
+}
+
+// What the classifier concluded, carried on every SensorData
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Activity {
+    pub state: MotionState,
+    // Counted since the sensor started
+    pub steps: u64,
+    pub impacts: u64,
+}
+
+// Labels the motion from the acceleration magnitude: its spread over the
+// last two seconds, peaks that look like steps, how often it swings across
+// its mean, and single jolts well over 1 g
+pub struct Classifier {
+    // (time, |a| - 1 g)
+    samples: VecDeque<(Instant, f32)>,
+    step_times: VecDeque<Instant>,
+    step_armed: bool,
+    impact_at: Option<Instant>,
+    activity: Activity,
+}
+
+impl Classifier {
+    pub fn new() -> Self {
+        Classifier {
+            samples: VecDeque::new(),
+            step_times: VecDeque::new(),
+            step_armed: true,
+            impact_at: None,
+            activity: Activity::default(),
+        }
+    }
+
+    // `acceleration` should be the unfiltered reading, since smoothing
+    // flattens the peaks steps and impacts are recognised by
+    pub fn update(&mut self, timestamp: Instant, acceleration: [f32; 3]) -> Activity {
+        let magnitude = acceleration.iter().map(|a| a * a).sum::<f32>().sqrt();
+        let excess = magnitude - STANDARD_GRAVITY as f32;
+
+        self.samples.push_back((timestamp, excess));
+        while let Some(&(time, _)) = self.samples.front() {
+            if timestamp.saturating_duration_since(time) <= WINDOW {
+                break;
+            }
+            self.samples.pop_front();
+        }
+        while let Some(&time) = self.step_times.front() {
+            if timestamp.saturating_duration_since(time) <= WINDOW {
+                break;
+            }
+            self.step_times.pop_front();
+        }
+
+        if excess > IMPACT_THRESHOLD {
+            // One jolt rings for a few readings; count it once
+            let new = self
+                .impact_at
+                .is_none_or(|at| timestamp.saturating_duration_since(at) > IMPACT_HOLD);
+            if new {
+                self.activity.impacts += 1;
+            }
+            self.impact_at = Some(timestamp);
+        } else if self.step_armed && excess > STEP_RISE {
+            let spaced = self
+                .step_times
+                .back()
+                .is_none_or(|&last| timestamp.saturating_duration_since(last) >= STEP_MIN_GAP);
+            if spaced {
+                self.step_times.push_back(timestamp);
+                self.activity.steps += 1;
+            }
+            self.step_armed = false;
+        } else if excess < STEP_REARM {
+            self.step_armed = true;
+        }
+
+        self.activity.state = self.classify(timestamp);
+        self.activity
+    }
+
+    fn classify(&self, now: Instant) -> MotionState {
+        if self
+            .impact_at
+            .is_some_and(|at| now.saturating_duration_since(at) <= IMPACT_HOLD)
+        {
+            return MotionState::Impact;
+        }
+        let count = self.samples.len() as f32;
+        let mean = self.samples.iter().map(|(_, v)| v).sum::<f32>() / count;
+        let spread = (self
+            .samples
+            .iter()
+            .map(|(_, v)| (v - mean).powi(2))
+            .sum::<f32>()
+            / count)
+            .sqrt();
+        if spread < STILL_SPREAD {
+            return MotionState::Still;
+        }
+        let span = match (self.samples.front(), self.samples.back()) {
+            (Some(&(first, _)), Some(&(last, _))) => {
+                last.saturating_duration_since(first).as_secs_f32()
+            }
+            _ => 0.0,
+        };
+        let crossings = self
+            .samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .filter(|((_, a), (_, b))| (a - mean).signum() != (b - mean).signum())
+            .count() as f32;
+        if span > 0.0 && crossings / 2.0 / span > VIBRATION_CROSSINGS_PER_SEC {
+            MotionState::Vibrating
+        } else if self.step_times.len() >= WALKING_MIN_STEPS {
+            MotionState::Walking
+        } else {
+            MotionState::Moving
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use colored::*;
+use log::{error, warn};
+
+use crate::clock;
+use crate::i18n;
+use crate::theme::Themed;
+
+// How many resolved/raised alerts the panel remembers
+const RECENT_ALERTS_LEN: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertLevel {
+    Warning,
+    Critical,
+}
+
+impl AlertLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertLevel::Warning => "WARN",
+            AlertLevel::Critical => "CRIT",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub level: AlertLevel,
+    pub source: String,
+    pub message: String,
+    pub raised_at: DateTime<Local>,
+}
+
+// Collects alert conditions from the monitors. Each condition is identified by a
+// key so a persistent problem raises once and stays active until cleared.
+pub struct AlertEngine {
+    active: HashMap<String, Alert>,
+    recent: VecDeque<(String, Alert)>,
+}
+
+impl AlertEngine {
+    pub fn new() -> Self {
+        AlertEngine {
+            active: HashMap::new(),
+            recent: VecDeque::with_capacity(RECENT_ALERTS_LEN),
+        }
+    }
+
+    // Raise an alert; re-raising an active key only escalates its level
+    pub fn raise(&mut self, key: &str, level: AlertLevel, source: &str, message: String) {
+        if let Some(existing) = self.active.get_mut(key) {
+            if level > existing.level {
+                existing.level = level;
+                existing.message = message;
+            }
+            return;
+        }
+
+        match level {
+            AlertLevel::Warning => warn!("[{}] {}", source, message),
+            AlertLevel::Critical => error!("[{}] {}", source, message),
+        }
+
+        let alert = Alert {
+            level,
+            source: source.to_string(),
+            message,
+            raised_at: Local::now(),
+        };
+
+        if self.recent.len() == RECENT_ALERTS_LEN {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((key.to_string(), alert.clone()));
+        self.active.insert(key.to_string(), alert);
+    }
+
+    // Mark a condition as resolved
+    pub fn clear(&mut self, key: &str) {
+        self.active.remove(key);
+    }
+
+    // Active alerts, most severe first
+    pub fn active(&self) -> Vec<&Alert> {
+        let mut alerts: Vec<_> = self.active.values().collect();
+        alerts.sort_by(|a, b| b.level.cmp(&a.level).then(a.raised_at.cmp(&b.raised_at)));
+        alerts
+    }
+
+    // Active alerts with the key they were raised under, for recordings
+    pub fn active_by_key(&self) -> impl Iterator<Item = (&String, &Alert)> {
+        self.active.iter()
+    }
+
+    // Make the active alerts match a recorded set. Unlike raise this does not
+    // log, the alerts were logged when they happened.
+    pub fn restore(&mut self, alerts: Vec<(String, Alert)>) {
+        self.active
+            .retain(|key, _| alerts.iter().any(|(restored, _)| restored == key));
+        for (key, alert) in alerts {
+            if let Some(existing) = self.active.get_mut(&key) {
+                existing.level = alert.level;
+                existing.message = alert.message;
+                continue;
+            }
+            if self.recent.len() == RECENT_ALERTS_LEN {
+                self.recent.pop_front();
+            }
+            self.recent.push_back((key.clone(), alert.clone()));
+            self.active.insert(key, alert);
+        }
+    }
+
+    // Level of the most severe active alert, None when all is well
+    pub fn highest_level(&self) -> Option<AlertLevel> {
+        self.active.values().map(|alert| alert.level).max()
+    }
+
+    // Recently raised alerts whose condition has since cleared, newest first
+    pub fn resolved(&self) -> impl Iterator<Item = &Alert> {
+        self.recent
+            .iter()
+            .rev()
+            .filter(|(key, alert)| {
+                !matches!(self.active.get(key), Some(active) if active.raised_at == alert.raised_at)
+            })
+            .map(|(_, alert)| alert)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recent.is_empty()
+    }
+}
+
+fn format_alert(alert: &Alert) -> String {
+    let level = match alert.level {
+        AlertLevel::Warning => alert.level.as_str().warning(),
+        AlertLevel::Critical => alert.level.as_str().critical().bold(),
+    };
+    format!(
+        "{} {} [{}] {}",
+        clock::time(&alert.raised_at).value(),
+        level,
+        alert.source,
+        alert.message
+    )
+}
+
+// Alerts panel: currently active conditions followed by recent history
+pub fn monitor_alerts(engine: &AlertEngine) -> Result<()> {
+    let heading = i18n::tr("heading-alerts");
+    println!("\n{}", heading.bold().critical());
+    println!("{}", i18n::underline(heading).critical());
+
+    let active = engine.active();
+    if active.is_empty() {
+        println!("  {}", i18n::tr("alerts-none").good());
+    } else {
+        for alert in active {
+            println!("  {}", format_alert(alert));
+        }
+    }
+
+    let resolved: Vec<_> = engine.resolved().take(5).collect();
+    if !resolved.is_empty() {
+        println!("  {}:", i18n::tr("alerts-resolved"));
+        for alert in resolved {
+            println!("    {}", format_alert(alert).dimmed());
+        }
+    }
+
+    Ok(())
+}
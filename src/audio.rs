@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::i18n;
+use crate::theme::{self, Themed};
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+// The sink PulseAudio and PipeWire fall back to when no output device is left
+const NULL_SINK: &str = "auto_null";
+
+// The default sink or source
+#[derive(Debug, Clone)]
+pub struct AudioDevice {
+    pub name: String,
+    pub description: String,
+    // Average over the channels, 100 is unamplified
+    pub volume: Option<u32>,
+    pub muted: bool,
+    // RUNNING, IDLE or SUSPENDED
+    pub state: String,
+}
+
+// An application playing or recording
+#[derive(Debug, Clone)]
+pub struct AudioStream {
+    pub application: String,
+    pub media: String,
+    pub playback: bool,
+    pub corked: bool,
+}
+
+// Default sink and source with their volume, and the active streams, from
+// `pactl`, which talks to PulseAudio and to PipeWire's pulse server alike
+pub struct AudioMonitor {
+    server: Option<String>,
+    sink: Option<AudioDevice>,
+    source: Option<AudioDevice>,
+    streams: Vec<AudioStream>,
+    error: Option<String>,
+    last_scan: Option<Instant>,
+}
+
+impl AudioMonitor {
+    pub fn new() -> Self {
+        AudioMonitor {
+            server: None,
+            sink: None,
+            source: None,
+            streams: Vec::new(),
+            error: None,
+            last_scan: None,
+        }
+    }
+
+    pub fn refresh(&mut self, alerts: &mut AlertEngine) {
+        if self
+            .last_scan
+            .is_some_and(|last| last.elapsed() < SCAN_INTERVAL)
+        {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+
+        if let Err(e) = self.read() {
+            alerts.raise(
+                "audio:sink",
+                AlertLevel::Warning,
+                "audio",
+                format!("Audio server unreachable: {}", e),
+            );
+            self.error = Some(e);
+            self.sink = None;
+            self.source = None;
+            self.streams.clear();
+            return;
+        }
+
+        // A kiosk that lost its output device plays into the null sink
+        match &self.sink {
+            Some(sink) if sink.name != NULL_SINK => alerts.clear("audio:sink"),
+            _ => alerts.raise(
+                "audio:sink",
+                AlertLevel::Warning,
+                "audio",
+                "No audio output device, sound goes nowhere".to_string(),
+            ),
+        }
+    }
+
+    fn read(&mut self) -> Result<(), String> {
+        let info: HashMap<String, String> = pactl(&["info"])?
+            .lines()
+            .filter_map(|line| line.split_once(": "))
+            .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+            .collect();
+        self.server = info.get("Server Name").cloned();
+        let default = |key: &str| info.get(key).cloned().unwrap_or_default();
+
+        let find = |kind: &str, name: &str| -> Result<Option<AudioDevice>, String> {
+            Ok(blocks(&pactl(&["list", kind])?)
+                .iter()
+                .find(|block| block.get("Name").map(String::as_str) == Some(name))
+                .map(device))
+        };
+        self.sink = find("sinks", &default("Default Sink"))?;
+        self.source = find("sources", &default("Default Source"))?;
+
+        let mut streams = Vec::new();
+        for (kind, playback) in [("sink-inputs", true), ("source-outputs", false)] {
+            for block in blocks(&pactl(&["list", kind])?) {
+                // Monitors of other streams, e.g. pavucontrol's level meters
+                if block.get("application.id").map(String::as_str)
+                    == Some("org.PulseAudio.pavucontrol")
+                {
+                    continue;
+                }
+                streams.push(AudioStream {
+                    application: block
+                        .get("application.name")
+                        .or_else(|| block.get("application.process.binary"))
+                        .cloned()
+                        .unwrap_or_else(|| "Unknown".to_string()),
+                    media: block.get("media.name").cloned().unwrap_or_default(),
+                    playback,
+                    corked: block.get("Corked").map(String::as_str) == Some("yes"),
+                });
+            }
+        }
+        self.streams = streams;
+        self.error = None;
+        Ok(())
+    }
+}
+
+fn pactl(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("pactl")
+        .args(args)
+        // The field names are translated otherwise
+        .env("LC_ALL", "C")
+        .output()
+        .map_err(|e| format!("Cannot run pactl: {} (is pulseaudio-utils installed?)", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "pactl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Splits `pactl list` output into one map per "Sink #0"-style block, with
+// both the "\tKey: value" fields and the "\t\tkey = \"value\"" properties
+fn blocks(output: &str) -> Vec<HashMap<String, String>> {
+    let mut blocks: Vec<HashMap<String, String>> = Vec::new();
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            blocks.push(HashMap::new());
+            continue;
+        }
+        let Some(block) = blocks.last_mut() else {
+            continue;
+        };
+        let line = line.trim();
+        if let Some((key, value)) = line.split_once(" = ") {
+            block.insert(key.to_string(), value.trim_matches('"').to_string());
+        } else if let Some((key, value)) = line.split_once(": ") {
+            block
+                .entry(key.to_string())
+                .or_insert_with(|| value.to_string());
+        }
+    }
+    blocks
+}
+
+fn device(block: &HashMap<String, String>) -> AudioDevice {
+    let field = |key: &str| block.get(key).cloned().unwrap_or_default();
+    // "front-left: 42597 /  65% / -11.23 dB,   front-right: 42597 /  65% / ..."
+    let percents: Vec<u32> = field("Volume")
+        .split('/')
+        .filter_map(|part| part.trim().strip_suffix('%')?.parse().ok())
+        .collect();
+    AudioDevice {
+        name: field("Name"),
+        description: field("Description"),
+        volume: (!percents.is_empty())
+            .then(|| percents.iter().sum::<u32>() / percents.len() as u32),
+        muted: field("Mute") == "yes",
+        state: field("State"),
+    }
+}
+
+fn print_device(label: &str, device: Option<&AudioDevice>) {
+    let Some(device) = device else {
+        println!("{}: {}", label, "none".critical());
+        return;
+    };
+    if device.name == NULL_SINK {
+        println!("{}: {}", label, "none (dummy output)".critical());
+        return;
+    }
+    let volume = match (device.muted, device.volume) {
+        (true, _) => "muted".warning(),
+        (false, Some(volume)) => format!("{}%", volume).value(),
+        (false, None) => "?".normal(),
+    };
+    let description = if device.description.is_empty() {
+        &device.name
+    } else {
+        &device.description
+    };
+    println!(
+        "{}: {} - {} ({})",
+        label,
+        description.warning(),
+        volume,
+        device.state.to_lowercase()
+    );
+}
+
+// Audio panel: default output and input, then what is playing or recording
+pub fn monitor_audio(monitor: &AudioMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-audio");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    if let Some(error) = &monitor.error {
+        println!("  {}", error.critical());
+        return Ok(());
+    }
+    if let Some(server) = &monitor.server {
+        println!("Server: {}", server);
+    }
+    print_device("Output", monitor.sink.as_ref());
+    print_device("Input", monitor.source.as_ref());
+
+    for stream in &monitor.streams {
+        // Out to the speakers, in from the microphone
+        let direction = if stream.playback { "→" } else { "⬅" };
+        let state = if stream.corked { " (paused)" } else { "" };
+        println!(
+            "  {} {}{}{}",
+            theme::ascii(direction),
+            stream.application.value(),
+            if stream.media.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", stream.media)
+            },
+            state
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,617 @@
+use std::fs::{self, File, OpenOptions};
+use std::hint::black_box;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use colored::*;
+use sysinfo::{CpuRefreshKind, System, SystemExt};
+
+use crate::alerts::AlertEngine;
+use crate::history::HistoryStore;
+use crate::i18n;
+use crate::theme::{self, Themed};
+use crate::throttle::ThrottleMonitor;
+use crate::units;
+
+// How often the thermal state is sampled while a benchmark runs
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+// Iterations in one unit of CPU work; the score counts these units
+const CPU_WORK_ITERATIONS: u64 = 100_000;
+
+// Name of the scratch file the disk benchmark writes
+const DISK_TEST_FILE: &str = ".hercules-bench.tmp";
+
+// Handle "hercules bench cpu [--threads N] [--seconds S]" and
+// "hercules bench disk [--path DIR] [--size SIZE] [--block SIZE]"
+pub fn run_bench(args: &[String]) -> Result<()> {
+    let no_record = || {
+        Arg::new("no-record")
+            .long("no-record")
+            .help("Do not save the results to the history database")
+            .action(ArgAction::SetTrue)
+    };
+
+    let matches = Command::new("bench")
+        .about("Benchmark the system while recording its thermal behaviour")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("cpu")
+                .about("Run a fixed CPU workload and report a score with a thermal summary")
+                .arg(
+                    Arg::new("threads")
+                        .long("threads")
+                        .short('t')
+                        .value_name("N")
+                        .help("Worker threads (default: one per logical CPU)")
+                        .value_parser(clap::value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("seconds")
+                        .long("seconds")
+                        .short('s')
+                        .value_name("S")
+                        .help("How long to run")
+                        .default_value("60")
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(no_record()),
+        )
+        .subcommand(
+            Command::new("disk")
+                .about("Measure sequential and random read/write throughput and IOPS")
+                .arg(
+                    Arg::new("path")
+                        .long("path")
+                        .short('p')
+                        .value_name("DIR")
+                        .help("Directory on the disk to test")
+                        .default_value("."),
+                )
+                .arg(
+                    Arg::new("size")
+                        .long("size")
+                        .value_name("SIZE")
+                        .help("Test file size, e.g. 512M or 2G")
+                        .default_value("256M")
+                        .value_parser(parse_size),
+                )
+                .arg(
+                    Arg::new("block")
+                        .long("block")
+                        .value_name("SIZE")
+                        .help("Block size for the sequential tests")
+                        .default_value("1M")
+                        .value_parser(parse_size),
+                )
+                .arg(
+                    Arg::new("random-block")
+                        .long("random-block")
+                        .value_name("SIZE")
+                        .help("Block size for the random tests")
+                        .default_value("4K")
+                        .value_parser(parse_size),
+                )
+                .arg(
+                    Arg::new("seconds")
+                        .long("seconds")
+                        .short('s')
+                        .value_name("S")
+                        .help("Time limit for each random test")
+                        .default_value("10")
+                        .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(no_record()),
+        )
+        .get_matches_from(args);
+
+    match matches.subcommand() {
+        Some(("cpu", matches)) => bench_cpu(matches),
+        Some(("disk", matches)) => bench_disk(matches),
+        _ => unreachable!("clap requires a subcommand"),
+    }
+}
+
+// One unit of deterministic integer and floating point work
+fn cpu_work_unit(seed: u64) -> u64 {
+    let mut state = seed | 1;
+    let mut acc = 0.0f64;
+    for i in 0..CPU_WORK_ITERATIONS {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        acc += ((state % 1000) as f64 + i as f64).sqrt();
+    }
+    state ^ acc.to_bits()
+}
+
+// Thermal readings collected while a benchmark runs
+struct ThermalLog {
+    temperatures: Vec<f32>,
+    clocks: Vec<u64>,
+    throttled_samples: usize,
+    throttle_reasons: Vec<String>,
+}
+
+impl ThermalLog {
+    fn new() -> Self {
+        ThermalLog {
+            temperatures: Vec::new(),
+            clocks: Vec::new(),
+            throttled_samples: 0,
+            throttle_reasons: Vec::new(),
+        }
+    }
+
+    fn sample(&mut self, system: &mut System, throttle: &mut ThrottleMonitor) {
+        system.refresh_cpu_specifics(CpuRefreshKind::everything());
+        system.refresh_components();
+        throttle.refresh(system, &mut AlertEngine::new());
+        if let Some(temp) = throttle.temperature() {
+            self.temperatures.push(temp);
+        }
+        if throttle.average_mhz() > 0 {
+            self.clocks.push(throttle.average_mhz());
+        }
+        if let Some(reason) = throttle.reason() {
+            self.throttled_samples += 1;
+            if !self.throttle_reasons.iter().any(|known| known == reason) {
+                self.throttle_reasons.push(reason.to_string());
+            }
+        }
+    }
+
+    fn print(&self) {
+        let heading = i18n::tr("heading-thermal-summary");
+        println!("\n{}", heading.bold().warning());
+        println!("{}", i18n::underline(heading).warning());
+        match (self.temperatures.first(), self.temperatures.last()) {
+            (Some(start), Some(end)) => {
+                let peak = self.temperatures.iter().cloned().fold(f32::MIN, f32::max);
+                println!(
+                    "Temperature: {} at start, {} peak, {} at end",
+                    units::format_temperature(*start as f64).good(),
+                    units::format_temperature(peak as f64).critical(),
+                    units::format_temperature(*end as f64).warning()
+                );
+            }
+            _ => println!("Temperature: {}", "no sensor available".value()),
+        }
+        if !self.clocks.is_empty() {
+            let min = self.clocks.iter().min().copied().unwrap_or_default();
+            let max = self.clocks.iter().max().copied().unwrap_or_default();
+            let avg = self.clocks.iter().sum::<u64>() / self.clocks.len() as u64;
+            println!(
+                "Clock: {} MHz min, {} MHz avg, {} MHz max",
+                min.to_string().warning(),
+                avg.to_string().value(),
+                max.to_string().good()
+            );
+        }
+        if self.throttled_samples > 0 {
+            println!(
+                "Throttling: {} for {}s ({})",
+                "YES".critical().bold(),
+                self.throttled_samples,
+                self.throttle_reasons.join("; ")
+            );
+        } else {
+            println!("Throttling: {}", "none".good());
+        }
+    }
+}
+
+fn bench_cpu(matches: &ArgMatches) -> Result<()> {
+    let threads = matches
+        .get_one::<usize>("threads")
+        .copied()
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .max(1);
+    let duration = Duration::from_secs(*matches.get_one::<u64>("seconds").unwrap_or(&60));
+
+    let heading = i18n::tr("heading-cpu-benchmark");
+    println!("{}", heading.bold().primary());
+    println!("{}", i18n::underline(heading).primary());
+    println!(
+        "Running {} thread{} for {}s...",
+        threads.to_string().value(),
+        if threads == 1 { "" } else { "s" },
+        duration.as_secs().to_string().value()
+    );
+
+    let mut system = System::new();
+    system.refresh_cpu_specifics(CpuRefreshKind::everything());
+    // refresh_components only updates sensors already listed
+    system.refresh_components_list();
+    let mut throttle = ThrottleMonitor::new(false);
+    let mut thermal = ThermalLog::new();
+    thermal.sample(&mut system, &mut throttle);
+
+    let units = Arc::new(AtomicU64::new(0));
+    let stop = Arc::new(AtomicBool::new(false));
+    let workers: Vec<_> = (0..threads)
+        .map(|worker| {
+            let units = units.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let mut seed = worker as u64 + 1;
+                while !stop.load(Ordering::Relaxed) {
+                    seed = black_box(cpu_work_unit(seed));
+                    units.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    // Work done in each sample interval, to see whether throughput sags as it heats up
+    let mut rates: Vec<f64> = Vec::new();
+    let start = Instant::now();
+    let mut last_units = 0;
+    let mut last_sample = start;
+    while start.elapsed() < duration {
+        thread::sleep(SAMPLE_INTERVAL.min(duration.saturating_sub(start.elapsed())));
+        let done = units.load(Ordering::Relaxed);
+        rates.push((done - last_units) as f64 / last_sample.elapsed().as_secs_f64());
+        last_units = done;
+        last_sample = Instant::now();
+        thermal.sample(&mut system, &mut throttle);
+
+        if theme::interactive() {
+            let temp = throttle
+                .temperature()
+                .map(|temp| format!(" - {}", units::format_temperature(temp as f64)))
+                .unwrap_or_default();
+            print!(
+                "\r  {:>3}s  {} MHz{}   ",
+                start.elapsed().as_secs(),
+                throttle.average_mhz(),
+                temp
+            );
+            io::stdout().flush()?;
+        }
+    }
+    stop.store(true, Ordering::Relaxed);
+    for worker in workers {
+        let _ = worker.join();
+    }
+    if theme::interactive() {
+        println!();
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    let score = units.load(Ordering::Relaxed) as f64 / elapsed;
+    println!(
+        "Score: {} ({} per thread)",
+        format!("{:.1}", score).bold().good(),
+        format!("{:.1}", score / threads as f64).value()
+    );
+
+    // Compare the first and last quarter of the run, leaving out the first
+    // interval where the workers were still starting
+    let rates = rates.get(1..).unwrap_or_default();
+    let quarter = (rates.len() / 4).max(1);
+    let sustained = if rates.len() >= 4 {
+        let average = |slice: &[f64]| slice.iter().sum::<f64>() / slice.len() as f64;
+        let first = average(&rates[..quarter]);
+        let last = average(&rates[rates.len() - quarter..]);
+        let change = if first > 0.0 {
+            (last / first - 1.0) * 100.0
+        } else {
+            0.0
+        };
+        let text = format!("{:+.1}%", change);
+        println!(
+            "Sustained: {} from the first to the last quarter of the run",
+            if change <= -10.0 {
+                text.critical()
+            } else if change <= -3.0 {
+                text.warning()
+            } else {
+                text.good()
+            }
+        );
+        Some(change)
+    } else {
+        None
+    };
+
+    thermal.print();
+
+    if !matches.get_flag("no-record") {
+        let store = HistoryStore::open()?;
+        store.record("bench.cpu_score", score)?;
+        let peak = thermal
+            .temperatures
+            .iter()
+            .cloned()
+            .fold(f32::MIN, f32::max);
+        let mut message = format!(
+            "cpu: score {:.1} with {} threads over {}s",
+            score, threads, elapsed as u64
+        );
+        if peak > f32::MIN {
+            message.push_str(&format!(
+                ", peak {}",
+                units::format_temperature(peak as f64)
+            ));
+        }
+        if let Some(change) = sustained {
+            message.push_str(&format!(", sustained {:+.1}%", change));
+        }
+        if thermal.throttled_samples > 0 {
+            message.push_str(&format!(", throttled {}s", thermal.throttled_samples));
+        }
+        store.record_event("bench", &message)?;
+        println!("{}", theme::ascii("✓ Results saved to history").good());
+    }
+
+    Ok(())
+}
+
+// "4K", "256M", "2G" or a plain byte count
+fn parse_size(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let (number, multiplier) = match text.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('K') => (&text[..text.len() - 1], 1 << 10),
+        Some('M') => (&text[..text.len() - 1], 1 << 20),
+        Some('G') => (&text[..text.len() - 1], 1 << 30),
+        _ => (text, 1),
+    };
+    // Zero and sizes past u64 are both invalid
+    number
+        .parse::<u64>()
+        .ok()
+        .filter(|&value| value > 0)
+        .and_then(|value| value.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid size '{}', use e.g. 4K, 256M or 2G", text))
+}
+
+// Deletes the scratch file however the benchmark ends
+struct ScratchFile(PathBuf);
+
+impl Drop for ScratchFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// Ask the kernel to forget cached pages so reads hit the disk
+#[cfg(target_os = "linux")]
+fn drop_cache(file: &File) {
+    use std::os::unix::io::AsRawFd;
+    let _ = nix::fcntl::posix_fadvise(
+        file.as_raw_fd(),
+        0,
+        0,
+        nix::fcntl::PosixFadviseAdvice::POSIX_FADV_DONTNEED,
+    );
+}
+
+#[cfg(not(target_os = "linux"))]
+fn drop_cache(_file: &File) {}
+
+// Turns off readahead, which would otherwise pull neighbouring blocks of a
+// random read into the cache
+#[cfg(target_os = "linux")]
+fn advise_random(file: &File) {
+    use std::os::unix::io::AsRawFd;
+    let _ = nix::fcntl::posix_fadvise(
+        file.as_raw_fd(),
+        0,
+        0,
+        nix::fcntl::PosixFadviseAdvice::POSIX_FADV_RANDOM,
+    );
+}
+
+#[cfg(not(target_os = "linux"))]
+fn advise_random(_file: &File) {}
+
+#[cfg(unix)]
+fn read_at(file: &File, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.read_exact_at(buffer, offset)
+}
+
+#[cfg(windows)]
+fn read_at(file: &File, buffer: &mut [u8], offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    file.seek_read(buffer, offset).map(|_| ())
+}
+
+#[cfg(unix)]
+fn write_at(file: &File, buffer: &[u8], offset: u64) -> io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.write_all_at(buffer, offset)
+}
+
+#[cfg(windows)]
+fn write_at(file: &File, buffer: &[u8], offset: u64) -> io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    file.seek_write(buffer, offset).map(|_| ())
+}
+
+// Incompressible filler, so drives that compress data cannot cheat
+fn random_bytes(len: usize, mut state: u64) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+// Block offsets visited by the random tests, the same sequence every run
+// Every block number once, in an order shuffled with xorshift, so random
+// tests never hit a block twice and read it back from the cache
+fn shuffled_blocks(blocks: u64, mut state: u64) -> Vec<u64> {
+    let mut order: Vec<u64> = (0..blocks).collect();
+    for i in (1..order.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        order.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    order
+}
+
+fn mb_per_sec(bytes: u64, elapsed: Duration) -> f64 {
+    bytes as f64 / 1_048_576.0 / elapsed.as_secs_f64().max(f64::EPSILON)
+}
+
+fn bench_disk(matches: &ArgMatches) -> Result<()> {
+    let directory = Path::new(
+        matches
+            .get_one::<String>("path")
+            .map_or(".", String::as_str),
+    );
+    if !directory.is_dir() {
+        return Err(anyhow!("{} is not a directory", directory.display()));
+    }
+    let block = *matches.get_one::<u64>("block").unwrap_or(&(1 << 20)) as usize;
+    let random_block = *matches.get_one::<u64>("random-block").unwrap_or(&4096) as usize;
+    // Whole blocks only, so every test covers the same bytes
+    let size = (*matches.get_one::<u64>("size").unwrap_or(&(256 << 20)) / block as u64).max(1)
+        * block as u64;
+    let time_limit = Duration::from_secs(*matches.get_one::<u64>("seconds").unwrap_or(&10));
+
+    let heading = i18n::tr("heading-disk-benchmark");
+    println!("{}", heading.bold().value());
+    println!("{}", i18n::underline(heading).value());
+    println!(
+        "Testing {} with a {} MB file, {} KB sequential and {} KB random blocks",
+        directory.display().to_string().value(),
+        size >> 20,
+        block >> 10,
+        random_block >> 10
+    );
+
+    let scratch = ScratchFile(directory.join(DISK_TEST_FILE));
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&scratch.0)?;
+
+    // Sequential write, including the flush to disk
+    let data = random_bytes(block, 0x9E37_79B9_7F4A_7C15);
+    let start = Instant::now();
+    for _ in 0..size / block as u64 {
+        file.write_all(&data)?;
+    }
+    file.sync_all()?;
+    let seq_write = mb_per_sec(size, start.elapsed());
+    println!(
+        "Sequential write: {} MB/s",
+        format!("{:.1}", seq_write).good()
+    );
+
+    // Sequential read
+    drop_cache(&file);
+    let mut reader = File::open(&scratch.0)?;
+    let mut buffer = vec![0u8; block];
+    let start = Instant::now();
+    let mut read = 0u64;
+    while read < size {
+        reader.read_exact(&mut buffer)?;
+        read += block as u64;
+    }
+    let seq_read = mb_per_sec(size, start.elapsed());
+    println!(
+        "Sequential read:  {} MB/s",
+        format!("{:.1}", seq_read).good()
+    );
+
+    let blocks = (size / random_block as u64).max(1);
+
+    // Random writes until every block is written once or time runs out
+    let data = random_bytes(random_block, 0xD1B5_4A32_D192_ED03);
+    let start = Instant::now();
+    let mut writes = 0u64;
+    for block in shuffled_blocks(blocks, 0x2545_F491_4F6C_DD1D) {
+        if start.elapsed() >= time_limit {
+            break;
+        }
+        write_at(&file, &data, block * random_block as u64)?;
+        writes += 1;
+    }
+    file.sync_all()?;
+    let elapsed = start.elapsed();
+    let write_iops = writes as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "Random write:     {} IOPS ({} MB/s)",
+        format!("{:.0}", write_iops).good(),
+        format!("{:.1}", mb_per_sec(writes * random_block as u64, elapsed)).value()
+    );
+
+    // Random reads, same limits, in a different order than the writes
+    drop_cache(&file);
+    advise_random(&file);
+    let mut buffer = vec![0u8; random_block];
+    let start = Instant::now();
+    let mut reads = 0u64;
+    for block in shuffled_blocks(blocks, 0x9FB2_1C65_1E98_DF25) {
+        if start.elapsed() >= time_limit {
+            break;
+        }
+        read_at(&file, &mut buffer, block * random_block as u64)?;
+        reads += 1;
+    }
+    let elapsed = start.elapsed();
+    let read_iops = reads as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+    println!(
+        "Random read:      {} IOPS ({} MB/s)",
+        format!("{:.0}", read_iops).good(),
+        format!("{:.1}", mb_per_sec(reads * random_block as u64, elapsed)).value()
+    );
+    // Running out of blocks before the time limit means the drive's own cache
+    // may hold much of the file; a larger --size gives a fairer figure
+    if reads == blocks && elapsed < time_limit {
+        println!(
+            "  {}",
+            format!(
+                "Every block was read in {:.1}s of the {}s limit; the file is smaller \
+                 than the working set, try a larger --size",
+                elapsed.as_secs_f64(),
+                time_limit.as_secs()
+            )
+            .warning()
+        );
+    }
+    drop(file);
+    drop(scratch);
+
+    if !matches.get_flag("no-record") {
+        let store = HistoryStore::open()?;
+        store.record("bench.disk_seq_write_mbps", seq_write)?;
+        store.record("bench.disk_seq_read_mbps", seq_read)?;
+        store.record("bench.disk_rand_write_iops", write_iops)?;
+        store.record("bench.disk_rand_read_iops", read_iops)?;
+        let path = fs::canonicalize(directory).unwrap_or_else(|_| directory.to_path_buf());
+        store.record_event(
+            "bench",
+            &format!(
+                "disk {}: seq write {:.1} MB/s, seq read {:.1} MB/s, \
+                 random write {:.0} IOPS, random read {:.0} IOPS ({} MB file)",
+                path.display(),
+                seq_write,
+                seq_read,
+                write_iops,
+                read_iops,
+                size >> 20
+            ),
+        )?;
+        println!("{}", theme::ascii("✓ Results saved to history").good());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+#[cfg(any(target_os = "linux", windows))]
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+#[cfg(target_os = "linux")]
+use log::debug;
+
+use crate::i18n;
+use crate::theme::Themed;
+
+// Every query starts a process per device, and battery levels move slowly
+const SCAN_INTERVAL: Duration = Duration::from_secs(30);
+
+// A paired Bluetooth device
+#[derive(Debug, Clone)]
+pub struct Peripheral {
+    pub name: String,
+    pub connected: bool,
+    // Only reported by devices with a battery service, and only while connected
+    pub battery: Option<u8>,
+}
+
+// Paired peripherals and their battery levels, from BlueZ through
+// bluetoothctl on Linux and from the PnP device properties on Windows
+pub struct PeripheralMonitor {
+    peripherals: Vec<Peripheral>,
+    error: Option<String>,
+    last_scan: Option<Instant>,
+}
+
+impl PeripheralMonitor {
+    pub fn new() -> Self {
+        PeripheralMonitor {
+            peripherals: Vec::new(),
+            error: None,
+            last_scan: None,
+        }
+    }
+
+    pub fn peripherals(&self) -> &[Peripheral] {
+        &self.peripherals
+    }
+
+    pub fn refresh(&mut self) {
+        if self
+            .last_scan
+            .is_some_and(|last| last.elapsed() < SCAN_INTERVAL)
+        {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+
+        match read_peripherals() {
+            Ok(mut peripherals) => {
+                // Connected first, then by name
+                peripherals.sort_by(|a, b| {
+                    b.connected
+                        .cmp(&a.connected)
+                        .then_with(|| a.name.cmp(&b.name))
+                });
+                self.peripherals = peripherals;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_peripherals() -> Result<Vec<Peripheral>, String> {
+    // BlueZ 5.65 renamed `paired-devices` to `devices Paired`
+    let mut listing = bluetoothctl(&["devices", "Paired"])?;
+    if !listing.lines().any(|line| line.starts_with("Device ")) {
+        listing = bluetoothctl(&["paired-devices"]).unwrap_or_default();
+    }
+
+    let mut peripherals = Vec::new();
+    // "Device AA:BB:CC:DD:EE:FF Keyboard K380"
+    for line in listing.lines() {
+        let Some(rest) = line.strip_prefix("Device ") else {
+            continue;
+        };
+        let (address, name) = rest.split_once(' ').unwrap_or((rest, rest));
+        let mut peripheral = Peripheral {
+            name: name.to_string(),
+            connected: false,
+            battery: None,
+        };
+        match bluetoothctl(&["info", address]) {
+            Ok(info) => parse_info(&info, &mut peripheral),
+            Err(e) => debug!("bluetoothctl info {}: {}", address, e),
+        }
+        peripherals.push(peripheral);
+    }
+    Ok(peripherals)
+}
+
+#[cfg(target_os = "linux")]
+fn bluetoothctl(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("bluetoothctl")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Cannot run bluetoothctl: {} (is bluez installed?)", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "bluetoothctl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Fields of `bluetoothctl info`, e.g. "\tConnected: yes" and
+// "\tBattery Percentage: 0x5a (90)"
+#[cfg(target_os = "linux")]
+fn parse_info(info: &str, peripheral: &mut Peripheral) {
+    for line in info.lines() {
+        let Some((key, value)) = line.trim().split_once(": ") else {
+            continue;
+        };
+        match key {
+            "Alias" => peripheral.name = value.to_string(),
+            "Connected" => peripheral.connected = value == "yes",
+            "Battery Percentage" => {
+                peripheral.battery = value
+                    .split_once('(')
+                    .and_then(|(_, percent)| percent.trim_end_matches(')').parse().ok())
+            }
+            _ => {}
+        }
+    }
+}
+
+// Windows keeps the level Settings shows in a PnP device property; only
+// devices that report one are listed, which leaves out radios and services
+#[cfg(windows)]
+fn read_peripherals() -> Result<Vec<Peripheral>, String> {
+    const SCRIPT: &str = "Get-PnpDevice -Class Bluetooth | ForEach-Object { \
+        $b = (Get-PnpDeviceProperty -InstanceId $_.InstanceId \
+        -KeyName '{104EA319-6EE2-4701-BD47-8DDBF425BBE5} 2' -ErrorAction SilentlyContinue).Data; \
+        if ($b -ne $null) { \"$($_.FriendlyName)`t$($_.Status)`t$b\" } }";
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", SCRIPT])
+        .output()
+        .map_err(|e| format!("Cannot run powershell: {}", e))?;
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    Ok(text
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.trim().split('\t').collect();
+            if fields.len() < 3 {
+                return None;
+            }
+            Some(Peripheral {
+                name: fields[0].to_string(),
+                // "OK" while connected, "Unknown" when paired but away
+                connected: fields[1] == "OK",
+                battery: fields[2].parse().ok(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn read_peripherals() -> Result<Vec<Peripheral>, String> {
+    Err("Bluetooth peripherals are only supported on Linux and Windows".to_string())
+}
+
+// Peripherals panel: one line per paired device with its battery level
+pub fn monitor_peripherals(monitor: &PeripheralMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-peripherals");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    if let Some(error) = &monitor.error {
+        println!("  {}", error.critical());
+        return Ok(());
+    }
+    if monitor.peripherals().is_empty() {
+        println!("  No paired Bluetooth devices");
+        return Ok(());
+    }
+
+    for peripheral in monitor.peripherals() {
+        let state = if peripheral.connected {
+            "connected".good()
+        } else {
+            "not connected".normal()
+        };
+        let battery = match peripheral.battery {
+            Some(level) if level < 15 => format!("{:>4}%", level).critical(),
+            Some(level) if level < 30 => format!("{:>4}%", level).warning(),
+            Some(level) => format!("{:>4}%", level).value(),
+            None => "    -".normal(),
+        };
+        println!(
+            "  {} {}  {}",
+            format!("{:<28}", peripheral.name).warning(),
+            battery,
+            state
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,349 @@
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::Path;
+use std::time::Instant;
+
+use anyhow::Result;
+use colored::*;
+
+use crate::i18n;
+use crate::theme::Themed;
+use crate::throttle::ThrottleMonitor;
+use crate::verbosity;
+
+#[cfg(target_os = "linux")]
+const CPU_DIR: &str = "/sys/devices/system/cpu";
+
+// Package power this close to its RAPL limit counts as power-limited
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const POWER_LIMIT_FRACTION: f64 = 0.95;
+
+// Clocks of one core in MHz, from its cpufreq directory
+#[derive(Debug, Clone)]
+pub struct CoreClock {
+    pub cpu: usize,
+    pub current: u64,
+    // Rated clock without boost, where the driver reports it
+    pub base: Option<u64>,
+    // Highest clock with boost
+    pub max: Option<u64>,
+    // Ceiling currently allowed by the cpufreq policy and cooling devices
+    pub limit: Option<u64>,
+}
+
+// Whether turbo/boost is on, per-core current, base and maximum clocks, and
+// what keeps the cores from boosting: thermal throttling events, cpufreq
+// cooling, a RAPL power limit or the Pi firmware
+pub struct BoostMonitor {
+    driver: Option<String>,
+    // None where the driver has no boost switch
+    enabled: Option<bool>,
+    cores: Vec<CoreClock>,
+    limits: Vec<String>,
+    // Thermal and power-limit event counters summed over all cores
+    throttle_events: Option<(u64, u64)>,
+    // Package energy counter in µJ, for the power draw between refreshes
+    energy: Option<(Instant, u64)>,
+    // arm_boost from the Pi firmware config, read once
+    arm_boost: Option<Option<bool>>,
+}
+
+impl BoostMonitor {
+    pub fn new() -> Self {
+        BoostMonitor {
+            driver: None,
+            enabled: None,
+            cores: Vec::new(),
+            limits: Vec::new(),
+            throttle_events: None,
+            energy: None,
+            arm_boost: None,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn refresh(&mut self, throttle: &ThrottleMonitor) {
+        let cpu_dir = Path::new(CPU_DIR);
+        self.driver = read(&cpu_dir.join("cpu0/cpufreq/scaling_driver"));
+        // intel_pstate has its own switch; acpi-cpufreq and amd-pstate use the
+        // global one, newer kernels also have one per policy
+        self.enabled = read(&cpu_dir.join("intel_pstate/no_turbo"))
+            .map(|value| value == "0")
+            .or_else(|| read(&cpu_dir.join("cpufreq/boost")).map(|value| value == "1"))
+            .or_else(|| read(&cpu_dir.join("cpufreq/policy0/boost")).map(|value| value == "1"));
+        // The Pi has no switch at runtime, only arm_boost in config.txt
+        if self.enabled.is_none() && throttle.pi_flags().is_some() {
+            self.enabled = *self.arm_boost.get_or_insert_with(read_arm_boost);
+        }
+        self.cores = read_cores(cpu_dir);
+
+        let mut limits = Vec::new();
+        self.check_throttle_events(&mut limits);
+        self.check_power_limit(&mut limits);
+
+        // Cooling devices and the policy lower scaling_max_freq below the
+        // hardware maximum; with boost off the drivers do so themselves
+        if let Some(core) = self
+            .cores
+            .iter()
+            .filter(
+                |core| matches!((core.limit, core.max), (Some(limit), Some(max)) if limit < max),
+            )
+            .min_by_key(|core| core.limit)
+            .filter(|_| self.enabled != Some(false))
+        {
+            let by = if cpufreq_cooling_active() {
+                "thermal cooling"
+            } else {
+                "scaling_max_freq"
+            };
+            limits.push(format!(
+                "capped at {} of {} MHz by {}",
+                core.limit.unwrap_or_default(),
+                core.max.unwrap_or_default(),
+                by
+            ));
+        }
+        if let Some(percent) = read(&cpu_dir.join("intel_pstate/max_perf_pct"))
+            .and_then(|value| value.parse::<u32>().ok())
+            .filter(|&percent| percent < 100)
+        {
+            limits.push(format!("intel_pstate max_perf_pct at {}%", percent));
+        }
+
+        if let Some(flags) = throttle.pi_flags() {
+            if flags & crate::throttle::PI_SOFT_TEMP_LIMIT != 0 {
+                limits.push("soft temperature limit".to_string());
+            }
+            if flags & crate::throttle::PI_FREQ_CAPPED != 0 {
+                limits.push("firmware capped the ARM clock".to_string());
+            }
+            if flags & crate::throttle::PI_THROTTLED != 0 {
+                limits.push("firmware throttling".to_string());
+            }
+            if flags & crate::throttle::PI_UNDER_VOLTAGE != 0 {
+                limits.push("under-voltage".to_string());
+            }
+        }
+        self.limits = limits;
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn refresh(&mut self, _throttle: &ThrottleMonitor) {}
+
+    // Intel's PROCHOT and power-limit counters in thermal_throttle, which
+    // only say something when they went up since the last refresh
+    #[cfg(target_os = "linux")]
+    fn check_throttle_events(&mut self, limits: &mut Vec<String>) {
+        let mut thermal = 0;
+        let mut power = 0;
+        let mut found = false;
+        for core in &self.cores {
+            let dir = Path::new(CPU_DIR).join(format!("cpu{}/thermal_throttle", core.cpu));
+            for (name, thermal_event) in [
+                ("core_throttle_count", true),
+                ("package_throttle_count", true),
+                ("core_power_limit_count", false),
+                ("package_power_limit_count", false),
+            ] {
+                let Some(count) = read(&dir.join(name)).and_then(|value| value.parse::<u64>().ok())
+                else {
+                    continue;
+                };
+                if thermal_event {
+                    thermal += count;
+                } else {
+                    power += count;
+                }
+                found = true;
+            }
+        }
+        if !found {
+            return;
+        }
+        if let Some((last_thermal, last_power)) = self.throttle_events {
+            if thermal > last_thermal {
+                limits.push(format!(
+                    "thermal throttling ({} events)",
+                    thermal - last_thermal
+                ));
+            }
+            if power > last_power {
+                limits.push(format!("power limit ({} events)", power - last_power));
+            }
+        }
+        self.throttle_events = Some((thermal, power));
+    }
+
+    // Package power from the RAPL energy counter against its long-term
+    // limit. Reading energy_uj needs root on current kernels.
+    #[cfg(target_os = "linux")]
+    fn check_power_limit(&mut self, limits: &mut Vec<String>) {
+        let rapl = Path::new("/sys/class/powercap/intel-rapl:0");
+        let Some(energy) =
+            read(&rapl.join("energy_uj")).and_then(|value| value.parse::<u64>().ok())
+        else {
+            return;
+        };
+        let now = Instant::now();
+        let last = self.energy.replace((now, energy));
+        let (Some((then, last_energy)), Some(limit)) = (
+            last,
+            read(&rapl.join("constraint_0_power_limit_uw"))
+                .and_then(|value| value.parse::<u64>().ok())
+                .filter(|&limit| limit > 0),
+        ) else {
+            return;
+        };
+        let seconds = now.duration_since(then).as_secs_f64();
+        // The counter wraps around at max_energy_range_uj
+        if seconds <= 0.0 || energy < last_energy {
+            return;
+        }
+        let watts = (energy - last_energy) as f64 / seconds / 1e6;
+        let limit_watts = limit as f64 / 1e6;
+        if watts >= limit_watts * POWER_LIMIT_FRACTION {
+            limits.push(format!(
+                "package at {:.1} W of its {:.1} W power limit",
+                watts, limit_watts
+            ));
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn read_cores(cpu_dir: &Path) -> Vec<CoreClock> {
+    let Ok(entries) = fs::read_dir(cpu_dir) else {
+        return Vec::new();
+    };
+    let mut cores: Vec<CoreClock> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let cpu = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("cpu")?
+                .parse()
+                .ok()?;
+            let cpufreq = entry.path().join("cpufreq");
+            let mhz = |names: &[&str]| {
+                names
+                    .iter()
+                    .find_map(|name| read(&cpufreq.join(name))?.parse::<u64>().ok())
+                    .map(|khz| khz / 1000)
+            };
+            // Offline cores and VMs without cpufreq have nothing to show
+            let current = mhz(&["scaling_cur_freq", "cpuinfo_cur_freq"])?;
+            Some(CoreClock {
+                cpu,
+                current,
+                base: mhz(&["base_frequency"]),
+                max: mhz(&["amd_pstate_max_freq", "cpuinfo_max_freq"]),
+                limit: mhz(&["scaling_max_freq"]),
+            })
+        })
+        .collect();
+    cores.sort_by_key(|core| core.cpu);
+    cores
+}
+
+// "arm_boost=1" from `vcgencmd get_config`
+#[cfg(target_os = "linux")]
+fn read_arm_boost() -> Option<bool> {
+    let output = std::process::Command::new("vcgencmd")
+        .args(["get_config", "arm_boost"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let value = text.trim().strip_prefix("arm_boost=")?;
+    Some(value.trim() == "1")
+}
+
+// A cpufreq cooling device of the thermal framework in a throttling state
+#[cfg(target_os = "linux")]
+fn cpufreq_cooling_active() -> bool {
+    let Ok(entries) = fs::read_dir("/sys/class/thermal") else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        let kind = read(&path.join("type")).unwrap_or_default();
+        (kind.starts_with("cpufreq") || kind == "Processor")
+            && read(&path.join("cur_state")).is_some_and(|state| state != "0")
+    })
+}
+
+fn mhz(value: Option<u64>) -> String {
+    value.map_or_else(|| "-".to_string(), |mhz| format!("{} MHz", mhz))
+}
+
+// Boost panel: the switch, what limits it, then the clocks of every core
+pub fn monitor_boost(monitor: &BoostMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-boost");
+    println!("\n{}", heading.bold().primary());
+    println!("{}", i18n::underline(heading).primary());
+
+    if !cfg!(target_os = "linux") {
+        println!("  Turbo/boost reporting is only supported on Linux");
+        return Ok(());
+    }
+    if monitor.cores.is_empty() {
+        println!("  No cpufreq driver, clocks are not visible (virtual machine?)");
+        return Ok(());
+    }
+
+    let state = match monitor.enabled {
+        Some(true) if monitor.limits.is_empty() => "enabled".good(),
+        Some(true) => "enabled, limited".warning(),
+        Some(false) => "disabled".critical(),
+        None => "no boost control".normal(),
+    };
+    println!(
+        "Boost: {} (driver {})",
+        state,
+        monitor.driver.as_deref().unwrap_or("unknown")
+    );
+    for limit in &monitor.limits {
+        println!("  {}", limit.warning());
+    }
+
+    if verbosity::quiet() {
+        return Ok(());
+    }
+    println!(
+        "  {:<5} {:>10} {:>10} {:>10} {:>10}",
+        "CPU", "CURRENT", "BASE", "MAX", "ALLOWED"
+    );
+    for core in &monitor.cores {
+        // Above base means the core is boosting right now
+        let current = format!("{:>10}", mhz(Some(core.current)));
+        let current = if core.base.is_some_and(|base| core.current > base) {
+            current.highlight()
+        } else {
+            current.value()
+        };
+        let limit = format!("{:>10}", mhz(core.limit));
+        let limit = match (core.limit, core.max) {
+            (Some(clock), Some(max)) if clock < max => limit.warning(),
+            _ => limit.normal(),
+        };
+        println!(
+            "  {:<5} {} {:>10} {:>10} {}",
+            format!("#{}", core.cpu),
+            current,
+            mhz(core.base),
+            mhz(core.max),
+            limit
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+
+use crate::i18n;
+use crate::process::format_duration;
+use crate::theme::Themed;
+
+// Scanning every process's open files is not done on every refresh
+const SCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+// V4L2 drivers of the Pi's CSI-2 receivers: unicam up to the Pi 4, rp1-cfe on the Pi 5
+const RECEIVERS: [&str; 3] = ["unicam", "bcm2835-unicam", "rp1-cfe"];
+
+// Capture tools of the legacy (MMAL) camera stack, which talk to the
+// firmware instead of opening a video device
+const LEGACY_TOOLS: [&str; 3] = ["raspistill", "raspivid", "raspiyuv"];
+
+// A camera sensor found on a CSI port
+#[derive(Debug, Clone)]
+pub struct Camera {
+    // Sensor model, e.g. "imx219"
+    pub model: String,
+    // Receiver driver, or "firmware" for the legacy stack
+    pub receiver: String,
+}
+
+// A process with a camera device open
+#[derive(Debug, Clone)]
+pub struct CameraUser {
+    pub pid: u32,
+    pub name: String,
+    pub device: String,
+    pub running_secs: u64,
+}
+
+// Camera detection from sysfs (libcamera stack) or `vcgencmd get_camera`
+// (legacy stack), and which processes are holding it
+pub struct CameraMonitor {
+    cameras: Vec<Camera>,
+    // Device nodes of the capture pipeline: video, media and sensor subdevices
+    devices: HashSet<PathBuf>,
+    legacy_detected: bool,
+    users: Vec<CameraUser>,
+    last_scan: Option<Instant>,
+}
+
+impl CameraMonitor {
+    pub fn new() -> Self {
+        CameraMonitor {
+            cameras: Vec::new(),
+            devices: HashSet::new(),
+            // The firmware only probes at boot, so ask once
+            legacy_detected: legacy_camera_detected(),
+            users: Vec::new(),
+            last_scan: None,
+        }
+    }
+
+    pub fn cameras(&self) -> &[Camera] {
+        &self.cameras
+    }
+
+    pub fn users(&self) -> &[CameraUser] {
+        &self.users
+    }
+
+    pub fn refresh(&mut self, system: &System) {
+        if self
+            .last_scan
+            .is_some_and(|last| last.elapsed() < SCAN_INTERVAL)
+        {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+
+        let (mut cameras, devices) = scan_devices();
+        if cameras.is_empty() && self.legacy_detected {
+            cameras.push(Camera {
+                model: "camera".to_string(),
+                receiver: "firmware".to_string(),
+            });
+        }
+        self.cameras = cameras;
+        self.devices = devices;
+        self.users = find_users(system, &self.devices, self.legacy_detected);
+    }
+}
+
+// Sensors are V4L2 subdevices sitting on an I2C bus; the receiver's video
+// and media nodes are what capture processes open
+#[cfg(target_os = "linux")]
+fn scan_devices() -> (Vec<Camera>, HashSet<PathBuf>) {
+    let mut sensors = Vec::new();
+    let mut receivers = Vec::new();
+    let mut devices = HashSet::new();
+
+    let entries = fs::read_dir("/sys/class/video4linux")
+        .into_iter()
+        .flatten()
+        .flatten();
+    for entry in entries {
+        let node = entry.file_name().to_string_lossy().to_string();
+        let name = fs::read_to_string(entry.path().join("name")).unwrap_or_default();
+        let name = name.trim();
+        let on_i2c = fs::canonicalize(entry.path().join("device"))
+            .is_ok_and(|device| device.to_string_lossy().contains("/i2c-"));
+
+        if node.starts_with("v4l-subdev") && on_i2c {
+            // "imx219 10-0010"
+            sensors.push(name.split_whitespace().next().unwrap_or(name).to_string());
+            devices.insert(PathBuf::from("/dev").join(&node));
+        } else if let Some(receiver) = RECEIVERS.iter().find(|driver| name.starts_with(*driver)) {
+            if !receivers.contains(receiver) {
+                receivers.push(*receiver);
+            }
+            devices.insert(PathBuf::from("/dev").join(&node));
+        }
+    }
+
+    let media = fs::read_dir("/sys/bus/media/devices")
+        .into_iter()
+        .flatten()
+        .flatten();
+    for entry in media {
+        let model = fs::read_to_string(entry.path().join("model")).unwrap_or_default();
+        if RECEIVERS
+            .iter()
+            .any(|driver| model.trim().starts_with(driver))
+        {
+            devices.insert(PathBuf::from("/dev").join(entry.file_name()));
+        }
+    }
+
+    let receiver = receivers.first().copied().unwrap_or("csi");
+    let cameras = sensors
+        .into_iter()
+        .map(|model| Camera {
+            model,
+            receiver: receiver.to_string(),
+        })
+        .collect();
+    (cameras, devices)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn scan_devices() -> (Vec<Camera>, HashSet<PathBuf>) {
+    (Vec::new(), HashSet::new())
+}
+
+// "supported=1 detected=1, libcamera interfaces=0"
+fn legacy_camera_detected() -> bool {
+    Command::new("vcgencmd")
+        .arg("get_camera")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .is_some_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .split([' ', ','])
+                .any(|field| field == "detected=1")
+        })
+}
+
+// Processes with a camera device open. Other users' file descriptors can
+// only be read as root.
+fn find_users(system: &System, devices: &HashSet<PathBuf>, legacy: bool) -> Vec<CameraUser> {
+    let mut users = Vec::new();
+    for (pid, process) in system.processes() {
+        if legacy && LEGACY_TOOLS.contains(&process.name()) {
+            users.push(CameraUser {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                device: "firmware".to_string(),
+                running_secs: process.run_time(),
+            });
+            continue;
+        }
+        if devices.is_empty() {
+            continue;
+        }
+        let Ok(fds) = fs::read_dir(format!("/proc/{}/fd", pid.as_u32())) else {
+            continue;
+        };
+        let device = fds
+            .flatten()
+            .filter_map(|fd| fs::read_link(fd.path()).ok())
+            .find(|target| devices.contains(target));
+        if let Some(device) = device {
+            users.push(CameraUser {
+                pid: pid.as_u32(),
+                name: process.name().to_string(),
+                device: device.display().to_string(),
+                running_secs: process.run_time(),
+            });
+        }
+    }
+    users.sort_by_key(|user| user.pid);
+    users
+}
+
+// Camera panel: detected sensors and the processes holding them
+pub fn monitor_camera(monitor: &CameraMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-camera");
+    println!("\n{}", heading.bold().accent());
+    println!("{}", i18n::underline(heading).accent());
+
+    if monitor.cameras().is_empty() {
+        println!("{}", "No camera detected".value());
+        return Ok(());
+    }
+    for camera in monitor.cameras() {
+        println!(
+            "Camera: {} on {}",
+            camera.model.warning(),
+            camera.receiver.value()
+        );
+    }
+
+    if monitor.users().is_empty() {
+        println!("Status: {}", "idle".good());
+        return Ok(());
+    }
+    println!("Status: {}", "IN USE".warning().bold());
+    for user in monitor.users() {
+        println!(
+            "  {:<8} {:<20} {:<16} running {}",
+            user.pid,
+            user.name,
+            user.device,
+            format_duration(user.running_secs).value()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+
+use crate::sockets::Protocol;
+
+// One conversation seen on the wire, from this host's side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Flow {
+    pub protocol: Protocol,
+    pub local_port: u16,
+    pub remote: SocketAddr,
+}
+
+// Bytes (received, sent) per flow since the last take()
+type FlowCounts = HashMap<Flow, (u64, u64)>;
+
+// Raw AF_PACKET capture of all interfaces, the way libpcap does it on Linux.
+// Needs CAP_NET_RAW; counts the full size of every TCP and UDP packet.
+pub struct PacketCapture {
+    counts: Arc<Mutex<FlowCounts>>,
+}
+
+impl PacketCapture {
+    #[cfg(target_os = "linux")]
+    pub fn start() -> Result<Self> {
+        use std::thread;
+
+        use anyhow::anyhow;
+        use log::debug;
+        use nix::errno::Errno;
+        use nix::sys::socket::{
+            recvfrom, setsockopt, socket, sockopt, AddressFamily, LinkAddr, SockFlag, SockProtocol,
+            SockType,
+        };
+        use nix::sys::time::{TimeVal, TimeValLike};
+
+        // Link-layer header types from linux/if_arp.h
+        const ARPHRD_LOOPBACK: u16 = 772;
+        const PACKET_OUTGOING: u8 = 4;
+
+        let fd = socket(
+            AddressFamily::Packet,
+            SockType::Raw,
+            SockFlag::SOCK_CLOEXEC,
+            SockProtocol::EthAll,
+        )
+        .map_err(|e| match e {
+            Errno::EPERM | Errno::EACCES => anyhow!(
+                "packet capture needs CAP_NET_RAW; run as root or grant it with \
+                 'sudo setcap cap_net_raw+ep $(which hercules)'"
+            ),
+            e => anyhow!("could not open a packet socket: {}", e),
+        })?;
+
+        // Wake up now and then on a quiet link to notice the capture was dropped
+        if let Err(e) = setsockopt(fd, sockopt::ReceiveTimeout, &TimeVal::seconds(1)) {
+            let _ = nix::unistd::close(fd);
+            return Err(anyhow!("could not set up the packet socket: {}", e));
+        }
+
+        let counts = Arc::new(Mutex::new(FlowCounts::new()));
+        // The thread only holds a weak reference and closes the socket once
+        // the capture is dropped, e.g. when a config reload replaces it
+        let weak = Arc::downgrade(&counts);
+        thread::spawn(move || {
+            let mut buffer = vec![0u8; 65536];
+            loop {
+                if weak.strong_count() == 0 {
+                    debug!("Packet capture stopped");
+                    break;
+                }
+                let (length, address) = match recvfrom::<LinkAddr>(fd, &mut buffer) {
+                    Ok(received) => received,
+                    Err(Errno::EINTR | Errno::EAGAIN) => continue,
+                    Err(e) => {
+                        debug!("Packet capture stopped: {}", e);
+                        break;
+                    }
+                };
+                let Some(address) = address else {
+                    continue;
+                };
+                // Loopback traffic is seen twice and never leaves the machine
+                if address.hatype() == ARPHRD_LOOPBACK {
+                    continue;
+                }
+                let Some((protocol, source, destination)) =
+                    parse_packet(&buffer[..length], address.hatype())
+                else {
+                    continue;
+                };
+
+                let outgoing = address.pkttype() == PACKET_OUTGOING;
+                let (local_port, remote) = if outgoing {
+                    (source.port(), destination)
+                } else {
+                    (destination.port(), source)
+                };
+                let Some(shared) = weak.upgrade() else {
+                    break;
+                };
+                let Ok(mut counts) = shared.lock() else {
+                    break;
+                };
+                let entry = counts
+                    .entry(Flow {
+                        protocol,
+                        local_port,
+                        remote,
+                    })
+                    .or_default();
+                if outgoing {
+                    entry.1 += length as u64;
+                } else {
+                    entry.0 += length as u64;
+                }
+            }
+            let _ = nix::unistd::close(fd);
+        });
+
+        Ok(PacketCapture { counts })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn start() -> Result<Self> {
+        Err(anyhow::anyhow!("packet capture is only supported on Linux"))
+    }
+
+    // Bytes per flow since the previous call
+    pub fn take(&self) -> FlowCounts {
+        self.counts
+            .lock()
+            .map(|mut counts| std::mem::take(&mut *counts))
+            .unwrap_or_default()
+    }
+}
+
+// Protocol, source and destination of a TCP or UDP packet
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_packet(frame: &[u8], hatype: u16) -> Option<(Protocol, SocketAddr, SocketAddr)> {
+    const ARPHRD_ETHER: u16 = 1;
+    const ETHERTYPE_VLAN: u16 = 0x8100;
+
+    // Ethernet and Wi-Fi frames carry a 14 byte header; tun, PPP and other
+    // point-to-point links deliver the bare IP packet
+    let packet = if hatype == ARPHRD_ETHER {
+        let mut ethertype = u16::from_be_bytes([*frame.get(12)?, *frame.get(13)?]);
+        let mut offset = 14;
+        if ethertype == ETHERTYPE_VLAN {
+            ethertype = u16::from_be_bytes([*frame.get(16)?, *frame.get(17)?]);
+            offset = 18;
+        }
+        if ethertype != 0x0800 && ethertype != 0x86DD {
+            return None;
+        }
+        frame.get(offset..)?
+    } else {
+        frame
+    };
+
+    let (protocol, source, destination, payload) = match packet.first()? >> 4 {
+        4 => {
+            let header_length = ((packet[0] & 0x0F) as usize) * 4;
+            let source: [u8; 4] = packet.get(12..16)?.try_into().ok()?;
+            let destination: [u8; 4] = packet.get(16..20)?.try_into().ok()?;
+            (
+                *packet.get(9)?,
+                IpAddr::V4(Ipv4Addr::from(source)),
+                IpAddr::V4(Ipv4Addr::from(destination)),
+                packet.get(header_length..)?,
+            )
+        }
+        // Extension headers are rare enough to skip
+        6 => {
+            let source: [u8; 16] = packet.get(8..24)?.try_into().ok()?;
+            let destination: [u8; 16] = packet.get(24..40)?.try_into().ok()?;
+            (
+                *packet.get(6)?,
+                IpAddr::V6(Ipv6Addr::from(source)),
+                IpAddr::V6(Ipv6Addr::from(destination)),
+                packet.get(40..)?,
+            )
+        }
+        _ => return None,
+    };
+
+    let protocol = match protocol {
+        6 => Protocol::Tcp,
+        17 => Protocol::Udp,
+        _ => return None,
+    };
+    let source_port = u16::from_be_bytes([*payload.first()?, *payload.get(1)?]);
+    let destination_port = u16::from_be_bytes([*payload.get(2)?, *payload.get(3)?]);
+    Some((
+        protocol,
+        SocketAddr::new(source, source_port),
+        SocketAddr::new(destination, destination_port),
+    ))
+}
@@ -0,0 +1,280 @@
+use std::process;
+use std::thread;
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::config;
+use crate::disks::{self, DiskFilter};
+use crate::throttle;
+use crate::units;
+
+// Nagios plugin exit codes
+const EXIT_OK: i32 = 0;
+const EXIT_WARNING: i32 = 1;
+const EXIT_CRITICAL: i32 = 2;
+const EXIT_UNKNOWN: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl Status {
+    fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "OK",
+            Status::Warning => "WARNING",
+            Status::Critical => "CRITICAL",
+        }
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            Status::Ok => EXIT_OK,
+            Status::Warning => EXIT_WARNING,
+            Status::Critical => EXIT_CRITICAL,
+        }
+    }
+}
+
+// One measured value and the thresholds it is held to
+struct Metric {
+    label: String,
+    value: f64,
+    unit: &'static str,
+    warn: Option<f64>,
+    crit: Option<f64>,
+}
+
+impl Metric {
+    fn status(&self) -> Status {
+        if self.crit.is_some_and(|crit| self.value >= crit) {
+            Status::Critical
+        } else if self.warn.is_some_and(|warn| self.value >= warn) {
+            Status::Warning
+        } else {
+            Status::Ok
+        }
+    }
+
+    fn describe(&self) -> String {
+        let threshold = match self.status() {
+            Status::Critical => self.crit.map(|crit| format!(" (crit {})", crit)),
+            Status::Warning => self.warn.map(|warn| format!(" (warn {})", warn)),
+            Status::Ok => None,
+        };
+        format!(
+            "{} {:.1}{}{}",
+            self.label,
+            self.value,
+            self.unit,
+            threshold.unwrap_or_default()
+        )
+    }
+
+    // Nagios performance data: 'label'=value[unit];warn;crit
+    fn perfdata(&self) -> String {
+        let threshold = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        let unit = if self.unit == "%" { "%" } else { "" };
+        format!(
+            "'{}'={:.1}{};{};{}",
+            self.label,
+            self.value,
+            unit,
+            threshold(self.warn),
+            threshold(self.crit)
+        )
+    }
+}
+
+// Handle "hercules check --cpu-warn 80 --cpu-crit 95 --disk-crit /:90 ..."
+pub fn run_check(args: &[String]) -> Result<()> {
+    let threshold = |name: &'static str, help: &'static str| {
+        Arg::new(name)
+            .long(name)
+            .value_name("VALUE")
+            .help(help)
+            .value_parser(clap::value_parser!(f64))
+    };
+    let disk_threshold = |name: &'static str, help: &'static str| {
+        Arg::new(name)
+            .long(name)
+            .value_name("MOUNT:PERCENT")
+            .help(help)
+            .action(ArgAction::Append)
+    };
+
+    let command = Command::new("check")
+        .about("Nagios-style health check: one status line, exit 0/1/2/3 for OK/WARNING/CRITICAL/UNKNOWN")
+        .arg(threshold("cpu-warn", "Warn when CPU usage reaches this percent"))
+        .arg(threshold("cpu-crit", "Critical when CPU usage reaches this percent"))
+        .arg(threshold("mem-warn", "Warn when memory usage reaches this percent"))
+        .arg(threshold("mem-crit", "Critical when memory usage reaches this percent"))
+        .arg(threshold("load-warn", "Warn when the 1 minute load average reaches this"))
+        .arg(threshold("load-crit", "Critical when the 1 minute load average reaches this"))
+        .arg(threshold("temp-warn", "Warn when the CPU temperature reaches this (°C, or °F with sensor_use_celsius off)"))
+        .arg(threshold("temp-crit", "Critical when the CPU temperature reaches this (°C, or °F with sensor_use_celsius off)"))
+        .arg(disk_threshold("disk-warn", "Warn when a mount is this full, e.g. /:80 (repeatable)"))
+        .arg(disk_threshold("disk-crit", "Critical when a mount is this full, e.g. /:90 (repeatable)"));
+
+    // Usage errors must not exit 2, which monitoring would read as CRITICAL
+    let matches = match command.try_get_matches_from(args) {
+        Ok(matches) => matches,
+        Err(e) => {
+            let _ = e.print();
+            process::exit(if e.use_stderr() {
+                EXIT_UNKNOWN
+            } else {
+                EXIT_OK
+            });
+        }
+    };
+
+    match collect_metrics(&matches) {
+        Ok(metrics) => {
+            let status = metrics
+                .iter()
+                .map(Metric::status)
+                .max()
+                .unwrap_or(Status::Ok);
+            let summary = metrics
+                .iter()
+                .map(Metric::describe)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let perfdata = metrics
+                .iter()
+                .map(Metric::perfdata)
+                .collect::<Vec<_>>()
+                .join(" ");
+            println!("HERCULES {} - {} | {}", status.label(), summary, perfdata);
+            process::exit(status.exit_code());
+        }
+        Err(e) => {
+            println!("HERCULES UNKNOWN - {}", e);
+            process::exit(EXIT_UNKNOWN);
+        }
+    }
+}
+
+fn collect_metrics(matches: &ArgMatches) -> Result<Vec<Metric>> {
+    let get = |name: &str| matches.get_one::<f64>(name).copied();
+
+    let config_manager = config::ConfigManager::new()?;
+    let file_config = config_manager.get_config();
+
+    // Listing disks statfs's every mount, so it runs with a timeout; a hung
+    // network mount must not hang the check itself
+    let mut system = disks::system_with_disk_list(file_config.network_mount_timeout_ms);
+    system.refresh_cpu();
+    system.refresh_memory();
+    disks::refresh_local_disks(&mut system);
+    // CPU usage is measured between two refreshes
+    thread::sleep(System::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_cpu();
+
+    let mut metrics = vec![Metric {
+        label: "cpu".to_string(),
+        value: system.global_cpu_info().cpu_usage() as f64,
+        unit: "%",
+        warn: get("cpu-warn"),
+        crit: get("cpu-crit"),
+    }];
+
+    if system.total_memory() > 0 {
+        metrics.push(Metric {
+            label: "mem".to_string(),
+            value: system.used_memory() as f64 / system.total_memory() as f64 * 100.0,
+            unit: "%",
+            warn: get("mem-warn"),
+            crit: get("mem-crit"),
+        });
+    }
+
+    if get("load-warn").is_some() || get("load-crit").is_some() {
+        metrics.push(Metric {
+            label: "load".to_string(),
+            value: system.load_average().one,
+            unit: "",
+            warn: get("load-warn"),
+            crit: get("load-crit"),
+        });
+    }
+
+    if get("temp-warn").is_some() || get("temp-crit").is_some() {
+        system.refresh_components_list();
+        let temp = throttle::cpu_temperature(&system)
+            .ok_or_else(|| anyhow!("no CPU temperature sensor found"))?;
+        metrics.push(Metric {
+            label: "temp".to_string(),
+            value: units::temperature(temp as f64),
+            unit: units::temperature_suffix(),
+            warn: get("temp-warn"),
+            crit: get("temp-crit"),
+        });
+    }
+
+    // Disk thresholds keyed by mount point, in the order first given
+    let mut disk_thresholds: Vec<(String, Option<f64>, Option<f64>)> = Vec::new();
+    for (name, critical) in [("disk-warn", false), ("disk-crit", true)] {
+        for spec in matches.get_many::<String>(name).into_iter().flatten() {
+            let (mount, percent) = parse_disk_threshold(spec)?;
+            let index = match disk_thresholds.iter().position(|(m, _, _)| *m == mount) {
+                Some(index) => index,
+                None => {
+                    disk_thresholds.push((mount, None, None));
+                    disk_thresholds.len() - 1
+                }
+            };
+            if critical {
+                disk_thresholds[index].2 = Some(percent);
+            } else {
+                disk_thresholds[index].1 = Some(percent);
+            }
+        }
+    }
+
+    let filter = DiskFilter::new(&[], file_config.disk_aggregate_bind_mounts);
+    let mounts = filter.mounts(&system);
+    for (mount, warn, crit) in disk_thresholds {
+        let view = mounts
+            .iter()
+            .find(|view| {
+                view.mount_points
+                    .iter()
+                    .any(|point| point.as_os_str() == mount.as_str())
+            })
+            .ok_or_else(|| anyhow!("mount point {} not found", mount))?;
+        let used = view.total_space.saturating_sub(view.available_space);
+        let percent = if view.total_space > 0 {
+            used as f64 / view.total_space as f64 * 100.0
+        } else {
+            0.0
+        };
+        metrics.push(Metric {
+            label: mount,
+            value: percent,
+            unit: "%",
+            warn,
+            crit,
+        });
+    }
+
+    Ok(metrics)
+}
+
+// "/var:90" -> ("/var", 90.0). Split on the last colon so Windows drives work.
+fn parse_disk_threshold(spec: &str) -> Result<(String, f64)> {
+    let (mount, percent) = spec
+        .rsplit_once(':')
+        .ok_or_else(|| anyhow!("invalid disk threshold '{}', expected MOUNT:PERCENT", spec))?;
+    let percent = percent
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map_err(|_| anyhow!("invalid percent in disk threshold '{}'", spec))?;
+    Ok((mount.to_string(), percent))
+}
@@ -0,0 +1,93 @@
+use std::env;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, Result};
+
+use crate::config::ConfigManager;
+
+// Copies text to the system clipboard. Builds with the clipboard feature
+// use the desktop clipboard when there is one; otherwise, e.g. over SSH to
+// a headless Pi, the terminal is asked to do it with an OSC 52 sequence,
+// which most terminals (and tmux with set-clipboard on) support.
+pub fn copy(text: &str) -> Result<&'static str> {
+    #[cfg(feature = "clipboard")]
+    match copy_desktop(text) {
+        Ok(()) => return Ok("clipboard"),
+        Err(e) => log::debug!("Desktop clipboard unavailable: {}", e),
+    }
+
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1B]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()?;
+    Ok("terminal clipboard")
+}
+
+// X11 and Wayland clipboards are served by the program that set them, so
+// the handle is kept for as long as Hercules runs
+#[cfg(feature = "clipboard")]
+fn copy_desktop(text: &str) -> Result<()> {
+    use std::sync::Mutex;
+
+    static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+    let mut clipboard = CLIPBOARD
+        .lock()
+        .map_err(|_| anyhow!("clipboard lock poisoned"))?;
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new()?);
+    }
+    if let Some(clipboard) = clipboard.as_mut() {
+        clipboard.set_text(text.to_string())?;
+    }
+    Ok(())
+}
+
+// The panels as plain text, from a fresh `hercules snapshot --plain` with
+// the same command line flags
+pub fn plain_snapshot(args: &[String]) -> Result<String> {
+    let mut forwarded = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Only mean something for a continuous run
+            "--count" | "--duration" => {
+                args.next();
+            }
+            "--once" => {}
+            arg if arg.starts_with("--count=") || arg.starts_with("--duration=") => {}
+            _ => forwarded.push(arg.clone()),
+        }
+    }
+
+    let output = Command::new(env::current_exe()?)
+        .arg("snapshot")
+        .args(forwarded)
+        .arg("--plain")
+        .arg("--config")
+        .arg(ConfigManager::new()?.config_path())
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow!("The snapshot failed ({})", output.status));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, &byte)| {
+            value | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(value >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,103 @@
+use std::env;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use anyhow::{anyhow, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, TimeZone};
+
+pub const DEFAULT_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const TIME_FORMAT: &str = "%H:%M:%S";
+
+// Set from timestamp_format; empty until then, meaning DEFAULT_FORMAT
+static FORMAT: RwLock<String> = RwLock::new(String::new());
+
+// TZ as the program was started with, restored by timezone = "local"
+static STARTUP_TZ: OnceLock<Option<String>> = OnceLock::new();
+
+// A strftime format, e.g. "%d.%m.%Y %H:%M" or "%Y-%m-%dT%H:%M:%S%z"
+pub fn validate_format(format: &str) -> Result<()> {
+    if format.is_empty() || StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return Err(anyhow!(
+            "Invalid timestamp format '{}'. Use strftime codes, e.g. \"{}\"",
+            format,
+            DEFAULT_FORMAT
+        ));
+    }
+    Ok(())
+}
+
+// "local", "utc", or a tz database name such as "Europe/Berlin"
+pub fn validate_timezone(timezone: &str) -> Result<()> {
+    match timezone.to_lowercase().as_str() {
+        "local" | "utc" => Ok(()),
+        _ if zone_file(timezone).is_some_and(|path| Path::new(&path).is_file()) => Ok(()),
+        _ => Err(anyhow!(
+            "Unknown timezone '{}'. Use local, utc or a name like Europe/Berlin",
+            timezone
+        )),
+    }
+}
+
+fn zone_file(name: &str) -> Option<String> {
+    // Keep names inside the zoneinfo directory
+    if name.starts_with('/') || name.split('/').any(|part| part == "..") {
+        return None;
+    }
+    let dir = env::var("TZDIR").unwrap_or_else(|_| "/usr/share/zoneinfo".to_string());
+    Some(format!("{}/{}", dir, name))
+}
+
+pub fn set_format(format: &str) {
+    if let Ok(mut current) = FORMAT.write() {
+        *current = format.to_string();
+    }
+}
+
+// Point local time at `timezone` by setting TZ, which chrono's Local follows,
+// so every displayed time moves with it. Only call this at startup, before
+// other threads read the environment.
+pub fn set_timezone(timezone: &str) -> Result<()> {
+    validate_timezone(timezone)?;
+    let startup = STARTUP_TZ.get_or_init(|| env::var("TZ").ok());
+    match timezone.to_lowercase().as_str() {
+        "local" => match startup {
+            Some(tz) => env::set_var("TZ", tz),
+            None => env::remove_var("TZ"),
+        },
+        "utc" => env::set_var("TZ", "UTC"),
+        _ => env::set_var("TZ", timezone),
+    }
+    Ok(())
+}
+
+// A timestamp in the configured format
+pub fn format<Tz: TimeZone>(time: &DateTime<Tz>) -> String
+where
+    Tz::Offset: Display,
+{
+    match FORMAT.read() {
+        Ok(format) if !format.is_empty() => time.format(&format).to_string(),
+        _ => time.format(DEFAULT_FORMAT).to_string(),
+    }
+}
+
+// Only the time of day, in the configured timezone, for event lists where
+// the date is today's anyway
+pub fn time<Tz: TimeZone>(time: &DateTime<Tz>) -> String {
+    time.with_timezone(&Local).format(TIME_FORMAT).to_string()
+}
+
+pub fn now() -> String {
+    format(&Local::now())
+}
+
+// Seconds since the epoch, as stored in the history database
+pub fn format_timestamp(ts: i64) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|time| format(&time))
+        .unwrap_or_default()
+}
@@ -1,46 +1,345 @@
 use crate::sensors::SensorConfig;
+use crate::theme::{self, Theme};
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// Groups shown by `conf list`, in display order
+const PROPERTY_GROUPS: [&str; 8] = [
+    "display",
+    "cpu",
+    "memory",
+    "disk",
+    "network",
+    "processes",
+    "system",
+    "sensors",
+];
+
+// Column used to order the process table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessSort {
+    Cpu,
+    Memory,
+    DiskRead,
+    DiskWrite,
+    DiskTotal,
+    Network,
+}
+
+impl ProcessSort {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "cpu" => Ok(ProcessSort::Cpu),
+            "memory" | "mem" => Ok(ProcessSort::Memory),
+            "disk_read" | "read" => Ok(ProcessSort::DiskRead),
+            "disk_write" | "write" => Ok(ProcessSort::DiskWrite),
+            "disk_total" | "disk" | "io" => Ok(ProcessSort::DiskTotal),
+            "network" | "net" => Ok(ProcessSort::Network),
+            _ => Err(anyhow!(
+                "Invalid sort '{}'. Use: cpu, memory, disk_read, disk_write, disk_total, network",
+                value
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessSort::Cpu => "cpu",
+            ProcessSort::Memory => "memory",
+            ProcessSort::DiskRead => "disk_read",
+            ProcessSort::DiskWrite => "disk_write",
+            ProcessSort::DiskTotal => "disk_total",
+            ProcessSort::Network => "network",
+        }
+    }
+}
 
 // Configuration structure that matches MonitorConfig
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct HerculesConfig {
     pub update_interval_ms: u64,
+    pub theme: String,
+    pub language: String,
+    pub units: String,
+    pub timestamp_format: String,
+    pub timezone: String,
     pub show_cpu: bool,
     pub show_memory: bool,
+    pub show_graphs: bool,
+    pub graph_height: usize,
+    pub show_rolling_stats: bool,
+    pub show_numa: bool,
+    pub numa_collapse_single_node: bool,
     pub show_disk: bool,
+    pub show_disk_io: bool,
+    pub inode_alert_percent: f64,
+    pub disk_exclude: Vec<String>,
+    pub disk_aggregate_bind_mounts: bool,
+    pub network_mount_timeout_ms: u64,
+    pub growth_paths: Vec<String>,
+    pub growth_alert_mb_per_hour: f64,
     pub show_network: bool,
     pub show_processes: bool,
     pub max_processes: usize,
+    pub process_sort: ProcessSort,
+    pub show_process_network: bool,
+    pub process_network_capture: bool,
+    pub process_memory_detail: bool,
+    pub show_containers: bool,
+    pub container_socket: String,
+    pub show_connections: bool,
+    pub connections_filter: String,
+    pub show_listening_ports: bool,
+    pub port_allowlist: Vec<u16>,
+    pub show_firewall: bool,
+    pub show_vpn: bool,
+    pub show_usb: bool,
+    pub show_peripherals: bool,
+    pub show_audio: bool,
+    pub show_displays: bool,
+    pub vpn_stale_secs: u64,
+    pub show_traffic_breakdown: bool,
+    pub tls_hosts: Vec<String>,
+    pub tls_alert_days: u64,
+    pub tls_check_hours: u64,
+    pub watchdog: Vec<String>,
+    pub watchdog_restart: bool,
+    pub ping_targets: Vec<String>,
+    pub show_connectivity: bool,
+    pub public_ip_url: String,
+    pub public_ip_refresh_mins: u64,
+    pub show_file_descriptors: bool,
+    pub fd_alert_percent: f64,
+    pub record_throttle_episodes: bool,
+    pub show_uptime: bool,
+    pub show_camera: bool,
+    pub check_eeprom_update: bool,
+    pub status_led: String,
+    pub display_backend: String,
+    pub display_i2c_bus: u8,
+    pub display_i2c_address: u16,
+    pub display_pages: Vec<String>,
+    pub display_page_secs: u64,
+    pub show_fan: bool,
+    pub show_boost: bool,
+    pub fan_thresholds: Vec<f64>,
+    pub fan_control: bool,
+    pub fan_control_pin: u8,
+    pub fan_control_frequency_hz: u64,
+    pub fan_control_curve: String,
+    pub fan_control_hysteresis: f64,
+    pub temp_warn_celsius: f64,
+    pub temp_crit_celsius: f64,
+    pub show_kernel_log: bool,
+    pub kernel_log_lines: usize,
+    pub show_journal: bool,
+    pub journal_units: Vec<String>,
+    pub journal_lines: usize,
+    pub boot_history_len: usize,
+    pub record_metrics: bool,
+    pub history_raw_hours: u64,
+    pub history_minute_days: u64,
+    pub history_hourly_days: u64,
+    pub report_schedule: String,
+    pub notify_webhook: String,
+    pub speedtest_download_url: String,
+    pub speedtest_upload_url: String,
+    pub speedtest_record: bool,
+    pub statsd_host: String,
+    pub statsd_prefix: String,
+    pub statsd_sample_rate: f64,
     pub continuous: bool,
     pub show_compact_mode: bool,
     pub show_installer: bool,
     pub show_sensors: bool,
     pub sensor_config: SensorConfig,
+    // Interactive keys by action, e.g. quit = "x" or scroll_down = "j down",
+    // replacing that action's default keys; see keymap.rs
+    pub keys: BTreeMap<String, String>,
 }
 
 impl Default for HerculesConfig {
     fn default() -> Self {
+        // Some defaults depend on the Raspberry Pi model, see hardware.rs
+        let profile = crate::hardware::profile();
         Self {
-            update_interval_ms: 1000,
+            update_interval_ms: profile.update_interval_ms,
+            theme: "default".to_string(),
+            language: "auto".to_string(),
+            units: "binary".to_string(),
+            timestamp_format: crate::clock::DEFAULT_FORMAT.to_string(),
+            timezone: "local".to_string(),
             show_cpu: true,
             show_memory: true,
+            show_graphs: profile.show_graphs,
+            graph_height: 4,
+            show_rolling_stats: true,
+            show_numa: false,
+            numa_collapse_single_node: true,
             show_disk: true,
+            show_disk_io: false,
+            inode_alert_percent: 90.0,
+            disk_exclude: crate::disks::DEFAULT_EXCLUDE
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            disk_aggregate_bind_mounts: false,
+            network_mount_timeout_ms: 1000,
+            growth_paths: Vec::new(),
+            growth_alert_mb_per_hour: 100.0,
             show_network: true,
             show_processes: false,
             max_processes: 10,
+            process_sort: ProcessSort::Cpu,
+            show_process_network: false,
+            process_network_capture: false,
+            process_memory_detail: false,
+            show_containers: false,
+            container_socket: String::new(),
+            show_connections: false,
+            connections_filter: String::new(),
+            show_listening_ports: false,
+            port_allowlist: Vec::new(),
+            show_firewall: false,
+            show_vpn: false,
+            show_usb: false,
+            show_peripherals: false,
+            show_audio: false,
+            show_displays: false,
+            vpn_stale_secs: 300,
+            show_traffic_breakdown: false,
+            tls_hosts: Vec::new(),
+            tls_alert_days: 14,
+            tls_check_hours: 12,
+            watchdog: Vec::new(),
+            watchdog_restart: false,
+            ping_targets: Vec::new(),
+            show_connectivity: false,
+            public_ip_url: "https://api.ipify.org".to_string(),
+            public_ip_refresh_mins: 30,
+            show_file_descriptors: false,
+            fd_alert_percent: 80.0,
+            record_throttle_episodes: true,
+            show_uptime: false,
+            show_camera: profile.show_camera,
+            check_eeprom_update: false,
+            status_led: String::new(),
+            display_backend: "none".to_string(),
+            display_i2c_bus: 1,
+            display_i2c_address: 0,
+            display_pages: vec![
+                "cpu".to_string(),
+                "memory".to_string(),
+                "temperature".to_string(),
+                "network".to_string(),
+            ],
+            display_page_secs: 5,
+            show_fan: profile.show_fan,
+            show_boost: false,
+            fan_thresholds: Vec::new(),
+            fan_control: false,
+            fan_control_pin: 18,
+            fan_control_frequency_hz: 25000,
+            fan_control_curve: "40:0,50:30,60:60,70:100".to_string(),
+            fan_control_hysteresis: 3.0,
+            temp_warn_celsius: profile.temp_warn_celsius,
+            temp_crit_celsius: profile.temp_crit_celsius,
+            show_kernel_log: false,
+            kernel_log_lines: 10,
+            show_journal: false,
+            journal_units: Vec::new(),
+            journal_lines: 10,
+            boot_history_len: 5,
+            record_metrics: false,
+            history_raw_hours: 24,
+            history_minute_days: 30,
+            history_hourly_days: 365,
+            report_schedule: String::new(),
+            notify_webhook: String::new(),
+            speedtest_download_url: "https://speed.cloudflare.com/__down?bytes=25000000"
+                .to_string(),
+            speedtest_upload_url: "https://speed.cloudflare.com/__up".to_string(),
+            speedtest_record: true,
+            statsd_host: String::new(),
+            statsd_prefix: "hercules".to_string(),
+            statsd_sample_rate: 1.0,
             continuous: true,
             show_compact_mode: false,
             show_installer: false,
             show_sensors: false,
             sensor_config: SensorConfig::default(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+// File formats the config can be stored in, picked by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ConfigFormat {
+    // Checked in this order when looking for an existing config file
+    const ALL: [ConfigFormat; 3] = [ConfigFormat::Toml, ConfigFormat::Yaml, ConfigFormat::Json];
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "toml" => Ok(ConfigFormat::Toml),
+            "yaml" | "yml" => Ok(ConfigFormat::Yaml),
+            "json" => Ok(ConfigFormat::Json),
+            _ => Err(anyhow!(
+                "Invalid config format '{}'. Use: toml, yaml, json",
+                value
+            )),
+        }
+    }
+
+    fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| Self::parse(ext).ok())
+            .unwrap_or(ConfigFormat::Toml)
+    }
+
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ConfigFormat::Toml => &["toml"],
+            ConfigFormat::Yaml => &["yaml", "yml"],
+            ConfigFormat::Json => &["json"],
         }
     }
+
+    fn deserialize(self, content: &str) -> Result<HerculesConfig> {
+        Ok(match self {
+            ConfigFormat::Toml => toml::from_str(content)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+            ConfigFormat::Json => serde_json::from_str(content)?,
+        })
+    }
+
+    fn serialize(self, config: &HerculesConfig) -> Result<String> {
+        Ok(match self {
+            ConfigFormat::Toml => toml::to_string_pretty(config)?,
+            ConfigFormat::Yaml => serde_yaml::to_string(config)?,
+            ConfigFormat::Json => serde_json::to_string_pretty(config)? + "\n",
+        })
+    }
 }
 
+// Set by --config, replaces the usual lookup in the config directory
+static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
 // Configuration manager
 pub struct ConfigManager {
     config_path: PathBuf,
@@ -49,12 +348,17 @@ pub struct ConfigManager {
 
 impl ConfigManager {
     pub fn new() -> Result<Self> {
-        let config_dir = Self::get_config_dir()?;
-        let config_path = config_dir.join("hercules.toml");
+        let config_path = match CONFIG_PATH.get() {
+            Some(path) => path.clone(),
+            None => Self::find_config_file(&Self::get_config_dir()?),
+        };
+        let config_dir = config_path
+            .parent()
+            .ok_or_else(|| anyhow!("Invalid config path: {}", config_path.display()))?;
 
         // Create config directory if it doesn't exist
         if !config_dir.exists() {
-            fs::create_dir_all(&config_dir)?;
+            fs::create_dir_all(config_dir)?;
         }
 
         let config = if config_path.exists() {
@@ -75,11 +379,24 @@ impl ConfigManager {
         &self.config
     }
 
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
     pub fn save(&self) -> Result<()> {
         Self::save_config(&self.config_path, &self.config)
     }
 
-    fn get_config_dir() -> Result<PathBuf> {
+    // Use this file instead of the one in the config directory. Made absolute
+    // so the reload watcher has a directory to watch.
+    pub fn set_config_path(path: &Path) -> Result<()> {
+        let path = std::path::absolute(path)?;
+        CONFIG_PATH
+            .set(path)
+            .map_err(|_| anyhow!("Config path already set"))
+    }
+
+    pub fn get_config_dir() -> Result<PathBuf> {
         if cfg!(windows) {
             if let Ok(appdata) = std::env::var("APPDATA") {
                 Ok(PathBuf::from(appdata).join("Hercules"))
@@ -87,7 +404,9 @@ impl ConfigManager {
                 Ok(PathBuf::from("C:\\ProgramData\\Hercules"))
             }
         } else {
-            if let Ok(home) = std::env::var("HOME") {
+            if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+                Ok(PathBuf::from(xdg).join("hercules"))
+            } else if let Ok(home) = std::env::var("HOME") {
                 Ok(PathBuf::from(home).join(".config").join("hercules"))
             } else {
                 Ok(PathBuf::from("/etc/hercules"))
@@ -95,27 +414,130 @@ impl ConfigManager {
         }
     }
 
-    fn load_config(path: &PathBuf) -> Result<HerculesConfig> {
+    // First of hercules.toml, .yaml, .yml or .json that exists, TOML for a new config
+    fn find_config_file(config_dir: &Path) -> PathBuf {
+        ConfigFormat::ALL
+            .iter()
+            .flat_map(|format| format.extensions())
+            .map(|ext| config_dir.join(format!("hercules.{}", ext)))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| config_dir.join("hercules.toml"))
+    }
+
+    fn load_config(path: &Path) -> Result<HerculesConfig> {
         let content = fs::read_to_string(path)?;
-        let config: HerculesConfig = toml::from_str(&content)?;
-        Ok(config)
+        ConfigFormat::from_path(path)
+            .deserialize(&content)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    fn save_config(path: &Path, config: &HerculesConfig) -> Result<()> {
+        fs::write(path, ConfigFormat::from_path(path).serialize(config)?)?;
+        Ok(())
     }
 
-    fn save_config(path: &PathBuf, config: &HerculesConfig) -> Result<()> {
-        let toml_string = toml::to_string_pretty(config)?;
-        fs::write(path, toml_string)?;
+    // Rewrite the config in another format. The old file is kept as a .bak so
+    // it is no longer picked up, but nothing is lost.
+    fn convert_config(format: &str) -> Result<()> {
+        let format = ConfigFormat::parse(format)?;
+        let config_manager = ConfigManager::new()?;
+        let old_path = config_manager.config_path.clone();
+        if ConfigFormat::from_path(&old_path) == format {
+            println!(
+                "Config is already {}: {}",
+                format.extensions()[0],
+                old_path.display()
+            );
+            return Ok(());
+        }
+
+        let new_path = old_path.with_extension(format.extensions()[0]);
+        Self::save_config(&new_path, &config_manager.config)?;
+        let mut backup = old_path.clone().into_os_string();
+        backup.push(".bak");
+        fs::rename(&old_path, &backup)?;
+
+        println!(
+            "{}",
+            theme::ascii(&format!(
+                "✓ Configuration converted to {}",
+                format.extensions()[0]
+            ))
+        );
+        println!("  Config file: {}", new_path.display());
+        println!("  Previous file kept as: {}", Path::new(&backup).display());
+        Ok(())
+    }
+
+    // Print the config to stdout, in the file's own format unless one is given
+    fn export_config(format: Option<&str>) -> Result<()> {
+        let config_manager = ConfigManager::new()?;
+        let format = match format {
+            Some(format) => ConfigFormat::parse(format)?,
+            None => ConfigFormat::from_path(&config_manager.config_path),
+        };
+        print!("{}", format.serialize(&config_manager.config)?);
+        Ok(())
+    }
+
+    // Replace the config with one read from a file, or stdin for "-". The
+    // format follows the file extension; stdin is tried as each format in turn.
+    fn import_config(source: &str) -> Result<()> {
+        let config = if source == "-" {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            ConfigFormat::ALL
+                .iter()
+                .find_map(|format| format.deserialize(&content).ok())
+                .ok_or_else(|| anyhow!("Could not parse stdin as TOML, YAML or JSON"))?
+        } else {
+            Self::load_config(Path::new(source))?
+        };
+
+        let mut config_manager = ConfigManager::new()?;
+        config_manager.config = config;
+        config_manager.save()?;
+        println!(
+            "{}",
+            theme::ascii(&format!("✓ Configuration imported from {}", source))
+        );
+        println!(
+            "  Config saved to: {}",
+            config_manager.config_path.display()
+        );
         Ok(())
     }
 
     // Handle the CLI configuration command with exact syntax: "hercules conf <property> -> <new value>"
     pub fn handle_conf_command(args: &[String]) -> Result<()> {
+        match args.get(1).map(String::as_str) {
+            Some("get") if args.len() == 3 => return Self::print_property(&args[2]),
+            Some("list") if args.len() <= 3 => {
+                return Self::list_config(args.get(2).map(String::as_str))
+            }
+            Some("convert") if args.len() == 3 => return Self::convert_config(&args[2]),
+            Some("export") if args.len() <= 3 => {
+                return Self::export_config(args.get(2).map(String::as_str))
+            }
+            Some("import") if args.len() == 3 => return Self::import_config(&args[2]),
+            _ => {}
+        }
+
         if args.len() < 4 || args[2] != "->" {
             return Err(anyhow!(
                 "Invalid syntax. Use: hercules conf <property> -> <new_value>\n\
                  Examples:\n\
                    hercules conf update_interval_ms -> 500\n\
                    hercules conf show_sensors -> true\n\
-                   hercules conf show_compact_mode -> false"
+                   hercules conf show_compact_mode -> false\n\
+                 Or read settings back with:\n\
+                   hercules conf get <property>\n\
+                   hercules conf list [group]\n\
+                 Or change the file format with:\n\
+                   hercules conf convert <toml|yaml|json>\n\
+                 Or share it between machines with:\n\
+                   hercules conf export [toml|yaml|json] > hercules.toml\n\
+                   hercules conf import <file|->"
             ));
         }
 
@@ -127,7 +549,13 @@ impl ConfigManager {
         match Self::set_property(&mut config_manager.config, property, new_value) {
             Ok(()) => {
                 config_manager.save()?;
-                println!("✓ Configuration updated: {} -> {}", property, new_value);
+                println!(
+                    "{}",
+                    theme::ascii(&format!(
+                        "✓ Configuration updated: {} -> {}",
+                        property, new_value
+                    ))
+                );
                 println!(
                     "  Config saved to: {}",
                     config_manager.config_path.display()
@@ -141,23 +569,277 @@ impl ConfigManager {
         Ok(())
     }
 
+    // Print just the value of one property, for scripts
+    fn print_property(property: &str) -> Result<()> {
+        let config_manager = ConfigManager::new()?;
+        let value = Self::property_value(&config_manager.config, property)?;
+        println!("{}", Self::format_value(property, &value));
+        Ok(())
+    }
+
+    // Print properties by group with their type, current and default value
+    fn list_config(group: Option<&str>) -> Result<()> {
+        if let Some(group) = group {
+            if !PROPERTY_GROUPS.contains(&group) && group != "hardware" {
+                return Err(anyhow!(
+                    "Unknown group '{}'. Use: {}, hardware",
+                    group,
+                    PROPERTY_GROUPS.join(", ")
+                ));
+            }
+        }
+
+        let config_manager = ConfigManager::new()?;
+        let defaults = HerculesConfig::default();
+        for name in PROPERTY_GROUPS {
+            if group.is_some_and(|group| group != name) {
+                continue;
+            }
+
+            println!("[{}]", name);
+            for (property, _) in Self::properties() {
+                if Self::property_group(property) != name {
+                    continue;
+                }
+                let current = Self::property_value(&config_manager.config, property)?;
+                let default = Self::property_value(&defaults, property)?;
+                let changed = if current != default {
+                    format!(" (default: {})", Self::format_value(property, &default))
+                } else {
+                    String::new()
+                };
+                println!(
+                    "  {:<28} {:<7} = {}{}",
+                    property,
+                    Self::value_type(&current),
+                    Self::format_value(property, &current),
+                    changed
+                );
+            }
+            println!();
+        }
+
+        if group.is_none_or(|group| group == "hardware") {
+            println!("[hardware] (detected, read-only)");
+            Self::print_hardware();
+            println!();
+        }
+        Ok(())
+    }
+
+    // Every show_* panel switch and whether the config file turns it on
+    pub fn panels() -> Result<Vec<(&'static str, bool)>> {
+        let config_manager = ConfigManager::new()?;
+        Self::properties()
+            .into_iter()
+            .filter(|(property, _)| property.starts_with("show_"))
+            .map(|(property, _)| {
+                let value = Self::property_value(&config_manager.config, property)?;
+                Ok((property, value.as_bool().unwrap_or(false)))
+            })
+            .collect()
+    }
+
+    // The detected board and the defaults tuned for it
+    fn print_hardware() {
+        for (name, value) in crate::hardware::describe() {
+            println!("  {:<28} = {}", name, value);
+        }
+    }
+
+    // Look a property up by its `conf` name in the serialized config
+    fn property_value(config: &HerculesConfig, property: &str) -> Result<serde_json::Value> {
+        let unknown = || {
+            anyhow!(
+                "Unknown property '{}'. Available properties:\n{}",
+                property,
+                Self::list_available_properties()
+            )
+        };
+        if !Self::properties().iter().any(|(name, _)| *name == property) {
+            return Err(unknown());
+        }
+
+        let json = serde_json::to_value(config)?;
+        // Sensor properties live in the nested sensor_config table
+        let value = match property.strip_prefix("sensor_") {
+            Some(field) => json
+                .get("sensor_config")
+                .and_then(|sensor| sensor.get(field)),
+            None => json.get(property),
+        };
+        value.cloned().ok_or_else(unknown)
+    }
+
+    // Render a value the way `conf <property> -> <value>` accepts it
+    fn format_value(property: &str, value: &serde_json::Value) -> String {
+        match value {
+            serde_json::Value::String(text) => text.clone(),
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(|item| Self::format_value(property, item))
+                .collect::<Vec<_>>()
+                .join(Self::list_separator(property)),
+            other => other.to_string(),
+        }
+    }
+
+    // Trigger commands may contain commas, so that list is split on ';'
+    fn list_separator(property: &str) -> &'static str {
+        match property {
+            "sensor_triggers" => "; ",
+            _ => ",",
+        }
+    }
+
+    fn value_type(value: &serde_json::Value) -> &'static str {
+        match value {
+            serde_json::Value::Bool(_) => "bool",
+            serde_json::Value::Number(_) => "number",
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Array(_) => "list",
+            _ => "value",
+        }
+    }
+
+    // Subsystem a property is listed under by `conf list`
+    fn property_group(property: &str) -> &'static str {
+        match property {
+            "show_sensors" => "sensors",
+            p if p.starts_with("sensor_") => "sensors",
+            "show_network"
+            | "show_connections"
+            | "connections_filter"
+            | "show_listening_ports"
+            | "port_allowlist"
+            | "show_firewall"
+            | "show_vpn"
+            | "show_traffic_breakdown"
+            | "ping_targets"
+            | "show_connectivity" => "network",
+            p if p.starts_with("public_ip_") || p.starts_with("vpn_") || p.starts_with("tls_") => {
+                "network"
+            }
+            p if p.starts_with("speedtest_") || p.starts_with("statsd_") => "network",
+            "inode_alert_percent" | "network_mount_timeout_ms" => "disk",
+            p if p.starts_with("growth_") => "disk",
+            p if p.contains("disk") => "disk",
+            "show_containers"
+            | "container_socket"
+            | "show_file_descriptors"
+            | "fd_alert_percent" => "processes",
+            p if p.contains("process") || p.starts_with("watchdog") => "processes",
+            "show_cpu" | "record_throttle_episodes" | "show_fan" | "show_boost" => "cpu",
+            p if p.starts_with("temp_") => "cpu",
+            p if p.starts_with("fan_") => "cpu",
+            p if p.contains("numa") => "cpu",
+            "show_memory" => "memory",
+            "show_uptime"
+            | "show_camera"
+            | "show_usb"
+            | "show_peripherals"
+            | "show_audio"
+            | "show_displays"
+            | "check_eeprom_update"
+            | "boot_history_len"
+            | "record_metrics"
+            | "report_schedule"
+            | "notify_webhook" => "system",
+            p if p.starts_with("history_") => "system",
+            p if p.contains("kernel_log") || p.contains("journal") => "system",
+            _ => "display",
+        }
+    }
+
     // Set a property value by string
-    fn set_property(config: &mut HerculesConfig, property: &str, value: &str) -> Result<()> {
+    pub fn set_property(config: &mut HerculesConfig, property: &str, value: &str) -> Result<()> {
         match property {
             "update_interval_ms" => {
                 config.update_interval_ms = value
                     .parse::<u64>()
                     .map_err(|_| anyhow!("Invalid number format for update_interval_ms"))?;
             }
+            "theme" => {
+                config.theme = Theme::find(value)?.name.to_string();
+            }
+            "language" => {
+                if !crate::i18n::is_available(value) {
+                    return Err(anyhow!(
+                        "Unknown language '{}'. Available: auto, {}",
+                        value,
+                        crate::i18n::available().join(", ")
+                    ));
+                }
+                config.language = value.to_string();
+            }
+            "units" => {
+                config.units = match crate::units::Units::parse(value)? {
+                    crate::units::Units::Binary => "binary",
+                    crate::units::Units::Si => "si",
+                }
+                .to_string();
+            }
+            "timestamp_format" => {
+                crate::clock::validate_format(value)?;
+                config.timestamp_format = value.to_string();
+            }
+            "timezone" => {
+                crate::clock::validate_timezone(value)?;
+                config.timezone = value.to_string();
+            }
             "show_cpu" => {
                 config.show_cpu = Self::parse_bool(value)?;
             }
             "show_memory" => {
                 config.show_memory = Self::parse_bool(value)?;
             }
+            "show_graphs" => {
+                config.show_graphs = Self::parse_bool(value)?;
+            }
+            "graph_height" => {
+                config.graph_height = value
+                    .parse::<usize>()
+                    .map_err(|_| anyhow!("Invalid number format for graph_height"))?;
+            }
+            "show_rolling_stats" => {
+                config.show_rolling_stats = Self::parse_bool(value)?;
+            }
+            "show_numa" => {
+                config.show_numa = Self::parse_bool(value)?;
+            }
+            "numa_collapse_single_node" => {
+                config.numa_collapse_single_node = Self::parse_bool(value)?;
+            }
             "show_disk" => {
                 config.show_disk = Self::parse_bool(value)?;
             }
+            "show_disk_io" => {
+                config.show_disk_io = Self::parse_bool(value)?;
+            }
+            "inode_alert_percent" => {
+                config.inode_alert_percent = value
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Invalid number format for inode_alert_percent"))?;
+            }
+            "disk_exclude" => {
+                config.disk_exclude = Self::parse_list(value);
+            }
+            "disk_aggregate_bind_mounts" => {
+                config.disk_aggregate_bind_mounts = Self::parse_bool(value)?;
+            }
+            "network_mount_timeout_ms" => {
+                config.network_mount_timeout_ms = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for network_mount_timeout_ms"))?;
+            }
+            "growth_paths" => {
+                config.growth_paths = Self::parse_list(value);
+            }
+            "growth_alert_mb_per_hour" => {
+                config.growth_alert_mb_per_hour = value
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Invalid number format for growth_alert_mb_per_hour"))?;
+            }
             "show_network" => {
                 config.show_network = Self::parse_bool(value)?;
             }
@@ -169,6 +851,263 @@ impl ConfigManager {
                     .parse::<usize>()
                     .map_err(|_| anyhow!("Invalid number format for max_processes"))?;
             }
+            "process_sort" => {
+                config.process_sort = ProcessSort::parse(value)?;
+            }
+            "show_process_network" => {
+                config.show_process_network = Self::parse_bool(value)?;
+            }
+            "process_network_capture" => {
+                config.process_network_capture = Self::parse_bool(value)?;
+            }
+            "process_memory_detail" => {
+                config.process_memory_detail = Self::parse_bool(value)?;
+            }
+            "show_containers" => {
+                config.show_containers = Self::parse_bool(value)?;
+            }
+            "container_socket" => {
+                config.container_socket = value.to_string();
+            }
+            "show_connections" => {
+                config.show_connections = Self::parse_bool(value)?;
+            }
+            "connections_filter" => {
+                config.connections_filter = value.to_string();
+            }
+            "show_listening_ports" => {
+                config.show_listening_ports = Self::parse_bool(value)?;
+            }
+            "port_allowlist" => {
+                config.port_allowlist = Self::parse_list(value)
+                    .iter()
+                    .map(|port| {
+                        port.parse::<u16>()
+                            .map_err(|_| anyhow!("Invalid port '{}' in port_allowlist", port))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+            }
+            "show_firewall" => {
+                config.show_firewall = Self::parse_bool(value)?;
+            }
+            "show_vpn" => {
+                config.show_vpn = Self::parse_bool(value)?;
+            }
+            "show_usb" => {
+                config.show_usb = Self::parse_bool(value)?;
+            }
+            "show_peripherals" => {
+                config.show_peripherals = Self::parse_bool(value)?;
+            }
+            "show_audio" => {
+                config.show_audio = Self::parse_bool(value)?;
+            }
+            "show_displays" => {
+                config.show_displays = Self::parse_bool(value)?;
+            }
+            "vpn_stale_secs" => {
+                config.vpn_stale_secs = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for vpn_stale_secs"))?;
+            }
+            "show_traffic_breakdown" => {
+                config.show_traffic_breakdown = Self::parse_bool(value)?;
+            }
+            "tls_hosts" => {
+                config.tls_hosts = Self::parse_list(value);
+            }
+            "tls_alert_days" => {
+                config.tls_alert_days = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for tls_alert_days"))?;
+            }
+            "tls_check_hours" => {
+                config.tls_check_hours = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for tls_check_hours"))?;
+            }
+            "watchdog" => {
+                config.watchdog = Self::parse_list(value);
+            }
+            "watchdog_restart" => {
+                config.watchdog_restart = Self::parse_bool(value)?;
+            }
+            "ping_targets" => {
+                config.ping_targets = Self::parse_list(value);
+            }
+            "show_connectivity" => {
+                config.show_connectivity = Self::parse_bool(value)?;
+            }
+            "public_ip_url" => {
+                config.public_ip_url = value.to_string();
+            }
+            "public_ip_refresh_mins" => {
+                config.public_ip_refresh_mins = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for public_ip_refresh_mins"))?;
+            }
+            "show_file_descriptors" => {
+                config.show_file_descriptors = Self::parse_bool(value)?;
+            }
+            "fd_alert_percent" => {
+                config.fd_alert_percent = value
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Invalid number format for fd_alert_percent"))?;
+            }
+            "record_throttle_episodes" => {
+                config.record_throttle_episodes = Self::parse_bool(value)?;
+            }
+            "show_uptime" => {
+                config.show_uptime = Self::parse_bool(value)?;
+            }
+            "show_camera" => {
+                config.show_camera = Self::parse_bool(value)?;
+            }
+            "check_eeprom_update" => {
+                config.check_eeprom_update = Self::parse_bool(value)?;
+            }
+            "status_led" => {
+                config.status_led = value.to_string();
+            }
+            "display_backend" => {
+                crate::output::validate_backend(value)?;
+                config.display_backend = value.to_string();
+            }
+            "display_i2c_bus" => {
+                config.display_i2c_bus = value
+                    .parse::<u8>()
+                    .map_err(|_| anyhow!("Invalid number format for display_i2c_bus"))?;
+            }
+            "display_i2c_address" => {
+                config.display_i2c_address = Self::parse_i2c_address(value)?;
+            }
+            "display_pages" => {
+                let pages = Self::parse_list(value);
+                crate::output::parse_pages(&pages)?;
+                config.display_pages = pages;
+            }
+            "display_page_secs" => {
+                config.display_page_secs = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for display_page_secs"))?;
+            }
+            "show_fan" => {
+                config.show_fan = Self::parse_bool(value)?;
+            }
+            "show_boost" => {
+                config.show_boost = Self::parse_bool(value)?;
+            }
+            "fan_thresholds" => {
+                config.fan_thresholds = crate::fan::parse_thresholds(value)?;
+            }
+            "fan_control" => {
+                config.fan_control = Self::parse_bool(value)?;
+            }
+            "fan_control_pin" => {
+                config.fan_control_pin = value
+                    .parse::<u8>()
+                    .map_err(|_| anyhow!("Invalid GPIO number for fan_control_pin"))?;
+                crate::pwmfan::validate_pin(config.fan_control_pin)?;
+            }
+            "fan_control_frequency_hz" => {
+                config.fan_control_frequency_hz = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for fan_control_frequency_hz"))?;
+            }
+            "fan_control_curve" => {
+                crate::pwmfan::parse_curve(value)?;
+                config.fan_control_curve = value.to_string();
+            }
+            "fan_control_hysteresis" => {
+                config.fan_control_hysteresis = value
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Invalid number format for fan_control_hysteresis"))?;
+            }
+            "temp_warn_celsius" => {
+                config.temp_warn_celsius = value
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Invalid number format for temp_warn_celsius"))?;
+            }
+            "temp_crit_celsius" => {
+                config.temp_crit_celsius = value
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Invalid number format for temp_crit_celsius"))?;
+            }
+            "boot_history_len" => {
+                config.boot_history_len = value
+                    .parse::<usize>()
+                    .map_err(|_| anyhow!("Invalid number format for boot_history_len"))?;
+            }
+            "record_metrics" => {
+                config.record_metrics = Self::parse_bool(value)?;
+            }
+            "history_raw_hours" => {
+                config.history_raw_hours = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for history_raw_hours"))?;
+            }
+            "history_minute_days" => {
+                config.history_minute_days = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for history_minute_days"))?;
+            }
+            "history_hourly_days" => {
+                config.history_hourly_days = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for history_hourly_days"))?;
+            }
+            "report_schedule" => {
+                if !value.trim().is_empty() {
+                    crate::report::Schedule::parse(value)?;
+                }
+                config.report_schedule = value.trim().to_string();
+            }
+            "notify_webhook" => {
+                config.notify_webhook = value.to_string();
+            }
+            "show_kernel_log" => {
+                config.show_kernel_log = Self::parse_bool(value)?;
+            }
+            "kernel_log_lines" => {
+                config.kernel_log_lines = value
+                    .parse::<usize>()
+                    .map_err(|_| anyhow!("Invalid number format for kernel_log_lines"))?;
+            }
+            "show_journal" => {
+                config.show_journal = Self::parse_bool(value)?;
+            }
+            "journal_units" => {
+                config.journal_units = Self::parse_list(value);
+            }
+            "journal_lines" => {
+                config.journal_lines = value
+                    .parse::<usize>()
+                    .map_err(|_| anyhow!("Invalid number format for journal_lines"))?;
+            }
+            "speedtest_download_url" => {
+                config.speedtest_download_url = value.to_string();
+            }
+            "speedtest_upload_url" => {
+                config.speedtest_upload_url = value.to_string();
+            }
+            "speedtest_record" => {
+                config.speedtest_record = Self::parse_bool(value)?;
+            }
+            "statsd_host" => {
+                config.statsd_host = value.to_string();
+            }
+            "statsd_prefix" => {
+                config.statsd_prefix = value.to_string();
+            }
+            "statsd_sample_rate" => {
+                let rate = value
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Invalid number format for statsd_sample_rate"))?;
+                if rate <= 0.0 || rate > 1.0 {
+                    return Err(anyhow!("statsd_sample_rate must be above 0 and at most 1"));
+                }
+                config.statsd_sample_rate = rate;
+            }
             "continuous" => {
                 config.continuous = Self::parse_bool(value)?;
             }
@@ -190,6 +1129,96 @@ impl ConfigManager {
             "sensor_use_celsius" => {
                 config.sensor_config.use_celsius = Self::parse_bool(value)?;
             }
+            "sensor_stream_target" => {
+                config.sensor_config.stream_target = value.to_string();
+            }
+            "sensor_stream_format" => {
+                crate::sensor_stream::StreamFormat::parse(value)?;
+                config.sensor_config.stream_format = value.to_lowercase();
+            }
+            "sensor_filter" => {
+                config.sensor_config.filter = crate::sensor_filter::FilterKind::parse(value)?
+                    .name()
+                    .to_string();
+            }
+            "sensor_filter_accel_cutoff_hz" => {
+                config.sensor_config.filter_accel_cutoff_hz =
+                    value.parse::<f64>().map_err(|_| {
+                        anyhow!("Invalid number format for sensor_filter_accel_cutoff_hz")
+                    })?;
+            }
+            "sensor_filter_gyro_cutoff_hz" => {
+                config.sensor_config.filter_gyro_cutoff_hz =
+                    value.parse::<f64>().map_err(|_| {
+                        anyhow!("Invalid number format for sensor_filter_gyro_cutoff_hz")
+                    })?;
+            }
+            "sensor_log_path" => {
+                config.sensor_config.log_path = value.to_string();
+            }
+            "sensor_log_batch_size" => {
+                config.sensor_config.log_batch_size = value
+                    .parse::<usize>()
+                    .map_err(|_| anyhow!("Invalid number format for sensor_log_batch_size"))?;
+            }
+            "sensor_log_flush_secs" => {
+                config.sensor_config.log_flush_secs = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for sensor_log_flush_secs"))?;
+            }
+            "sensor_triggers" => {
+                // Separated by ';' since commands may contain commas
+                let triggers: Vec<String> = value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                crate::sensor_triggers::validate(&triggers)?;
+                config.sensor_config.triggers = triggers;
+            }
+            "sensor_trigger_debounce_ms" => {
+                config.sensor_config.trigger_debounce_ms = value
+                    .parse::<u64>()
+                    .map_err(|_| anyhow!("Invalid number format for sensor_trigger_debounce_ms"))?;
+            }
+            "sensor_dead_reckoning" => {
+                config.sensor_config.dead_reckoning = Self::parse_bool(value)?;
+            }
+            "sensor_accel_units" => {
+                config.sensor_config.accel_units =
+                    crate::units::parse_acceleration_units(value)?.to_string();
+            }
+            "sensor_gyro_units" => {
+                config.sensor_config.gyro_units =
+                    crate::units::parse_rotation_units(value)?.to_string();
+            }
+            "sensor_ros2" => {
+                config.sensor_config.ros2 = Self::parse_bool(value)?;
+            }
+            "sensor_ros2_topic" => {
+                config.sensor_config.ros2_topic = value.to_string();
+            }
+            "sensor_ros2_frame_id" => {
+                config.sensor_config.ros2_frame_id = value.to_string();
+            }
+            "sensor_serial" => {
+                config.sensor_config.serial = value.to_string();
+            }
+            "sensor_interface" => {
+                config.sensor_config.interface = value
+                    .parse::<i32>()
+                    .map_err(|_| anyhow!("Invalid number format for sensor_interface"))?;
+            }
+            "sensor_hid_backend" => {
+                crate::sensors::HidBackend::parse(value)?;
+                config.sensor_config.hid_backend = value.to_lowercase();
+            }
+            "hardware" => {
+                return Err(anyhow!(
+                    "hardware is detected at startup and read-only, see `hercules conf list hardware`"
+                ));
+            }
             _ => {
                 return Err(anyhow!(
                     "Unknown property '{}'. Available properties:\n{}",
@@ -212,18 +1241,216 @@ impl ConfigManager {
         }
     }
 
-    fn list_available_properties() -> String {
-        let properties = vec![
+    // "0x3c" or "60"
+    fn parse_i2c_address(value: &str) -> Result<u16> {
+        let address = match value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+        {
+            Some(hex) => u16::from_str_radix(hex, 16),
+            None => value.parse::<u16>(),
+        }
+        .map_err(|_| anyhow!("Invalid I2C address '{}', e.g. 0x3c", value))?;
+        if address > 0x7F {
+            return Err(anyhow!("I2C addresses go up to 0x7f"));
+        }
+        Ok(address)
+    }
+
+    // Split a comma separated value, dropping empty entries
+    fn parse_list(value: &str) -> Vec<String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    // Every settable property with a short description
+    pub fn properties() -> Vec<(&'static str, &'static str)> {
+        vec![
             (
                 "update_interval_ms",
                 "Update interval in milliseconds (number)",
             ),
+            (
+                "theme",
+                "Color theme (default/solarized/gruvbox/monochrome/high-contrast)",
+            ),
+            ("language", "Language of panel headings and the CPU, memory and alerts labels (auto follows LANG; en/de/es/fr or a <config dir>/locales/<lang>.ftl)"),
+            ("units", "Size units: binary (KiB, MiB, GiB; powers of 1024) or si (kB, MB, GB; powers of 1000)"),
+            ("timestamp_format", "strftime format for timestamps in the header, logs, history and exports"),
+            ("timezone", "Timezone for timestamps: local, utc or a name like Europe/Berlin"),
             ("show_cpu", "Show CPU information (true/false)"),
             ("show_memory", "Show memory information (true/false)"),
+            (
+                "show_graphs",
+                "Show CPU, memory and network history charts (true/false)",
+            ),
+            ("graph_height", "Height of the history charts in lines (number)"),
+            ("show_rolling_stats", "Show 1m/5m/15m min/avg/max next to current values (true/false)"),
+            (
+                "show_numa",
+                "Show memory and CPU usage per NUMA node (true/false)",
+            ),
+            (
+                "numa_collapse_single_node",
+                "Collapse the NUMA panel to one line on single-node systems (true/false)",
+            ),
             ("show_disk", "Show disk information (true/false)"),
+            (
+                "show_disk_io",
+                "Show per-device disk throughput, IOPS and utilization (true/false)",
+            ),
+            (
+                "inode_alert_percent",
+                "Alert when a filesystem uses this % of its inodes, 0 to disable (number)",
+            ),
+            (
+                "disk_exclude",
+                "Hide disks whose mount, device or fs type matches, comma separated (e.g. /snap/*,tmpfs)",
+            ),
+            (
+                "disk_aggregate_bind_mounts",
+                "Show bind mounts of the same filesystem as one row (true/false)",
+            ),
+            (
+                "network_mount_timeout_ms",
+                "Time before an NFS/SMB mount is reported as stale (milliseconds)",
+            ),
+            ("growth_paths", "Files or directories whose growth is tracked, e.g. /var/log,/var/lib/docker/volumes (comma separated)"),
+            ("growth_alert_mb_per_hour", "Alert when a single file under growth_paths grows faster than this many MB per hour (number)"),
             ("show_network", "Show network information (true/false)"),
             ("show_processes", "Show process information (true/false)"),
             ("max_processes", "Maximum processes to show (number)"),
+            (
+                "process_sort",
+                "Process table order (cpu/memory/disk_read/disk_write/disk_total/network)",
+            ),
+            (
+                "show_process_network",
+                "Show per-process RX/TX in the process table, Linux only (true/false)",
+            ),
+            ("process_network_capture", "Measure per-process network traffic by packet capture instead of TCP counters; includes UDP, needs CAP_NET_RAW (true/false)"),
+            (
+                "process_memory_detail",
+                "Show PSS/USS from smaps_rollup in the process table, Linux 4.14+ only (true/false)",
+            ),
+            (
+                "show_containers",
+                "Show Docker/Podman containers (true/false)",
+            ),
+            (
+                "container_socket",
+                "Container engine socket path, empty to auto-detect (path)",
+            ),
+            (
+                "show_connections",
+                "Show open TCP/UDP connections (true/false)",
+            ),
+            (
+                "connections_filter",
+                "Only list connections on this port or process name (text)",
+            ),
+            (
+                "show_listening_ports",
+                "Show listening ports and flag unexpected ones (true/false)",
+            ),
+            (
+                "port_allowlist",
+                "Expected listening ports, comma separated (e.g. 22,80,443)",
+            ),
+            ("show_firewall", "Show the firewall status panel (nftables, iptables, ufw, Windows Firewall) (true/false)"),
+            ("show_vpn", "Show WireGuard, Tailscale and OpenVPN tunnel health (true/false)"),
+            ("vpn_stale_secs", "Alert when a tunnel has had no handshake or traffic for this many seconds (number)"),
+            ("show_traffic_breakdown", "Show live traffic by protocol, port and remote host (true/false)"),
+            ("tls_hosts", "Endpoints whose TLS certificates are checked, host or host:port (comma-separated list)"),
+            ("tls_alert_days", "Alert when a certificate expires within this many days (number)"),
+            ("tls_check_hours", "Hours between TLS certificate checks (number)"),
+            ("watchdog", "Processes or systemd units to supervise, as name or name=restart command (comma-separated list)"),
+            ("watchdog_restart", "Run the restart command when a watched process or unit is down (true/false)"),
+            (
+                "ping_targets",
+                "Hosts to ping for latency, comma separated; 'gateway' = default route",
+            ),
+            ("show_connectivity", "Show the public IP and internet connectivity panel (true/false)"),
+            ("public_ip_url", "URL that returns the public IP as plain text"),
+            ("public_ip_refresh_mins", "Minutes between public IP lookups; the last answer is cached across runs (number)"),
+            (
+                "show_file_descriptors",
+                "Show open file descriptors against limits (true/false)",
+            ),
+            (
+                "fd_alert_percent",
+                "Alert when descriptors reach this % of the limit, 0 to disable (number)",
+            ),
+            (
+                "record_throttle_episodes",
+                "Log CPU throttling episodes to the history database (true/false)",
+            ),
+            (
+                "show_uptime",
+                "Show uptime against the record uptime and recent boots (true/false)",
+            ),
+            ("show_camera", "Show Raspberry Pi camera status and which process is using it (true/false)"),
+            ("show_usb", "Show connected USB devices and when they were attached or detached (true/false)"),
+            ("show_peripherals", "Show paired Bluetooth devices and their battery levels (true/false)"),
+            ("show_audio", "Show the default audio output and input, volume and playing streams (true/false)"),
+            ("show_displays", "Show connected monitors and whether a desktop session or only a console is running (true/false)"),
+            ("check_eeprom_update", "Ask rpi-eeprom-update at startup whether a newer bootloader is available (true/false)"),
+            ("status_led", "LED showing system health: ACT, an LED in /sys/class/leds, or gpioN; solid when OK, blinking on alerts (empty to disable)"),
+            ("display_backend", "Also show stats on an attached display: none, ssd1306 (128x64 I2C OLED) or lcd1602 (16x2 LCD with I2C backpack)"),
+            ("display_i2c_bus", "I2C bus of the attached display, 1 for GPIO 2/3 on a Pi (number)"),
+            ("display_i2c_address", "I2C address of the attached display, e.g. 0x3c; 0 for the usual address of the backend"),
+            ("display_pages", "Pages a character LCD rotates through: cpu, memory, temperature, network, traffic, uptime (comma separated)"),
+            ("display_page_secs", "Seconds each page stays on a character LCD (number)"),
+            ("show_fan", "Show PoE HAT and case fan speed against the CPU temperature (true/false)"),
+            ("show_boost", "Show whether turbo/boost is on, per-core current, base and max clocks, and thermal or power limits (true/false)"),
+            ("fan_thresholds", "Fan curve in °C, one temperature per fan step, e.g. 45,55,65,75 (needs root)"),
+            ("fan_control", "Drive a PWM fan on a GPIO pin from the CPU temperature in daemon mode (true/false)"),
+            ("fan_control_pin", "GPIO pin with hardware PWM the fan is wired to: 12, 13, 18 or 19 (number)"),
+            ("fan_control_frequency_hz", "PWM frequency for fan control, 25000 for 4-pin PC fans (number)"),
+            ("fan_control_curve", "Fan speed per CPU temperature as °C:percent points, e.g. 40:0,50:30,60:60,70:100"),
+            ("fan_control_hysteresis", "Degrees °C the temperature must fall before the fan slows down (number)"),
+            ("temp_warn_celsius", "Raise a warning when the CPU reaches this temperature in °C (default depends on the board)"),
+            ("temp_crit_celsius", "Raise a critical alert when the CPU reaches this temperature in °C (default depends on the board)"),
+            ("boot_history_len", "Number of recent boots to list (number)"),
+            ("record_metrics", "Save CPU, memory, load, temperature and network samples to the history database every refresh (true/false)"),
+            ("history_raw_hours", "Hours to keep every recorded sample before rolling it into 1-minute averages (number)"),
+            ("history_minute_days", "Days to keep 1-minute averages before rolling them into hourly averages (number)"),
+            ("history_hourly_days", "Days to keep hourly averages (number)"),
+            ("report_schedule", "Cron schedule for summary reports in daemon mode, e.g. '0 8 * * *' (empty = off)"),
+            ("notify_webhook", "Webhook URL that scheduled reports are posted to (empty = none)"),
+            (
+                "show_kernel_log",
+                "Show kernel warnings/errors and alert on I/O, OOM, USB, power issues (true/false)",
+            ),
+            ("kernel_log_lines", "Kernel log lines to show (number)"),
+            (
+                "show_journal",
+                "Show systemd journal warnings and errors (true/false)",
+            ),
+            (
+                "journal_units",
+                "Units to follow, comma separated; empty = all (e.g. nginx.service)",
+            ),
+            ("journal_lines", "Journal lines to show (number)"),
+            (
+                "speedtest_download_url",
+                "URL downloaded by 'hercules speedtest' (url)",
+            ),
+            (
+                "speedtest_upload_url",
+                "URL that 'hercules speedtest' uploads to (url)",
+            ),
+            (
+                "speedtest_record",
+                "Save speed test results to the history database (true/false)",
+            ),
+            ("statsd_host", "StatsD server to send gauges to; empty = off (host:port)"),
+            ("statsd_prefix", "Prefix for StatsD metric names (text)"),
+            ("statsd_sample_rate", "Fraction of refreshes sent to StatsD (0-1)"),
             ("continuous", "Run in continuous mode (true/false)"),
             ("show_compact_mode", "Use compact display mode (true/false)"),
             ("show_installer", "Show installer options (true/false)"),
@@ -234,11 +1461,86 @@ impl ConfigManager {
             ),
             (
                 "sensor_use_celsius",
-                "Use Celsius for sensor temperature (true/false)",
+                "Show temperatures in Celsius, or Fahrenheit when false (true/false)",
             ),
-        ];
+            (
+                "sensor_stream_target",
+                "Stream every sensor reading over UDP to this address; empty = off (host:port)",
+            ),
+            (
+                "sensor_stream_format",
+                "Sensor stream packet format: osc or csv",
+            ),
+            (
+                "sensor_filter",
+                "Sensor smoothing: none, average, exponential or median",
+            ),
+            (
+                "sensor_filter_accel_cutoff_hz",
+                "Accelerometer filter cutoff in Hz; 0 = unfiltered (number)",
+            ),
+            (
+                "sensor_filter_gyro_cutoff_hz",
+                "Gyro filter cutoff in Hz; 0 = unfiltered (number)",
+            ),
+            (
+                "sensor_log_path",
+                "CSV file every sensor reading is appended to; empty = off (path)",
+            ),
+            (
+                "sensor_log_batch_size",
+                "Sensor readings buffered before they are written to the log (number)",
+            ),
+            (
+                "sensor_log_flush_secs",
+                "Longest time sensor readings stay buffered before being written (number)",
+            ),
+            (
+                "sensor_triggers",
+                "Sensor rules like 'tilt > 45deg -> alert' or 'gyro_magnitude > 200 deg/s -> run \"cmd\"', separated by ';'",
+            ),
+            (
+                "sensor_trigger_debounce_ms",
+                "How long a sensor trigger condition must hold before it fires (number)",
+            ),
+            (
+                "sensor_dead_reckoning",
+                "Experimental: estimate velocity and displacement from acceleration (true/false)",
+            ),
+            (
+                "sensor_accel_units",
+                "Acceleration units for display and export: m/s2 or g",
+            ),
+            (
+                "sensor_gyro_units",
+                "Gyro units for display and export: deg/s or rad/s",
+            ),
+            (
+                "sensor_ros2",
+                "Publish sensor readings as ROS 2 sensor_msgs/Imu; needs a ros2 build (true/false)",
+            ),
+            ("sensor_ros2_topic", "ROS 2 topic for IMU messages (text)"),
+            (
+                "sensor_ros2_frame_id",
+                "frame_id stamped on ROS 2 IMU messages (text)",
+            ),
+            (
+                "sensor_serial",
+                "Only use the sensor with this serial number, empty for any (text)",
+            ),
+            (
+                "sensor_interface",
+                "Only use this USB interface of the sensor, -1 for any (number)",
+            ),
+            (
+                "sensor_hid_backend",
+                "How HID sensors are reached: hidraw, libusb",
+            ),
+        ]
+    }
 
-        properties
+    fn list_available_properties() -> String {
+        Self::properties()
             .iter()
             .map(|(prop, desc)| format!("  {:<25} - {}", prop, desc))
             .collect::<Vec<_>>()
@@ -250,25 +1552,154 @@ impl ConfigManager {
         let config_manager = ConfigManager::new()?;
         let config = &config_manager.config;
 
-        println!("🔧 Hercules Configuration");
+        println!("{}", theme::ascii("🔧 Hercules Configuration"));
         println!("========================");
         println!("Config file: {}", config_manager.config_path.display());
         println!();
 
-        println!("📊 Display Settings:");
+        println!("{}", theme::ascii("📊 Display Settings:"));
         println!("  update_interval_ms      = {}", config.update_interval_ms);
+        println!("  theme                  = {:?}", config.theme);
+        println!("  language               = {:?}", config.language);
+        println!("  units                  = {:?}", config.units);
+        println!("  timestamp_format       = {:?}", config.timestamp_format);
+        println!("  timezone               = {:?}", config.timezone);
         println!("  show_cpu               = {}", config.show_cpu);
         println!("  show_memory            = {}", config.show_memory);
+        println!("  show_graphs            = {}", config.show_graphs);
+        println!("  graph_height           = {}", config.graph_height);
+        println!("  show_rolling_stats     = {}", config.show_rolling_stats);
+        println!("  show_numa              = {}", config.show_numa);
+        println!(
+            "  numa_collapse_single_node = {}",
+            config.numa_collapse_single_node
+        );
         println!("  show_disk              = {}", config.show_disk);
+        println!("  show_disk_io           = {}", config.show_disk_io);
+        println!("  inode_alert_percent    = {}", config.inode_alert_percent);
+        println!("  disk_exclude           = {:?}", config.disk_exclude);
+        println!(
+            "  disk_aggregate_bind_mounts = {}",
+            config.disk_aggregate_bind_mounts
+        );
+        println!(
+            "  network_mount_timeout_ms = {}",
+            config.network_mount_timeout_ms
+        );
+        println!("  growth_paths           = {:?}", config.growth_paths);
+        println!(
+            "  growth_alert_mb_per_hour = {}",
+            config.growth_alert_mb_per_hour
+        );
         println!("  show_network           = {}", config.show_network);
         println!("  show_processes         = {}", config.show_processes);
         println!("  max_processes          = {}", config.max_processes);
+        println!(
+            "  process_sort           = {}",
+            config.process_sort.as_str()
+        );
+        println!("  show_process_network   = {}", config.show_process_network);
+        println!(
+            "  process_network_capture = {}",
+            config.process_network_capture
+        );
+        println!(
+            "  process_memory_detail  = {}",
+            config.process_memory_detail
+        );
+        println!("  show_containers        = {}", config.show_containers);
+        println!("  container_socket       = {:?}", config.container_socket);
+        println!("  show_connections       = {}", config.show_connections);
+        println!("  connections_filter     = {:?}", config.connections_filter);
+        println!("  show_listening_ports   = {}", config.show_listening_ports);
+        println!("  port_allowlist         = {:?}", config.port_allowlist);
+        println!("  show_firewall          = {}", config.show_firewall);
+        println!("  show_vpn               = {}", config.show_vpn);
+        println!("  vpn_stale_secs         = {}", config.vpn_stale_secs);
+        println!(
+            "  show_traffic_breakdown = {}",
+            config.show_traffic_breakdown
+        );
+        println!("  tls_hosts              = {:?}", config.tls_hosts);
+        println!("  tls_alert_days         = {}", config.tls_alert_days);
+        println!("  tls_check_hours        = {}", config.tls_check_hours);
+        println!("  watchdog               = {:?}", config.watchdog);
+        println!("  watchdog_restart       = {}", config.watchdog_restart);
+        println!("  ping_targets           = {:?}", config.ping_targets);
+        println!("  show_connectivity      = {}", config.show_connectivity);
+        println!("  public_ip_url          = {}", config.public_ip_url);
+        println!(
+            "  public_ip_refresh_mins = {}",
+            config.public_ip_refresh_mins
+        );
+        println!(
+            "  show_file_descriptors  = {}",
+            config.show_file_descriptors
+        );
+        println!("  fd_alert_percent       = {}", config.fd_alert_percent);
+        println!(
+            "  record_throttle_episodes = {}",
+            config.record_throttle_episodes
+        );
+        println!("  show_uptime            = {}", config.show_uptime);
+        println!("  show_camera            = {}", config.show_camera);
+        println!("  show_usb               = {}", config.show_usb);
+        println!("  show_peripherals       = {}", config.show_peripherals);
+        println!("  show_audio             = {}", config.show_audio);
+        println!("  show_displays          = {}", config.show_displays);
+        println!("  check_eeprom_update    = {}", config.check_eeprom_update);
+        println!("  status_led             = {:?}", config.status_led);
+        println!("  display_backend        = {:?}", config.display_backend);
+        println!("  display_i2c_bus        = {}", config.display_i2c_bus);
+        println!("  display_i2c_address    = {}", config.display_i2c_address);
+        println!("  display_pages          = {:?}", config.display_pages);
+        println!("  display_page_secs      = {}", config.display_page_secs);
+        println!("  show_fan               = {}", config.show_fan);
+        println!("  show_boost             = {}", config.show_boost);
+        println!("  fan_thresholds         = {:?}", config.fan_thresholds);
+        println!("  fan_control            = {}", config.fan_control);
+        println!("  fan_control_pin        = {}", config.fan_control_pin);
+        println!(
+            "  fan_control_frequency_hz = {}",
+            config.fan_control_frequency_hz
+        );
+        println!("  fan_control_curve      = {}", config.fan_control_curve);
+        println!(
+            "  fan_control_hysteresis = {}",
+            config.fan_control_hysteresis
+        );
+        println!("  temp_warn_celsius      = {}", config.temp_warn_celsius);
+        println!("  temp_crit_celsius      = {}", config.temp_crit_celsius);
+        println!("  boot_history_len       = {}", config.boot_history_len);
+        println!("  record_metrics         = {}", config.record_metrics);
+        println!("  history_raw_hours      = {}", config.history_raw_hours);
+        println!("  history_minute_days    = {}", config.history_minute_days);
+        println!("  history_hourly_days    = {}", config.history_hourly_days);
+        println!("  report_schedule        = {}", config.report_schedule);
+        println!("  notify_webhook         = {}", config.notify_webhook);
+        println!("  show_kernel_log        = {}", config.show_kernel_log);
+        println!("  kernel_log_lines       = {}", config.kernel_log_lines);
+        println!("  show_journal           = {}", config.show_journal);
+        println!("  journal_units          = {:?}", config.journal_units);
+        println!("  journal_lines          = {}", config.journal_lines);
+        println!(
+            "  speedtest_download_url = {:?}",
+            config.speedtest_download_url
+        );
+        println!(
+            "  speedtest_upload_url   = {:?}",
+            config.speedtest_upload_url
+        );
+        println!("  speedtest_record       = {}", config.speedtest_record);
+        println!("  statsd_host            = {:?}", config.statsd_host);
+        println!("  statsd_prefix          = {:?}", config.statsd_prefix);
+        println!("  statsd_sample_rate     = {}", config.statsd_sample_rate);
         println!("  continuous             = {}", config.continuous);
         println!("  show_compact_mode      = {}", config.show_compact_mode);
         println!("  show_installer         = {}", config.show_installer);
         println!();
 
-        println!("🔬 Sensor Settings:");
+        println!("{}", theme::ascii("🔬 Sensor Settings:"));
         println!("  show_sensors           = {}", config.show_sensors);
         println!(
             "  sensor_update_interval_ms = {}",
@@ -278,9 +1709,111 @@ impl ConfigManager {
             "  sensor_use_celsius     = {}",
             config.sensor_config.use_celsius
         );
+        println!(
+            "  sensor_stream_target   = {:?}",
+            config.sensor_config.stream_target
+        );
+        println!(
+            "  sensor_stream_format   = {:?}",
+            config.sensor_config.stream_format
+        );
+        println!(
+            "  sensor_filter          = {:?}",
+            config.sensor_config.filter
+        );
+        println!(
+            "  sensor_filter_accel_cutoff_hz = {}",
+            config.sensor_config.filter_accel_cutoff_hz
+        );
+        println!(
+            "  sensor_filter_gyro_cutoff_hz  = {}",
+            config.sensor_config.filter_gyro_cutoff_hz
+        );
+        // Device descriptors are edited in the config file, see the README
+        for device in &config.sensor_config.devices {
+            println!(
+                "  sensor device          = {} ({:04x}:{:04x}{})",
+                device.name,
+                device.vendor_id,
+                device.product_id,
+                device
+                    .report_id
+                    .map(|id| format!(", report {:#04x}", id))
+                    .unwrap_or_default()
+            );
+        }
+        println!(
+            "  sensor_log_path        = {:?}",
+            config.sensor_config.log_path
+        );
+        println!(
+            "  sensor_log_batch_size  = {}",
+            config.sensor_config.log_batch_size
+        );
+        println!(
+            "  sensor_log_flush_secs  = {}",
+            config.sensor_config.log_flush_secs
+        );
+        println!(
+            "  sensor_triggers        = {:?}",
+            config.sensor_config.triggers
+        );
+        println!(
+            "  sensor_trigger_debounce_ms = {}",
+            config.sensor_config.trigger_debounce_ms
+        );
+        println!(
+            "  sensor_dead_reckoning  = {}",
+            config.sensor_config.dead_reckoning
+        );
+        println!(
+            "  sensor_accel_units     = {:?}",
+            config.sensor_config.accel_units
+        );
+        println!(
+            "  sensor_gyro_units      = {:?}",
+            config.sensor_config.gyro_units
+        );
+        println!("  sensor_ros2            = {}", config.sensor_config.ros2);
+        println!(
+            "  sensor_ros2_topic      = {:?}",
+            config.sensor_config.ros2_topic
+        );
+        println!(
+            "  sensor_ros2_frame_id   = {:?}",
+            config.sensor_config.ros2_frame_id
+        );
+        println!(
+            "  sensor_serial          = {:?}",
+            config.sensor_config.serial
+        );
+        println!(
+            "  sensor_interface       = {}",
+            config.sensor_config.interface
+        );
+        println!(
+            "  sensor_hid_backend     = {}",
+            config.sensor_config.hid_backend
+        );
+        println!();
+
+        println!(
+            "{}",
+            theme::ascii("⌨️ Keys (the [keys] table in the config file):")
+        );
+        if config.keys.is_empty() {
+            println!("  defaults, press ? in the monitor to list them");
+        }
+        for (action, keys) in &config.keys {
+            println!("  {:<22} = {:?}", action, keys);
+        }
+        println!();
+
+        println!("{}", theme::ascii("🍓 Hardware (detected, read-only):"));
+        Self::print_hardware();
         println!();
 
-        println!("💡 Usage Examples:");
+        println!("{}", theme::ascii("💡 Usage Examples:"));
         println!("  hercules conf show_sensors -> true");
         println!("  hercules conf update_interval_ms -> 500");
         println!("  hercules conf show_compact_mode -> false");
@@ -294,7 +1827,7 @@ impl ConfigManager {
         let mut config_manager = ConfigManager::new()?;
         config_manager.config = HerculesConfig::default();
         config_manager.save()?;
-        println!("✓ Configuration reset to defaults");
+        println!("{}", theme::ascii("✓ Configuration reset to defaults"));
         println!("  Config file: {}", config_manager.config_path.display());
         Ok(())
     }
@@ -307,15 +1840,92 @@ impl From<&HerculesConfig> for crate::MonitorConfig {
             update_interval_ms: config.update_interval_ms,
             show_cpu: config.show_cpu,
             show_memory: config.show_memory,
+            show_graphs: config.show_graphs,
+            graph_height: config.graph_height,
+            show_rolling_stats: config.show_rolling_stats,
+            show_numa: config.show_numa,
+            numa_collapse_single_node: config.numa_collapse_single_node,
             show_disk: config.show_disk,
+            show_disk_io: config.show_disk_io,
+            inode_alert_percent: config.inode_alert_percent,
+            disk_exclude: config.disk_exclude.clone(),
+            disk_aggregate_bind_mounts: config.disk_aggregate_bind_mounts,
+            network_mount_timeout_ms: config.network_mount_timeout_ms,
+            growth_paths: config.growth_paths.clone(),
+            growth_alert_mb_per_hour: config.growth_alert_mb_per_hour,
             show_network: config.show_network,
             show_processes: config.show_processes,
             max_processes: config.max_processes,
+            process_sort: config.process_sort,
+            show_process_network: config.show_process_network,
+            process_network_capture: config.process_network_capture,
+            process_memory_detail: config.process_memory_detail,
+            show_containers: config.show_containers,
+            container_socket: config.container_socket.clone(),
+            show_connections: config.show_connections,
+            connections_filter: config.connections_filter.clone(),
+            show_listening_ports: config.show_listening_ports,
+            port_allowlist: config.port_allowlist.clone(),
+            show_firewall: config.show_firewall,
+            show_vpn: config.show_vpn,
+            show_usb: config.show_usb,
+            show_peripherals: config.show_peripherals,
+            show_audio: config.show_audio,
+            show_displays: config.show_displays,
+            vpn_stale_secs: config.vpn_stale_secs,
+            show_traffic_breakdown: config.show_traffic_breakdown,
+            tls_hosts: config.tls_hosts.clone(),
+            tls_alert_days: config.tls_alert_days,
+            tls_check_hours: config.tls_check_hours,
+            watchdog: config.watchdog.clone(),
+            watchdog_restart: config.watchdog_restart,
+            ping_targets: config.ping_targets.clone(),
+            show_connectivity: config.show_connectivity,
+            public_ip_url: config.public_ip_url.clone(),
+            public_ip_refresh_mins: config.public_ip_refresh_mins,
+            show_file_descriptors: config.show_file_descriptors,
+            fd_alert_percent: config.fd_alert_percent,
+            record_throttle_episodes: config.record_throttle_episodes,
+            show_uptime: config.show_uptime,
+            show_camera: config.show_camera,
+            check_eeprom_update: config.check_eeprom_update,
+            status_led: config.status_led.clone(),
+            display_backend: config.display_backend.clone(),
+            display_i2c_bus: config.display_i2c_bus,
+            display_i2c_address: config.display_i2c_address,
+            display_pages: config.display_pages.clone(),
+            display_page_secs: config.display_page_secs,
+            show_fan: config.show_fan,
+            show_boost: config.show_boost,
+            fan_thresholds: config.fan_thresholds.clone(),
+            fan_control: config.fan_control,
+            fan_control_pin: config.fan_control_pin,
+            fan_control_frequency_hz: config.fan_control_frequency_hz,
+            fan_control_curve: config.fan_control_curve.clone(),
+            fan_control_hysteresis: config.fan_control_hysteresis,
+            temp_warn_celsius: config.temp_warn_celsius,
+            temp_crit_celsius: config.temp_crit_celsius,
+            boot_history_len: config.boot_history_len,
+            record_metrics: config.record_metrics,
+            report_schedule: config.report_schedule.clone(),
+            notify_webhook: config.notify_webhook.clone(),
+            history_raw_hours: config.history_raw_hours,
+            history_minute_days: config.history_minute_days,
+            history_hourly_days: config.history_hourly_days,
+            show_kernel_log: config.show_kernel_log,
+            kernel_log_lines: config.kernel_log_lines,
+            show_journal: config.show_journal,
+            journal_units: config.journal_units.clone(),
+            journal_lines: config.journal_lines,
+            statsd_host: config.statsd_host.clone(),
+            statsd_prefix: config.statsd_prefix.clone(),
+            statsd_sample_rate: config.statsd_sample_rate,
             continuous: config.continuous,
             show_compact_mode: config.show_compact_mode,
             show_installer: config.show_installer,
             show_sensors: config.show_sensors,
             sensor_config: config.sensor_config.clone(),
+            keys: config.keys.clone(),
         }
     }
 }
@@ -0,0 +1,320 @@
+use std::fs;
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Local, TimeZone, Utc};
+use colored::*;
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::clock;
+use crate::config::ConfigManager;
+use crate::i18n;
+use crate::ping;
+use crate::process::format_duration;
+use crate::theme::Themed;
+
+// How often the default route and reachability are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+// Why the last check failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outage {
+    NoRoute,
+    Unreachable,
+}
+
+// What the connectivity panel shows
+#[derive(Debug, Clone, Default)]
+pub struct Connectivity {
+    pub public_ip: Option<String>,
+    // When the public IP was last looked up, as a Unix timestamp
+    pub ip_checked: Option<i64>,
+    pub gateway: Option<String>,
+    // None until the first check finishes
+    pub outage: Option<Option<Outage>>,
+    pub down_since: Option<DateTime<Local>>,
+    // When the previous outage ended and how long it lasted
+    pub last_outage: Option<(DateTime<Local>, u64)>,
+}
+
+// The last public IP answer, kept so a restart does not have to ask again
+#[derive(Serialize, Deserialize)]
+struct CachedIp {
+    ip: String,
+    checked: i64,
+}
+
+// Watches the default route and internet reachability from a background
+// thread, and looks up the public IP every `refresh_mins`
+pub struct ConnectivityMonitor {
+    state: Arc<Mutex<Connectivity>>,
+}
+
+impl ConnectivityMonitor {
+    pub fn start(lookup_url: &str, refresh_mins: u64) -> Self {
+        let cached = load_cached_ip();
+        let state = Arc::new(Mutex::new(Connectivity {
+            public_ip: cached.as_ref().map(|cached| cached.ip.clone()),
+            ip_checked: cached.as_ref().map(|cached| cached.checked),
+            ..Connectivity::default()
+        }));
+
+        // A weak reference lets the thread end once the monitor is dropped,
+        // e.g. when a config reload replaces it
+        let weak = Arc::downgrade(&state);
+        let url = lookup_url.to_string();
+        let refresh_secs = (refresh_mins.max(1) * 60) as i64;
+        thread::spawn(move || {
+            let agent = ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(5))
+                .timeout(Duration::from_secs(10))
+                .build();
+            loop {
+                if weak.strong_count() == 0 {
+                    debug!("Stopping connectivity monitor");
+                    return;
+                }
+                let gateway = ping::default_gateway();
+                let outage = if gateway.is_none() && !has_ipv6_default_route() {
+                    Some(Outage::NoRoute)
+                } else if !reachable(&url) {
+                    Some(Outage::Unreachable)
+                } else {
+                    None
+                };
+
+                let Some(shared) = weak.upgrade() else {
+                    return;
+                };
+                let (ip_checked, came_back) = match shared.lock() {
+                    Ok(mut state) => {
+                        let came_back = state.down_since.is_some() && outage.is_none();
+                        update_outage(&mut state, outage);
+                        state.gateway = gateway;
+                        (state.ip_checked, came_back)
+                    }
+                    Err(_) => return,
+                };
+
+                // Mobile links usually get a new address when they reconnect
+                let now = Utc::now().timestamp();
+                let stale = ip_checked.is_none_or(|checked| now - checked >= refresh_secs);
+                if outage.is_none() && (stale || came_back) {
+                    match lookup_public_ip(&agent, &url) {
+                        Ok(ip) => {
+                            save_cached_ip(&CachedIp {
+                                ip: ip.clone(),
+                                checked: now,
+                            });
+                            if let Ok(mut state) = shared.lock() {
+                                if state.public_ip.as_deref() != Some(ip.as_str()) {
+                                    info!("Public IP is now {}", ip);
+                                }
+                                state.public_ip = Some(ip);
+                                state.ip_checked = Some(now);
+                            }
+                        }
+                        Err(e) => debug!("Public IP lookup failed: {}", e),
+                    }
+                }
+                drop(shared);
+
+                thread::sleep(CHECK_INTERVAL);
+            }
+        });
+
+        ConnectivityMonitor { state }
+    }
+
+    pub fn snapshot(&self) -> Connectivity {
+        self.state
+            .lock()
+            .map(|state| state.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn refresh(&self, alerts: &mut AlertEngine) {
+        let state = self.snapshot();
+        match (state.outage, state.down_since) {
+            (Some(Some(outage)), Some(since)) => alerts.raise(
+                "connectivity",
+                AlertLevel::Critical,
+                "network",
+                format!(
+                    "No internet connectivity since {} ({})",
+                    clock::time(&since),
+                    describe(outage)
+                ),
+            ),
+            _ => alerts.clear("connectivity"),
+        }
+    }
+}
+
+// Record the start or end of an outage
+fn update_outage(state: &mut Connectivity, outage: Option<Outage>) {
+    let now = Local::now();
+    match (outage, state.down_since) {
+        (Some(_), None) => state.down_since = Some(now),
+        (None, Some(since)) => {
+            let lasted = (now - since).num_seconds().max(0) as u64;
+            info!("Connectivity restored after {}", format_duration(lasted));
+            state.last_outage = Some((now, lasted));
+            state.down_since = None;
+        }
+        _ => {}
+    }
+    state.outage = Some(outage);
+}
+
+fn describe(outage: Outage) -> &'static str {
+    match outage {
+        Outage::NoRoute => "no default route",
+        Outage::Unreachable => "internet unreachable",
+    }
+}
+
+// IPv6-only links have no IPv4 gateway but still a default route
+#[cfg(target_os = "linux")]
+fn has_ipv6_default_route() -> bool {
+    let Ok(routes) = fs::read_to_string("/proc/net/ipv6_route") else {
+        return false;
+    };
+    routes.lines().any(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        fields.len() == 10
+            && fields[0] == "00000000000000000000000000000000"
+            && fields[1] == "00"
+            && fields[9] != "lo"
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn has_ipv6_default_route() -> bool {
+    false
+}
+
+// A TCP connection to the lookup host; this needs DNS and a working route,
+// without spending a request against the lookup service
+fn reachable(url: &str) -> bool {
+    let (secure, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (scheme.eq_ignore_ascii_case("https"), rest),
+        None => (false, url),
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let address = if authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()))
+    {
+        authority.to_string()
+    } else {
+        format!("{}:{}", authority, if secure { 443 } else { 80 })
+    };
+
+    let Ok(addresses) = address.to_socket_addrs() else {
+        return false;
+    };
+    addresses
+        .into_iter()
+        .any(|address| TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).is_ok())
+}
+
+// The lookup service answers with the address as plain text
+fn lookup_public_ip(agent: &ureq::Agent, url: &str) -> Result<String> {
+    let body = agent.get(url).call()?.into_string()?;
+    let ip: IpAddr = body
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{} did not return an IP address", url))?;
+    Ok(ip.to_string())
+}
+
+fn cache_path() -> Option<PathBuf> {
+    ConfigManager::get_config_dir()
+        .ok()
+        .map(|dir| dir.join("public_ip.json"))
+}
+
+fn load_cached_ip() -> Option<CachedIp> {
+    let text = fs::read_to_string(cache_path()?).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn save_cached_ip(cached: &CachedIp) {
+    let Some(path) = cache_path() else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, serde_json::to_string(cached).unwrap_or_default()));
+    if let Err(e) = result {
+        debug!("Could not cache public IP in {}: {}", path.display(), e);
+    }
+}
+
+// Connectivity panel: online state, public IP and outage times
+pub fn monitor_connectivity(monitor: &ConnectivityMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-connectivity");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    let state = monitor.snapshot();
+    match state.outage {
+        None => println!("Internet: {}", "checking...".value()),
+        Some(None) => println!("Internet: {}", "ONLINE".good()),
+        Some(Some(outage)) => {
+            let since = state
+                .down_since
+                .map(|since| {
+                    let down = (Local::now() - since).num_seconds().max(0) as u64;
+                    format!(
+                        " since {} ({})",
+                        clock::format(&since),
+                        format_duration(down)
+                    )
+                })
+                .unwrap_or_default();
+            println!(
+                "Internet: {} - {}{}",
+                "OFFLINE".critical(),
+                describe(outage),
+                since
+            );
+        }
+    }
+
+    println!(
+        "Default Gateway: {}",
+        state.gateway.as_deref().unwrap_or("none").value()
+    );
+
+    let checked = state.ip_checked.and_then(|ts| {
+        Local.timestamp_opt(ts, 0).single().map(|time| {
+            let age = (Local::now() - time).num_seconds().max(0) as u64;
+            format!(" (checked {} ago)", format_duration(age))
+        })
+    });
+    match state.public_ip {
+        Some(ref ip) => println!("Public IP: {}{}", ip.value(), checked.unwrap_or_default()),
+        None => println!("Public IP: {}", "unknown".value()),
+    }
+
+    if let Some((ended, lasted)) = state.last_outage {
+        println!(
+            "Last Outage: ended {}, lasted {}",
+            clock::format(&ended),
+            format_duration(lasted).warning()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+#[cfg(unix)]
+use std::io::{Read, Write};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use log::debug;
+use serde_json::Value;
+
+use crate::i18n;
+use crate::theme::Themed;
+use crate::units;
+
+// Sockets probed (in order) when no explicit container_socket is configured
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+const PODMAN_ROOT_SOCKET: &str = "/run/podman/podman.sock";
+
+// One row of the containers panel
+#[derive(Debug, Clone)]
+pub struct ContainerStats {
+    pub name: String,
+    pub image: String,
+    pub state: String,
+    pub cpu_percent: Option<f64>,
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    pub net_rx_rate: f64,
+    pub net_tx_rate: f64,
+}
+
+// Counters from the previous stats call, used to turn totals into rates
+#[derive(Debug, Clone, Copy)]
+struct CounterSample {
+    cpu_total: u64,
+    system_total: u64,
+    net_rx: u64,
+    net_tx: u64,
+}
+
+// Polls the Docker-compatible API (Docker or Podman) for container stats
+pub struct ContainerMonitor {
+    socket: Option<PathBuf>,
+    last_samples: HashMap<String, CounterSample>,
+    containers: Vec<ContainerStats>,
+    error: Option<String>,
+}
+
+impl ContainerMonitor {
+    pub fn new(socket_override: &str) -> Self {
+        let socket = if socket_override.is_empty() {
+            find_container_socket()
+        } else {
+            Some(PathBuf::from(socket_override))
+        };
+
+        ContainerMonitor {
+            socket,
+            last_samples: HashMap::new(),
+            containers: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn refresh(&mut self, elapsed_secs: f64) {
+        match self.collect(elapsed_secs) {
+            Ok(containers) => {
+                self.containers = containers;
+                self.error = None;
+            }
+            Err(e) => {
+                debug!("Container refresh failed: {}", e);
+                self.containers.clear();
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn collect(&mut self, elapsed_secs: f64) -> Result<Vec<ContainerStats>> {
+        let socket = self
+            .socket
+            .clone()
+            .ok_or_else(|| anyhow!("No Docker or Podman socket found"))?;
+
+        let list = api_get(&socket, "/containers/json?all=true")?;
+        let entries = list
+            .as_array()
+            .ok_or_else(|| anyhow!("Unexpected response from container engine"))?;
+
+        let mut containers = Vec::new();
+        let mut samples = HashMap::new();
+
+        for entry in entries {
+            let id = entry["Id"].as_str().unwrap_or_default().to_string();
+            let name = entry["Names"][0]
+                .as_str()
+                .unwrap_or(&id)
+                .trim_start_matches('/')
+                .to_string();
+            let state = entry["State"].as_str().unwrap_or("unknown").to_string();
+
+            let mut stats = ContainerStats {
+                name,
+                image: entry["Image"].as_str().unwrap_or_default().to_string(),
+                state,
+                cpu_percent: None,
+                memory_usage: 0,
+                memory_limit: 0,
+                net_rx_rate: 0.0,
+                net_tx_rate: 0.0,
+            };
+
+            if stats.state == "running" {
+                let path = format!("/containers/{}/stats?stream=false&one-shot=true", id);
+                match api_get(&socket, &path) {
+                    Ok(raw) => {
+                        let sample = self.apply_stats(&id, &raw, elapsed_secs, &mut stats);
+                        samples.insert(id, sample);
+                    }
+                    Err(e) => debug!("Failed to read stats for {}: {}", stats.name, e),
+                }
+            }
+
+            containers.push(stats);
+        }
+
+        self.last_samples = samples;
+        Ok(containers)
+    }
+
+    fn apply_stats(
+        &self,
+        id: &str,
+        raw: &Value,
+        elapsed_secs: f64,
+        stats: &mut ContainerStats,
+    ) -> CounterSample {
+        let cpu_total = raw["cpu_stats"]["cpu_usage"]["total_usage"]
+            .as_u64()
+            .unwrap_or(0);
+        let system_total = raw["cpu_stats"]["system_cpu_usage"].as_u64().unwrap_or(0);
+        let online_cpus = raw["cpu_stats"]["online_cpus"].as_u64().unwrap_or(1).max(1);
+
+        // Page cache is reclaimable, so report usage the way `docker stats` does
+        let usage = raw["memory_stats"]["usage"].as_u64().unwrap_or(0);
+        let cache = raw["memory_stats"]["stats"]["inactive_file"]
+            .as_u64()
+            .or_else(|| raw["memory_stats"]["stats"]["cache"].as_u64())
+            .unwrap_or(0);
+        stats.memory_usage = usage.saturating_sub(cache);
+        stats.memory_limit = raw["memory_stats"]["limit"].as_u64().unwrap_or(0);
+
+        let (mut net_rx, mut net_tx) = (0u64, 0u64);
+        if let Some(networks) = raw["networks"].as_object() {
+            for interface in networks.values() {
+                net_rx += interface["rx_bytes"].as_u64().unwrap_or(0);
+                net_tx += interface["tx_bytes"].as_u64().unwrap_or(0);
+            }
+        }
+
+        if let Some(previous) = self.last_samples.get(id) {
+            let cpu_delta = cpu_total.saturating_sub(previous.cpu_total) as f64;
+            let system_delta = system_total.saturating_sub(previous.system_total) as f64;
+            if system_delta > 0.0 {
+                stats.cpu_percent = Some(cpu_delta / system_delta * online_cpus as f64 * 100.0);
+            }
+            if elapsed_secs > 0.0 {
+                stats.net_rx_rate = net_rx.saturating_sub(previous.net_rx) as f64 / elapsed_secs;
+                stats.net_tx_rate = net_tx.saturating_sub(previous.net_tx) as f64 / elapsed_secs;
+            }
+        }
+
+        CounterSample {
+            cpu_total,
+            system_total,
+            net_rx,
+            net_tx,
+        }
+    }
+
+    pub fn containers(&self) -> &[ContainerStats] {
+        &self.containers
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+fn find_container_socket() -> Option<PathBuf> {
+    let mut candidates = vec![
+        PathBuf::from(DOCKER_SOCKET),
+        PathBuf::from(PODMAN_ROOT_SOCKET),
+    ];
+    // Rootless Podman lives under the user's runtime directory
+    if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
+        candidates.push(
+            PathBuf::from(runtime_dir)
+                .join("podman")
+                .join("podman.sock"),
+        );
+    }
+
+    candidates.into_iter().find(|path| path.exists())
+}
+
+// Minimal HTTP/1.0 GET over the engine's unix socket. HTTP/1.0 keeps the
+// response unchunked so the body can be parsed directly.
+#[cfg(unix)]
+fn api_get(socket: &PathBuf, path: &str) -> Result<Value> {
+    let mut stream = UnixStream::connect(socket)
+        .map_err(|e| anyhow!("Failed to connect to {}: {}", socket.display(), e))?;
+    stream.set_read_timeout(Some(Duration::from_secs(3)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(3)))?;
+
+    write!(stream, "GET {} HTTP/1.0\r\nHost: localhost\r\n\r\n", path)?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    let response = String::from_utf8_lossy(&response);
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| anyhow!("Malformed HTTP response"))?;
+    let status_line = head.lines().next().unwrap_or_default();
+    if !status_line.contains(" 200 ") {
+        return Err(anyhow!("Container engine returned: {}", status_line));
+    }
+
+    Ok(serde_json::from_str(body)?)
+}
+
+#[cfg(not(unix))]
+fn api_get(_socket: &PathBuf, _path: &str) -> Result<Value> {
+    Err(anyhow!(
+        "Container monitoring is only supported on Unix sockets"
+    ))
+}
+
+// Containers panel
+pub fn monitor_containers(monitor: &ContainerMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-containers");
+    println!("\n{}", heading.bold().primary());
+    println!("{}", i18n::underline(heading).primary());
+
+    if let Some(error) = monitor.error() {
+        println!("  {}", error.critical());
+        return Ok(());
+    }
+
+    if monitor.containers().is_empty() {
+        println!("  No containers found");
+        return Ok(());
+    }
+
+    let kilo = units::Scale::fixed(1);
+    let mega = units::Scale::fixed(2);
+    println!(
+        "{:<20} {:<10} {:<8} {:<18} {:<10} {:<10} {:<20}",
+        "NAME",
+        "STATE",
+        "CPU%",
+        format!("MEM {} (LIMIT)", mega.name),
+        format!("RX {}/s", kilo.name),
+        format!("TX {}/s", kilo.name),
+        "IMAGE"
+    );
+
+    for container in monitor.containers() {
+        let name = if container.name.len() > 20 {
+            &container.name[0..17]
+        } else {
+            &container.name
+        };
+        let state = if container.state == "running" {
+            container.state.good()
+        } else {
+            container.state.warning()
+        };
+        let cpu = container
+            .cpu_percent
+            .map(|cpu| format!("{:.1}", cpu))
+            .unwrap_or_else(|| "-".to_string());
+        let memory = format!(
+            "{:.0} ({:.0})",
+            mega.apply(container.memory_usage as f64),
+            mega.apply(container.memory_limit as f64)
+        );
+
+        println!(
+            "{:<20} {:<10} {:<8} {:<18} {:<10.1} {:<10.1} {:<20}",
+            name,
+            state,
+            cpu,
+            memory,
+            kilo.apply(container.net_rx_rate),
+            kilo.apply(container.net_tx_rate),
+            container.image
+        );
+    }
+
+    Ok(())
+}
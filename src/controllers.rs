@@ -0,0 +1,132 @@
+use std::thread;
+use std::time::Duration;
+
+use crate::sensors::SensorDevice;
+use log::debug;
+
+use crate::sensors::SensorData;
+use crate::units;
+
+const GRAVITY: f32 = units::STANDARD_GRAVITY as f32;
+
+// Game controllers whose IMU reports need their own parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    DualShock4,
+    SwitchPro,
+}
+
+impl Controller {
+    // vendor id, product id
+    pub fn from_ids(vendor_id: u16, product_id: u16) -> Option<Self> {
+        match (vendor_id, product_id) {
+            (0x054c, 0x05c4 | 0x09cc | 0x0ba0) => Some(Controller::DualShock4),
+            (0x057e, 0x2009) => Some(Controller::SwitchPro),
+            _ => None,
+        }
+    }
+
+    // Both controllers start out sending reports without motion data
+    pub fn enable_motion(self, device: &SensorDevice) {
+        match self {
+            Controller::DualShock4 => ds4_enable_full_reports(device),
+            Controller::SwitchPro => switch_enable_imu(device),
+        }
+    }
+
+    // None for reports that carry no motion data, like button-only ones
+    pub fn parse(self, report: &[u8]) -> Option<SensorData> {
+        match self {
+            Controller::DualShock4 => parse_ds4(report),
+            Controller::SwitchPro => parse_switch_pro(report),
+        }
+    }
+}
+
+fn le_i16(report: &[u8], offset: usize) -> f32 {
+    i16::from_le_bytes([report[offset], report[offset + 1]]) as f32
+}
+
+// DualShock 4 nominal resolution; the factory calibration only trims these
+// by a few percent
+const DS4_GYRO_PER_DEG_S: f32 = 16.0;
+const DS4_ACCEL_PER_G: f32 = 8192.0;
+
+// Over USB the full report (0x01) comes by default. Over Bluetooth the pad
+// sends a reduced 0x01 report without motion data until a calibration
+// feature report is read, after which it sends 0x11 reports.
+fn ds4_enable_full_reports(device: &SensorDevice) {
+    for report_id in [0x02, 0x05] {
+        let mut buf = [0u8; 64];
+        buf[0] = report_id;
+        if let Err(e) = device.get_feature_report(&mut buf) {
+            debug!("DualShock 4 feature report {:#04x}: {}", report_id, e);
+        }
+    }
+}
+
+fn parse_ds4(report: &[u8]) -> Option<SensorData> {
+    // Bluetooth reports have two extra bytes before the USB layout
+    let offset = match report.first()? {
+        0x01 if report.len() >= 25 => 0,
+        0x11 if report.len() >= 27 => 2,
+        _ => return None,
+    };
+    let mut data = SensorData::default();
+    for axis in 0..3 {
+        data.gyro[axis] = le_i16(report, offset + 13 + axis * 2) / DS4_GYRO_PER_DEG_S;
+        data.acceleration[axis] =
+            le_i16(report, offset + 19 + axis * 2) / DS4_ACCEL_PER_G * GRAVITY;
+    }
+    Some(data)
+}
+
+// Switch Pro Controller sensitivities at its default ±8 g and ±2000 deg/s
+const SWITCH_ACCEL_G_PER_LSB: f32 = 0.000244;
+const SWITCH_GYRO_DEG_S_PER_LSB: f32 = 0.06103;
+
+// Neutral rumble data that has to precede every subcommand
+const SWITCH_NEUTRAL_RUMBLE: [u8; 8] = [0x00, 0x01, 0x40, 0x40, 0x00, 0x01, 0x40, 0x40];
+
+// Over USB the controller first needs a handshake and to be told to stay on
+// USB HID; then the IMU is switched on and the 0x30 full report mode picked.
+// Over Bluetooth the 0x80 commands are ignored.
+fn switch_enable_imu(device: &SensorDevice) {
+    for command in [[0x80, 0x02], [0x80, 0x03], [0x80, 0x02], [0x80, 0x04]] {
+        if let Err(e) = device.write(&command) {
+            debug!("Switch Pro USB command {:02x?}: {}", command, e);
+        }
+    }
+    // (subcommand, argument): enable the IMU, then standard full reports
+    for (counter, (subcommand, argument)) in
+        [(0x40u8, 0x01u8), (0x03, 0x30)].into_iter().enumerate()
+    {
+        let mut packet = vec![0x01, counter as u8];
+        packet.extend_from_slice(&SWITCH_NEUTRAL_RUMBLE);
+        packet.extend_from_slice(&[subcommand, argument]);
+        if let Err(e) = device.write(&packet) {
+            debug!("Switch Pro subcommand {:#04x}: {}", subcommand, e);
+        }
+        // Give the controller time to act on it before the next one
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+// A 0x30 report holds three IMU samples taken 5 ms apart; they are averaged
+// since the reports arrive every 15 ms
+fn parse_switch_pro(report: &[u8]) -> Option<SensorData> {
+    if report.first() != Some(&0x30) || report.len() < 49 {
+        return None;
+    }
+    let mut data = SensorData::default();
+    for sample in 0..3 {
+        let start = 13 + sample * 12;
+        for axis in 0..3 {
+            data.acceleration[axis] +=
+                le_i16(report, start + axis * 2) * SWITCH_ACCEL_G_PER_LSB * GRAVITY / 3.0;
+            data.gyro[axis] +=
+                le_i16(report, start + 6 + axis * 2) * SWITCH_GYRO_DEG_S_PER_LSB / 3.0;
+        }
+    }
+    Some(data)
+}
@@ -0,0 +1,91 @@
+use crate::fusion::{rotate, Quaternion};
+
+// Canvas size in characters; terminal cells are about twice as tall as wide,
+// so x is stretched by ASPECT to keep the box in proportion
+const WIDTH: usize = 27;
+const HEIGHT: usize = 11;
+const ASPECT: f32 = 2.0;
+
+// Half extents of a flat board, so up and down can be told apart at a glance
+const HALF: [f32; 3] = [1.0, 0.7, 0.3];
+
+// The view looks slightly down on the board, so a level board shows its top
+const VIEW_TILT_DEGREES: f32 = 25.0;
+const CAMERA_DISTANCE: f32 = 5.0;
+// Screen units per world unit at the centre of the box
+const ZOOM: f32 = 4.0;
+
+// The sensor as a wireframe box rotated by `quaternion`, seen from the front
+// with +x to the right and +z up. Top face corners are drawn as 'O' and
+// bottom ones as '+', so an upside-down mount shows immediately.
+pub fn render(quaternion: Quaternion) -> Vec<String> {
+    let corners: Vec<[f32; 3]> = (0..8)
+        .map(|i| {
+            let sign = |bit: usize| if i & bit == 0 { -1.0 } else { 1.0 };
+            [sign(1) * HALF[0], sign(2) * HALF[1], sign(4) * HALF[2]]
+        })
+        .collect();
+    let points: Vec<(f32, f32)> = corners
+        .iter()
+        .map(|corner| project(rotate(quaternion, *corner)))
+        .collect();
+
+    let mut canvas = vec![vec![' '; WIDTH]; HEIGHT];
+    // Edges join corners that differ in exactly one coordinate
+    for a in 0..8 {
+        for bit in [1, 2, 4] {
+            let b = a | bit;
+            if a != b {
+                line(&mut canvas, points[a], points[b]);
+            }
+        }
+    }
+    for (i, &(x, y)) in points.iter().enumerate() {
+        let top = i & 4 != 0;
+        plot(&mut canvas, x, y, if top { 'O' } else { '+' });
+    }
+
+    canvas
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>().trim_end().to_string())
+        .collect()
+}
+
+// World point to canvas column and row, in perspective
+fn project([x, y, z]: [f32; 3]) -> (f32, f32) {
+    let (sin, cos) = VIEW_TILT_DEGREES.to_radians().sin_cos();
+    let depth = y * cos - z * sin;
+    let up = y * sin + z * cos;
+    let scale = ZOOM * CAMERA_DISTANCE / (CAMERA_DISTANCE + depth);
+    (
+        (WIDTH as f32 - 1.0) / 2.0 + x * scale * ASPECT,
+        (HEIGHT as f32 - 1.0) / 2.0 - up * scale,
+    )
+}
+
+fn plot(canvas: &mut [Vec<char>], x: f32, y: f32, c: char) {
+    let (column, row) = (x.round(), y.round());
+    if column >= 0.0 && row >= 0.0 && (column as usize) < WIDTH && (row as usize) < HEIGHT {
+        canvas[row as usize][column as usize] = c;
+    }
+}
+
+// A line drawn with the character closest to its slope
+fn line(canvas: &mut [Vec<char>], (x0, y0): (f32, f32), (x1, y1): (f32, f32)) {
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let (across, down) = (dx / ASPECT, dy);
+    let c = if down.abs() < across.abs() * 0.4 {
+        '-'
+    } else if across.abs() < down.abs() * 0.4 {
+        '|'
+    } else if across * down > 0.0 {
+        '\\'
+    } else {
+        '/'
+    };
+    let steps = dx.abs().max(dy.abs()).ceil().max(1.0) as usize;
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        plot(canvas, x0 + dx * t, y0 + dy * t, c);
+    }
+}
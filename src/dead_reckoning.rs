@@ -0,0 +1,108 @@
+use std::time::Instant;
+
+use crate::fusion::rotate;
+use crate::sensors::SensorData;
+use crate::units::STANDARD_GRAVITY;
+
+// A reading counts as "at rest" when the acceleration is within this of 1 g
+// and the rotation rate below REST_GYRO_DEG_S
+const REST_ACCEL_TOLERANCE: f32 = 0.15; // m/s²
+const REST_GYRO_DEG_S: f32 = 2.0;
+// Consecutive rest readings before velocity is reset to zero
+const REST_SAMPLES: u32 = 5;
+// How fast the bias estimate follows the accelerometer while at rest
+const BIAS_RATE: f32 = 0.05;
+// Time after the last rest over which confidence falls to about a third;
+// double integration drifts quadratically, so estimates age fast
+const CONFIDENCE_SECS: f32 = 3.0;
+// Longest step to integrate, so a stall does not add a jump
+const MAX_STEP_SECS: f32 = 0.5;
+
+// Estimated motion since dead reckoning started, in the world frame (x and y
+// horizontal, z up)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Motion {
+    pub velocity: [f32; 3],     // m/s
+    pub displacement: [f32; 3], // m
+    // 1.0 right after a zero-velocity update, falling while moving
+    pub confidence: f32,
+    pub at_rest: bool,
+    // Seconds since the sensor was last at rest
+    pub moving_secs: f32,
+}
+
+// Integrates bias-corrected acceleration twice. Whenever the sensor is still
+// the velocity is known to be zero, which resets the drift the integration
+// has built up (a zero-velocity update) and refines the bias estimate.
+pub struct DeadReckoning {
+    motion: Motion,
+    // World-frame acceleration left over at rest: sensor offset plus any
+    // tilt error in the orientation
+    bias: [f32; 3],
+    rest_samples: u32,
+    last: Option<Instant>,
+    last_rest: Instant,
+}
+
+impl DeadReckoning {
+    pub fn new() -> Self {
+        DeadReckoning {
+            motion: Motion {
+                confidence: 1.0,
+                ..Motion::default()
+            },
+            bias: [0.0; 3],
+            rest_samples: 0,
+            last: None,
+            last_rest: Instant::now(),
+        }
+    }
+
+    // Needs data.quaternion, so runs after the orientation filter
+    pub fn apply(&mut self, data: &mut SensorData) {
+        let Some(quaternion) = data.quaternion else {
+            return;
+        };
+        let step = self
+            .last
+            .map(|last| data.timestamp.saturating_duration_since(last).as_secs_f32())
+            .unwrap_or(0.0)
+            .min(MAX_STEP_SECS);
+        self.last = Some(data.timestamp);
+
+        let mut world = rotate(quaternion, data.acceleration);
+        world[2] -= STANDARD_GRAVITY as f32;
+
+        let magnitude = data.acceleration.iter().map(|a| a * a).sum::<f32>().sqrt();
+        let spin = data.gyro.iter().map(|g| g * g).sum::<f32>().sqrt();
+        let still = (magnitude - STANDARD_GRAVITY as f32).abs() < REST_ACCEL_TOLERANCE
+            && spin < REST_GYRO_DEG_S;
+        self.rest_samples = if still { self.rest_samples + 1 } else { 0 };
+
+        if self.rest_samples >= REST_SAMPLES {
+            for (bias, value) in self.bias.iter_mut().zip(world) {
+                *bias += (value - *bias) * BIAS_RATE;
+            }
+            self.motion.velocity = [0.0; 3];
+            self.motion.at_rest = true;
+            self.last_rest = data.timestamp;
+        } else {
+            for (axis, value) in world.into_iter().enumerate() {
+                let acceleration = value - self.bias[axis];
+                let velocity = self.motion.velocity[axis] + acceleration * step;
+                // Trapezoidal step for the position
+                self.motion.displacement[axis] +=
+                    (self.motion.velocity[axis] + velocity) / 2.0 * step;
+                self.motion.velocity[axis] = velocity;
+            }
+            self.motion.at_rest = false;
+        }
+
+        self.motion.moving_secs = data
+            .timestamp
+            .saturating_duration_since(self.last_rest)
+            .as_secs_f32();
+        self.motion.confidence = (-self.motion.moving_secs / CONFIDENCE_SECS).exp();
+        data.motion = Some(self.motion);
+    }
+}
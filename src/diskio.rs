@@ -0,0 +1,250 @@
+use std::collections::{HashMap, VecDeque};
+#[cfg(windows)]
+use std::process::Command;
+
+use anyhow::Result;
+use colored::*;
+use log::debug;
+
+use crate::graphs;
+use crate::i18n;
+use crate::theme::Themed;
+use crate::units;
+
+// Number of samples kept per device for the throughput sparkline
+const DISK_IO_HISTORY_LEN: usize = 30;
+
+// Per-device rates for the last refresh interval
+#[derive(Debug, Clone, Default)]
+pub struct DiskIoStats {
+    pub device: String,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub utilization: f64,
+    pub history: VecDeque<f64>,
+}
+
+// Cumulative kernel counters for one device
+#[derive(Debug, Clone, Copy, Default)]
+struct DiskCounters {
+    reads: u64,
+    writes: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+    io_ms: u64,
+}
+
+// Tracks block device throughput between refreshes
+pub struct DiskIoMonitor {
+    last_counters: HashMap<String, DiskCounters>,
+    stats: Vec<DiskIoStats>,
+}
+
+impl DiskIoMonitor {
+    pub fn new() -> Self {
+        let mut monitor = DiskIoMonitor {
+            last_counters: HashMap::new(),
+            stats: Vec::new(),
+        };
+        monitor.refresh(0.0);
+        monitor
+    }
+
+    #[cfg(not(windows))]
+    pub fn refresh(&mut self, elapsed_secs: f64) {
+        let counters = match read_disk_counters() {
+            Ok(counters) => counters,
+            Err(e) => {
+                debug!("Failed to read disk counters: {}", e);
+                return;
+            }
+        };
+
+        let mut stats = Vec::new();
+        for (device, current) in &counters {
+            let mut entry = self
+                .stats
+                .iter()
+                .find(|stats| &stats.device == device)
+                .cloned()
+                .unwrap_or_else(|| DiskIoStats {
+                    device: device.clone(),
+                    ..Default::default()
+                });
+
+            if let Some(previous) = self.last_counters.get(device) {
+                if elapsed_secs > 0.0 {
+                    let rate =
+                        |now: u64, before: u64| now.saturating_sub(before) as f64 / elapsed_secs;
+                    entry.read_bytes_per_sec = rate(current.read_bytes, previous.read_bytes);
+                    entry.write_bytes_per_sec = rate(current.write_bytes, previous.write_bytes);
+                    entry.read_iops = rate(current.reads, previous.reads);
+                    entry.write_iops = rate(current.writes, previous.writes);
+                    // Time spent doing I/O as a share of wall time
+                    entry.utilization = (rate(current.io_ms, previous.io_ms) / 10.0).min(100.0);
+                    push_history(&mut entry);
+                }
+            }
+
+            stats.push(entry);
+        }
+
+        stats.sort_by(|a, b| a.device.cmp(&b.device));
+        self.stats = stats;
+        self.last_counters = counters;
+    }
+
+    // Windows perf counters are already per-second, so no deltas are needed
+    #[cfg(windows)]
+    pub fn refresh(&mut self, _elapsed_secs: f64) {
+        let output = match Command::new("wmic")
+            .args([
+                "path",
+                "Win32_PerfFormattedData_PerfDisk_PhysicalDisk",
+                "get",
+                "Name,DiskReadBytesPersec,DiskWriteBytesPersec,DiskReadsPersec,DiskWritesPersec,PercentIdleTime",
+                "/format:csv",
+            ])
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => {
+                debug!("Failed to query disk perf counters: {}", e);
+                return;
+            }
+        };
+
+        // CSV columns come back alphabetically: Node, DiskReadBytes, DiskReads,
+        // DiskWriteBytes, DiskWrites, Name, PercentIdleTime
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut stats = Vec::new();
+        for line in text.lines().skip(2) {
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            if fields.len() < 7 || fields[5] == "_Total" {
+                continue;
+            }
+            let number = |i: usize| fields[i].parse::<f64>().unwrap_or(0.0);
+            let mut entry = self
+                .stats
+                .iter()
+                .find(|stats| stats.device == fields[5])
+                .cloned()
+                .unwrap_or_else(|| DiskIoStats {
+                    device: fields[5].to_string(),
+                    ..Default::default()
+                });
+            entry.read_bytes_per_sec = number(1);
+            entry.read_iops = number(2);
+            entry.write_bytes_per_sec = number(3);
+            entry.write_iops = number(4);
+            entry.utilization = (100.0 - number(6)).clamp(0.0, 100.0);
+            push_history(&mut entry);
+            stats.push(entry);
+        }
+
+        self.stats = stats;
+    }
+
+    pub fn stats(&self) -> &[DiskIoStats] {
+        &self.stats
+    }
+}
+
+fn push_history(entry: &mut DiskIoStats) {
+    if entry.history.len() == DISK_IO_HISTORY_LEN {
+        entry.history.pop_front();
+    }
+    entry
+        .history
+        .push_back(entry.read_bytes_per_sec + entry.write_bytes_per_sec);
+}
+
+// Whole-disk counters from /proc/diskstats. Partitions and virtual devices
+// are skipped so traffic is not counted twice.
+#[cfg(not(windows))]
+fn read_disk_counters() -> Result<HashMap<String, DiskCounters>> {
+    // /proc/diskstats always counts in 512-byte sectors
+    const SECTOR_SIZE: u64 = 512;
+
+    let content = std::fs::read_to_string("/proc/diskstats")?;
+    let mut counters = HashMap::new();
+
+    for line in content.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 14 {
+            continue;
+        }
+        let device = fields[2];
+        if device.starts_with("loop") || device.starts_with("ram") || device.starts_with("zram") {
+            continue;
+        }
+        if !std::path::Path::new("/sys/block").join(device).exists() {
+            continue;
+        }
+
+        let field = |i: usize| fields[i].parse::<u64>().unwrap_or(0);
+        counters.insert(
+            device.to_string(),
+            DiskCounters {
+                reads: field(3),
+                read_bytes: field(5) * SECTOR_SIZE,
+                writes: field(7),
+                write_bytes: field(9) * SECTOR_SIZE,
+                io_ms: field(12),
+            },
+        );
+    }
+
+    Ok(counters)
+}
+
+// Disk I/O panel: throughput, IOPS and utilization per device
+pub fn monitor_disk_io(monitor: &DiskIoMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-disk-io");
+    println!("\n{}", heading.bold().value());
+    println!("{}", i18n::underline(heading).value());
+
+    if monitor.stats().is_empty() {
+        println!("  No block devices found");
+        return Ok(());
+    }
+
+    let kilo = units::Scale::fixed(1);
+    println!(
+        "{:<12} {:<11} {:<11} {:<8} {:<8} {:<20} HISTORY",
+        "DEVICE",
+        format!("READ {}/s", kilo.name),
+        format!("WRITE {}/s", kilo.name),
+        "R IOPS",
+        "W IOPS",
+        "UTIL%"
+    );
+
+    for stats in monitor.stats() {
+        let bar = graphs::bar(stats.utilization, 10);
+        let bar = if stats.utilization < 50.0 {
+            bar.good()
+        } else if stats.utilization < 90.0 {
+            bar.warning()
+        } else {
+            bar.critical()
+        };
+        let series: Vec<f64> = stats.history.iter().copied().collect();
+
+        println!(
+            "{:<12} {:<11.1} {:<11.1} {:<8.0} {:<8.0} {} {:<9} {}",
+            stats.device.warning(),
+            kilo.apply(stats.read_bytes_per_sec),
+            kilo.apply(stats.write_bytes_per_sec),
+            stats.read_iops,
+            stats.write_iops,
+            bar,
+            format!("{:.1}", stats.utilization),
+            graphs::sparkline(&series, None).value()
+        );
+    }
+
+    Ok(())
+}
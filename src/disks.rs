@@ -0,0 +1,456 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+use log::warn;
+use sysinfo::{DiskExt, RefreshKind, System, SystemExt};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::theme::Themed;
+use crate::units;
+
+// Filesystem types served over the network; statfs on these can block for
+// a long time when the server goes away
+const NETWORK_FILESYSTEMS: [&str; 12] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "fuse.sshfs",
+    "9p",
+    "glusterfs",
+    "fuse.glusterfs",
+    "ceph",
+    "davfs",
+    "afs",
+];
+
+pub fn is_network_filesystem(file_system: &str) -> bool {
+    NETWORK_FILESYSTEMS.contains(&file_system)
+}
+
+// Inode counters for one filesystem
+#[derive(Debug, Clone, Copy)]
+pub struct InodeUsage {
+    pub used: u64,
+    pub total: u64,
+}
+
+impl InodeUsage {
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+// Inode usage for the filesystem mounted at `mount_point`. Filesystems without
+// a fixed inode table (btrfs, vfat, NTFS...) report zero and yield None.
+#[cfg(target_os = "linux")]
+pub fn inode_usage(mount_point: &Path) -> Option<InodeUsage> {
+    let stats = nix::sys::statvfs::statvfs(mount_point).ok()?;
+    let total = stats.files() as u64;
+    if total == 0 {
+        return None;
+    }
+    Some(InodeUsage {
+        used: total.saturating_sub(stats.files_free() as u64),
+        total,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn inode_usage(_mount_point: &Path) -> Option<InodeUsage> {
+    None
+}
+
+// One row of the disk panel. With bind mount aggregation a row can cover
+// several mount points of the same filesystem.
+#[derive(Debug, Clone)]
+pub struct MountView {
+    pub name: String,
+    pub file_system: String,
+    pub mount_points: Vec<PathBuf>,
+    pub total_space: u64,
+    pub available_space: u64,
+}
+
+impl MountView {
+    pub fn mount_point(&self) -> &Path {
+        &self.mount_points[0]
+    }
+}
+
+// disk_exclude out of the box: snap packages each mount a full squashfs image
+pub const DEFAULT_EXCLUDE: &[&str] = &["/snap/*", "squashfs"];
+
+// Decides which mounts the disk panel and disk alerts look at
+#[derive(Debug, Clone, Default)]
+pub struct DiskFilter {
+    exclude: Vec<String>,
+    aggregate_bind_mounts: bool,
+}
+
+impl DiskFilter {
+    pub fn new(exclude: &[String], aggregate_bind_mounts: bool) -> Self {
+        DiskFilter {
+            exclude: exclude.to_vec(),
+            aggregate_bind_mounts,
+        }
+    }
+
+    // Patterns are matched against the mount point, device name and filesystem type
+    pub fn is_excluded(&self, name: &str, mount_point: &Path, file_system: &str) -> bool {
+        let mount_point = mount_point.to_string_lossy();
+        self.exclude.iter().any(|pattern| {
+            glob_match(pattern, &mount_point)
+                || glob_match(pattern, name)
+                || glob_match(pattern, file_system)
+        })
+    }
+
+    pub fn mounts(&self, system: &System) -> Vec<MountView> {
+        let mut views: Vec<MountView> = Vec::new();
+
+        for disk in system.disks() {
+            let name = disk.name().to_string_lossy().to_string();
+            let file_system = String::from_utf8_lossy(disk.file_system()).to_string();
+            // Network mounts are probed separately by NetworkMountMonitor
+            if is_network_filesystem(&file_system)
+                || self.is_excluded(&name, disk.mount_point(), &file_system)
+            {
+                continue;
+            }
+
+            // Bind mounts show up as the same device with identical capacity
+            if self.aggregate_bind_mounts {
+                if let Some(existing) = views.iter_mut().find(|view| {
+                    view.name == name
+                        && view.file_system == file_system
+                        && view.total_space == disk.total_space()
+                }) {
+                    existing.mount_points.push(disk.mount_point().to_path_buf());
+                    continue;
+                }
+            }
+
+            views.push(MountView {
+                name,
+                file_system,
+                mount_points: vec![disk.mount_point().to_path_buf()],
+                total_space: disk.total_space(),
+                available_space: disk.available_space(),
+            });
+        }
+
+        views
+    }
+}
+
+// Shell-style wildcard match supporting `*` and `?`
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            // Let the last `*` swallow one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+// Raise an alert for every filesystem whose inode usage crosses the threshold
+pub fn check_inode_alerts(
+    system: &System,
+    filter: &DiskFilter,
+    alerts: &mut AlertEngine,
+    threshold_percent: f64,
+) {
+    if threshold_percent <= 0.0 {
+        return;
+    }
+
+    for mount in filter.mounts(system) {
+        let mount_point = mount.mount_point();
+        let key = format!("inodes:{}", mount_point.display());
+        match inode_usage(mount_point) {
+            Some(usage) if usage.percent() >= threshold_percent => {
+                let level = if usage.percent() >= 98.0 {
+                    AlertLevel::Critical
+                } else {
+                    AlertLevel::Warning
+                };
+                alerts.raise(
+                    &key,
+                    level,
+                    "disk",
+                    format!(
+                        "{} is using {:.1}% of its inodes ({}/{})",
+                        mount_point.display(),
+                        usage.percent(),
+                        usage.used,
+                        usage.total
+                    ),
+                );
+            }
+            _ => alerts.clear(&key),
+        }
+    }
+}
+
+// A System holding only the disk list. sysinfo statfs's every mount while it
+// builds the list, CIFS and sshfs included, so that runs on a helper thread
+// and a hung server leaves the list empty instead of blocking startup.
+pub fn system_with_disk_list(timeout_ms: u64) -> System {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(System::new_with_specifics(
+            RefreshKind::new().with_disks_list(),
+        ));
+    });
+    receiver
+        .recv_timeout(Duration::from_millis(timeout_ms.max(100)))
+        .unwrap_or_else(|_| {
+            warn!("Listing disks timed out, a network mount is not responding");
+            System::new()
+        })
+}
+
+// Refresh local disks only. sysinfo would otherwise statfs network mounts on
+// the refresh thread and hang the whole display on an unreachable server.
+pub fn refresh_local_disks(system: &mut System) {
+    for disk in system.disks_mut() {
+        if !is_network_filesystem(&String::from_utf8_lossy(disk.file_system())) {
+            disk.refresh();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MountStatus {
+    Ok {
+        total_space: u64,
+        available_space: u64,
+    },
+    Error(String),
+    // statfs has not returned within the timeout
+    Stale(Duration),
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkMount {
+    pub source: String,
+    pub mount_point: PathBuf,
+    pub file_system: String,
+    pub status: MountStatus,
+}
+
+// A statfs probe still running on a background thread
+struct PendingProbe {
+    started: Instant,
+    result: Receiver<Result<(u64, u64), String>>,
+}
+
+// Probes NFS/SMB/sshfs mounts off the main thread with a timeout so a hung
+// server shows up as a stale mount instead of freezing the refresh
+pub struct NetworkMountMonitor {
+    timeout: Duration,
+    mounts: Vec<NetworkMount>,
+    pending: HashMap<PathBuf, PendingProbe>,
+}
+
+impl NetworkMountMonitor {
+    pub fn new(timeout_ms: u64) -> Self {
+        NetworkMountMonitor {
+            timeout: Duration::from_millis(timeout_ms.max(100)),
+            mounts: Vec::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn refresh(&mut self, alerts: &mut AlertEngine) {
+        let mut mounts = read_network_mounts();
+
+        // Start a probe for every mount that does not already have one in flight
+        for mount in &mounts {
+            if !self.pending.contains_key(&mount.mount_point) {
+                self.pending
+                    .insert(mount.mount_point.clone(), spawn_probe(&mount.mount_point));
+            }
+        }
+
+        let deadline = Instant::now() + self.timeout;
+        for mount in &mut mounts {
+            let Some(probe) = self.pending.get(&mount.mount_point) else {
+                continue;
+            };
+            let wait = deadline.saturating_duration_since(Instant::now());
+            let received = match probe.result.try_recv() {
+                Err(TryRecvError::Empty) => probe.result.recv_timeout(wait).ok(),
+                Ok(result) => Some(result),
+                Err(TryRecvError::Disconnected) => Some(Err("probe thread died".to_string())),
+            };
+
+            mount.status = match received {
+                Some(Ok((total_space, available_space))) => MountStatus::Ok {
+                    total_space,
+                    available_space,
+                },
+                Some(Err(e)) => MountStatus::Error(e),
+                None => MountStatus::Stale(probe.started.elapsed()),
+            };
+            if !matches!(mount.status, MountStatus::Stale(_)) {
+                self.pending.remove(&mount.mount_point);
+            }
+
+            let key = format!("mount:{}", mount.mount_point.display());
+            match &mount.status {
+                MountStatus::Ok { .. } => alerts.clear(&key),
+                MountStatus::Error(e) => alerts.raise(
+                    &key,
+                    AlertLevel::Warning,
+                    "disk",
+                    format!("{} is unavailable: {}", mount.mount_point.display(), e),
+                ),
+                MountStatus::Stale(waited) => alerts.raise(
+                    &key,
+                    AlertLevel::Critical,
+                    "disk",
+                    format!(
+                        "{} ({}) has not responded for {:.0}s",
+                        mount.mount_point.display(),
+                        mount.source,
+                        waited.as_secs_f64()
+                    ),
+                ),
+            }
+        }
+
+        // Forget probes for mounts that have since been unmounted
+        self.pending
+            .retain(|path, _| mounts.iter().any(|mount| &mount.mount_point == path));
+        self.mounts = mounts;
+    }
+
+    pub fn mounts(&self) -> &[NetworkMount] {
+        &self.mounts
+    }
+}
+
+fn spawn_probe(mount_point: &Path) -> PendingProbe {
+    let (sender, result) = mpsc::channel();
+    let path = mount_point.to_path_buf();
+    thread::spawn(move || {
+        let _ = sender.send(statfs_space(&path));
+    });
+    PendingProbe {
+        started: Instant::now(),
+        result,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn statfs_space(path: &Path) -> Result<(u64, u64), String> {
+    let stats = nix::sys::statvfs::statvfs(path).map_err(|e| e.desc().to_string())?;
+    let block_size = stats.fragment_size() as u64;
+    Ok((
+        stats.blocks() as u64 * block_size,
+        stats.blocks_available() as u64 * block_size,
+    ))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn statfs_space(_path: &Path) -> Result<(u64, u64), String> {
+    Err("not supported on this platform".to_string())
+}
+
+// Network mounts from /proc/mounts; the status is filled in by the probe
+#[cfg(target_os = "linux")]
+fn read_network_mounts() -> Vec<NetworkMount> {
+    let content = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 3 || !is_network_filesystem(fields[2]) {
+                return None;
+            }
+            Some(NetworkMount {
+                source: fields[0].to_string(),
+                mount_point: PathBuf::from(fields[1].replace("\\040", " ")),
+                file_system: fields[2].to_string(),
+                status: MountStatus::Stale(Duration::ZERO),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_network_mounts() -> Vec<NetworkMount> {
+    Vec::new()
+}
+
+// Network mounts section of the disk panel
+pub fn monitor_network_mounts(monitor: &NetworkMountMonitor) -> Result<()> {
+    if monitor.mounts().is_empty() {
+        return Ok(());
+    }
+
+    println!("Network Mounts:");
+    for mount in monitor.mounts() {
+        let status = match &mount.status {
+            MountStatus::Ok {
+                total_space,
+                available_space,
+            } => {
+                let total = *total_space as f64;
+                let used = total - *available_space as f64;
+                let scale = units::Scale::of(total);
+                format!(
+                    "{:.2}/{:.2} {}",
+                    scale.apply(used),
+                    scale.apply(total),
+                    scale.name
+                )
+                .good()
+            }
+            MountStatus::Error(e) => format!("ERROR: {}", e).critical(),
+            MountStatus::Stale(waited) => {
+                format!("STALE (no response for {:.0}s)", waited.as_secs_f64())
+                    .critical()
+                    .bold()
+            }
+        };
+        println!(
+            "  {} [{}]: {} - Mount: {}",
+            mount.source.warning(),
+            mount.file_system,
+            status,
+            mount.mount_point.to_string_lossy().value()
+        );
+    }
+
+    Ok(())
+}
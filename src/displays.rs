@@ -0,0 +1,250 @@
+use std::fs;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+use sysinfo::{ProcessExt, System, SystemExt};
+
+use crate::i18n;
+use crate::theme::Themed;
+
+// Hotplug and logins are rare, and loginctl starts a process per session
+const SCAN_INTERVAL: Duration = Duration::from_secs(10);
+
+// Compositors and X servers, for systems without logind
+const GRAPHICAL_PROCESSES: [(&str, &str); 9] = [
+    ("Xorg", "x11"),
+    ("X", "x11"),
+    ("labwc", "wayland"),
+    ("wayfire", "wayland"),
+    ("weston", "wayland"),
+    ("sway", "wayland"),
+    ("cage", "wayland"),
+    ("gnome-shell", "wayland"),
+    ("kwin_wayland", "wayland"),
+];
+
+// A DRM connector with something plugged in
+#[derive(Debug, Clone)]
+pub struct Monitor {
+    // e.g. "HDMI-A-1"
+    pub connector: String,
+    pub enabled: bool,
+    // The monitor's preferred mode, e.g. "1920x1080"; sysfs does not say
+    // which mode is in use
+    pub mode: Option<String>,
+    pub modes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub user: String,
+    // x11, wayland, tty or mir
+    pub kind: String,
+    // Where it runs: a seat's VT, an X display or a remote host
+    pub place: String,
+    pub active: bool,
+    pub remote: bool,
+}
+
+// Connected monitors from /sys/class/drm, and the login sessions from
+// logind, to tell a headless Pi from one driving a desktop
+pub struct DisplayMonitor {
+    monitors: Vec<Monitor>,
+    sessions: Vec<Session>,
+    // Compositor or X server seen when logind is not available
+    graphical_process: Option<(String, &'static str)>,
+    last_scan: Option<Instant>,
+}
+
+impl DisplayMonitor {
+    pub fn new() -> Self {
+        DisplayMonitor {
+            monitors: Vec::new(),
+            sessions: Vec::new(),
+            graphical_process: None,
+            last_scan: None,
+        }
+    }
+
+    pub fn refresh(&mut self, system: &System) {
+        if self
+            .last_scan
+            .is_some_and(|last| last.elapsed() < SCAN_INTERVAL)
+        {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+
+        self.monitors = read_monitors();
+        self.sessions = read_sessions().unwrap_or_default();
+        self.graphical_process = system.processes().values().find_map(|process| {
+            GRAPHICAL_PROCESSES
+                .iter()
+                .find(|(name, _)| process.name() == *name)
+                .map(|&(name, kind)| (name.to_string(), kind))
+        });
+    }
+
+    // "desktop (wayland)" or "headless"
+    fn mode(&self) -> String {
+        let local = self.sessions.iter().find(|session| {
+            !session.remote && matches!(session.kind.as_str(), "x11" | "wayland" | "mir")
+        });
+        match (local, &self.graphical_process) {
+            (Some(session), _) => format!("desktop ({})", session.kind),
+            (None, Some((name, kind))) => format!("desktop ({}, {})", kind, name),
+            (None, None) if self.monitors.is_empty() => "headless".to_string(),
+            (None, None) => "console".to_string(),
+        }
+    }
+}
+
+fn read_monitors() -> Vec<Monitor> {
+    let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+    let mut monitors: Vec<Monitor> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // Connectors are "card0-HDMI-A-1"; "card0" itself is the GPU
+            let (_, connector) = name.split_once('-')?;
+            let attribute = |file: &str| {
+                fs::read_to_string(path.join(file))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+            if attribute("status") != "connected" {
+                return None;
+            }
+            let modes = attribute("modes");
+            Some(Monitor {
+                connector: connector.to_string(),
+                enabled: attribute("enabled") == "enabled",
+                mode: modes.lines().next().map(str::to_string),
+                modes: modes.lines().count(),
+            })
+        })
+        .collect();
+    monitors.sort_by(|a, b| a.connector.cmp(&b.connector));
+    monitors
+}
+
+fn read_sessions() -> Option<Vec<Session>> {
+    let output = Command::new("loginctl")
+        .args(["list-sessions", "--no-legend"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let listing = String::from_utf8_lossy(&output.stdout);
+
+    let mut sessions = Vec::new();
+    for id in listing
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+    {
+        let Ok(output) = Command::new("loginctl")
+            .args([
+                "show-session",
+                id,
+                "-p",
+                "Name",
+                "-p",
+                "Type",
+                "-p",
+                "Class",
+                "-p",
+                "State",
+                "-p",
+                "Remote",
+                "-p",
+                "RemoteHost",
+                "-p",
+                "TTY",
+                "-p",
+                "Display",
+            ])
+            .output()
+        else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(&output.stdout);
+        let property = |key: &str| {
+            text.lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+                .unwrap_or_default()
+                .to_string()
+        };
+        // Display managers' greeters and systemd --user managers are not logins
+        if property("Class") != "user" {
+            continue;
+        }
+        let remote = property("Remote") == "yes";
+        let place = [property("Display"), property("TTY"), property("RemoteHost")]
+            .into_iter()
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        sessions.push(Session {
+            user: property("Name"),
+            kind: property("Type"),
+            place,
+            active: property("State") == "active",
+            remote,
+        });
+    }
+    Some(sessions)
+}
+
+// Displays panel: desktop or headless, connected monitors, then sessions
+pub fn monitor_displays(monitor: &DisplayMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-displays");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    println!("Mode: {}", monitor.mode().value());
+
+    if monitor.monitors.is_empty() {
+        println!("  No monitors connected");
+    }
+    for display in &monitor.monitors {
+        let state = if display.enabled {
+            "on".good()
+        } else {
+            "off".warning()
+        };
+        println!(
+            "  {} {} - {} ({} modes)",
+            display.connector.warning(),
+            state,
+            display.mode.as_deref().unwrap_or("no modes").value(),
+            display.modes
+        );
+    }
+
+    for session in &monitor.sessions {
+        let kind = if session.kind.is_empty() {
+            "unspecified"
+        } else {
+            &session.kind
+        };
+        println!(
+            "  {} {} on {}{}{}",
+            session.user.warning(),
+            kind,
+            if session.place.is_empty() {
+                "?"
+            } else {
+                &session.place
+            },
+            if session.remote { " (remote)" } else { "" },
+            if session.active { "" } else { ", inactive" }
+        );
+    }
+
+    Ok(())
+}
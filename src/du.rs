@@ -0,0 +1,405 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, Command};
+use colored::*;
+use console::{Key, Term};
+
+use crate::graphs;
+use crate::theme::{self, Themed};
+
+// Files kept per directory for the drill-down; the rest are only counted
+const FILES_PER_DIRECTORY: usize = 20;
+
+// A directory and everything under it
+struct DirNode {
+    name: String,
+    size: u64,
+    files: u64,
+    dirs: Vec<DirNode>,
+    // The largest files directly in this directory
+    largest_files: Vec<(String, u64)>,
+    // Files directly in this directory that did not make the list above
+    other_files: (u64, u64),
+    unreadable: u64,
+}
+
+impl DirNode {
+    fn new(name: String) -> Self {
+        DirNode {
+            name,
+            size: 0,
+            files: 0,
+            dirs: Vec::new(),
+            largest_files: Vec::new(),
+            other_files: (0, 0),
+            unreadable: 0,
+        }
+    }
+
+    fn add_file(&mut self, name: String, size: u64) {
+        self.size += size;
+        self.files += 1;
+        self.largest_files.push((name, size));
+        if self.largest_files.len() > FILES_PER_DIRECTORY {
+            let smallest = (0..self.largest_files.len())
+                .min_by_key(|&i| self.largest_files[i].1)
+                .unwrap_or_default();
+            let (_, dropped) = self.largest_files.swap_remove(smallest);
+            self.other_files.0 += 1;
+            self.other_files.1 += dropped;
+        }
+    }
+
+    fn add_dir(&mut self, dir: DirNode) {
+        self.size += dir.size;
+        self.files += dir.files;
+        self.unreadable += dir.unreadable;
+        self.dirs.push(dir);
+    }
+
+    // Children largest first, ready for display
+    fn finish(&mut self) {
+        self.dirs.sort_by_key(|dir| Reverse(dir.size));
+        self.largest_files.sort_by_key(|(_, size)| Reverse(*size));
+    }
+}
+
+// Settings shared by every walker thread
+struct Walker {
+    apparent: bool,
+    device: Option<u64>,
+    // Hard-linked files seen so far, so each is only counted once
+    seen_links: Mutex<HashSet<(u64, u64)>>,
+}
+
+impl Walker {
+    // Bytes a file takes: allocated blocks on Unix unless --apparent-size.
+    // None for a hard link that was already counted.
+    fn file_size(&self, metadata: &fs::Metadata) -> Option<u64> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if metadata.nlink() > 1 {
+                let mut seen = self.seen_links.lock().ok()?;
+                if !seen.insert((metadata.dev(), metadata.ino())) {
+                    return None;
+                }
+            }
+            if !self.apparent {
+                return Some(metadata.blocks() * 512);
+            }
+        }
+        Some(metadata.len())
+    }
+
+    // Directories on another filesystem are skipped unless --cross-mounts
+    fn same_device(&self, metadata: &fs::Metadata) -> bool {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            self.device.is_none_or(|device| metadata.dev() == device)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = metadata;
+            true
+        }
+    }
+
+    fn walk(&self, path: &Path, name: String) -> DirNode {
+        let mut node = DirNode::new(name);
+        let Ok(entries) = fs::read_dir(path) else {
+            node.unreadable += 1;
+            return node;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                node.unreadable += 1;
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if metadata.is_dir() {
+                if self.same_device(&metadata) {
+                    let child = self.walk(&entry.path(), name);
+                    node.add_dir(child);
+                }
+            } else if let Some(size) = self.file_size(&metadata) {
+                node.add_file(name, size);
+            }
+        }
+        node.finish();
+        node
+    }
+
+    // Walk the top-level directories on all cores, each thread taking the
+    // next one when it finishes
+    fn walk_parallel(&self, root: &Path) -> Result<DirNode> {
+        let mut node = DirNode::new(root.display().to_string());
+        let mut subdirs = Vec::new();
+        for entry in fs::read_dir(root)
+            .map_err(|e| anyhow!("Cannot read {}: {}", root.display(), e))?
+            .flatten()
+        {
+            let Ok(metadata) = entry.metadata() else {
+                node.unreadable += 1;
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if metadata.is_dir() {
+                if self.same_device(&metadata) {
+                    subdirs.push((entry.path(), name));
+                }
+            } else if let Some(size) = self.file_size(&metadata) {
+                node.add_file(name, size);
+            }
+        }
+
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::new());
+        let threads = thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(subdirs.len().max(1));
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((path, name)) = subdirs.get(index) else {
+                        return;
+                    };
+                    let child = self.walk(path, name.clone());
+                    if let Ok(mut results) = results.lock() {
+                        results.push(child);
+                    }
+                });
+            }
+        });
+        for child in results.into_inner().unwrap_or_default() {
+            node.add_dir(child);
+        }
+        node.finish();
+        Ok(node)
+    }
+}
+
+// Handle "hercules du [path] [--top N] [--apparent-size] [--cross-mounts]"
+pub fn run_du(args: &[String]) -> Result<()> {
+    let matches = Command::new("du")
+        .about("Find what is using disk space: the largest directories and files under a path")
+        .arg(
+            Arg::new("path")
+                .value_name("PATH")
+                .help("Directory to scan (default: the current directory)")
+                .default_value("."),
+        )
+        .arg(
+            Arg::new("top")
+                .long("top")
+                .short('n')
+                .value_name("N")
+                .help("Entries to list per directory")
+                .default_value("20")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("apparent-size")
+                .long("apparent-size")
+                .help("Count file lengths instead of the disk blocks they use")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("cross-mounts")
+                .long("cross-mounts")
+                .help("Descend into directories on other filesystems")
+                .action(ArgAction::SetTrue),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|e| e.exit());
+
+    let root = PathBuf::from(matches.get_one::<String>("path").unwrap());
+    let root = root.canonicalize().unwrap_or(root);
+    let top = *matches.get_one::<usize>("top").unwrap();
+
+    let root_metadata =
+        fs::metadata(&root).map_err(|e| anyhow!("Cannot read {}: {}", root.display(), e))?;
+    if !root_metadata.is_dir() {
+        return Err(anyhow!("{} is not a directory", root.display()));
+    }
+    #[cfg(unix)]
+    let device = {
+        use std::os::unix::fs::MetadataExt;
+        (!matches.get_flag("cross-mounts")).then(|| root_metadata.dev())
+    };
+    #[cfg(not(unix))]
+    let device = None;
+
+    let walker = Walker {
+        apparent: matches.get_flag("apparent-size"),
+        device,
+        seen_links: Mutex::new(HashSet::new()),
+    };
+
+    if theme::interactive() {
+        eprintln!("Scanning {}...", root.display());
+    }
+    let started = Instant::now();
+    let tree = walker.walk_parallel(&root)?;
+    let summary = format!(
+        "Scanned {} files in {:.1}s",
+        tree.files,
+        started.elapsed().as_secs_f64()
+    );
+
+    if theme::interactive() && io::stdin().is_terminal() {
+        explore(&tree, top, &summary)
+    } else {
+        print_dir(&tree, &tree.name, top, None);
+        println!("\n{}", summary);
+        Ok(())
+    }
+}
+
+// Drill into the tree with the keyboard: arrows select, Enter opens, Backspace goes up
+fn explore(tree: &DirNode, top: usize, summary: &str) -> Result<()> {
+    let term = Term::stdout();
+    // Indexes into .dirs from the root down to the directory shown
+    let mut path: Vec<usize> = Vec::new();
+    let mut selected: Vec<usize> = vec![0];
+    loop {
+        let mut node = tree;
+        let mut title = tree.name.clone();
+        for &index in &path {
+            node = &node.dirs[index];
+            title = Path::new(&title).join(&node.name).display().to_string();
+        }
+        let shown_dirs = node.dirs.len().min(top);
+        let current = selected.last_mut().unwrap();
+        *current = (*current).min(shown_dirs.saturating_sub(1));
+
+        print!("\x1B[2J\x1B[1;1H");
+        print_dir(node, &title, top, (shown_dirs > 0).then_some(*current));
+        println!("\n{}", summary);
+        println!(
+            "{}",
+            theme::ascii("↑↓ select, Enter/→ open, Backspace/← back, q quit").dimmed()
+        );
+        io::stdout().flush()?;
+
+        match term.read_key()? {
+            Key::ArrowUp | Key::Char('k') => *current = current.saturating_sub(1),
+            Key::ArrowDown | Key::Char('j') => *current += 1,
+            Key::Enter | Key::ArrowRight | Key::Char('l') if shown_dirs > 0 => {
+                path.push(*current);
+                selected.push(0);
+            }
+            Key::Backspace | Key::ArrowLeft | Key::Char('h') if !path.is_empty() => {
+                path.pop();
+                selected.pop();
+            }
+            Key::Char('q') | Key::Escape => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+// One directory: its largest subdirectories, then its largest files
+fn print_dir(node: &DirNode, title: &str, top: usize, selected: Option<usize>) {
+    println!(
+        "{} {} in {} files",
+        title.emphasis(),
+        format_size(node.size).value(),
+        node.files
+    );
+    println!("{}", "==================================".good());
+
+    let bar = |size: u64| {
+        let percent = if node.size > 0 {
+            size as f64 / node.size as f64 * 100.0
+        } else {
+            0.0
+        };
+        (graphs::bar(percent, 20), percent)
+    };
+
+    for (i, dir) in node.dirs.iter().take(top).enumerate() {
+        let (bar, percent) = bar(dir.size);
+        let line = format!(
+            "{:>10} {:>5.1}% {} {}/",
+            format_size(dir.size),
+            percent,
+            bar,
+            dir.name
+        );
+        if selected == Some(i) {
+            println!("{}", line.reversed());
+        } else {
+            println!("{}", line.primary());
+        }
+    }
+    if node.dirs.len() > top {
+        let rest: u64 = node.dirs[top..].iter().map(|dir| dir.size).sum();
+        println!(
+            "{:>10}        {} more directories",
+            format_size(rest),
+            node.dirs.len() - top
+        );
+    }
+
+    for (name, size) in node.largest_files.iter().take(top) {
+        let (bar, percent) = bar(*size);
+        println!(
+            "{:>10} {:>5.1}% {} {}",
+            format_size(*size),
+            percent,
+            bar,
+            name
+        );
+    }
+    let (hidden_count, mut hidden_size) = node.other_files;
+    let mut hidden_count = hidden_count;
+    if node.largest_files.len() > top {
+        hidden_count += (node.largest_files.len() - top) as u64;
+        hidden_size += node.largest_files[top..]
+            .iter()
+            .map(|(_, size)| size)
+            .sum::<u64>();
+    }
+    if hidden_count > 0 {
+        println!(
+            "{:>10}        {} more files",
+            format_size(hidden_size),
+            hidden_count
+        );
+    }
+    if node.unreadable > 0 {
+        println!(
+            "{}",
+            format!("{} entries could not be read", node.unreadable).warning()
+        );
+    }
+}
+
+// 1536 -> "1.5K", binary units like du -h
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}B", bytes)
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,365 @@
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, Result};
+use sysinfo::{System, SystemExt};
+
+use crate::clock;
+use crate::config::ConfigManager;
+use crate::theme::{self, Themed};
+
+// Page colors, close to a typical dark terminal
+const BACKGROUND: &str = "#1e1e1e";
+const FOREGROUND: &str = "#d4d4d4";
+
+// SVG text metrics for a 14px monospace font
+const FONT_SIZE: f64 = 14.0;
+const CHAR_WIDTH: f64 = 8.4;
+const LINE_HEIGHT: f64 = 18.0;
+const PADDING: f64 = 16.0;
+
+// The 16 basic ANSI colors, VS Code's terminal palette
+const PALETTE: [&str; 16] = [
+    "#000000", "#cd3131", "#0dbc79", "#e5e510", "#2472c8", "#bc3fbc", "#11a8cd", "#e5e5e5",
+    "#666666", "#f14c4c", "#23d18b", "#f5f543", "#3b8eea", "#d670d6", "#29b8db", "#ffffff",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Html,
+    Svg,
+}
+
+// Text attributes set by SGR escape sequences
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Style {
+    foreground: Option<String>,
+    background: Option<String>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    reverse: bool,
+}
+
+impl Style {
+    // Foreground and background with reverse video applied
+    fn colors(&self) -> (Option<String>, Option<String>) {
+        if self.reverse {
+            (
+                Some(self.background.clone().unwrap_or(BACKGROUND.to_string())),
+                Some(self.foreground.clone().unwrap_or(FOREGROUND.to_string())),
+            )
+        } else {
+            (self.foreground.clone(), self.background.clone())
+        }
+    }
+}
+
+// A line of output split into runs of equally styled text
+type Line = Vec<(Style, String)>;
+
+// Handle "hercules snapshot --html out.html" and "--svg out.svg": run the
+// snapshot with colors forced on and convert its output
+pub fn run_export(format: Format, path: &str, args: &[String]) -> Result<()> {
+    let mut command = Command::new(env::current_exe()?);
+    command.arg("snapshot").args(args);
+    if theme::is_plain() {
+        command.arg("--plain");
+    } else if env::var_os("NO_COLOR").is_none() {
+        command.env("CLICOLOR_FORCE", "1");
+    }
+    command
+        .arg("--config")
+        .arg(ConfigManager::new()?.config_path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(anyhow!("The snapshot failed ({})", output.status));
+    }
+    let lines = parse_ansi(&String::from_utf8_lossy(&output.stdout));
+
+    let title = format!(
+        "Hercules snapshot of {} at {}",
+        System::new().host_name().unwrap_or_default(),
+        clock::now()
+    );
+    let document = match format {
+        Format::Html => render_html(&lines, &title),
+        Format::Svg => render_svg(&lines, &title),
+    };
+    fs::write(path, document).map_err(|e| anyhow!("Cannot write {}: {}", path, e))?;
+    eprintln!("Saved snapshot to {}", path.value());
+    Ok(())
+}
+
+// Splits terminal output into styled runs. SGR sequences set the style;
+// other escape sequences (cursor movement, OSC) are dropped.
+fn parse_ansi(text: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut line: Line = Vec::new();
+    let mut style = Style::default();
+    let mut run = String::new();
+    let mut chars = text.chars().peekable();
+
+    let flush = |line: &mut Line, run: &mut String, style: &Style| {
+        if !run.is_empty() {
+            line.push((style.clone(), std::mem::take(run)));
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1B' => match chars.next() {
+                Some('[') => {
+                    let mut sequence = String::new();
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            if c == 'm' {
+                                flush(&mut line, &mut run, &style);
+                                apply_sgr(&mut style, &sequence);
+                            }
+                            break;
+                        }
+                        sequence.push(c);
+                    }
+                }
+                // OSC runs until BEL or ESC \
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1B' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\n' => {
+                flush(&mut line, &mut run, &style);
+                lines.push(std::mem::take(&mut line));
+            }
+            '\r' => {}
+            c => run.push(c),
+        }
+    }
+    flush(&mut line, &mut run, &style);
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn apply_sgr(style: &mut Style, sequence: &str) {
+    let codes: Vec<u32> = if sequence.is_empty() {
+        vec![0]
+    } else {
+        sequence
+            .split(';')
+            .map(|code| code.parse().unwrap_or(0))
+            .collect()
+    };
+    let mut codes = codes.into_iter();
+    while let Some(code) = codes.next() {
+        match code {
+            0 => *style = Style::default(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => style.underline = true,
+            7 => style.reverse = true,
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = false,
+            27 => style.reverse = false,
+            30..=37 => style.foreground = Some(PALETTE[(code - 30) as usize].to_string()),
+            90..=97 => style.foreground = Some(PALETTE[(code - 90 + 8) as usize].to_string()),
+            40..=47 => style.background = Some(PALETTE[(code - 40) as usize].to_string()),
+            100..=107 => style.background = Some(PALETTE[(code - 100 + 8) as usize].to_string()),
+            39 => style.foreground = None,
+            49 => style.background = None,
+            38 | 48 => {
+                let color = match codes.next() {
+                    Some(5) => codes.next().map(color_256),
+                    Some(2) => match (codes.next(), codes.next(), codes.next()) {
+                        (Some(r), Some(g), Some(b)) => {
+                            Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if code == 38 {
+                    style.foreground = color;
+                } else {
+                    style.background = color;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// xterm's 256-color palette: the 16 basic colors, a 6x6x6 cube, then grays
+fn color_256(index: u32) -> String {
+    match index {
+        0..=15 => PALETTE[index as usize].to_string(),
+        16..=231 => {
+            let index = index - 16;
+            let level = |value: u32| if value == 0 { 0 } else { 55 + value * 40 };
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(index / 36),
+                level((index / 6) % 6),
+                level(index % 6)
+            )
+        }
+        _ => {
+            let gray = 8 + (index.min(255) - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", gray, gray, gray)
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn css(style: &Style) -> String {
+    let (foreground, background) = style.colors();
+    let mut css = String::new();
+    if let Some(color) = foreground {
+        let _ = write!(css, "color:{};", color);
+    }
+    if let Some(color) = background {
+        let _ = write!(css, "background:{};", color);
+    }
+    if style.bold {
+        css.push_str("font-weight:bold;");
+    }
+    if style.dim {
+        css.push_str("opacity:0.7;");
+    }
+    if style.italic {
+        css.push_str("font-style:italic;");
+    }
+    if style.underline {
+        css.push_str("text-decoration:underline;");
+    }
+    css
+}
+
+fn render_html(lines: &[Line], title: &str) -> String {
+    let mut body = String::new();
+    for line in lines {
+        for (style, text) in line {
+            let css = css(style);
+            if css.is_empty() {
+                body.push_str(&escape(text));
+            } else {
+                let _ = write!(body, "<span style=\"{}\">{}</span>", css, escape(text));
+            }
+        }
+        body.push('\n');
+    }
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+<style>
+body {{ background: {BACKGROUND}; margin: 0; }}
+pre {{ color: {FOREGROUND}; font: 14px/1.3 \"DejaVu Sans Mono\", Menlo, Consolas, monospace; padding: 16px; margin: 0; }}
+</style>
+</head>
+<body>
+<pre>{body}</pre>
+</body>
+</html>
+",
+        title = escape(title),
+    )
+}
+
+fn render_svg(lines: &[Line], title: &str) -> String {
+    let columns = lines
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|(_, text)| console::measure_text_width(text))
+                .sum::<usize>()
+        })
+        .max()
+        .unwrap_or(0);
+    let width = PADDING * 2.0 + columns as f64 * CHAR_WIDTH;
+    let height = PADDING * 2.0 + lines.len() as f64 * LINE_HEIGHT;
+
+    let mut backgrounds = String::new();
+    let mut text = String::new();
+    for (row, line) in lines.iter().enumerate() {
+        let top = PADDING + row as f64 * LINE_HEIGHT;
+        let _ = write!(
+            text,
+            "<text x=\"{:.1}\" y=\"{:.1}\">",
+            PADDING,
+            top + FONT_SIZE
+        );
+        let mut column = 0;
+        for (style, run) in line {
+            let run_width = console::measure_text_width(run);
+            let (foreground, background) = style.colors();
+            if let Some(color) = background {
+                let _ = writeln!(
+                    backgrounds,
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>",
+                    PADDING + column as f64 * CHAR_WIDTH,
+                    top,
+                    run_width as f64 * CHAR_WIDTH,
+                    LINE_HEIGHT,
+                    color
+                );
+            }
+            // Position each run by column so wide characters do not shift the rest
+            let mut attributes = format!(" x=\"{:.1}\"", PADDING + column as f64 * CHAR_WIDTH);
+            if let Some(color) = foreground {
+                let _ = write!(attributes, " fill=\"{}\"", color);
+            }
+            if style.bold {
+                attributes.push_str(" font-weight=\"bold\"");
+            }
+            if style.dim {
+                attributes.push_str(" opacity=\"0.7\"");
+            }
+            if style.italic {
+                attributes.push_str(" font-style=\"italic\"");
+            }
+            if style.underline {
+                attributes.push_str(" text-decoration=\"underline\"");
+            }
+            let _ = write!(text, "<tspan{}>{}</tspan>", attributes, escape(run));
+            column += run_width;
+        }
+        text.push_str("</text>\n");
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" viewBox=\"0 0 {width:.0} {height:.0}\">
+<title>{title}</title>
+<rect width=\"100%\" height=\"100%\" fill=\"{BACKGROUND}\"/>
+{backgrounds}<g font-family=\"DejaVu Sans Mono, Menlo, Consolas, monospace\" font-size=\"{FONT_SIZE}\" fill=\"{FOREGROUND}\" xml:space=\"preserve\">
+{text}</g>
+</svg>
+",
+        title = escape(title),
+    )
+}
@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use log::{debug, info};
+
+use crate::graphs;
+use crate::i18n;
+use crate::theme::Themed;
+use crate::units;
+
+// The SoC's thermal zone, whose active trip points switch the fan speeds
+const THERMAL_ZONE: &str = "/sys/class/thermal/thermal_zone0";
+
+// A fan driven by the kernel's thermal framework: the PoE HAT fan, the Pi 5
+// active cooler or a GPIO fan set up with a device tree overlay
+#[derive(Debug, Clone)]
+pub struct Fan {
+    // Cooling device type, e.g. "rpi-poe-fan" or "pwm-fan"
+    pub name: String,
+    pub state: u64,
+    pub max_state: u64,
+    pub rpm: Option<u64>,
+    path: PathBuf,
+}
+
+impl Fan {
+    pub fn percent(&self) -> f64 {
+        if self.max_state == 0 {
+            0.0
+        } else {
+            self.state as f64 / self.max_state as f64 * 100.0
+        }
+    }
+}
+
+// An active trip point: the fan steps up one state above each temperature
+#[derive(Debug, Clone, Copy)]
+pub struct TripPoint {
+    index: usize,
+    pub celsius: f64,
+}
+
+pub struct FanMonitor {
+    fans: Vec<Fan>,
+    trips: Vec<TripPoint>,
+    // Whether the trip points were set from fan_thresholds
+    configured: bool,
+    // Why fan_thresholds could not be applied
+    apply_error: Option<String>,
+}
+
+impl FanMonitor {
+    // With `thresholds` set (°C, lowest first), they replace the active trip
+    // points of the SoC's thermal zone, which needs root
+    pub fn new(thresholds: &[f64]) -> Self {
+        let mut monitor = FanMonitor {
+            fans: find_fans(),
+            trips: read_trip_points(),
+            configured: false,
+            apply_error: None,
+        };
+        if !thresholds.is_empty() {
+            match apply_thresholds(&monitor.trips, thresholds) {
+                Ok(()) => {
+                    monitor.trips = read_trip_points();
+                    monitor.configured = true;
+                }
+                Err(e) => {
+                    debug!("Could not set the fan curve: {}", e);
+                    monitor.apply_error = Some(e.to_string());
+                }
+            }
+        }
+        monitor
+    }
+
+    pub fn fans(&self) -> &[Fan] {
+        &self.fans
+    }
+
+    pub fn refresh(&mut self) {
+        for fan in &mut self.fans {
+            fan.state = read_number(&fan.path.join("cur_state")).unwrap_or(fan.state);
+        }
+        let rpm = read_rpm();
+        if let Some(fan) = self.fans.first_mut() {
+            fan.rpm = rpm;
+        }
+    }
+}
+
+fn read_number(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn find_fans() -> Vec<Fan> {
+    let mut fans: Vec<Fan> = fs::read_dir("/sys/class/thermal")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("cooling_device")
+        })
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = fs::read_to_string(path.join("type"))
+                .ok()?
+                .trim()
+                .to_string();
+            if !name.contains("fan") {
+                return None;
+            }
+            Some(Fan {
+                name,
+                state: read_number(&path.join("cur_state")).unwrap_or(0),
+                max_state: read_number(&path.join("max_state")).unwrap_or(0),
+                rpm: None,
+                path,
+            })
+        })
+        .collect();
+    fans.sort_by(|a, b| a.path.cmp(&b.path));
+    fans
+}
+
+// Tachometer reading, which only fans with a tach wire such as the Pi 5
+// active cooler have
+fn read_rpm() -> Option<u64> {
+    fs::read_dir("/sys/class/hwmon")
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            fs::read_to_string(entry.path().join("name")).is_ok_and(|name| name.contains("fan"))
+        })
+        .find_map(|entry| read_number(&entry.path().join("fan1_input")))
+}
+
+fn read_trip_points() -> Vec<TripPoint> {
+    let zone = Path::new(THERMAL_ZONE);
+    (0..)
+        .map_while(|index| {
+            let kind = fs::read_to_string(zone.join(format!("trip_point_{}_type", index))).ok()?;
+            let millidegrees = read_number(&zone.join(format!("trip_point_{}_temp", index)));
+            Some((index, kind, millidegrees))
+        })
+        .filter(|(_, kind, _)| kind.trim() == "active")
+        .filter_map(|(index, _, millidegrees)| {
+            Some(TripPoint {
+                index,
+                celsius: millidegrees? as f64 / 1000.0,
+            })
+        })
+        .collect()
+}
+
+fn apply_thresholds(trips: &[TripPoint], thresholds: &[f64]) -> Result<()> {
+    if trips.is_empty() {
+        return Err(anyhow!("the thermal zone has no active trip points"));
+    }
+    if thresholds.len() > trips.len() {
+        return Err(anyhow!(
+            "{} thresholds given but the fan has only {} steps",
+            thresholds.len(),
+            trips.len()
+        ));
+    }
+    for (trip, celsius) in trips.iter().zip(thresholds) {
+        if (trip.celsius - celsius).abs() < 0.5 {
+            continue;
+        }
+        let path = Path::new(THERMAL_ZONE).join(format!("trip_point_{}_temp", trip.index));
+        fs::write(&path, format!("{}", (celsius * 1000.0).round() as i64))
+            .map_err(|e| anyhow!("cannot write {}: {}", path.display(), e))?;
+        info!(
+            "Fan trip point {} set to {}",
+            trip.index,
+            units::format_temperature(*celsius)
+        );
+    }
+    Ok(())
+}
+
+// "45,55,65,75", lowest first
+pub fn parse_thresholds(value: &str) -> Result<Vec<f64>> {
+    let thresholds = value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            item.parse::<f64>()
+                .map_err(|_| anyhow!("Invalid fan threshold '{}', expected °C", item))
+        })
+        .collect::<Result<Vec<f64>>>()?;
+    if thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(anyhow!("Fan thresholds must rise, e.g. 45,55,65,75"));
+    }
+    Ok(thresholds)
+}
+
+// Fan panel: speed of each fan against the temperature and its trip points
+pub fn monitor_fan(monitor: &FanMonitor, temperature: Option<f32>) -> Result<()> {
+    let heading = i18n::tr("heading-fan");
+    println!("\n{}", heading.bold().accent());
+    println!("{}", i18n::underline(heading).accent());
+
+    if monitor.fans().is_empty() {
+        println!("{}", "No fan found".value());
+        return Ok(());
+    }
+    for fan in monitor.fans() {
+        let speed = match fan.rpm {
+            Some(rpm) => format!(" - {} RPM", rpm),
+            None => String::new(),
+        };
+        println!(
+            "{:<16} state {}/{} {}{}",
+            fan.name.warning(),
+            fan.state,
+            fan.max_state,
+            graphs::bar(fan.percent(), 10).accent(),
+            speed.value()
+        );
+    }
+    if let Some(temp) = temperature {
+        println!(
+            "CPU Temperature: {}",
+            units::format_temperature(temp as f64).warning()
+        );
+    }
+    if !monitor.trips.is_empty() {
+        println!(
+            "Trip Points: {}{}",
+            monitor
+                .trips
+                .iter()
+                .map(|trip| units::format_temperature(trip.celsius))
+                .collect::<Vec<_>>()
+                .join(", ")
+                .value(),
+            if monitor.configured {
+                " (from fan_thresholds)"
+            } else {
+                ""
+            }
+        );
+    }
+    if let Some(ref error) = monitor.apply_error {
+        println!(
+            "{}",
+            format!("fan_thresholds not applied: {}", error).warning()
+        );
+    }
+    Ok(())
+}
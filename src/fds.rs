@@ -0,0 +1,227 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use colored::*;
+use sysinfo::{PidExt, ProcessExt, System, SystemExt};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::graphs;
+use crate::i18n;
+use crate::process::fd_count;
+use crate::theme::Themed;
+
+// Processes listed in the file descriptor panel
+const MAX_FD_ROWS: usize = 10;
+
+// System-wide handle counters from /proc/sys/fs/file-nr
+#[derive(Debug, Clone, Copy)]
+pub struct SystemFdUsage {
+    pub allocated: u64,
+    pub max: u64,
+}
+
+impl SystemFdUsage {
+    pub fn percent(&self) -> f64 {
+        if self.max == 0 {
+            0.0
+        } else {
+            self.allocated as f64 / self.max as f64 * 100.0
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessFdUsage {
+    pub pid: u32,
+    pub name: String,
+    pub open: usize,
+    pub soft_limit: Option<u64>,
+}
+
+impl ProcessFdUsage {
+    pub fn percent(&self) -> Option<f64> {
+        self.soft_limit
+            .filter(|limit| *limit > 0)
+            .map(|limit| self.open as f64 / limit as f64 * 100.0)
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn system_fd_usage() -> Option<SystemFdUsage> {
+    // "<allocated> <free> <max>"; free is always 0 on modern kernels
+    let content = std::fs::read_to_string("/proc/sys/fs/file-nr").ok()?;
+    let fields: Vec<u64> = content
+        .split_whitespace()
+        .filter_map(|field| field.parse().ok())
+        .collect();
+    if fields.len() < 3 {
+        return None;
+    }
+    Some(SystemFdUsage {
+        allocated: fields[0].saturating_sub(fields[1]),
+        max: fields[2],
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn system_fd_usage() -> Option<SystemFdUsage> {
+    None
+}
+
+// Soft "Max open files" limit from /proc/<pid>/limits
+#[cfg(target_os = "linux")]
+pub fn open_files_limit(pid: u32) -> Option<u64> {
+    let limits = std::fs::read_to_string(format!("/proc/{}/limits", pid)).ok()?;
+    limits
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|soft| soft.parse().ok())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn open_files_limit(_pid: u32) -> Option<u64> {
+    None
+}
+
+// Collects descriptor usage and raises alerts when limits get close
+pub struct FdMonitor {
+    alert_percent: f64,
+    system: Option<SystemFdUsage>,
+    processes: Vec<ProcessFdUsage>,
+    // PIDs with an active alert, so alerts for exited processes get cleared
+    alerted: HashSet<u32>,
+}
+
+impl FdMonitor {
+    pub fn new(alert_percent: f64) -> Self {
+        FdMonitor {
+            alert_percent,
+            system: None,
+            processes: Vec::new(),
+            alerted: HashSet::new(),
+        }
+    }
+
+    pub fn refresh(&mut self, system: &System, alerts: &mut AlertEngine) {
+        self.system = system_fd_usage();
+
+        let mut processes: Vec<ProcessFdUsage> = system
+            .processes()
+            .iter()
+            .filter_map(|(pid, process)| {
+                let pid = pid.as_u32();
+                Some(ProcessFdUsage {
+                    pid,
+                    name: process.name().to_string(),
+                    open: fd_count(pid)?,
+                    soft_limit: open_files_limit(pid),
+                })
+            })
+            .collect();
+        processes.sort_by_key(|process| std::cmp::Reverse(process.open));
+
+        if self.alert_percent > 0.0 {
+            self.check_alerts(&processes, alerts);
+        }
+        processes.truncate(MAX_FD_ROWS);
+        self.processes = processes;
+    }
+
+    fn check_alerts(&mut self, processes: &[ProcessFdUsage], alerts: &mut AlertEngine) {
+        match self.system {
+            Some(usage) if usage.percent() >= self.alert_percent => alerts.raise(
+                "fds:system",
+                AlertLevel::Critical,
+                "fds",
+                format!(
+                    "System file handles at {:.1}% ({}/{})",
+                    usage.percent(),
+                    usage.allocated,
+                    usage.max
+                ),
+            ),
+            _ => alerts.clear("fds:system"),
+        }
+
+        let mut alerted = HashSet::new();
+        for process in processes {
+            if matches!(process.percent(), Some(percent) if percent >= self.alert_percent) {
+                alerts.raise(
+                    &format!("fds:{}", process.pid),
+                    AlertLevel::Warning,
+                    "fds",
+                    format!(
+                        "{} ({}) has {} of {} file descriptors open",
+                        process.name,
+                        process.pid,
+                        process.open,
+                        process.soft_limit.unwrap_or_default()
+                    ),
+                );
+                alerted.insert(process.pid);
+            }
+        }
+        for pid in self.alerted.difference(&alerted) {
+            alerts.clear(&format!("fds:{}", pid));
+        }
+        self.alerted = alerted;
+    }
+
+    pub fn system(&self) -> Option<SystemFdUsage> {
+        self.system
+    }
+
+    pub fn processes(&self) -> &[ProcessFdUsage] {
+        &self.processes
+    }
+}
+
+// File descriptor panel: system-wide handles and the heaviest processes
+pub fn monitor_fds(monitor: &FdMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-file-descriptors");
+    println!("\n{}", heading.bold().warning());
+    println!("{}", i18n::underline(heading).warning());
+
+    match monitor.system() {
+        Some(usage) => println!(
+            "System: {}/{} open ({}%) {}",
+            usage.allocated,
+            usage.max,
+            format!("{:.2}", usage.percent()).value(),
+            graphs::bar(usage.percent(), 20).good()
+        ),
+        None => println!("  System-wide file handle counts are only available on Linux"),
+    }
+
+    if monitor.processes().is_empty() {
+        return Ok(());
+    }
+
+    println!(
+        "{:<8} {:<20} {:<8} {:<10} USED%",
+        "PID", "NAME", "OPEN", "LIMIT"
+    );
+    for process in monitor.processes() {
+        let name = if process.name.len() > 20 {
+            &process.name[0..17]
+        } else {
+            &process.name
+        };
+        let limit = process
+            .soft_limit
+            .map(|limit| limit.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let percent = match process.percent() {
+            Some(percent) if percent >= 80.0 => format!("{:.1}", percent).critical(),
+            Some(percent) => format!("{:.1}", percent).normal(),
+            None => "-".normal(),
+        };
+        println!(
+            "{:<8} {:<20} {:<8} {:<10} {}",
+            process.pid, name, process.open, limit, percent
+        );
+    }
+
+    Ok(())
+}
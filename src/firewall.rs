@@ -0,0 +1,283 @@
+use std::io::ErrorKind;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+use log::debug;
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::i18n;
+use crate::theme::Themed;
+
+// Rule sets rarely change, so the tools are not run on every refresh
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub enum BackendState {
+    Active(String),
+    Inactive(String),
+    // The tool is installed but could not be queried, usually for lack of root
+    Unknown(String),
+}
+
+// One firewall tool found on the system and what it reported
+#[derive(Debug, Clone)]
+pub struct Backend {
+    pub name: &'static str,
+    pub state: BackendState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Protected,
+    Unprotected,
+    Unknown,
+}
+
+// Firewall state from nftables, iptables, ufw or Windows Firewall
+pub struct FirewallMonitor {
+    backends: Vec<Backend>,
+    last_check: Option<Instant>,
+}
+
+impl FirewallMonitor {
+    pub fn new() -> Self {
+        FirewallMonitor {
+            backends: Vec::new(),
+            last_check: None,
+        }
+    }
+
+    pub fn backends(&self) -> &[Backend] {
+        &self.backends
+    }
+
+    // Protected when any tool has rules in force; unprotected only when every
+    // tool could be read and none has
+    pub fn verdict(&self) -> Verdict {
+        let any = |wanted: fn(&BackendState) -> bool| {
+            self.backends.iter().any(|backend| wanted(&backend.state))
+        };
+        if any(|state| matches!(state, BackendState::Active(_))) {
+            Verdict::Protected
+        } else if self.backends.is_empty() || any(|state| matches!(state, BackendState::Unknown(_)))
+        {
+            Verdict::Unknown
+        } else {
+            Verdict::Unprotected
+        }
+    }
+
+    pub fn refresh(&mut self, alerts: &mut AlertEngine) {
+        if matches!(self.last_check, Some(last) if last.elapsed() < CHECK_INTERVAL) {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+        self.backends = detect_backends();
+
+        if self.verdict() == Verdict::Unprotected {
+            alerts.raise(
+                "firewall",
+                AlertLevel::Warning,
+                "firewall",
+                "No firewall rules are active".to_string(),
+            );
+        } else {
+            alerts.clear("firewall");
+        }
+    }
+}
+
+// Turns a tool's output into its state
+type Parser = fn(&str) -> BackendState;
+
+fn detect_backends() -> Vec<Backend> {
+    let mut backends = Vec::new();
+    if cfg!(windows) {
+        if let Some(state) = run("netsh", &["advfirewall", "show", "allprofiles", "state"])
+            .map(|output| output.map(|text| windows_state(&text)))
+        {
+            backends.push(Backend {
+                name: "Windows Firewall",
+                state: state.unwrap_or_else(BackendState::Unknown),
+            });
+        }
+        return backends;
+    }
+
+    let probes: [(&'static str, &str, &[&str], Parser); 3] = [
+        ("nftables", "nft", &["list", "ruleset"], nft_state),
+        ("iptables", "iptables", &["-S"], iptables_state),
+        ("ufw", "ufw", &["status"], ufw_state),
+    ];
+    for (name, program, args, parse) in probes {
+        if let Some(output) = run(program, args) {
+            backends.push(Backend {
+                name,
+                state: output
+                    .map(|text| parse(&text))
+                    .unwrap_or_else(BackendState::Unknown),
+            });
+        }
+    }
+    backends
+}
+
+// None when the tool is not installed; Err with the reason when it failed
+fn run(program: &str, args: &[&str]) -> Option<std::result::Result<String, String>> {
+    let output = match Command::new(program).args(args).output() {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return None,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+    if output.status.success() {
+        return Some(Ok(String::from_utf8_lossy(&output.stdout).into_owned()));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    debug!("{} failed: {}", program, stderr.trim());
+    let lower = stderr.to_lowercase();
+    let reason = if lower.contains("permission denied")
+        || lower.contains("not permitted")
+        || lower.contains("root")
+    {
+        "run as root to read the rules".to_string()
+    } else {
+        stderr.lines().next().unwrap_or("failed").trim().to_string()
+    };
+    Some(Err(reason))
+}
+
+// Counts rules per chain in `nft list ruleset` and picks up the input hook policy
+fn nft_state(ruleset: &str) -> BackendState {
+    let mut chains = 0;
+    let mut rules = 0;
+    let mut input_policy = None;
+    let mut in_chain = false;
+    for line in ruleset.lines().map(str::trim) {
+        if line.starts_with("chain ") {
+            in_chain = true;
+            chains += 1;
+        } else if !in_chain || line.is_empty() {
+            continue;
+        } else if line == "}" {
+            in_chain = false;
+        } else if line.starts_with("type ") {
+            // "type filter hook input priority filter; policy drop;"
+            if line.contains("hook input") {
+                input_policy = line
+                    .split(';')
+                    .find_map(|part| part.trim().strip_prefix("policy "))
+                    .map(str::to_string);
+            }
+        } else {
+            rules += 1;
+        }
+    }
+
+    let detail = format!(
+        "{} rules in {} chains, input policy {}",
+        rules,
+        chains,
+        input_policy.as_deref().unwrap_or("accept")
+    );
+    if rules > 0 || input_policy.as_deref().is_some_and(is_blocking) {
+        BackendState::Active(detail)
+    } else {
+        BackendState::Inactive(detail)
+    }
+}
+
+// `iptables -S`: "-P INPUT DROP" for policies, "-A ..." for rules
+fn iptables_state(rules: &str) -> BackendState {
+    let mut count = 0;
+    let mut input_policy = "ACCEPT";
+    for line in rules.lines() {
+        if let Some(policy) = line.strip_prefix("-P INPUT ") {
+            input_policy = policy.trim();
+        } else if line.starts_with("-A ") {
+            count += 1;
+        }
+    }
+
+    let detail = format!("{} rules, input policy {}", count, input_policy);
+    if count > 0 || is_blocking(input_policy) {
+        BackendState::Active(detail)
+    } else {
+        BackendState::Inactive(detail)
+    }
+}
+
+// `ufw status`: "Status: active", then a table of rules under a dashed line
+fn ufw_state(status: &str) -> BackendState {
+    let active = status
+        .lines()
+        .any(|line| line.trim().eq_ignore_ascii_case("status: active"));
+    if !active {
+        return BackendState::Inactive("inactive".to_string());
+    }
+    let rules = status
+        .lines()
+        .skip_while(|line| !line.starts_with("--"))
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .count();
+    BackendState::Active(format!("active, {} rules", rules))
+}
+
+// `netsh advfirewall show allprofiles state` lists "State ON/OFF" per profile
+fn windows_state(output: &str) -> BackendState {
+    let mut profile = "";
+    let mut on = Vec::new();
+    let mut off = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if let Some(name) = line.strip_suffix(" Profile Settings:") {
+            profile = name;
+        } else if let Some(state) = line.strip_prefix("State") {
+            if state.trim().eq_ignore_ascii_case("on") {
+                on.push(profile);
+            } else {
+                off.push(profile);
+            }
+        }
+    }
+
+    if on.is_empty() {
+        BackendState::Inactive("all profiles off".to_string())
+    } else if off.is_empty() {
+        BackendState::Active(format!("{} on", on.join(", ")))
+    } else {
+        BackendState::Active(format!("{} on; {} off", on.join(", "), off.join(", ")))
+    }
+}
+
+fn is_blocking(policy: &str) -> bool {
+    policy.eq_ignore_ascii_case("drop") || policy.eq_ignore_ascii_case("reject")
+}
+
+// Firewall panel: overall verdict, then one line per tool
+pub fn monitor_firewall(monitor: &FirewallMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-firewall");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    let status = match monitor.verdict() {
+        Verdict::Protected => "ACTIVE".good(),
+        Verdict::Unprotected => "NO FIREWALL".critical().bold(),
+        Verdict::Unknown if monitor.backends().is_empty() => "no firewall tools found".warning(),
+        Verdict::Unknown => "UNKNOWN".warning(),
+    };
+    println!("Status: {}", status);
+
+    for backend in monitor.backends() {
+        let detail = match backend.state {
+            BackendState::Active(ref detail) => detail.good(),
+            BackendState::Inactive(ref detail) => detail.critical(),
+            BackendState::Unknown(ref reason) => reason.warning(),
+        };
+        println!("  {}: {}", backend.name, detail);
+    }
+
+    Ok(())
+}
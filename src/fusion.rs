@@ -0,0 +1,154 @@
+use std::time::Instant;
+
+use crate::sensors::SensorData;
+
+// How strongly the accelerometer pulls the estimate back towards gravity;
+// higher settles faster but lets vibration through
+const BETA: f32 = 0.1;
+
+// Longest step to integrate, so a stall does not fling the estimate around
+const MAX_STEP_SECS: f32 = 0.5;
+
+// Orientation as a unit quaternion [w, x, y, z]
+pub type Quaternion = [f32; 4];
+
+// Tracks the orientation of devices that do not report one, and derives the
+// roll/pitch/yaw shown from the quaternion either way
+pub struct OrientationFilter {
+    quaternion: Option<Quaternion>,
+    last: Option<Instant>,
+}
+
+impl OrientationFilter {
+    pub fn new() -> Self {
+        OrientationFilter {
+            quaternion: None,
+            last: None,
+        }
+    }
+
+    // Fills in data.quaternion when the device did not supply one, then sets
+    // data.orientation from it
+    pub fn apply(&mut self, data: &mut SensorData) {
+        let quaternion = match data.quaternion {
+            Some(quaternion) => quaternion,
+            // Some devices report Euler angles but no quaternion
+            None if data.orientation != [0.0; 3] => from_euler(data.orientation),
+            None => self.fuse(data),
+        };
+        data.quaternion = Some(quaternion);
+        data.orientation = to_euler(quaternion);
+    }
+
+    // Madgwick's gradient descent filter for a gyro plus accelerometer: the
+    // gyro is integrated and the accelerometer corrects roll and pitch drift.
+    // Yaw has no reference without a magnetometer, so it slowly drifts.
+    fn fuse(&mut self, data: &SensorData) -> Quaternion {
+        let step = self
+            .last
+            .map(|last| data.timestamp.saturating_duration_since(last).as_secs_f32())
+            .unwrap_or(0.0)
+            .min(MAX_STEP_SECS);
+        self.last = Some(data.timestamp);
+
+        let Some([q0, q1, q2, q3]) = self.quaternion else {
+            // Start level with gravity rather than converging from identity
+            let [ax, ay, az] = data.acceleration;
+            let roll = ay.atan2(az).to_degrees();
+            let pitch = (-ax).atan2((ay * ay + az * az).sqrt()).to_degrees();
+            let start = from_euler([roll, pitch, 0.0]);
+            self.quaternion = Some(start);
+            return start;
+        };
+
+        let [gx, gy, gz] = data.gyro.map(f32::to_radians);
+        let mut rate = [
+            0.5 * (-q1 * gx - q2 * gy - q3 * gz),
+            0.5 * (q0 * gx + q2 * gz - q3 * gy),
+            0.5 * (q0 * gy - q1 * gz + q3 * gx),
+            0.5 * (q0 * gz + q1 * gy - q2 * gx),
+        ];
+
+        let norm = data.acceleration.iter().map(|a| a * a).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            let [ax, ay, az] = data.acceleration.map(|a| a / norm);
+            let (q0q0, q1q1, q2q2, q3q3) = (q0 * q0, q1 * q1, q2 * q2, q3 * q3);
+            let gradient = normalize([
+                4.0 * q0 * q2q2 + 2.0 * q2 * ax + 4.0 * q0 * q1q1 - 2.0 * q1 * ay,
+                4.0 * q1 * q3q3 - 2.0 * q3 * ax + 4.0 * q0q0 * q1 - 2.0 * q0 * ay - 4.0 * q1
+                    + 8.0 * q1 * q1q1
+                    + 8.0 * q1 * q2q2
+                    + 4.0 * q1 * az,
+                4.0 * q0q0 * q2 + 2.0 * q0 * ax + 4.0 * q2 * q3q3 - 2.0 * q3 * ay - 4.0 * q2
+                    + 8.0 * q2 * q1q1
+                    + 8.0 * q2 * q2q2
+                    + 4.0 * q2 * az,
+                4.0 * q1q1 * q3 - 2.0 * q1 * ax + 4.0 * q2q2 * q3 - 2.0 * q2 * ay,
+            ]);
+            for (r, g) in rate.iter_mut().zip(gradient) {
+                *r -= BETA * g;
+            }
+        }
+
+        let quaternion = normalize([
+            q0 + rate[0] * step,
+            q1 + rate[1] * step,
+            q2 + rate[2] * step,
+            q3 + rate[3] * step,
+        ]);
+        self.quaternion = Some(quaternion);
+        quaternion
+    }
+}
+
+fn normalize(q: Quaternion) -> Quaternion {
+    let norm = q.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        q.map(|v| v / norm)
+    } else {
+        q
+    }
+}
+
+// `v` turned by the quaternion: v + 2w(u × v) + 2u × (u × v), with u its
+// vector part
+pub fn rotate([w, x, y, z]: Quaternion, v: [f32; 3]) -> [f32; 3] {
+    let cross = |a: [f32; 3], b: [f32; 3]| {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    };
+    let u = [x, y, z];
+    let t = cross(u, v).map(|c| 2.0 * c);
+    let ut = cross(u, t);
+    [
+        v[0] + w * t[0] + ut[0],
+        v[1] + w * t[1] + ut[1],
+        v[2] + w * t[2] + ut[2],
+    ]
+}
+
+// Roll, pitch and yaw in degrees (applied yaw, then pitch, then roll)
+pub fn from_euler(orientation: [f32; 3]) -> Quaternion {
+    let [roll, pitch, yaw] = orientation.map(|angle| angle.to_radians() / 2.0);
+    let (sr, cr) = roll.sin_cos();
+    let (sp, cp) = pitch.sin_cos();
+    let (sy, cy) = yaw.sin_cos();
+    [
+        cr * cp * cy + sr * sp * sy,
+        sr * cp * cy - cr * sp * sy,
+        cr * sp * cy + sr * cp * sy,
+        cr * cp * sy - sr * sp * cy,
+    ]
+}
+
+// Roll, pitch and yaw in degrees. Pitch is clamped at ±90°, where roll and
+// yaw become one axis; the quaternion itself has no such singularity.
+pub fn to_euler([w, x, y, z]: Quaternion) -> [f32; 3] {
+    let roll = (2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y));
+    let pitch = (2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin();
+    let yaw = (2.0 * (w * z + x * y)).atan2(1.0 - 2.0 * (y * y + z * z));
+    [roll, pitch, yaw].map(f32::to_degrees)
+}
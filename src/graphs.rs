@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+
+use crate::theme::{self, Role, Themed};
+
+// Block characters used for sparklines, lowest to highest
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Width of the full-view history charts, in cells of two samples each
+pub const CHART_WIDTH: usize = 40;
+
+// Dot bits of a braille cell, indexed by [column][row from the top]
+const BRAILLE_DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+// Render a series of values as a one-line sparkline.
+// When `max` is None the series is scaled to its own peak.
+pub fn sparkline(values: &[f64], max: Option<f64>) -> String {
+    if values.is_empty() {
+        return String::new();
+    }
+
+    let peak = max.unwrap_or_else(|| values.iter().cloned().fold(0.0, f64::max));
+
+    let line: String = values
+        .iter()
+        .map(|&value| {
+            if peak <= 0.0 {
+                return SPARK_CHARS[0];
+            }
+            let ratio = (value / peak).clamp(0.0, 1.0);
+            let index = (ratio * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+            SPARK_CHARS[index]
+        })
+        .collect();
+    theme::ascii(&line).into_owned()
+}
+
+// Render a horizontal usage bar `width` cells wide for a 0-100 percentage
+pub fn bar(percent: f64, width: usize) -> String {
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+    let bar = format!("{}{}", "█".repeat(filled), "░".repeat(width - filled));
+    theme::ascii(&bar).into_owned()
+}
+
+// Render a series as a braille line chart `width` cells wide and `height` lines
+// tall. Each cell holds two samples and four dot rows, so the newest
+// `2 * width` values are drawn, right-aligned. Returns the lines top to bottom.
+pub fn braille_chart(values: &[f64], max: Option<f64>, width: usize, height: usize) -> Vec<String> {
+    let dot_rows = height * 4;
+    let samples = &values[values.len().saturating_sub(width * 2)..];
+    let peak = max.unwrap_or_else(|| samples.iter().cloned().fold(0.0, f64::max));
+    let level = |value: f64| {
+        if peak <= 0.0 {
+            return 0;
+        }
+        ((value / peak).clamp(0.0, 1.0) * (dot_rows - 1) as f64).round() as usize
+    };
+
+    let mut cells = vec![vec![0u8; width]; height];
+    let offset = width * 2 - samples.len();
+    let mut previous: Option<usize> = None;
+    for (i, &value) in samples.iter().enumerate() {
+        let x = offset + i;
+        let y = level(value);
+        // Join each sample to the one before so steep changes stay one line
+        let (low, high) = match previous {
+            Some(prev) => (prev.min(y), prev.max(y)),
+            None => (y, y),
+        };
+        for dot in low..=high {
+            let row = dot_rows - 1 - dot;
+            cells[row / 4][x / 2] |= BRAILLE_DOTS[x % 2][row % 4];
+        }
+        previous = Some(y);
+    }
+
+    cells
+        .iter()
+        .map(|row| {
+            let line: String = row
+                .iter()
+                .map(|&bits| char::from_u32(0x2800 + bits as u32).unwrap_or(' '))
+                .collect();
+            theme::ascii(&line).into_owned()
+        })
+        .collect()
+}
+
+// Recent CPU, memory and network samples for the full-view charts
+pub struct ResourceHistory {
+    pub cpu: VecDeque<f64>,
+    pub memory: VecDeque<f64>,
+    pub net_rx: VecDeque<f64>,
+    pub net_tx: VecDeque<f64>,
+    pub height: usize,
+}
+
+impl ResourceHistory {
+    pub fn new(height: usize) -> Self {
+        ResourceHistory {
+            cpu: VecDeque::new(),
+            memory: VecDeque::new(),
+            net_rx: VecDeque::new(),
+            net_tx: VecDeque::new(),
+            height: height.max(1),
+        }
+    }
+
+    // Percentages for CPU and memory, bytes per second for the network
+    pub fn push(&mut self, cpu: f64, memory: f64, net_rx: f64, net_tx: f64) {
+        for (series, value) in [
+            (&mut self.cpu, cpu),
+            (&mut self.memory, memory),
+            (&mut self.net_rx, net_rx),
+            (&mut self.net_tx, net_tx),
+        ] {
+            if series.len() == CHART_WIDTH * 2 {
+                series.pop_front();
+            }
+            series.push_back(value);
+        }
+    }
+
+    // Print one series under its title as a chart with its scale on the
+    // left. Needs two samples to draw a line, so neither shows on the first
+    // refresh.
+    pub fn print_chart(
+        &self,
+        title: &str,
+        values: &VecDeque<f64>,
+        max: Option<f64>,
+        role: Role,
+        label: impl Fn(f64) -> String,
+    ) {
+        if values.len() < 2 {
+            return;
+        }
+        println!("{}:", title);
+        let series: Vec<f64> = values.iter().copied().collect();
+        let peak = max.unwrap_or_else(|| series.iter().cloned().fold(0.0, f64::max));
+        let lines = braille_chart(&series, Some(peak), CHART_WIDTH, self.height);
+        let last = lines.len() - 1;
+        for (i, line) in lines.iter().enumerate() {
+            let axis = match i {
+                0 => label(peak),
+                i if i == last => label(0.0),
+                _ => String::new(),
+            };
+            println!("  {:>10} {}", axis, line.as_str().paint(role));
+        }
+    }
+}
+
+// Columns available for output. COLUMNS wins so redirected output can be sized.
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|&columns| columns > 0)
+        .or_else(|| {
+            console::Term::stdout()
+                .size_checked()
+                .map(|(_, columns)| columns as usize)
+        })
+        .unwrap_or(80)
+}
@@ -0,0 +1,440 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::Utc;
+use colored::*;
+use crossbeam_channel::Receiver;
+use log::debug;
+use sysinfo::{DiskExt, System, SystemExt};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::history::HistoryStore;
+use crate::i18n;
+use crate::process::format_duration;
+use crate::theme::Themed;
+use crate::units;
+
+// Walking a large tree is slow, so paths are rescanned on their own schedule
+const SCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+// Growth rates look back this far in the history database
+const RATE_WINDOW_SECS: i64 = 86_400;
+
+// Shorter spans than this give too noisy a rate to project from
+const MIN_RATE_SPAN_SECS: i64 = 300;
+
+// A projected time-to-full under this is critical
+const FULL_SOON_SECS: f64 = 86_400.0;
+
+const BYTES_PER_MB: f64 = 1_048_576.0;
+
+// A single file and how fast it grew between the last two scans
+#[derive(Debug, Clone)]
+pub struct FileGrowth {
+    pub path: PathBuf,
+    pub size: u64,
+    pub bytes_per_hour: f64,
+}
+
+// A file as the previous scan saw it: (inode, size). A rotated or truncated
+// file starts counting from zero again.
+type SeenFiles = HashMap<PathBuf, (u64, u64)>;
+
+// One scan of a tracked path, made on the background thread
+struct Scan {
+    path: PathBuf,
+    size: Option<u64>,
+    inode: Option<u64>,
+    largest: Option<(PathBuf, u64)>,
+    fastest: Option<FileGrowth>,
+    // Files growing faster than the alert threshold
+    runaway: Vec<FileGrowth>,
+}
+
+// What the panel shows for one tracked path
+#[derive(Debug, Clone)]
+pub struct PathGrowth {
+    pub path: PathBuf,
+    pub scanned: bool,
+    pub size: Option<u64>,
+    pub bytes_per_hour: Option<f64>,
+    pub available: Option<u64>,
+    pub largest: Option<(PathBuf, u64)>,
+    pub fastest: Option<FileGrowth>,
+}
+
+impl PathGrowth {
+    // Seconds until the filesystem holding the path fills at the current rate
+    pub fn seconds_to_full(&self) -> Option<f64> {
+        match (self.bytes_per_hour, self.available) {
+            (Some(rate), Some(available)) if rate > 0.0 => Some(available as f64 / rate * 3600.0),
+            _ => None,
+        }
+    }
+}
+
+// Where the growth rate of a tracked path is measured from
+struct Baseline {
+    // The previous scan, for the rate when there is no history yet
+    at: Instant,
+    size: u64,
+    inode: Option<u64>,
+    // History samples older than this are ignored; moved up when the path
+    // shrinks or is replaced, so a rotated log does not show a negative rate
+    since: i64,
+}
+
+// Tracks the size of configured files and directories across runs, using the
+// history database, and raises alerts for runaway files and filling disks
+pub struct GrowthMonitor {
+    scans: Receiver<Vec<Scan>>,
+    store: Option<HistoryStore>,
+    paths: Vec<PathGrowth>,
+    previous: HashMap<PathBuf, Baseline>,
+    alerted: HashSet<String>,
+}
+
+impl GrowthMonitor {
+    pub fn new(paths: &[String], alert_mb_per_hour: f64) -> Self {
+        let paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        let (sender, scans) = crossbeam_channel::unbounded();
+        let alert_bytes_per_hour = alert_mb_per_hour * BYTES_PER_MB;
+        let watched = paths.clone();
+        thread::spawn(move || {
+            let mut previous_files = SeenFiles::new();
+            let mut previous_scan: Option<Instant> = None;
+            loop {
+                let started = Instant::now();
+                let hours = previous_scan.map(|at| at.elapsed().as_secs_f64() / 3600.0);
+                let mut files = HashMap::new();
+                let scans: Vec<Scan> = watched
+                    .iter()
+                    .map(|path| {
+                        let scan = scan_path(path, &previous_files, hours, alert_bytes_per_hour);
+                        files.extend(scan.1);
+                        scan.0
+                    })
+                    .collect();
+                previous_files = files;
+                previous_scan = Some(started);
+                if sender.send(scans).is_err() {
+                    return;
+                }
+                thread::sleep(SCAN_INTERVAL);
+            }
+        });
+
+        let store = HistoryStore::open()
+            .map_err(|e| debug!("Path growth history disabled: {}", e))
+            .ok();
+        GrowthMonitor {
+            scans,
+            store,
+            paths: paths
+                .into_iter()
+                .map(|path| PathGrowth {
+                    path,
+                    scanned: false,
+                    size: None,
+                    bytes_per_hour: None,
+                    available: None,
+                    largest: None,
+                    fastest: None,
+                })
+                .collect(),
+            previous: HashMap::new(),
+            alerted: HashSet::new(),
+        }
+    }
+
+    pub fn paths(&self) -> &[PathGrowth] {
+        &self.paths
+    }
+
+    pub fn refresh(&mut self, system: &System, alerts: &mut AlertEngine) {
+        let Some(scans) = self.scans.try_iter().last() else {
+            return;
+        };
+
+        let mut alerted = HashSet::new();
+        for scan in scans {
+            let bytes_per_hour = scan
+                .size
+                .and_then(|size| self.growth_rate(&scan.path, size, scan.inode));
+            let growth = PathGrowth {
+                scanned: true,
+                available: free_space(system, &scan.path),
+                bytes_per_hour,
+                size: scan.size,
+                largest: scan.largest,
+                fastest: scan.fastest,
+                path: scan.path,
+            };
+
+            for file in &scan.runaway {
+                let key = format!("growth:file:{}", file.path.display());
+                alerts.raise(
+                    &key,
+                    AlertLevel::Warning,
+                    "disk",
+                    format!(
+                        "{} is growing at {}/h ({})",
+                        file.path.display(),
+                        units::format_bytes(file.bytes_per_hour),
+                        units::format_bytes(file.size as f64)
+                    ),
+                );
+                alerted.insert(key);
+            }
+
+            let key = format!("growth:full:{}", growth.path.display());
+            match growth.seconds_to_full() {
+                Some(seconds) if seconds < FULL_SOON_SECS => {
+                    alerts.raise(
+                        &key,
+                        AlertLevel::Critical,
+                        "disk",
+                        format!(
+                            "{} will fill its disk in {} at {}/h",
+                            growth.path.display(),
+                            format_duration(seconds as u64),
+                            units::format_bytes(growth.bytes_per_hour.unwrap_or_default())
+                        ),
+                    );
+                    alerted.insert(key);
+                }
+                _ => alerts.clear(&key),
+            }
+
+            if let Some(slot) = self.paths.iter_mut().find(|p| p.path == growth.path) {
+                *slot = growth;
+            }
+        }
+
+        // Files that calmed down or were removed
+        for key in self.alerted.difference(&alerted) {
+            alerts.clear(key);
+        }
+        self.alerted = alerted;
+    }
+
+    // Bytes per hour over the last day of recorded sizes, falling back to the
+    // previous scan of this run. A smaller size or a new inode starts the
+    // measurement over. Records the new size as it goes.
+    fn growth_rate(&mut self, path: &Path, size: u64, inode: Option<u64>) -> Option<f64> {
+        let now = Utc::now().timestamp();
+        let metric = format!("growth:{}", path.display());
+        let previous = self.previous.remove(path);
+        let reset = previous
+            .as_ref()
+            .is_some_and(|baseline| size < baseline.size || inode != baseline.inode);
+        let mut since = match previous {
+            _ if reset => now,
+            Some(ref baseline) => baseline.since,
+            None => 0,
+        }
+        .max(now - RATE_WINDOW_SECS);
+        let mut rate = None;
+        if let Some(ref store) = self.store {
+            match store.oldest_sample(&metric, since) {
+                // Shrank while Hercules was not running
+                Ok(Some((_, old_size))) if (size as f64) < old_size => since = now,
+                Ok(Some((ts, old_size))) if now - ts >= MIN_RATE_SPAN_SECS => {
+                    rate = Some((size as f64 - old_size) / (now - ts) as f64 * 3600.0);
+                }
+                _ => {}
+            }
+            if let Err(e) = store.record(&metric, size as f64) {
+                debug!("Could not record size of {}: {}", path.display(), e);
+            }
+        }
+        self.previous.insert(
+            path.to_path_buf(),
+            Baseline {
+                at: Instant::now(),
+                size,
+                inode,
+                since,
+            },
+        );
+        rate.or_else(|| {
+            let baseline = previous.filter(|_| !reset)?;
+            let hours = baseline.at.elapsed().as_secs_f64() / 3600.0;
+            (hours > 0.0).then(|| (size as f64 - baseline.size as f64) / hours)
+        })
+    }
+}
+
+// Size of a file or directory tree, with its largest and fastest-growing files.
+// Also returns every file size seen, for the next scan to compare against.
+fn scan_path(
+    path: &Path,
+    previous: &SeenFiles,
+    hours: Option<f64>,
+    alert_bytes_per_hour: f64,
+) -> (Scan, SeenFiles) {
+    let mut files = SeenFiles::new();
+    let metadata = fs::symlink_metadata(path).ok();
+    let size = metadata.as_ref().map(|metadata| {
+        if metadata.is_dir() {
+            collect_files(path, &mut files)
+        } else {
+            files.insert(path.to_path_buf(), (inode(metadata), metadata.len()));
+            metadata.len()
+        }
+    });
+
+    let largest = files
+        .iter()
+        .max_by_key(|(_, (_, size))| *size)
+        .map(|(path, &(_, size))| (path.clone(), size));
+
+    let mut growing: Vec<FileGrowth> = match hours {
+        Some(hours) if hours > 0.0 => files
+            .iter()
+            .filter_map(|(path, &(inode, size))| {
+                // New, rotated and truncated files count from zero, so a
+                // fresh runaway log still shows
+                let old = match previous.get(path) {
+                    Some(&(old_inode, old)) if old_inode == inode && old <= size => old,
+                    _ => 0,
+                };
+                (size > old).then(|| FileGrowth {
+                    path: path.clone(),
+                    size,
+                    bytes_per_hour: (size - old) as f64 / hours,
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    growing.sort_by(|a, b| b.bytes_per_hour.total_cmp(&a.bytes_per_hour));
+    let fastest = growing.first().cloned();
+    let runaway = growing
+        .into_iter()
+        .take_while(|file| {
+            alert_bytes_per_hour > 0.0 && file.bytes_per_hour >= alert_bytes_per_hour
+        })
+        .collect();
+
+    (
+        Scan {
+            path: path.to_path_buf(),
+            size,
+            inode: metadata.as_ref().map(inode),
+            largest,
+            fastest,
+            runaway,
+        },
+        files,
+    )
+}
+
+// Add every regular file under `dir` to `files`; returns their total size.
+// Symlinks are not followed.
+fn collect_files(dir: &Path, files: &mut SeenFiles) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut total = 0;
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            total += collect_files(&entry.path(), files);
+        } else if metadata.is_file() {
+            total += metadata.len();
+            files.insert(entry.path(), (inode(&metadata), metadata.len()));
+        }
+    }
+    total
+}
+
+// Tells a rotated file from the one it replaced at the same path
+fn inode(metadata: &fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.ino()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        0
+    }
+}
+
+// Free space on the filesystem holding `path`: the disk with the longest
+// mount point that is a prefix of it
+fn free_space(system: &System, path: &Path) -> Option<u64> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    system
+        .disks()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+// Path growth panel: size, growth rate and projected time-to-full per path
+pub fn monitor_growth(monitor: &GrowthMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-growth");
+    println!("\n{}", heading.bold().warning());
+    println!("{}", i18n::underline(heading).warning());
+
+    for growth in monitor.paths() {
+        let Some(size) = growth.size else {
+            let status = if growth.scanned {
+                "not found".critical()
+            } else {
+                "scanning...".value()
+            };
+            println!("{}: {}", growth.path.display(), status);
+            continue;
+        };
+
+        let rate = match growth.bytes_per_hour {
+            Some(rate) if rate > 0.0 => format!("+{}/h", units::format_bytes(rate)).warning(),
+            Some(rate) if rate < 0.0 => format!("-{}/h", units::format_bytes(-rate)).good(),
+            Some(_) => "stable".good(),
+            None => "measuring".value(),
+        };
+        let full = match growth.seconds_to_full() {
+            Some(seconds) if seconds < FULL_SOON_SECS => {
+                format!(", disk full in {}", format_duration(seconds as u64)).critical()
+            }
+            Some(seconds) => format!(", disk full in {}", format_duration(seconds as u64)).value(),
+            None => "".normal(),
+        };
+        println!(
+            "{}: {} ({}{})",
+            growth.path.display(),
+            units::format_bytes(size as f64).value(),
+            rate,
+            full
+        );
+
+        if let Some(ref file) = growth.fastest {
+            println!(
+                "  Fastest growing: {} {} ({})",
+                file.path.display(),
+                format!("+{}/h", units::format_bytes(file.bytes_per_hour)).warning(),
+                units::format_bytes(file.size as f64)
+            );
+        }
+        if let Some((ref path, size)) = growth.largest {
+            println!(
+                "  Largest file:    {} ({})",
+                path.display(),
+                units::format_bytes(size as f64).value()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use std::sync::OnceLock;
+
+use crate::pi;
+
+// Raspberry Pi board families that call for different defaults
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Board {
+    Pi1,
+    Zero,
+    Pi2,
+    Pi3,
+    Pi3Plus,
+    Zero2,
+    Pi4,
+    Pi400,
+    Pi5,
+    Pi500,
+    Cm3,
+    Cm4,
+    Cm5,
+    // A Pi whose revision code we do not know yet
+    OtherPi,
+    NotPi,
+}
+
+impl Board {
+    // Board type from the revision code: new-style codes (bit 23 set) carry it
+    // in bits 4-11, old-style codes only exist on the original Pi 1
+    fn from_revision(revision: &str) -> Self {
+        let Ok(code) = u32::from_str_radix(revision, 16) else {
+            return Board::OtherPi;
+        };
+        if code & (1 << 23) == 0 {
+            return Board::Pi1;
+        }
+        match (code >> 4) & 0xFF {
+            0x00..=0x03 | 0x06 => Board::Pi1,
+            0x04 => Board::Pi2,
+            0x08 | 0x0e => Board::Pi3,
+            0x0d => Board::Pi3Plus,
+            0x09 | 0x0c => Board::Zero,
+            0x0a | 0x10 => Board::Cm3,
+            0x11 => Board::Pi4,
+            0x12 => Board::Zero2,
+            0x13 => Board::Pi400,
+            0x14 | 0x15 => Board::Cm4,
+            0x17 => Board::Pi5,
+            0x18 | 0x1a => Board::Cm5,
+            0x19 => Board::Pi500,
+            _ => Board::OtherPi,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Board::Pi1 => "Raspberry Pi 1",
+            Board::Zero => "Raspberry Pi Zero",
+            Board::Pi2 => "Raspberry Pi 2",
+            Board::Pi3 => "Raspberry Pi 3",
+            Board::Pi3Plus => "Raspberry Pi 3 B+/A+",
+            Board::Zero2 => "Raspberry Pi Zero 2",
+            Board::Pi4 => "Raspberry Pi 4",
+            Board::Pi400 => "Raspberry Pi 400",
+            Board::Pi5 => "Raspberry Pi 5",
+            Board::Pi500 => "Raspberry Pi 500",
+            Board::Cm3 => "Compute Module 3",
+            Board::Cm4 => "Compute Module 4",
+            Board::Cm5 => "Compute Module 5",
+            Board::OtherPi => "Raspberry Pi (unknown model)",
+            Board::NotPi => "not a Raspberry Pi",
+        }
+    }
+
+    // Defaults suited to the board, used where the config does not say otherwise
+    pub fn profile(self) -> Profile {
+        let mut profile = Profile::default();
+        if self == Board::NotPi {
+            return profile;
+        }
+        // Firmware throttling starts at 80°C on everything before the Pi 5
+        profile.temp_warn_celsius = 70.0;
+        profile.temp_crit_celsius = 80.0;
+        // The keyboard models have no camera connector
+        profile.show_camera = !matches!(self, Board::Pi400 | Board::Pi500);
+        match self {
+            // Single ARMv6 core: refresh less often and skip the history charts
+            Board::Pi1 | Board::Zero => {
+                profile.update_interval_ms = 3000;
+                profile.show_graphs = false;
+            }
+            Board::Pi2 | Board::Pi3 | Board::Zero2 | Board::Cm3 => {
+                profile.update_interval_ms = 2000;
+            }
+            // The firmware's soft temperature limit starts at 60°C
+            Board::Pi3Plus => {
+                profile.update_interval_ms = 2000;
+                profile.temp_warn_celsius = 60.0;
+            }
+            // Throttling starts at 85°C, and the Pi 5 has a fan header
+            Board::Pi5 | Board::Pi500 | Board::Cm5 => {
+                profile.show_fan = self == Board::Pi5;
+                profile.temp_warn_celsius = 75.0;
+                profile.temp_crit_celsius = 85.0;
+            }
+            _ => {}
+        }
+        profile
+    }
+}
+
+// Model-dependent defaults
+#[derive(Debug, Clone, Copy)]
+pub struct Profile {
+    pub update_interval_ms: u64,
+    pub show_graphs: bool,
+    pub show_fan: bool,
+    pub show_camera: bool,
+    pub temp_warn_celsius: f64,
+    pub temp_crit_celsius: f64,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile {
+            update_interval_ms: 1000,
+            show_graphs: true,
+            show_fan: false,
+            show_camera: false,
+            temp_warn_celsius: 80.0,
+            temp_crit_celsius: 90.0,
+        }
+    }
+}
+
+// The machine as detected at startup
+#[derive(Debug, Clone)]
+pub struct Hardware {
+    // Device tree model, e.g. "Raspberry Pi 4 Model B Rev 1.4"
+    pub model: Option<String>,
+    pub revision: Option<String>,
+    pub board: Board,
+}
+
+static HARDWARE: OnceLock<Hardware> = OnceLock::new();
+
+// Detected once; the board cannot change while we run
+pub fn detect() -> &'static Hardware {
+    HARDWARE.get_or_init(|| match pi::board_identity() {
+        Some((model, revision)) => Hardware {
+            board: revision
+                .as_deref()
+                .map(Board::from_revision)
+                .unwrap_or(Board::OtherPi),
+            model: Some(model),
+            revision,
+        },
+        None => Hardware {
+            model: None,
+            revision: None,
+            board: Board::NotPi,
+        },
+    })
+}
+
+pub fn profile() -> Profile {
+    detect().board.profile()
+}
+
+// Rows of the read-only `hardware` section of `hercules conf`
+pub fn describe() -> Vec<(&'static str, String)> {
+    let hardware = detect();
+    let profile = hardware.board.profile();
+    let mut rows = vec![("board", hardware.board.name().to_string())];
+    if let Some(ref model) = hardware.model {
+        rows.push(("model", model.clone()));
+    }
+    if let Some(ref revision) = hardware.revision {
+        rows.push(("revision", revision.clone()));
+    }
+    rows.extend([
+        ("update_interval_ms", profile.update_interval_ms.to_string()),
+        ("show_graphs", profile.show_graphs.to_string()),
+        ("show_fan", profile.show_fan.to_string()),
+        ("show_camera", profile.show_camera.to_string()),
+        ("temp_warn_celsius", profile.temp_warn_celsius.to_string()),
+        ("temp_crit_celsius", profile.temp_crit_celsius.to_string()),
+    ]);
+    rows
+}
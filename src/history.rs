@@ -0,0 +1,692 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{Local, TimeZone, Utc};
+use clap::{Arg, ArgAction, Command};
+use log::debug;
+use rusqlite::{params, Connection};
+use sysinfo::{CpuExt, NetworkExt, SystemExt};
+
+use crate::clock;
+use crate::config::ConfigManager;
+use crate::graphs;
+use crate::summary::parse_duration;
+use crate::theme::{Role, Themed};
+use crate::units;
+use crate::SystemResources;
+
+// Height of the history query chart, in lines
+const CHART_HEIGHT: usize = 8;
+
+// How often the background task rolls up and prunes old samples
+const COMPACTION_INTERVAL: Duration = Duration::from_secs(3600);
+
+// How long samples are kept at each resolution before being rolled up or dropped
+#[derive(Debug, Clone, Copy)]
+pub struct Retention {
+    pub raw_hours: u64,
+    pub minute_days: u64,
+    pub hourly_days: u64,
+}
+
+impl Retention {
+    pub fn from_config(config: &crate::config::HerculesConfig) -> Self {
+        Retention {
+            raw_hours: config.history_raw_hours,
+            minute_days: config.history_minute_days,
+            hourly_days: config.history_hourly_days,
+        }
+    }
+}
+
+// Rows changed by one compaction pass
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Compaction {
+    pub rolled_up: usize,
+    pub deleted: usize,
+}
+
+// A boot seen by Hercules and the latest time it was still running
+#[derive(Debug, Clone, Copy)]
+pub struct BootRecord {
+    pub boot_time: i64,
+    pub last_seen: i64,
+}
+
+impl BootRecord {
+    pub fn uptime_secs(&self) -> u64 {
+        self.last_seen.saturating_sub(self.boot_time).max(0) as u64
+    }
+}
+
+// Samples of one metric over a time bucket
+#[derive(Debug, Clone, Copy)]
+pub struct Bucket {
+    pub ts: i64,
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+}
+
+// Persistent metric history kept in a SQLite database next to the config file
+pub struct HistoryStore {
+    conn: Connection,
+}
+
+impl HistoryStore {
+    pub fn open() -> Result<Self> {
+        let config_dir = ConfigManager::get_config_dir()?;
+        if !config_dir.exists() {
+            fs::create_dir_all(&config_dir)?;
+        }
+        Self::open_at(config_dir.join("history.db"))
+    }
+
+    pub fn open_at(path: PathBuf) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        // The monitor and the compaction task write from separate connections
+        conn.busy_timeout(Duration::from_secs(10))?;
+        conn.execute_batch(
+            "PRAGMA auto_vacuum = INCREMENTAL;
+             CREATE TABLE IF NOT EXISTS samples (
+                 ts     INTEGER NOT NULL,
+                 metric TEXT    NOT NULL,
+                 value  REAL    NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS samples_metric_ts ON samples (metric, ts);
+             CREATE TABLE IF NOT EXISTS samples_1m (
+                 ts     INTEGER NOT NULL,
+                 metric TEXT    NOT NULL,
+                 min    REAL    NOT NULL,
+                 avg    REAL    NOT NULL,
+                 max    REAL    NOT NULL,
+                 count  INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS samples_1m_metric_ts ON samples_1m (metric, ts);
+             CREATE TABLE IF NOT EXISTS samples_1h (
+                 ts     INTEGER NOT NULL,
+                 metric TEXT    NOT NULL,
+                 min    REAL    NOT NULL,
+                 avg    REAL    NOT NULL,
+                 max    REAL    NOT NULL,
+                 count  INTEGER NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS samples_1h_metric_ts ON samples_1h (metric, ts);
+             CREATE TABLE IF NOT EXISTS events (
+                 ts      INTEGER NOT NULL,
+                 kind    TEXT    NOT NULL,
+                 message TEXT    NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS events_kind_ts ON events (kind, ts);
+             CREATE TABLE IF NOT EXISTS boots (
+                 boot_time INTEGER PRIMARY KEY,
+                 last_seen INTEGER NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS lan_devices (
+                 mac        TEXT PRIMARY KEY,
+                 ip         TEXT NOT NULL,
+                 hostname   TEXT,
+                 first_seen INTEGER NOT NULL,
+                 last_seen  INTEGER NOT NULL
+             );",
+        )?;
+        Ok(HistoryStore { conn })
+    }
+
+    // Store one metric value stamped with the current time
+    pub fn record(&self, metric: &str, value: f64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO samples (ts, metric, value) VALUES (?1, ?2, ?3)",
+            params![Utc::now().timestamp(), metric, value],
+        )?;
+        Ok(())
+    }
+
+    // Store several metric values in one transaction, so a refresh costs a
+    // single write on the SD card
+    pub fn record_all(&self, samples: &[(&str, f64)]) -> Result<()> {
+        let transaction = self.conn.unchecked_transaction()?;
+        let now = Utc::now().timestamp();
+        for (metric, value) in samples {
+            transaction.execute(
+                "INSERT INTO samples (ts, metric, value) VALUES (?1, ?2, ?3)",
+                params![now, metric, value],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    // Samples of `metric` since `since`, averaged into buckets of
+    // `resolution` seconds: (bucket start, min, avg, max), oldest first
+    pub fn samples(&self, metric: &str, since: i64, resolution: i64) -> Result<Vec<Bucket>> {
+        // Rolled-up rows are weighted by how many samples they stand for
+        let mut statement = self.conn.prepare(
+            "SELECT (ts / ?3) * ?3 AS bucket, MIN(min), SUM(avg * count) / SUM(count), MAX(max)
+             FROM (
+                 SELECT ts, value AS min, value AS avg, value AS max, 1 AS count
+                 FROM samples WHERE metric = ?1 AND ts >= ?2
+                 UNION ALL
+                 SELECT ts, min, avg, max, count FROM samples_1m WHERE metric = ?1 AND ts >= ?2
+                 UNION ALL
+                 SELECT ts, min, avg, max, count FROM samples_1h WHERE metric = ?1 AND ts >= ?2
+             )
+             GROUP BY bucket ORDER BY bucket",
+        )?;
+        let buckets = statement
+            .query_map(params![metric, since, resolution.max(1)], |row| {
+                Ok(Bucket {
+                    ts: row.get(0)?,
+                    min: row.get(1)?,
+                    avg: row.get(2)?,
+                    max: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(buckets)
+    }
+
+    // Min, average and max of `metric` from `since` up to `until`, or None
+    // when nothing was recorded then
+    pub fn summarize(&self, metric: &str, since: i64, until: i64) -> Result<Option<Bucket>> {
+        let mut statement = self.conn.prepare(
+            "SELECT MIN(min), SUM(avg * count) / SUM(count), MAX(max)
+             FROM (
+                 SELECT ts, value AS min, value AS avg, value AS max, 1 AS count
+                 FROM samples WHERE metric = ?1 AND ts >= ?2 AND ts < ?3
+                 UNION ALL
+                 SELECT ts, min, avg, max, count FROM samples_1m
+                 WHERE metric = ?1 AND ts >= ?2 AND ts < ?3
+                 UNION ALL
+                 SELECT ts, min, avg, max, count FROM samples_1h
+                 WHERE metric = ?1 AND ts >= ?2 AND ts < ?3
+             )",
+        )?;
+        let row: (Option<f64>, Option<f64>, Option<f64>) = statement
+            .query_row(params![metric, since, until], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?;
+        Ok(match row {
+            (Some(min), Some(avg), Some(max)) => Some(Bucket {
+                ts: since,
+                min,
+                avg,
+                max,
+            }),
+            _ => None,
+        })
+    }
+
+    // Oldest value of `metric` since `since`, from whichever resolution holds it
+    pub fn oldest_sample(&self, metric: &str, since: i64) -> Result<Option<(i64, f64)>> {
+        let mut statement = self.conn.prepare(
+            "SELECT ts, value FROM (
+                 SELECT ts, value FROM samples WHERE metric = ?1 AND ts >= ?2
+                 UNION ALL
+                 SELECT ts, avg FROM samples_1m WHERE metric = ?1 AND ts >= ?2
+                 UNION ALL
+                 SELECT ts, avg FROM samples_1h WHERE metric = ?1 AND ts >= ?2
+             )
+             ORDER BY ts LIMIT 1",
+        )?;
+        let mut rows =
+            statement.query_map(params![metric, since], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    // Every stored metric with its sample count and newest timestamp
+    pub fn metrics(&self) -> Result<Vec<(String, i64, i64)>> {
+        let mut statement = self.conn.prepare(
+            "SELECT metric, SUM(count), MAX(ts) FROM (
+                 SELECT metric, COUNT(*) AS count, MAX(ts) AS ts FROM samples GROUP BY metric
+                 UNION ALL
+                 SELECT metric, SUM(count), MAX(ts) FROM samples_1m GROUP BY metric
+                 UNION ALL
+                 SELECT metric, SUM(count), MAX(ts) FROM samples_1h GROUP BY metric
+             )
+             GROUP BY metric ORDER BY metric",
+        )?;
+        let metrics = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(metrics)
+    }
+
+    // Roll raw samples past their retention into 1-minute buckets, those into
+    // hourly buckets, and drop hourly buckets older than a year (by default)
+    pub fn compact(&self, retention: Retention) -> Result<Compaction> {
+        let now = Utc::now().timestamp();
+        // Cutoffs fall on bucket boundaries so no bucket is rolled up twice
+        let raw_cutoff = (now - retention.raw_hours as i64 * 3600) / 60 * 60;
+        let minute_cutoff = (now - retention.minute_days as i64 * 86_400) / 3600 * 3600;
+        let hourly_cutoff = now - retention.hourly_days as i64 * 86_400;
+
+        let transaction = self.conn.unchecked_transaction()?;
+        let mut result = Compaction::default();
+        result.rolled_up += transaction.execute(
+            "INSERT INTO samples_1m (ts, metric, min, avg, max, count)
+             SELECT (ts / 60) * 60 AS bucket, metric, MIN(value), AVG(value), MAX(value), COUNT(*)
+             FROM samples WHERE ts < ?1 GROUP BY metric, bucket",
+            params![raw_cutoff],
+        )?;
+        result.deleted +=
+            transaction.execute("DELETE FROM samples WHERE ts < ?1", params![raw_cutoff])?;
+        result.rolled_up += transaction.execute(
+            "INSERT INTO samples_1h (ts, metric, min, avg, max, count)
+             SELECT (ts / 3600) * 3600 AS bucket, metric, MIN(min),
+                    SUM(avg * count) / SUM(count), MAX(max), SUM(count)
+             FROM samples_1m WHERE ts < ?1 GROUP BY metric, bucket",
+            params![minute_cutoff],
+        )?;
+        result.deleted += transaction.execute(
+            "DELETE FROM samples_1m WHERE ts < ?1",
+            params![minute_cutoff],
+        )?;
+        result.deleted += transaction.execute(
+            "DELETE FROM samples_1h WHERE ts < ?1",
+            params![hourly_cutoff],
+        )?;
+        transaction.commit()?;
+
+        // Hand freed pages back to the filesystem
+        if result.deleted > 0 {
+            self.conn.execute_batch("PRAGMA incremental_vacuum;")?;
+        }
+        Ok(result)
+    }
+
+    // Store a discrete event (boot, throttle episode, benchmark run, ...)
+    pub fn record_event(&self, kind: &str, message: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO events (ts, kind, message) VALUES (?1, ?2, ?3)",
+            params![Utc::now().timestamp(), kind, message],
+        )?;
+        Ok(())
+    }
+
+    // Events recorded since `since`, oldest first: (timestamp, kind, message)
+    pub fn events(&self, since: i64) -> Result<Vec<(i64, String, String)>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT ts, kind, message FROM events WHERE ts >= ?1 ORDER BY ts")?;
+        let events = statement
+            .query_map(params![since], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(events)
+    }
+
+    // Note that the system booted at `boot_time` was still up at `now`
+    pub fn record_boot(&self, boot_time: i64, now: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO boots (boot_time, last_seen) VALUES (?1, ?2)
+             ON CONFLICT(boot_time) DO UPDATE SET last_seen = MAX(last_seen, excluded.last_seen)",
+            params![boot_time, now],
+        )?;
+        Ok(())
+    }
+
+    // Most recent boots, newest first
+    pub fn recent_boots(&self, limit: usize) -> Result<Vec<BootRecord>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT boot_time, last_seen FROM boots ORDER BY boot_time DESC LIMIT ?1")?;
+        let boots = statement
+            .query_map(params![limit as i64], |row| {
+                Ok(BootRecord {
+                    boot_time: row.get(0)?,
+                    last_seen: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(boots)
+    }
+
+    // Boot with the longest uptime on record
+    pub fn uptime_record(&self) -> Result<Option<BootRecord>> {
+        let mut statement = self.conn.prepare(
+            "SELECT boot_time, last_seen FROM boots ORDER BY last_seen - boot_time DESC LIMIT 1",
+        )?;
+        let mut rows = statement.query_map([], |row| {
+            Ok(BootRecord {
+                boot_time: row.get(0)?,
+                last_seen: row.get(1)?,
+            })
+        })?;
+        Ok(rows.next().transpose()?)
+    }
+
+    // MACs of every LAN device seen by earlier scans, with when each was first seen
+    pub fn known_devices(&self) -> Result<HashMap<String, i64>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT mac, first_seen FROM lan_devices")?;
+        let devices = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+        Ok(devices)
+    }
+
+    // Add a device to the baseline, or update its address and last-seen time
+    pub fn record_device(
+        &self,
+        mac: &str,
+        ip: &str,
+        hostname: Option<&str>,
+        now: i64,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO lan_devices (mac, ip, hostname, first_seen, last_seen)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(mac) DO UPDATE SET ip = excluded.ip,
+                 hostname = COALESCE(excluded.hostname, hostname),
+                 last_seen = excluded.last_seen",
+            params![mac, ip, hostname, now],
+        )?;
+        Ok(())
+    }
+
+    // Forget the baseline so the next scan starts a new one
+    pub fn clear_devices(&self) -> Result<usize> {
+        Ok(self.conn.execute("DELETE FROM lan_devices", [])?)
+    }
+}
+
+// The headline metrics saved every refresh when record_metrics is on
+pub fn record_resources(store: &HistoryStore, res: &SystemResources) -> Result<()> {
+    let system = &res.system;
+    let percent = |part: u64, total: u64| {
+        if total > 0 {
+            part as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        }
+    };
+    let mut samples = vec![
+        ("cpu", system.global_cpu_info().cpu_usage() as f64),
+        (
+            "memory",
+            percent(system.used_memory(), system.total_memory()),
+        ),
+        ("swap", percent(system.used_swap(), system.total_swap())),
+        ("load", system.load_average().one),
+    ];
+    if res.throttle.average_mhz() > 0 {
+        samples.push(("cpu_mhz", res.throttle.average_mhz() as f64));
+    }
+    if let Some(temp) = res.throttle.temperature() {
+        samples.push(("temp", temp as f64));
+    }
+    let seconds = res.refresh_interval_secs;
+    if seconds > 0.0 {
+        let (received, transmitted) = system
+            .networks()
+            .into_iter()
+            .fold((0, 0), |(rx, tx), (_, network)| {
+                (rx + network.received(), tx + network.transmitted())
+            });
+        samples.push(("net_rx", received as f64 / seconds));
+        samples.push(("net_tx", transmitted as f64 / seconds));
+    }
+    store.record_all(&samples)
+}
+
+// Keeps the compaction thread running; dropping it ends the thread
+pub struct CompactionThread {
+    _stop: mpsc::Sender<()>,
+}
+
+// Compact the history database now and then every hour, for as long as
+// the returned handle is kept
+pub fn spawn_compaction(retention: Retention) -> CompactionThread {
+    let (stop, stopped) = mpsc::channel::<()>();
+    thread::spawn(move || loop {
+        match HistoryStore::open().and_then(|store| store.compact(retention)) {
+            Ok(result) => debug!(
+                "History compaction: {} buckets rolled up, {} rows deleted",
+                result.rolled_up, result.deleted
+            ),
+            Err(e) => debug!("History compaction failed: {}", e),
+        }
+        if let Err(RecvTimeoutError::Disconnected) = stopped.recv_timeout(COMPACTION_INTERVAL) {
+            debug!("Stopping history compaction");
+            return;
+        }
+    });
+    CompactionThread { _stop: stop }
+}
+
+// Display a stored value with the unit of its metric
+fn format_value(metric: &str, value: f64) -> String {
+    match metric {
+        "cpu" | "memory" | "swap" => format!("{:.1}%", value),
+        "temp" => units::format_temperature(value),
+        "cpu_mhz" => format!("{:.0} MHz", value),
+        "net_rx" | "net_tx" => units::format_rate(value),
+        _ => format!("{:.2}", value),
+    }
+}
+
+fn format_time(ts: i64, with_date: bool) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|time| {
+            if with_date {
+                clock::format(&time)
+            } else {
+                clock::time(&time)
+            }
+        })
+        .unwrap_or_else(|| ts.to_string())
+}
+
+// Handle "hercules history --metric cpu --last 2h [--resolution 1m] [--json]"
+pub fn run_history(args: &[String]) -> Result<()> {
+    let matches = Command::new("history")
+        .about("Show samples stored in the history database")
+        .arg(
+            Arg::new("metric")
+                .long("metric")
+                .short('m')
+                .value_name("METRIC")
+                .help("Metric to show, e.g. cpu, memory, swap, load, temp, cpu_mhz, net_rx, net_tx")
+                .required_unless_present_any(["list", "compact"]),
+        )
+        .arg(
+            Arg::new("last")
+                .long("last")
+                .short('l')
+                .value_name("TIME")
+                .help("How far back to look, e.g. 30m, 2h, 7d")
+                .default_value("1h"),
+        )
+        .arg(
+            Arg::new("resolution")
+                .long("resolution")
+                .short('r')
+                .value_name("TIME")
+                .help("Average samples into buckets this long (default: fit the chart)"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print the samples as JSON")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("table")
+                .long("table")
+                .help("Print one line per sample instead of a chart")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("json"),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .help("Roll up and prune old samples now, per the history_* retention settings")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["metric", "json", "table", "list"]),
+        )
+        .arg(
+            Arg::new("list")
+                .long("list")
+                .help("List the metrics stored in the database")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["metric", "json", "table"]),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|e| e.exit());
+
+    let store = HistoryStore::open()?;
+
+    if matches.get_flag("compact") {
+        let config_manager = ConfigManager::new()?;
+        let retention = Retention::from_config(config_manager.get_config());
+        let result = store.compact(retention)?;
+        println!(
+            "Rolled up {} buckets and deleted {} rows",
+            result.rolled_up.to_string().value(),
+            result.deleted.to_string().value()
+        );
+        return Ok(());
+    }
+
+    if matches.get_flag("list") {
+        let metrics = store.metrics()?;
+        if metrics.is_empty() {
+            println!("No samples stored yet. Enable record_metrics to save them while monitoring.");
+        }
+        for (metric, count, newest) in metrics {
+            println!(
+                "{:<28} {:>8} samples, newest {}",
+                metric.as_str().primary(),
+                count,
+                format_time(newest, true)
+            );
+        }
+        return Ok(());
+    }
+
+    let metric = matches
+        .get_one::<String>("metric")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let last_text = matches
+        .get_one::<String>("last")
+        .map_or("1h", String::as_str);
+    let last = parse_duration(last_text)?.as_secs() as i64;
+    let width = graphs::terminal_width().saturating_sub(14).clamp(20, 120);
+    let resolution = match matches.get_one::<String>("resolution") {
+        Some(text) => parse_duration(text)?.as_secs() as i64,
+        // Two samples fit in each chart cell
+        None => (last + width as i64 * 2 - 1) / (width as i64 * 2),
+    };
+
+    let since = Utc::now().timestamp() - last;
+    let buckets = store.samples(metric, since, resolution)?;
+
+    if matches.get_flag("json") {
+        let samples: Vec<_> = buckets
+            .iter()
+            .map(|bucket| {
+                serde_json::json!({
+                    "timestamp": bucket.ts,
+                    "min": bucket.min,
+                    "avg": bucket.avg,
+                    "max": bucket.max,
+                })
+            })
+            .collect();
+        let output = serde_json::json!({
+            "metric": metric,
+            "since": since,
+            "resolution_secs": resolution.max(1),
+            "samples": samples,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if buckets.is_empty() {
+        return Err(anyhow!(
+            "No '{}' samples in the last {}. Run 'hercules history --list' to see what is stored.",
+            metric,
+            last_text
+        ));
+    }
+
+    let with_date = last > 86_400;
+    let peak = buckets.iter().copied().fold(buckets[0], |peak, bucket| {
+        if bucket.max > peak.max {
+            bucket
+        } else {
+            peak
+        }
+    });
+    let low = buckets
+        .iter()
+        .map(|bucket| bucket.min)
+        .fold(f64::MAX, f64::min);
+    let average = buckets.iter().map(|bucket| bucket.avg).sum::<f64>() / buckets.len() as f64;
+
+    println!(
+        "{} over the last {} ({} points, {}s resolution)",
+        metric.emphasis(),
+        last_text,
+        buckets.len(),
+        resolution.max(1)
+    );
+    println!(
+        "min {}  avg {}  max {} at {}",
+        format_value(metric, low).good(),
+        format_value(metric, average).value(),
+        format_value(metric, peak.max).critical(),
+        format_time(peak.ts, with_date)
+    );
+    println!();
+
+    if matches.get_flag("table") {
+        for bucket in &buckets {
+            println!(
+                "{}  min {:>12}  avg {:>12}  max {:>12}",
+                format_time(bucket.ts, with_date),
+                format_value(metric, bucket.min),
+                format_value(metric, bucket.avg),
+                format_value(metric, bucket.max)
+            );
+        }
+        return Ok(());
+    }
+
+    let values: Vec<f64> = buckets.iter().map(|bucket| bucket.avg).collect();
+    let top = match metric {
+        "cpu" | "memory" | "swap" => 100.0,
+        _ => peak.max.max(f64::EPSILON),
+    };
+    let lines = graphs::braille_chart(&values, Some(top), width, CHART_HEIGHT);
+    let last_line = lines.len() - 1;
+    for (i, line) in lines.iter().enumerate() {
+        let axis = match i {
+            0 => format_value(metric, top),
+            i if i == last_line => format_value(metric, 0.0),
+            _ => String::new(),
+        };
+        println!("{:>12} {}", axis, line.as_str().paint(Role::Primary));
+    }
+    // Older buckets than fit are cut from the left of the chart
+    let shown = &buckets[buckets.len().saturating_sub(width * 2)..];
+    println!(
+        "{:>12} {} - {}",
+        "",
+        format_time(shown[0].ts, with_date),
+        format_time(shown[shown.len() - 1].ts, with_date)
+    );
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use log::debug;
+
+use crate::config::ConfigManager;
+
+// Built-in translations, in a small subset of Fluent's syntax:
+// "message-id = Text", "# comments" and "{ $name }" placeholders
+const BUILTIN: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+    ("fr", include_str!("../locales/fr.ftl")),
+];
+
+// Messages of the chosen language, with English filling any gaps
+static MESSAGES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+// Load `language` for the rest of the run: "auto" follows LC_ALL, LC_MESSAGES
+// or LANG. A <config dir>/locales/<language>.ftl file adds a language or
+// overrides built-in messages. Only panel headings and the CPU, memory and
+// alerts labels are looked up here; other output is English.
+pub fn set_language(language: &str) -> Result<()> {
+    let language = resolve(language);
+    let mut messages = parse(BUILTIN[0].1);
+    let builtin = BUILTIN.iter().find(|(code, _)| *code == language);
+    if let Some((_, source)) = builtin {
+        messages.extend(parse(source));
+    }
+    let user = user_catalog(&language);
+    if builtin.is_none() && user.is_none() {
+        let _ = MESSAGES.set(messages);
+        return Err(anyhow!(
+            "No translation for language '{}'. Available: {}",
+            language,
+            available().join(", ")
+        ));
+    }
+    if let Some(source) = user {
+        messages.extend(parse(&source));
+    }
+    let _ = MESSAGES.set(messages);
+    Ok(())
+}
+
+// Languages with a built-in or user-provided catalog
+pub fn available() -> Vec<String> {
+    let mut languages: Vec<String> = BUILTIN.iter().map(|(code, _)| code.to_string()).collect();
+    if let Ok(entries) =
+        ConfigManager::get_config_dir().and_then(|dir| Ok(fs::read_dir(dir.join("locales"))?))
+    {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "ftl") {
+                if let Some(code) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    if !languages.iter().any(|known| known == code) {
+                        languages.push(code.to_string());
+                    }
+                }
+            }
+        }
+    }
+    languages
+}
+
+// Whether `language` can be used, for validating the config value
+pub fn is_available(language: &str) -> bool {
+    language == "auto" || available().iter().any(|code| *code == resolve(language))
+}
+
+// "de_DE.UTF-8" and "de-AT" become "de"; "C" and "POSIX" mean English
+fn resolve(language: &str) -> String {
+    let language = if language == "auto" {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+    } else {
+        language.to_string()
+    };
+    let code = language
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if code.is_empty() || code == "c" || code == "posix" {
+        "en".to_string()
+    } else {
+        code
+    }
+}
+
+fn user_catalog(language: &str) -> Option<String> {
+    let path = ConfigManager::get_config_dir()
+        .ok()?
+        .join("locales")
+        .join(format!("{}.ftl", language));
+    match fs::read_to_string(&path) {
+        Ok(source) => Some(source),
+        Err(e) => {
+            debug!("No translations at {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn parse(source: &str) -> HashMap<String, String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(id, text)| (id.trim().to_string(), text.trim().to_string()))
+        .collect()
+}
+
+// The message for `id` in the current language. Unknown ids come back as
+// they are, so a missing translation shows up without breaking the display.
+pub fn tr(id: &str) -> &str {
+    MESSAGES
+        .get_or_init(|| parse(BUILTIN[0].1))
+        .get(id)
+        .map(String::as_str)
+        .unwrap_or(id)
+}
+
+// The message for `id` with each "{ $name }" replaced by its value
+pub fn tr_args(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = tr(id).to_string();
+    for (name, value) in args {
+        text = text.replace(&format!("{{ ${} }}", name), &value.to_string());
+    }
+    text
+}
+
+// A dashed line as wide as a translated heading
+pub fn underline(heading: &str) -> String {
+    "-".repeat(console::measure_text_width(heading))
+}
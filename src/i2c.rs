@@ -0,0 +1,45 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+
+// A device on one of the Pi's I2C buses, through the kernel's i2c-dev
+// interface. The bus needs dtparam=i2c_arm=on in config.txt, and the user
+// needs to be in the i2c group or root.
+pub struct I2cDevice {
+    file: File,
+    address: u16,
+}
+
+// I2C_SLAVE from linux/i2c-dev.h: address the following reads and writes
+#[cfg(target_os = "linux")]
+nix::ioctl_write_int_bad!(set_slave_address, 0x0703);
+
+impl I2cDevice {
+    #[cfg(target_os = "linux")]
+    pub fn open(bus: u8, address: u16) -> Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let path = format!("/dev/i2c-{}", bus);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map_err(|e| anyhow!("cannot open {}: {} (is I2C enabled?)", path, e))?;
+        // Safety: the descriptor is open and I2C_SLAVE takes the address by value
+        unsafe { set_slave_address(file.as_raw_fd(), address.into()) }
+            .map_err(|e| anyhow!("cannot select I2C address {:#04x}: {}", address, e))?;
+        Ok(I2cDevice { file, address })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn open(_bus: u8, _address: u16) -> Result<Self> {
+        Err(anyhow!("I2C displays are only supported on Linux"))
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.file
+            .write_all(bytes)
+            .map_err(|e| anyhow!("I2C write to {:#04x} failed: {}", self.address, e))
+    }
+}
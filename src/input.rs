@@ -0,0 +1,47 @@
+use std::io::IsTerminal;
+use std::thread;
+use std::time::Instant;
+
+use console::{Key, Term};
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+
+use crate::theme;
+
+// Keys typed while the monitor runs, read on a background thread so the
+// refresh loop never blocks on the keyboard
+pub struct KeyReader {
+    keys: Receiver<Key>,
+}
+
+impl KeyReader {
+    // Only on an interactive terminal; piped or recorded runs have no keyboard
+    pub fn start() -> Option<Self> {
+        if !theme::interactive() || !std::io::stdin().is_terminal() {
+            return None;
+        }
+        let (sender, keys) = crossbeam_channel::unbounded();
+        thread::spawn(move || {
+            let term = Term::stdout();
+            // read_key raises SIGINT itself for Ctrl+C, so that still quits
+            while let Ok(key) = term.read_key() {
+                if sender.send(key).is_err() {
+                    return;
+                }
+            }
+        });
+        Some(KeyReader { keys })
+    }
+
+    // Next key pressed before `deadline`, or None once it passes
+    pub fn next_key(&self, deadline: Instant) -> Option<Key> {
+        match self.keys.recv_deadline(deadline) {
+            Ok(key) => Some(key),
+            Err(RecvTimeoutError::Timeout) => None,
+            // The reader stopped, e.g. stdin closed; just wait out the interval
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                None
+            }
+        }
+    }
+}
@@ -23,8 +23,6 @@ use is_elevated::is_elevated;
 use std::process::Command;
 #[cfg(target_os = "linux")]
 use users::get_current_uid;
-#[cfg(target_os = "linux")]
-use users::os::unix::UserExt;
 
 #[cfg(target_os = "windows")]
 fn show_message_box(title: &str, message: &str, is_success: bool) {
@@ -57,7 +55,7 @@ fn show_message_box(title: &str, message: &str, is_success: bool) {
     // On Linux, we just print to the console
     println!("\n{} {}", if is_success { "✓" } else { "!" }, title);
     println!("{}", message);
-    println!("");
+    println!();
     
     log_message(&format!("Displayed message: {} - {}", title, message));
 }
@@ -428,6 +426,25 @@ fn install(install_dir: &str) -> Result<(), Box<dyn Error>> {
         log_message("Set executable permissions on Linux");
     }
     
+    #[cfg(target_os = "linux")]
+    {
+        // Let non-root users read the sensors; not fatal, it can be redone
+        // with `hercules sensors setup-permissions`
+        let devices = crate::config::ConfigManager::new()
+            .map(|manager| manager.get_config().sensor_config.devices.clone())
+            .unwrap_or_default();
+        match crate::sensor_permissions::install(&devices) {
+            Ok(_) => {
+                println!("Installed udev rules for sensor access");
+                log_message("Installed udev rules for sensor access");
+            },
+            Err(e) => {
+                println!("Could not set up sensor permissions: {}", e);
+                log_message(&format!("Could not set up sensor permissions: {}", e));
+            }
+        }
+    }
+    
     // Create desktop shortcut
     create_desktop_shortcut(&target_exe)?;
     
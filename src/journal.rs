@@ -0,0 +1,204 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+
+use anyhow::Result;
+use chrono::{Local, TimeZone};
+use colored::*;
+use crossbeam_channel::{Receiver, Sender};
+use log::debug;
+use serde_json::Value;
+
+use crate::clock;
+use crate::i18n;
+use crate::theme::Themed;
+
+// Journal entries kept for the panel
+const JOURNAL_HISTORY_LEN: usize = 50;
+
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    // syslog priority: 0 = emergency ... 4 = warning
+    pub priority: u8,
+    // Microseconds since the epoch
+    pub timestamp: i64,
+    pub unit: String,
+    pub message: String,
+}
+
+// Follows journald for warning-and-above entries from the selected units
+pub struct JournalMonitor {
+    receiver: Receiver<Result<JournalEntry, String>>,
+    follower: Option<Child>,
+    entries: VecDeque<JournalEntry>,
+    error: Option<String>,
+}
+
+impl JournalMonitor {
+    pub fn start(units: &[String], backlog: usize) -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+
+        // Load the recent backlog synchronously so a one-shot display has it
+        let mut args = journalctl_args(units);
+        args.extend(["-n".to_string(), backlog.to_string()]);
+        match Command::new("journalctl").args(&args).output() {
+            Ok(output) => {
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    if let Some(entry) = parse_entry(line) {
+                        let _ = sender.send(Ok(entry));
+                    }
+                }
+            }
+            Err(e) => {
+                let _ = sender.send(Err(format!("Failed to run journalctl: {}", e)));
+            }
+        }
+
+        let follower = spawn_follower(units, sender);
+
+        JournalMonitor {
+            receiver,
+            follower,
+            entries: VecDeque::with_capacity(JOURNAL_HISTORY_LEN),
+            error: None,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        while let Ok(result) = self.receiver.try_recv() {
+            match result {
+                Ok(entry) => {
+                    if self.entries.len() == JOURNAL_HISTORY_LEN {
+                        self.entries.pop_front();
+                    }
+                    self.entries.push_back(entry);
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+impl Drop for JournalMonitor {
+    fn drop(&mut self) {
+        if let Some(ref mut child) = self.follower {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+fn journalctl_args(units: &[String]) -> Vec<String> {
+    let mut args = vec![
+        "--no-pager".to_string(),
+        "-o".to_string(),
+        "json".to_string(),
+        "-p".to_string(),
+        "warning".to_string(),
+    ];
+    for unit in units {
+        args.push("-u".to_string());
+        args.push(unit.clone());
+    }
+    args
+}
+
+// Run `journalctl -f` and forward new entries from a background thread
+fn spawn_follower(units: &[String], sender: Sender<Result<JournalEntry, String>>) -> Option<Child> {
+    let mut args = journalctl_args(units);
+    args.extend(["-f".to_string(), "-n".to_string(), "0".to_string()]);
+
+    let mut child = match Command::new("journalctl")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            debug!("Failed to follow journal: {}", e);
+            return None;
+        }
+    };
+
+    let stdout = child.stdout.take()?;
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(entry) = parse_entry(&line) {
+                if sender.send(Ok(entry)).is_err() {
+                    return;
+                }
+            }
+        }
+        let _ = sender.send(Err("journalctl stopped".to_string()));
+    });
+
+    Some(child)
+}
+
+// journalctl's JSON output encodes every field as a string
+fn parse_entry(line: &str) -> Option<JournalEntry> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    let field = |name: &str| value[name].as_str().map(|s| s.to_string());
+
+    Some(JournalEntry {
+        priority: field("PRIORITY")?.parse().ok()?,
+        timestamp: field("__REALTIME_TIMESTAMP")?.parse().ok()?,
+        unit: field("_SYSTEMD_UNIT")
+            .or_else(|| field("SYSLOG_IDENTIFIER"))
+            .unwrap_or_else(|| "-".to_string()),
+        // Binary messages come back as byte arrays and are skipped
+        message: field("MESSAGE")?,
+    })
+}
+
+// Journal panel with the newest warnings and errors first
+pub fn monitor_journal(monitor: &JournalMonitor, max_lines: usize) -> Result<()> {
+    let heading = i18n::tr("heading-journal");
+    println!("\n{}", heading.bold().critical());
+    println!("{}", i18n::underline(heading).critical());
+
+    if let Some(error) = monitor.error() {
+        println!("  {}", error.critical());
+    }
+
+    let mut shown = 0;
+    for entry in monitor.entries().take(max_lines) {
+        let time = Local
+            .timestamp_micros(entry.timestamp)
+            .single()
+            .map(|time| clock::time(&time))
+            .unwrap_or_default();
+        let level = match entry.priority {
+            0..=2 => "CRIT".critical().bold(),
+            3 => "ERR".critical(),
+            _ => "WARN".warning(),
+        };
+        println!(
+            "  {} {:<4} {:<24} {}",
+            time.value(),
+            level,
+            entry.unit,
+            entry.message
+        );
+        shown += 1;
+    }
+
+    if shown == 0 && monitor.error().is_none() {
+        println!("  {}", "No journal warnings or errors".good());
+    }
+
+    Ok(())
+}
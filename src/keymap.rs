@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use console::Key;
+
+use crate::config::ConfigManager;
+use crate::theme::{self, Themed};
+
+// Everything a key can do in the interactive monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    ScrollUp,
+    ScrollDown,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Search,
+    ClearSearch,
+    SortCpu,
+    SortMemory,
+    SortDiskRead,
+    SortDiskWrite,
+    SortDiskTotal,
+    SortNetwork,
+    ReverseSort,
+    OnlyMine,
+    Renice,
+    Affinity,
+    CopyText,
+    CopyJson,
+    Help,
+    Quit,
+}
+
+impl Action {
+    // In the order the help overlay lists them
+    pub const ALL: [Action; 22] = [
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::Search,
+        Action::ClearSearch,
+        Action::SortCpu,
+        Action::SortMemory,
+        Action::SortDiskRead,
+        Action::SortDiskWrite,
+        Action::SortDiskTotal,
+        Action::SortNetwork,
+        Action::ReverseSort,
+        Action::OnlyMine,
+        Action::Renice,
+        Action::Affinity,
+        Action::CopyText,
+        Action::CopyJson,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    // Its name in the [keys] config table
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Search => "search",
+            Action::ClearSearch => "clear_search",
+            Action::SortCpu => "sort_cpu",
+            Action::SortMemory => "sort_memory",
+            Action::SortDiskRead => "sort_disk_read",
+            Action::SortDiskWrite => "sort_disk_write",
+            Action::SortDiskTotal => "sort_disk_total",
+            Action::SortNetwork => "sort_network",
+            Action::ReverseSort => "reverse_sort",
+            Action::OnlyMine => "only_mine",
+            Action::Renice => "renice",
+            Action::Affinity => "affinity",
+            Action::CopyText => "copy_text",
+            Action::CopyJson => "copy_json",
+            Action::Help => "help",
+            Action::Quit => "quit",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::ScrollUp => "Move the selection up one process",
+            Action::ScrollDown => "Move the selection down one process",
+            Action::PageUp => "Move up one page",
+            Action::PageDown => "Move down one page",
+            Action::Top => "Jump to the top",
+            Action::Bottom => "Jump to the bottom",
+            Action::Search => "Search processes, disks, interfaces and connections",
+            Action::ClearSearch => "Clear the search",
+            Action::SortCpu => "Sort by CPU (again to reverse)",
+            Action::SortMemory => "Sort by memory",
+            Action::SortDiskRead => "Sort by disk read",
+            Action::SortDiskWrite => "Sort by disk write",
+            Action::SortDiskTotal => "Sort by disk total",
+            Action::SortNetwork => "Sort by network",
+            Action::ReverseSort => "Reverse the sort order",
+            Action::OnlyMine => "Show only your own processes",
+            Action::Renice => "Change the nice value of the selected process",
+            Action::Affinity => "Pin the selected process to some CPUs",
+            Action::CopyText => "Copy a text snapshot to the clipboard",
+            Action::CopyJson => "Copy the metrics as JSON to the clipboard",
+            Action::Help => "Show or hide this help",
+            Action::Quit => "Quit",
+        }
+    }
+}
+
+// Which key does what. Several keys may share an action.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = [
+            (Key::ArrowUp, Action::ScrollUp),
+            (Key::Char('k'), Action::ScrollUp),
+            (Key::ArrowDown, Action::ScrollDown),
+            (Key::Char('j'), Action::ScrollDown),
+            (Key::PageUp, Action::PageUp),
+            (Key::PageDown, Action::PageDown),
+            (Key::Char(' '), Action::PageDown),
+            (Key::Home, Action::Top),
+            (Key::Char('g'), Action::Top),
+            (Key::End, Action::Bottom),
+            (Key::Char('G'), Action::Bottom),
+            (Key::Char('/'), Action::Search),
+            (Key::Escape, Action::ClearSearch),
+            (Key::Char('c'), Action::SortCpu),
+            (Key::Char('m'), Action::SortMemory),
+            (Key::Char('r'), Action::SortDiskRead),
+            (Key::Char('w'), Action::SortDiskWrite),
+            (Key::Char('d'), Action::SortDiskTotal),
+            (Key::Char('n'), Action::SortNetwork),
+            (Key::Char('i'), Action::ReverseSort),
+            (Key::Char('u'), Action::OnlyMine),
+            (Key::Char('N'), Action::Renice),
+            (Key::Char('a'), Action::Affinity),
+            (Key::Char('y'), Action::CopyText),
+            (Key::Char('Y'), Action::CopyJson),
+            (Key::Char('?'), Action::Help),
+            (Key::Char('q'), Action::Quit),
+        ];
+        Keymap {
+            bindings: bindings.to_vec(),
+        }
+    }
+}
+
+impl Keymap {
+    // The defaults with the [keys] table applied. Each entry replaces all
+    // keys of its action; an empty value leaves the action unbound. A key
+    // may only do one thing, so remapping onto a key that is still bound
+    // elsewhere is an error rather than a silent override.
+    pub fn new(overrides: &BTreeMap<String, String>) -> Result<Self> {
+        let mut keymap = Keymap::default();
+        for (name, keys) in overrides {
+            let action = Action::ALL
+                .into_iter()
+                .find(|action| action.name() == name)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Unknown action '{}' in [keys]. Use: {}",
+                        name,
+                        Action::ALL.map(Action::name).join(", ")
+                    )
+                })?;
+            keymap.bindings.retain(|&(_, bound)| bound != action);
+            for key in keys.split_whitespace() {
+                keymap.bindings.push((parse_key(key)?, action));
+            }
+        }
+
+        for (i, (key, action)) in keymap.bindings.iter().enumerate() {
+            if let Some((_, other)) = keymap.bindings[..i].iter().find(|(bound, _)| bound == key) {
+                if other != action {
+                    return Err(anyhow!(
+                        "Key {} is bound to both {} and {} in [keys]",
+                        key_name(key),
+                        other.name(),
+                        action.name()
+                    ));
+                }
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn action(&self, key: &Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| bound == key)
+            .map(|&(_, action)| action)
+    }
+
+    // "↑ k" for ScrollUp
+    pub fn keys(&self, action: Action) -> String {
+        self.bindings
+            .iter()
+            .filter(|&&(_, bound)| bound == action)
+            .map(|(key, _)| key_name(key))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+// "x", "up", "pgdn", "space", "esc", "ctrl-y"; single characters are case-sensitive
+pub fn parse_key(name: &str) -> Result<Key> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(Key::Char(c));
+    }
+    let lower = name.to_lowercase();
+    if let Some(letter) = lower.strip_prefix("ctrl-").or(lower.strip_prefix("ctrl+")) {
+        return match letter.as_bytes() {
+            [c @ b'a'..=b'z'] => Ok(Key::Char((c - b'a' + 1) as char)),
+            _ => Err(anyhow!(
+                "Invalid key '{}'. Ctrl only combines with a letter",
+                name
+            )),
+        };
+    }
+    Ok(match lower.as_str() {
+        "up" => Key::ArrowUp,
+        "down" => Key::ArrowDown,
+        "left" => Key::ArrowLeft,
+        "right" => Key::ArrowRight,
+        "pgup" | "pageup" => Key::PageUp,
+        "pgdn" | "pagedown" => Key::PageDown,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "enter" => Key::Enter,
+        "esc" | "escape" => Key::Escape,
+        "tab" => Key::Tab,
+        "backspace" => Key::Backspace,
+        "del" | "delete" => Key::Del,
+        "space" => Key::Char(' '),
+        _ => {
+            return Err(anyhow!(
+                "Invalid key '{}'. Use a character, ctrl-<letter>, or: up, down, left, right, \
+                 pgup, pgdn, home, end, enter, esc, tab, backspace, del, space",
+                name
+            ))
+        }
+    })
+}
+
+pub fn key_name(key: &Key) -> String {
+    match key {
+        Key::ArrowUp => "↑".to_string(),
+        Key::ArrowDown => "↓".to_string(),
+        Key::ArrowLeft => "←".to_string(),
+        Key::ArrowRight => "→".to_string(),
+        Key::PageUp => "PgUp".to_string(),
+        Key::PageDown => "PgDn".to_string(),
+        Key::Home => "Home".to_string(),
+        Key::End => "End".to_string(),
+        Key::Enter => "Enter".to_string(),
+        Key::Escape => "Esc".to_string(),
+        Key::Tab => "Tab".to_string(),
+        Key::Backspace => "Backspace".to_string(),
+        Key::Del => "Del".to_string(),
+        Key::Char(' ') => "Space".to_string(),
+        // Ctrl+letter arrives as the control character
+        Key::Char(c) if (*c as u32) < 0x20 => {
+            format!("Ctrl-{}", ((*c as u8) + b'a' - 1) as char)
+        }
+        Key::Char(c) => c.to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+// The `?` overlay: every binding in the keymap, then the panel switches
+pub fn print_help(keymap: &Keymap) {
+    println!("{}", "KEYS".bold().good());
+    println!("{}", "----".good());
+    for action in Action::ALL {
+        let keys = keymap.keys(action);
+        if keys.is_empty() {
+            continue;
+        }
+        println!(
+            "  {}  {}",
+            format!("{:<14}", theme::ascii(&keys)).highlight(),
+            action.description()
+        );
+    }
+
+    println!("\n{}", "PANELS".bold().good());
+    println!("{}", "------".good());
+    match ConfigManager::panels() {
+        Ok(panels) => {
+            for (property, on) in panels {
+                let state = if on { "on ".good() } else { "off".normal() };
+                println!("  {} {}", state, property);
+            }
+        }
+        Err(e) => println!("  {}", e.to_string().critical()),
+    }
+    println!("  Switch one with `hercules conf <panel> -> true` or for one run with --no-<panel>");
+    println!("\n{}", "Press any key to close".dimmed());
+}
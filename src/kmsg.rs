@@ -0,0 +1,272 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use colored::*;
+use crossbeam_channel::{Receiver, TryRecvError};
+#[cfg(target_os = "linux")]
+use log::debug;
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::i18n;
+use crate::theme::Themed;
+
+// Kernel messages kept for the panel
+const KERNEL_LOG_HISTORY_LEN: usize = 50;
+
+// How long a message category keeps its alert active without new messages
+const KERNEL_ALERT_HOLD: Duration = Duration::from_secs(300);
+
+// Messages worth raising an alert for, matched case-insensitively
+const KERNEL_ALERT_PATTERNS: [(&str, &str); 8] = [
+    ("i/o error", "I/O error"),
+    ("blk_update_request", "I/O error"),
+    ("out of memory", "OOM killer"),
+    ("oom-kill", "OOM killer"),
+    ("reset high-speed usb device", "USB reset"),
+    ("reset full-speed usb device", "USB reset"),
+    ("under-voltage detected", "under-voltage"),
+    ("undervoltage detected", "under-voltage"),
+];
+
+#[derive(Debug, Clone)]
+pub struct KernelMessage {
+    // syslog level: 0 = emergency ... 4 = warning
+    pub level: u8,
+    // Seconds since boot
+    pub timestamp: f64,
+    pub text: String,
+}
+
+impl KernelMessage {
+    fn category(&self) -> Option<&'static str> {
+        let text = self.text.to_lowercase();
+        KERNEL_ALERT_PATTERNS
+            .iter()
+            .find(|(pattern, _)| text.contains(pattern))
+            .map(|(_, category)| *category)
+    }
+}
+
+// Follows the kernel ring buffer for warnings and errors
+pub struct KernelLogMonitor {
+    receiver: Receiver<Result<KernelMessage, String>>,
+    messages: VecDeque<KernelMessage>,
+    error: Option<String>,
+    // Messages logged before startup are shown but do not raise alerts
+    started_at: f64,
+    last_alert: HashMap<&'static str, Instant>,
+    // Set on drop so the reader thread closes /dev/kmsg and exits
+    stop: Arc<AtomicBool>,
+}
+
+impl KernelLogMonitor {
+    pub fn start(uptime_secs: u64) -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let stop = Arc::new(AtomicBool::new(false));
+        spawn_reader(sender, Arc::clone(&stop));
+
+        KernelLogMonitor {
+            receiver,
+            messages: VecDeque::with_capacity(KERNEL_LOG_HISTORY_LEN),
+            error: None,
+            started_at: uptime_secs as f64,
+            last_alert: HashMap::new(),
+            stop,
+        }
+    }
+
+    pub fn refresh(&mut self, alerts: &mut AlertEngine) {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(Ok(message)) => self.push(message, alerts),
+                Ok(Err(e)) => self.error = Some(e),
+                Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        self.last_alert.retain(|category, raised| {
+            let keep = raised.elapsed() < KERNEL_ALERT_HOLD;
+            if !keep {
+                alerts.clear(&format!("kmsg:{}", category));
+            }
+            keep
+        });
+    }
+
+    fn push(&mut self, message: KernelMessage, alerts: &mut AlertEngine) {
+        if message.timestamp >= self.started_at {
+            if let Some(category) = message.category() {
+                let level = if message.level <= 3 {
+                    AlertLevel::Critical
+                } else {
+                    AlertLevel::Warning
+                };
+                alerts.raise(
+                    &format!("kmsg:{}", category),
+                    level,
+                    "kernel",
+                    format!("{}: {}", category, message.text),
+                );
+                self.last_alert.insert(category, Instant::now());
+            }
+        }
+
+        if self.messages.len() == KERNEL_LOG_HISTORY_LEN {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(message);
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &KernelMessage> {
+        self.messages.iter().rev()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+impl Drop for KernelLogMonitor {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+// Read /dev/kmsg. The backlog already in the ring buffer is read before
+// returning so a one-shot display has something to show; new records are
+// then polled from a background thread.
+#[cfg(target_os = "linux")]
+fn spawn_reader(
+    sender: crossbeam_channel::Sender<Result<KernelMessage, String>>,
+    stop: Arc<AtomicBool>,
+) {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(nix::fcntl::OFlag::O_NONBLOCK.bits())
+        .open("/dev/kmsg");
+    let mut file = match file {
+        Ok(file) => file,
+        Err(e) => {
+            let _ = sender.send(Err(format!(
+                "Cannot read /dev/kmsg: {} (run as root or set kernel.dmesg_restrict=0)",
+                e
+            )));
+            return;
+        }
+    };
+
+    if !read_available(&mut file, &sender) {
+        return;
+    }
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(500));
+        if stop.load(Ordering::Relaxed) {
+            debug!("Stopping kernel log reader");
+            return;
+        }
+        if !read_available(&mut file, &sender) {
+            return;
+        }
+    });
+}
+
+// Forward every pending record; every read returns one record of the form
+// "<prio>,<seq>,<usec>,<flags>;<message>". Returns false once reading should stop.
+#[cfg(target_os = "linux")]
+fn read_available(
+    file: &mut std::fs::File,
+    sender: &crossbeam_channel::Sender<Result<KernelMessage, String>>,
+) -> bool {
+    use std::io::{ErrorKind, Read};
+
+    let mut buffer = vec![0u8; 8192];
+    loop {
+        let read = match file.read(&mut buffer) {
+            Ok(0) => return true,
+            Ok(read) => read,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => return true,
+            // EPIPE: the ring buffer wrapped past our position, keep reading
+            Err(e) if e.raw_os_error() == Some(32) || e.kind() == ErrorKind::Interrupted => {
+                continue
+            }
+            Err(e) => {
+                debug!("Kernel log reader stopped: {}", e);
+                let _ = sender.send(Err(format!("Kernel log reader stopped: {}", e)));
+                return false;
+            }
+        };
+
+        if let Some(message) = parse_kmsg_record(&String::from_utf8_lossy(&buffer[..read])) {
+            if message.level <= 4 && sender.send(Ok(message)).is_err() {
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn spawn_reader(
+    sender: crossbeam_channel::Sender<Result<KernelMessage, String>>,
+    _stop: Arc<AtomicBool>,
+) {
+    let _ = sender.send(Err(
+        "Kernel log monitoring is only supported on Linux".to_string()
+    ));
+}
+
+#[cfg(target_os = "linux")]
+fn parse_kmsg_record(record: &str) -> Option<KernelMessage> {
+    let (header, body) = record.split_once(';')?;
+    let mut fields = header.split(',');
+    let priority: u32 = fields.next()?.parse().ok()?;
+    let _sequence = fields.next()?;
+    let usec: u64 = fields.next()?.parse().ok()?;
+
+    // Continuation lines carry device metadata, only the first line is the message
+    let text = body.lines().next().unwrap_or_default().trim().to_string();
+
+    Some(KernelMessage {
+        level: (priority & 7) as u8,
+        timestamp: usec as f64 / 1_000_000.0,
+        text,
+    })
+}
+
+// Kernel log panel with the most recent warnings and errors
+pub fn monitor_kernel_log(monitor: &KernelLogMonitor, max_lines: usize) -> Result<()> {
+    let heading = i18n::tr("heading-kernel-log");
+    println!("\n{}", heading.bold().critical());
+    println!("{}", i18n::underline(heading).critical());
+
+    if let Some(error) = monitor.error() {
+        println!("  {}", error.critical());
+    }
+
+    let mut shown = 0;
+    for message in monitor.messages().take(max_lines) {
+        let level = match message.level {
+            0..=2 => "CRIT".critical().bold(),
+            3 => "ERR".critical(),
+            _ => "WARN".warning(),
+        };
+        println!(
+            "  {} {:<4} {}",
+            format!("[{:>10.3}]", message.timestamp).value(),
+            level,
+            message.text
+        );
+        shown += 1;
+    }
+
+    if shown == 0 && monitor.error().is_none() {
+        println!("  {}", "No kernel warnings or errors".good());
+    }
+
+    Ok(())
+}
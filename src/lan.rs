@@ -0,0 +1,394 @@
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::process::Command as Process;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{Local, TimeZone, Utc};
+use clap::{Arg, ArgAction, Command};
+use colored::*;
+
+use crate::clock;
+use crate::history::HistoryStore;
+use crate::i18n;
+use crate::netinfo;
+use crate::theme::{self, Themed};
+
+// Larger subnets are only read from the neighbor table, never probed
+const MAX_PROBE_HOSTS: u32 = 1024;
+
+// Time for ARP replies to land in the neighbor table after probing
+const PROBE_SETTLE: Duration = Duration::from_millis(1500);
+
+// OUI databases shipped by ieee-data, arp-scan and nmap
+const OUI_FILES: [&str; 5] = [
+    "/usr/share/ieee-data/oui.txt",
+    "/var/lib/ieee-data/oui.txt",
+    "/usr/share/misc/oui.txt",
+    "/usr/share/arp-scan/ieee-oui.txt",
+    "/usr/share/nmap/nmap-mac-prefixes",
+];
+
+// Vendors common on a Pi's network, used when no OUI database is installed
+const BUILTIN_OUIS: [(&str, &str); 10] = [
+    ("B827EB", "Raspberry Pi Foundation"),
+    ("DCA632", "Raspberry Pi Trading"),
+    ("E45F01", "Raspberry Pi Trading"),
+    ("D83ADD", "Raspberry Pi Trading"),
+    ("28CDC1", "Raspberry Pi Trading"),
+    ("2CCF67", "Raspberry Pi Trading"),
+    ("240AC4", "Espressif"),
+    ("246F28", "Espressif"),
+    ("30AEA4", "Espressif"),
+    ("A4CF12", "Espressif"),
+];
+
+// One device found on the LAN
+struct Device {
+    ip: Ipv4Addr,
+    mac: String,
+    interface: String,
+    vendor: Option<String>,
+    hostname: Option<String>,
+    first_seen: Option<i64>,
+}
+
+// Handle "hercules lan-scan [--interface IF] [--passive] [--json] [--reset-baseline]"
+pub fn run_lan_scan(args: &[String]) -> Result<()> {
+    let matches = Command::new("lan-scan")
+        .about("Discover devices on the local network and flag ones not seen before")
+        .arg(
+            Arg::new("interface")
+                .long("interface")
+                .short('i')
+                .value_name("IF")
+                .help("Only scan the subnet of this interface"),
+        )
+        .arg(
+            Arg::new("passive")
+                .long("passive")
+                .help("Only read the neighbor table; do not probe the subnet")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("no-resolve")
+                .long("no-resolve")
+                .help("Skip reverse DNS lookups of hostnames")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .help("Print the devices as JSON")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("reset-baseline")
+                .long("reset-baseline")
+                .help("Forget previously seen devices; this scan becomes the new baseline")
+                .action(ArgAction::SetTrue),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|e| e.exit());
+
+    let interface = matches.get_one::<String>("interface");
+    let networks: Vec<_> = netinfo::ipv4_networks()
+        .into_iter()
+        .filter(|(name, _, _)| interface.is_none_or(|wanted| wanted == name))
+        .collect();
+    if networks.is_empty() {
+        return Err(match interface {
+            Some(name) => anyhow!("Interface {} has no IPv4 address or is down", name),
+            None => anyhow!("No network interface with an IPv4 address is up"),
+        });
+    }
+
+    let json = matches.get_flag("json");
+    if !matches.get_flag("passive") {
+        for (name, address, prefix) in &networks {
+            let hosts = host_count(*prefix);
+            if hosts > MAX_PROBE_HOSTS {
+                if !json {
+                    eprintln!(
+                        "{}",
+                        format!(
+                            "{}/{} on {} is too large to probe, reading the neighbor table only",
+                            address, prefix, name
+                        )
+                        .warning()
+                    );
+                }
+                continue;
+            }
+            if !json && theme::interactive() {
+                eprintln!("Probing {}/{} on {}...", address, prefix, name);
+            }
+            probe_subnet(*address, *prefix);
+        }
+        thread::sleep(PROBE_SETTLE);
+    }
+
+    let mut devices: Vec<Device> = neighbor_table()
+        .into_iter()
+        .filter(|(_, _, name)| networks.iter().any(|(wanted, _, _)| wanted == name))
+        .map(|(ip, mac, interface)| Device {
+            ip,
+            vendor: vendor(&mac),
+            mac,
+            interface,
+            hostname: None,
+            first_seen: None,
+        })
+        .collect();
+    devices.sort_by_key(|device| device.ip);
+
+    if !matches.get_flag("no-resolve") {
+        let hostnames: Vec<Option<String>> = thread::scope(|scope| {
+            let lookups: Vec<_> = devices
+                .iter()
+                .map(|device| scope.spawn(|| reverse_lookup(device.ip.into())))
+                .collect();
+            lookups
+                .into_iter()
+                .map(|lookup| lookup.join().ok().flatten())
+                .collect()
+        });
+        for (device, hostname) in devices.iter_mut().zip(hostnames) {
+            device.hostname = hostname;
+        }
+    }
+
+    let store = HistoryStore::open()?;
+    if matches.get_flag("reset-baseline") {
+        store.clear_devices()?;
+    }
+    let known = store.known_devices()?;
+    // The first scan only creates the baseline, so nothing is new yet
+    let first_scan = known.is_empty();
+    let now = Utc::now().timestamp();
+    for device in &mut devices {
+        device.first_seen = known.get(&device.mac).copied();
+        store.record_device(
+            &device.mac,
+            &device.ip.to_string(),
+            device.hostname.as_deref(),
+            now,
+        )?;
+    }
+    let is_new = |device: &Device| !first_scan && device.first_seen.is_none();
+
+    if json {
+        let output: Vec<_> = devices
+            .iter()
+            .map(|device| {
+                serde_json::json!({
+                    "ip": device.ip.to_string(),
+                    "mac": device.mac,
+                    "interface": device.interface,
+                    "vendor": device.vendor,
+                    "hostname": device.hostname,
+                    "first_seen": device.first_seen.unwrap_or(now),
+                    "new": is_new(device),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let heading = i18n::tr("heading-lan-devices");
+    println!("{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+    println!(
+        "{:<16} {:<18} {:<24} {:<28} FIRST SEEN",
+        "IP", "MAC", "VENDOR", "HOSTNAME"
+    );
+    for device in &devices {
+        let first_seen = if is_new(device) {
+            "NEW".critical()
+        } else {
+            format_timestamp(device.first_seen.unwrap_or(now)).normal()
+        };
+        println!(
+            "{:<16} {:<18} {:<24} {:<28} {}",
+            device.ip.to_string().value(),
+            device.mac,
+            truncate(device.vendor.as_deref().unwrap_or("-"), 24),
+            truncate(device.hostname.as_deref().unwrap_or("-"), 28),
+            first_seen
+        );
+    }
+
+    let new = devices.iter().filter(|device| is_new(device)).count();
+    println!();
+    if first_scan {
+        println!(
+            "{} devices found; saved as the baseline for future scans",
+            devices.len()
+        );
+    } else if new > 0 {
+        println!(
+            "{} devices found, {}",
+            devices.len(),
+            format!("{} not seen before", new).critical()
+        );
+    } else {
+        println!(
+            "{} devices found, {}",
+            devices.len(),
+            "all seen before".good()
+        );
+    }
+
+    Ok(())
+}
+
+fn host_count(prefix: u32) -> u32 {
+    1u32.checked_shl(32 - prefix.min(32)).unwrap_or(u32::MAX)
+}
+
+// Sending a datagram to each address makes the kernel ARP for it, which fills
+// the neighbor table without needing raw sockets or root
+fn probe_subnet(address: Ipv4Addr, prefix: u32) {
+    let Ok(socket) = UdpSocket::bind("0.0.0.0:0") else {
+        return;
+    };
+    let mask = u32::MAX.checked_shl(32 - prefix.min(32)).unwrap_or(0);
+    let network = u32::from(address) & mask;
+    let hosts = host_count(prefix);
+    // /31 and /32 have no network or broadcast address to skip
+    let range = if hosts > 2 { 1..hosts - 1 } else { 0..hosts };
+    for host in range {
+        let target = Ipv4Addr::from(network | host);
+        if target != address {
+            // The discard port; replies do not matter, only the ARP exchange
+            let _ = socket.send_to(&[0], (target, 9));
+        }
+    }
+}
+
+// Resolved IPv4 neighbors as (IP, MAC, interface)
+#[cfg(target_os = "linux")]
+fn neighbor_table() -> Vec<(Ipv4Addr, String, String)> {
+    const ATF_COM: u32 = 0x2;
+    let Ok(table) = fs::read_to_string("/proc/net/arp") else {
+        return Vec::new();
+    };
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 {
+                return None;
+            }
+            let flags = u32::from_str_radix(fields[2].trim_start_matches("0x"), 16).ok()?;
+            // Incomplete entries are probes that got no answer
+            if flags & ATF_COM == 0 || fields[3] == "00:00:00:00:00:00" {
+                return None;
+            }
+            Some((
+                fields[0].parse().ok()?,
+                fields[3].to_lowercase(),
+                fields[5].to_string(),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn neighbor_table() -> Vec<(Ipv4Addr, String, String)> {
+    Vec::new()
+}
+
+// Vendor from the first three bytes of the MAC
+fn vendor(mac: &str) -> Option<String> {
+    let oui: String = mac
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .take(6)
+        .collect::<String>()
+        .to_uppercase();
+    if oui.len() < 6 {
+        return None;
+    }
+    // Locally administered: usually a phone or laptop randomizing its MAC, or a VM
+    let first = u8::from_str_radix(&oui[..2], 16).ok()?;
+    if first & 0x02 != 0 {
+        return Some("(locally administered)".to_string());
+    }
+    oui_database().get(&oui).cloned().or_else(|| {
+        BUILTIN_OUIS
+            .iter()
+            .find(|(prefix, _)| *prefix == oui)
+            .map(|(_, name)| name.to_string())
+    })
+}
+
+// The first OUI database found, as prefix -> vendor
+fn oui_database() -> &'static HashMap<String, String> {
+    static DATABASE: std::sync::OnceLock<HashMap<String, String>> = std::sync::OnceLock::new();
+    DATABASE.get_or_init(|| {
+        OUI_FILES
+            .iter()
+            .find_map(|path| fs::read_to_string(path).ok())
+            .map(|text| parse_oui_file(&text))
+            .unwrap_or_default()
+    })
+}
+
+// Handles "28-6F-B9   (hex)  Vendor" (IEEE) and "286FB9 Vendor" (nmap, arp-scan)
+fn parse_oui_file(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (prefix, vendor) = match line.split_once("(hex)") {
+                Some((prefix, vendor)) => (prefix.trim().replace('-', ""), vendor.trim()),
+                None => {
+                    let (prefix, vendor) = line.split_once(char::is_whitespace)?;
+                    (prefix.to_string(), vendor.trim())
+                }
+            };
+            (prefix.len() == 6 && prefix.chars().all(|c| c.is_ascii_hexdigit()))
+                .then(|| (prefix.to_uppercase(), vendor.to_string()))
+        })
+        .collect()
+}
+
+// Reverse DNS through the system resolver, so /etc/hosts and mDNS count too
+pub fn reverse_lookup(ip: IpAddr) -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let output = Process::new("getent")
+        .args(["hosts", &ip.to_string()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(1)
+        .map(|name| name.trim_end_matches('.').to_string())
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| clock::format(&time))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() > width {
+        let mut short: String = text.chars().take(width - 1).collect();
+        short.push('~');
+        short
+    } else {
+        text.to_string()
+    }
+}
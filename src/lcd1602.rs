@@ -0,0 +1,122 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::i2c::I2cDevice;
+use crate::output::{DisplayOutput, Page, Stats};
+
+const COLUMNS: usize = 16;
+// Address of PCF8574 backpacks; PCF8574A ones use 0x3F
+const DEFAULT_ADDRESS: u16 = 0x27;
+
+// PCF8574 port bits: the HD44780 data lines D4-D7 sit on P4-P7
+const REGISTER_SELECT: u8 = 0x01;
+const ENABLE: u8 = 0x04;
+const BACKLIGHT: u8 = 0x08;
+
+// DDRAM address of the start of each row
+const ROW_OFFSETS: [u8; 2] = [0x00, 0x40];
+
+// A 16x2 character LCD behind an I2C backpack, showing one page of metrics
+// at a time
+pub struct Lcd1602 {
+    device: I2cDevice,
+    pages: Vec<Page>,
+    page_time: Duration,
+    page: usize,
+    page_started: Instant,
+    // What is on screen, to skip rewriting unchanged rows
+    shown: [String; 2],
+}
+
+impl Lcd1602 {
+    // `address` 0 picks the usual 0x27
+    pub fn open(bus: u8, address: u16, pages: Vec<Page>, page_secs: u64) -> Result<Self> {
+        let address = if address == 0 {
+            DEFAULT_ADDRESS
+        } else {
+            address
+        };
+        let mut lcd = Lcd1602 {
+            device: I2cDevice::open(bus, address)?,
+            pages,
+            page_time: Duration::from_secs(page_secs.max(1)),
+            page: 0,
+            page_started: Instant::now(),
+            shown: [String::new(), String::new()],
+        };
+        lcd.init()?;
+        Ok(lcd)
+    }
+
+    // The controller powers up in 8-bit mode; three 0x3 nibbles resync it
+    // whatever state it was left in, then 0x2 switches to 4-bit mode
+    fn init(&mut self) -> Result<()> {
+        thread::sleep(Duration::from_millis(50));
+        for _ in 0..3 {
+            self.nibble(0x03, 0)?;
+            thread::sleep(Duration::from_millis(5));
+        }
+        self.nibble(0x02, 0)?;
+        self.command(0x28)?; // two lines, 5x8 dots
+        self.command(0x0C)?; // display on, no cursor
+        self.command(0x06)?; // move right after each character
+        self.command(0x01)?; // clear
+        thread::sleep(Duration::from_millis(2));
+        Ok(())
+    }
+
+    fn nibble(&mut self, value: u8, mode: u8) -> Result<()> {
+        let byte = (value << 4) | mode | BACKLIGHT;
+        // The controller latches on the falling edge of enable
+        self.device.write(&[byte | ENABLE])?;
+        self.device.write(&[byte])
+    }
+
+    fn send(&mut self, value: u8, mode: u8) -> Result<()> {
+        self.nibble(value >> 4, mode)?;
+        self.nibble(value & 0x0F, mode)
+    }
+
+    fn command(&mut self, command: u8) -> Result<()> {
+        self.send(command, 0)
+    }
+
+    fn write_row(&mut self, row: usize, text: &str) -> Result<()> {
+        let line = format!("{:<width$.width$}", text, width = COLUMNS);
+        if self.shown[row] == line {
+            return Ok(());
+        }
+        self.command(0x80 | ROW_OFFSETS[row])?;
+        for c in line.chars() {
+            self.send(character_code(c), REGISTER_SELECT)?;
+        }
+        self.shown[row] = line;
+        Ok(())
+    }
+}
+
+// The A00 character ROM is ASCII plus Japanese kana; its degree sign is 0xDF
+fn character_code(c: char) -> u8 {
+    match c {
+        '°' => 0xDF,
+        ' '..='}' => c as u8,
+        _ => b'?',
+    }
+}
+
+impl DisplayOutput for Lcd1602 {
+    fn show(&mut self, stats: &Stats) -> Result<()> {
+        if self.pages.is_empty() {
+            return Ok(());
+        }
+        if self.page_started.elapsed() >= self.page_time {
+            self.page = (self.page + 1) % self.pages.len();
+            self.page_started = Instant::now();
+        }
+        let [top, bottom] = self.pages[self.page].lines(stats);
+        self.write_row(0, &top)?;
+        self.write_row(1, &bottom)
+    }
+}
@@ -1,52 +1,301 @@
+use std::collections::{BTreeMap, HashMap};
 use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
-use chrono::Local;
-use clap::{Arg, ArgAction, Command};
+use clap::{Arg, ArgAction, ArgMatches, Command};
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::env;
-use sysinfo::{CpuExt, DiskExt, NetworkExt, PidExt, ProcessExt, System, SystemExt};
-
+use sysinfo::{CpuExt, NetworkExt, PidExt, ProcessExt, RefreshKind, System, SystemExt};
+
+use crate::theme::{Role, Themed};
+
+mod activity;
+mod alerts;
+mod audio;
+mod bench;
+mod bluetooth;
+mod boost;
+mod camera;
+mod capture;
+mod check;
+mod clipboard;
+mod clock;
 mod config;
+mod connectivity;
+mod containers;
+mod controllers;
+mod cube;
+mod dead_reckoning;
+mod diskio;
+mod disks;
+mod displays;
+mod du;
+mod export;
+mod fan;
+mod fds;
+mod firewall;
+mod fusion;
+mod graphs;
+mod growth;
+mod hardware;
+mod history;
+mod i18n;
+mod i2c;
+mod input;
 mod installer;
+mod journal;
+mod keymap;
+mod kmsg;
+mod lan;
+mod lcd1602;
+mod meminfo;
+mod metrics;
+mod netinfo;
+mod numa;
+mod output;
+mod pi;
+mod ping;
+mod portscan;
+mod process;
+mod process_control;
+mod process_table;
+mod pwmfan;
+mod record;
+mod reload;
+mod report;
+mod rolling;
+mod ros2;
+mod scheduler;
+mod search;
 #[allow(dead_code)]
+mod sensor_devices;
+mod sensor_filter;
+mod sensor_libusb;
+mod sensor_log;
+mod sensor_permissions;
+mod sensor_stream;
+mod sensor_triggers;
 mod sensors;
+mod sockets;
+mod speedtest;
+mod ssd1306;
+mod statsd;
+mod statusled;
+mod summary;
+mod theme;
+mod throttle;
+mod tls;
+mod traffic;
+mod units;
+mod uptime;
+mod usb;
+mod verbosity;
+mod virt;
+mod vpn;
+mod watchdog;
+mod wifi;
 
 // Configuration for resource monitoring
 struct MonitorConfig {
     update_interval_ms: u64,
     show_cpu: bool,
+    show_graphs: bool,
+    graph_height: usize,
+    show_rolling_stats: bool,
     show_memory: bool,
+    show_numa: bool,
+    numa_collapse_single_node: bool,
     show_disk: bool,
+    show_disk_io: bool,
+    inode_alert_percent: f64,
+    disk_exclude: Vec<String>,
+    disk_aggregate_bind_mounts: bool,
+    network_mount_timeout_ms: u64,
+    growth_paths: Vec<String>,
+    growth_alert_mb_per_hour: f64,
     show_network: bool,
     show_processes: bool,
     max_processes: usize,
+    process_sort: config::ProcessSort,
+    show_process_network: bool,
+    process_network_capture: bool,
+    process_memory_detail: bool,
+    show_containers: bool,
+    container_socket: String,
+    show_connections: bool,
+    connections_filter: String,
+    show_listening_ports: bool,
+    port_allowlist: Vec<u16>,
+    show_firewall: bool,
+    show_vpn: bool,
+    show_usb: bool,
+    show_peripherals: bool,
+    show_audio: bool,
+    show_displays: bool,
+    vpn_stale_secs: u64,
+    show_traffic_breakdown: bool,
+    tls_hosts: Vec<String>,
+    tls_alert_days: u64,
+    tls_check_hours: u64,
+    watchdog: Vec<String>,
+    watchdog_restart: bool,
+    ping_targets: Vec<String>,
+    show_connectivity: bool,
+    public_ip_url: String,
+    public_ip_refresh_mins: u64,
+    show_file_descriptors: bool,
+    fd_alert_percent: f64,
+    record_throttle_episodes: bool,
+    show_uptime: bool,
+    show_camera: bool,
+    check_eeprom_update: bool,
+    status_led: String,
+    display_backend: String,
+    display_i2c_bus: u8,
+    display_i2c_address: u16,
+    display_pages: Vec<String>,
+    display_page_secs: u64,
+    show_fan: bool,
+    show_boost: bool,
+    fan_thresholds: Vec<f64>,
+    fan_control: bool,
+    fan_control_pin: u8,
+    fan_control_frequency_hz: u64,
+    fan_control_curve: String,
+    fan_control_hysteresis: f64,
+    temp_warn_celsius: f64,
+    temp_crit_celsius: f64,
+    boot_history_len: usize,
+    record_metrics: bool,
+    report_schedule: String,
+    notify_webhook: String,
+    history_raw_hours: u64,
+    history_minute_days: u64,
+    history_hourly_days: u64,
+    show_kernel_log: bool,
+    kernel_log_lines: usize,
+    show_journal: bool,
+    journal_units: Vec<String>,
+    journal_lines: usize,
+    statsd_host: String,
+    statsd_prefix: String,
+    statsd_sample_rate: f64,
     continuous: bool,
     show_compact_mode: bool,
     show_installer: bool,
     show_sensors: bool,
     sensor_config: sensors::SensorConfig,
+    keys: BTreeMap<String, String>,
 }
 
 impl Default for MonitorConfig {
     fn default() -> Self {
+        // Some defaults depend on the Raspberry Pi model, see hardware.rs
+        let profile = hardware::profile();
         Self {
-            update_interval_ms: 1000,
+            update_interval_ms: profile.update_interval_ms,
             show_cpu: true,
+            show_graphs: profile.show_graphs,
+            graph_height: 4,
+            show_rolling_stats: true,
             show_memory: true,
+            show_numa: false,
+            numa_collapse_single_node: true,
             show_disk: true,
+            show_disk_io: false,
+            inode_alert_percent: 90.0,
+            disk_exclude: disks::DEFAULT_EXCLUDE
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            disk_aggregate_bind_mounts: false,
+            network_mount_timeout_ms: 1000,
+            growth_paths: Vec::new(),
+            growth_alert_mb_per_hour: 100.0,
             show_network: true,
             show_processes: false,
             max_processes: 10,
+            process_sort: config::ProcessSort::Cpu,
+            show_process_network: false,
+            process_network_capture: false,
+            process_memory_detail: false,
+            show_containers: false,
+            container_socket: String::new(),
+            show_connections: false,
+            connections_filter: String::new(),
+            show_listening_ports: false,
+            port_allowlist: Vec::new(),
+            show_firewall: false,
+            show_vpn: false,
+            show_usb: false,
+            show_peripherals: false,
+            show_audio: false,
+            show_displays: false,
+            vpn_stale_secs: 300,
+            show_traffic_breakdown: false,
+            tls_hosts: Vec::new(),
+            tls_alert_days: 14,
+            tls_check_hours: 12,
+            watchdog: Vec::new(),
+            watchdog_restart: false,
+            ping_targets: Vec::new(),
+            show_connectivity: false,
+            public_ip_url: "https://api.ipify.org".to_string(),
+            public_ip_refresh_mins: 30,
+            show_file_descriptors: false,
+            fd_alert_percent: 80.0,
+            record_throttle_episodes: true,
+            show_uptime: false,
+            show_camera: profile.show_camera,
+            check_eeprom_update: false,
+            status_led: String::new(),
+            display_backend: "none".to_string(),
+            display_i2c_bus: 1,
+            display_i2c_address: 0,
+            display_pages: vec![
+                "cpu".to_string(),
+                "memory".to_string(),
+                "temperature".to_string(),
+                "network".to_string(),
+            ],
+            display_page_secs: 5,
+            show_fan: profile.show_fan,
+            show_boost: false,
+            fan_thresholds: Vec::new(),
+            fan_control: false,
+            fan_control_pin: 18,
+            fan_control_frequency_hz: 25000,
+            fan_control_curve: "40:0,50:30,60:60,70:100".to_string(),
+            fan_control_hysteresis: 3.0,
+            temp_warn_celsius: profile.temp_warn_celsius,
+            temp_crit_celsius: profile.temp_crit_celsius,
+            boot_history_len: 5,
+            record_metrics: false,
+            report_schedule: String::new(),
+            notify_webhook: String::new(),
+            history_raw_hours: 24,
+            history_minute_days: 30,
+            history_hourly_days: 365,
+            show_kernel_log: false,
+            kernel_log_lines: 10,
+            show_journal: false,
+            journal_units: Vec::new(),
+            journal_lines: 10,
+            statsd_host: String::new(),
+            statsd_prefix: "hercules".to_string(),
+            statsd_sample_rate: 1.0,
             continuous: true,
             show_compact_mode: false,
-            show_installer: false,`
+            show_installer: false,
             show_sensors: false,
             sensor_config: sensors::SensorConfig::default(),
+            keys: BTreeMap::new(),
         }
     }
 }
@@ -54,51 +303,497 @@ impl Default for MonitorConfig {
 // System resources data container
 struct SystemResources {
     system: System,
-    last_net_receive: u64,
-    last_net_transmit: u64,
     last_update: Instant,
+    // Seconds covered by the most recent refresh, used for per-process rates
+    refresh_interval_secs: f64,
+    scheduler: scheduler::SchedulerMonitor,
+    virt: virt::VirtInfo,
+    pi: Option<pi::PiInfo>,
+    history: Option<graphs::ResourceHistory>,
+    rolling: Option<rolling::RollingStats>,
+    metric_store: Option<history::HistoryStore>,
+    compaction: Option<history::CompactionThread>,
+    reports: Option<report::ReportScheduler>,
+    throttle: throttle::ThrottleMonitor,
+    process_net: Option<sockets::ProcessNetTracker>,
+    disk_io: Option<diskio::DiskIoMonitor>,
+    containers: Option<containers::ContainerMonitor>,
+    listening_ports: Option<sockets::ListeningPortMonitor>,
+    firewall: Option<firewall::FirewallMonitor>,
+    vpn: Option<vpn::VpnMonitor>,
+    usb: Option<usb::UsbMonitor>,
+    peripherals: Option<bluetooth::PeripheralMonitor>,
+    audio: Option<audio::AudioMonitor>,
+    displays: Option<displays::DisplayMonitor>,
+    traffic: Option<traffic::TrafficMonitor>,
+    tls: Option<tls::TlsMonitor>,
+    watchdog: Option<watchdog::Watchdog>,
+    alerts: alerts::AlertEngine,
+    disk_filter: disks::DiskFilter,
+    network_mounts: Option<disks::NetworkMountMonitor>,
+    growth: Option<growth::GrowthMonitor>,
+    interfaces: Option<netinfo::InterfaceMonitor>,
+    inode_alert_percent: f64,
+    ping: Option<ping::PingMonitor>,
+    connectivity: Option<connectivity::ConnectivityMonitor>,
+    fds: Option<fds::FdMonitor>,
+    uptime: Option<uptime::UptimeTracker>,
+    camera: Option<camera::CameraMonitor>,
+    fan: Option<fan::FanMonitor>,
+    boost: Option<boost::BoostMonitor>,
+    kernel_log: Option<kmsg::KernelLogMonitor>,
+    journal: Option<journal::JournalMonitor>,
+    statsd: Option<statsd::StatsdSink>,
+    status_led: Option<statusled::StatusLed>,
+    display_output: Option<Box<dyn output::DisplayOutput>>,
     sensor_manager: Option<sensors::SensorManager>,
     last_sensor_data: sensors::SensorData,
 }
 
 impl SystemResources {
     fn new(config: &MonitorConfig) -> Self {
-        let mut system = System::new_all();
-        system.refresh_all();
+        // Everything System::new_all lists, except that disks are listed with a
+        // timeout so a dead network mount cannot hang startup or a reload
+        let mut system = disks::system_with_disk_list(config.network_mount_timeout_ms);
+        system.refresh_specifics(
+            RefreshKind::everything()
+                .without_disks_list()
+                .without_disks(),
+        );
+        let created = Instant::now();
+        refresh_system(&mut system);
+        let refresh_interval_secs = created.elapsed().as_secs_f64();
 
-        let mut total_received = 0;
-        let mut total_transmitted = 0;
+        let mut res = Self {
+            system,
+            last_update: Instant::now(),
+            refresh_interval_secs,
+            scheduler: scheduler::SchedulerMonitor::new(),
+            virt: virt::detect(),
+            pi: None,
+            history: None,
+            rolling: None,
+            metric_store: None,
+            compaction: None,
+            reports: None,
+            throttle: throttle::ThrottleMonitor::new(config.record_throttle_episodes),
+            process_net: None,
+            disk_io: None,
+            containers: None,
+            listening_ports: None,
+            firewall: None,
+            vpn: None,
+            usb: None,
+            peripherals: None,
+            audio: None,
+            displays: None,
+            camera: None,
+            fan: None,
+            boost: None,
+            traffic: None,
+            tls: None,
+            watchdog: None,
+            alerts: alerts::AlertEngine::new(),
+            disk_filter: disks::DiskFilter::default(),
+            network_mounts: None,
+            growth: None,
+            interfaces: None,
+            inode_alert_percent: config.inode_alert_percent,
+            ping: None,
+            connectivity: None,
+            fds: None,
+            uptime: None,
+            kernel_log: None,
+            journal: None,
+            statsd: None,
+            status_led: None,
+            display_output: None,
+            sensor_manager: None,
+            last_sensor_data: sensors::SensorData::default(),
+        };
+        res.apply_config(None, config);
+        res
+    }
 
-        for (_, network) in system.networks() {
-            total_received += network.received();
-            total_transmitted += network.transmitted();
+    // Set up the optional monitors from the config. On a reload `old` is the
+    // previous config and only monitors whose settings changed are replaced,
+    // so the others keep their history, alert state and threads.
+    fn apply_config(&mut self, old: Option<&MonitorConfig>, config: &MonitorConfig) {
+        let interval = self.refresh_interval_secs;
+
+        if changed(old, config, |c| c.check_eeprom_update) {
+            self.pi = pi::detect(config.check_eeprom_update);
+        }
+
+        if changed(old, config, |c| (c.show_graphs, c.graph_height)) {
+            self.history = config
+                .show_graphs
+                .then(|| graphs::ResourceHistory::new(config.graph_height));
+        }
+
+        if changed(old, config, |c| c.show_rolling_stats) {
+            self.rolling = config
+                .show_rolling_stats
+                .then(rolling::RollingStats::default);
+        }
+
+        // Per-process network accounting walks /proc and runs ss, so only when asked
+        if changed(old, config, |c| {
+            (
+                c.show_processes,
+                c.show_process_network,
+                c.process_network_capture,
+            )
+        }) {
+            self.process_net = (config.show_processes && config.show_process_network).then(|| {
+                let mut tracker = sockets::ProcessNetTracker::new(config.process_network_capture);
+                tracker.sample(interval);
+                tracker
+            });
+        }
+
+        if changed(old, config, |c| (c.show_disk_io, c.show_disk)) {
+            self.disk_io = (config.show_disk_io || (config.show_disk && verbosity::verbose()))
+                .then(diskio::DiskIoMonitor::new);
+        }
+
+        if changed(old, config, |c| {
+            (c.show_containers, c.container_socket.clone())
+        }) {
+            self.containers = config.show_containers.then(|| {
+                let mut monitor = containers::ContainerMonitor::new(&config.container_socket);
+                monitor.refresh(interval);
+                monitor
+            });
+        }
+
+        self.disk_filter =
+            disks::DiskFilter::new(&config.disk_exclude, config.disk_aggregate_bind_mounts);
+        self.inode_alert_percent = config.inode_alert_percent;
+        disks::check_inode_alerts(
+            &self.system,
+            &self.disk_filter,
+            &mut self.alerts,
+            self.inode_alert_percent,
+        );
+
+        if changed(old, config, |c| (c.show_disk, c.network_mount_timeout_ms)) {
+            self.network_mounts = config.show_disk.then(|| {
+                let mut monitor = disks::NetworkMountMonitor::new(config.network_mount_timeout_ms);
+                monitor.refresh(&mut self.alerts);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| {
+            (c.growth_paths.clone(), c.growth_alert_mb_per_hour)
+        }) {
+            self.growth = (!config.growth_paths.is_empty()).then(|| {
+                growth::GrowthMonitor::new(&config.growth_paths, config.growth_alert_mb_per_hour)
+            });
+        }
+
+        if changed(old, config, |c| c.show_network) {
+            self.interfaces = config.show_network.then(|| {
+                let mut monitor = netinfo::InterfaceMonitor::new();
+                monitor.refresh(&self.system, &mut self.alerts);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| {
+            (c.show_listening_ports, c.port_allowlist.clone())
+        }) {
+            self.listening_ports = config.show_listening_ports.then(|| {
+                let mut monitor = sockets::ListeningPortMonitor::new(config.port_allowlist.clone());
+                monitor.refresh(&self.system, &mut self.alerts);
+                monitor
+            });
         }
 
-        // Initialize sensor manager if sensors are enabled
-        let sensor_manager = if config.show_sensors {
-            match sensors::initialize_sensors(config.sensor_config.clone()) {
-                Ok(manager) => Some(manager),
-                Err(e) => {
-                    eprintln!("Failed to initialize sensors: {}", e);
-                    None
+        if changed(old, config, |c| (c.watchdog.clone(), c.watchdog_restart)) {
+            self.watchdog = (!config.watchdog.is_empty()).then(|| {
+                let mut watchdog =
+                    watchdog::Watchdog::new(&config.watchdog, config.watchdog_restart);
+                watchdog.refresh(&self.system, &mut self.alerts);
+                watchdog
+            });
+        }
+
+        if changed(old, config, |c| c.show_firewall) {
+            self.firewall = config.show_firewall.then(|| {
+                let mut monitor = firewall::FirewallMonitor::new();
+                monitor.refresh(&mut self.alerts);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| c.show_camera) {
+            self.camera = config.show_camera.then(|| {
+                let mut monitor = camera::CameraMonitor::new();
+                monitor.refresh(&self.system);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| c.show_displays) {
+            self.displays = config.show_displays.then(|| {
+                let mut monitor = displays::DisplayMonitor::new();
+                monitor.refresh(&self.system);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| (c.show_fan, c.fan_thresholds.clone())) {
+            self.fan = config
+                .show_fan
+                .then(|| fan::FanMonitor::new(&config.fan_thresholds));
+        }
+
+        if changed(old, config, |c| (c.show_vpn, c.vpn_stale_secs)) {
+            self.vpn = config.show_vpn.then(|| {
+                let mut monitor = vpn::VpnMonitor::new(config.vpn_stale_secs);
+                monitor.refresh(&mut self.alerts);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| {
+            (c.show_usb, c.sensor_config.devices.clone())
+        }) {
+            self.usb = config
+                .show_usb
+                .then(|| usb::UsbMonitor::new(&config.sensor_config.devices));
+        }
+
+        if changed(old, config, |c| c.show_peripherals) {
+            self.peripherals = config.show_peripherals.then(|| {
+                let mut monitor = bluetooth::PeripheralMonitor::new();
+                monitor.refresh();
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| c.show_audio) {
+            self.audio = config.show_audio.then(|| {
+                let mut monitor = audio::AudioMonitor::new();
+                monitor.refresh(&mut self.alerts);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| c.show_traffic_breakdown) {
+            // Close the old capture socket before opening another
+            self.traffic = None;
+            self.traffic = config
+                .show_traffic_breakdown
+                .then(traffic::TrafficMonitor::start);
+        }
+
+        if changed(old, config, |c| {
+            (c.tls_hosts.clone(), c.tls_alert_days, c.tls_check_hours)
+        }) {
+            self.tls = (!config.tls_hosts.is_empty()).then(|| {
+                tls::TlsMonitor::start(
+                    &config.tls_hosts,
+                    config.tls_alert_days,
+                    config.tls_check_hours,
+                )
+            });
+        }
+
+        if changed(old, config, |c| {
+            (c.ping_targets.clone(), c.update_interval_ms)
+        }) {
+            self.ping = (!config.ping_targets.is_empty())
+                .then(|| ping::PingMonitor::start(&config.ping_targets, config.update_interval_ms));
+        }
+
+        if changed(old, config, |c| {
+            (
+                c.show_connectivity,
+                c.public_ip_url.clone(),
+                c.public_ip_refresh_mins,
+            )
+        }) {
+            self.connectivity = config.show_connectivity.then(|| {
+                connectivity::ConnectivityMonitor::start(
+                    &config.public_ip_url,
+                    config.public_ip_refresh_mins,
+                )
+            });
+        }
+
+        if changed(old, config, |c| {
+            (c.show_file_descriptors, c.fd_alert_percent)
+        }) {
+            self.fds = config.show_file_descriptors.then(|| {
+                let mut monitor = fds::FdMonitor::new(config.fd_alert_percent);
+                monitor.refresh(&self.system, &mut self.alerts);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| (c.show_uptime, c.boot_history_len)) {
+            self.uptime = if config.show_uptime {
+                match uptime::UptimeTracker::new(config.boot_history_len) {
+                    Ok(mut tracker) => {
+                        tracker.refresh(&self.system);
+                        Some(tracker)
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to open history database: {}", e);
+                        None
+                    }
                 }
-            }
-        } else {
-            None
-        };
+            } else {
+                None
+            };
+        }
 
-        Self {
-            system,
-            last_net_receive: total_received,
-            last_net_transmit: total_transmitted,
-            last_update: Instant::now(),
-            sensor_manager,
-            last_sensor_data: sensors::SensorData::default(),
+        // Throttle counters survive a reload unless episode recording changes
+        if old.is_some_and(|old| old.record_throttle_episodes != config.record_throttle_episodes) {
+            self.throttle = throttle::ThrottleMonitor::new(config.record_throttle_episodes);
+        }
+        self.throttle
+            .set_temperature_limits(config.temp_warn_celsius, config.temp_crit_celsius);
+        if changed(old, config, |c| c.record_throttle_episodes) {
+            self.throttle.refresh(&self.system, &mut self.alerts);
+        }
+
+        if changed(old, config, |c| c.show_boost) {
+            self.boost = config.show_boost.then(|| {
+                let mut monitor = boost::BoostMonitor::new();
+                monitor.refresh(&self.throttle);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| c.show_kernel_log) {
+            self.kernel_log = config.show_kernel_log.then(|| {
+                let mut monitor = kmsg::KernelLogMonitor::start(self.system.uptime());
+                monitor.refresh(&mut self.alerts);
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| {
+            (c.show_journal, c.journal_units.clone(), c.journal_lines)
+        }) {
+            self.journal = config.show_journal.then(|| {
+                let mut monitor =
+                    journal::JournalMonitor::start(&config.journal_units, config.journal_lines);
+                monitor.refresh();
+                monitor
+            });
+        }
+
+        if changed(old, config, |c| {
+            (
+                c.statsd_host.clone(),
+                c.statsd_prefix.clone(),
+                c.statsd_sample_rate,
+            )
+        }) {
+            self.statsd = if config.statsd_host.is_empty() {
+                None
+            } else {
+                statsd::StatsdSink::new(
+                    &config.statsd_host,
+                    &config.statsd_prefix,
+                    config.statsd_sample_rate,
+                )
+                .map_err(|e| eprintln!("StatsD disabled: {}", e))
+                .ok()
+            };
+        }
+
+        // The LED, the display and the sensor hold on to their device, so
+        // the old one is released before the new one opens it
+        if changed(old, config, |c| c.status_led.clone()) {
+            self.status_led = None;
+            self.status_led = if config.status_led.is_empty() {
+                None
+            } else {
+                statusled::StatusLed::start(&config.status_led)
+                    .map_err(|e| eprintln!("Status LED disabled: {}", e))
+                    .ok()
+            };
+        }
+
+        if changed(old, config, |c| {
+            (
+                c.display_backend.clone(),
+                c.display_i2c_bus,
+                c.display_i2c_address,
+                c.display_pages.clone(),
+                c.display_page_secs,
+            )
+        }) {
+            self.display_output = None;
+            self.display_output = output::open(config).unwrap_or_else(|e| {
+                eprintln!("Display output disabled: {}", e);
+                None
+            });
+        }
+
+        if changed(old, config, |c| c.record_metrics) {
+            self.metric_store = if config.record_metrics {
+                history::HistoryStore::open()
+                    .map_err(|e| eprintln!("Metric recording disabled: {}", e))
+                    .ok()
+            } else {
+                None
+            };
+        }
+        // Dropping the old handle ends its compaction thread
+        if changed(old, config, |c| {
+            (
+                c.record_metrics,
+                c.history_raw_hours,
+                c.history_minute_days,
+                c.history_hourly_days,
+            )
+        }) {
+            self.compaction = self.metric_store.as_ref().map(|_| {
+                history::spawn_compaction(history::Retention {
+                    raw_hours: config.history_raw_hours,
+                    minute_days: config.history_minute_days,
+                    hourly_days: config.history_hourly_days,
+                })
+            });
+        }
+
+        if changed(old, config, |c| {
+            (c.report_schedule.clone(), c.notify_webhook.clone())
+        }) {
+            self.reports = if config.report_schedule.is_empty() {
+                None
+            } else {
+                report::ReportScheduler::new(&config.report_schedule, &config.notify_webhook)
+                    .map_err(|e| eprintln!("Scheduled reports disabled: {}", e))
+                    .ok()
+            };
+        }
+
+        if changed(old, config, |c| (c.show_sensors, c.sensor_config.clone())) {
+            self.sensor_manager = None;
+            self.sensor_manager = if config.show_sensors {
+                match sensors::initialize_sensors(config.sensor_config.clone()) {
+                    Ok(manager) => Some(manager),
+                    Err(e) => {
+                        eprintln!("Failed to initialize sensors: {}", e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
         }
     }
 
     fn refresh(&mut self) {
-        self.system.refresh_all();
+        refresh_system(&mut self.system);
         let mut total_received = 0;
         let mut total_transmitted = 0;
 
@@ -107,35 +802,378 @@ impl SystemResources {
             total_transmitted += network.transmitted();
         }
 
-        self.last_net_receive = total_received;
-        self.last_net_transmit = total_transmitted;
+        self.refresh_interval_secs = self.last_update.elapsed().as_secs_f64();
         self.last_update = Instant::now();
 
-        // Update sensor data if available
+        self.scheduler.refresh(self.refresh_interval_secs);
+
+        if self.history.is_some() || self.rolling.is_some() {
+            let cpu = self.system.global_cpu_info().cpu_usage() as f64;
+            let total_mem = self.system.total_memory();
+            let memory = if total_mem > 0 {
+                self.system.used_memory() as f64 / total_mem as f64 * 100.0
+            } else {
+                0.0
+            };
+            let seconds = self.refresh_interval_secs;
+            let (rx, tx) = if seconds > 0.0 {
+                (
+                    total_received as f64 / seconds,
+                    total_transmitted as f64 / seconds,
+                )
+            } else {
+                (0.0, 0.0)
+            };
+            if let Some(ref mut history) = self.history {
+                history.push(cpu, memory, rx, tx);
+            }
+            if let Some(ref mut rolling) = self.rolling {
+                rolling.push(cpu, memory, rx, tx);
+            }
+        }
+        self.system.refresh_components();
+        self.throttle.refresh(&self.system, &mut self.alerts);
+
+        if let Some(ref mut tracker) = self.process_net {
+            tracker.sample(self.refresh_interval_secs);
+        }
+
+        if let Some(ref mut monitor) = self.disk_io {
+            monitor.refresh(self.refresh_interval_secs);
+        }
+
+        if let Some(ref mut monitor) = self.containers {
+            monitor.refresh(self.refresh_interval_secs);
+        }
+
+        if let Some(ref mut monitor) = self.listening_ports {
+            monitor.refresh(&self.system, &mut self.alerts);
+        }
+
+        if let Some(ref mut watchdog) = self.watchdog {
+            watchdog.refresh(&self.system, &mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.firewall {
+            monitor.refresh(&mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.vpn {
+            monitor.refresh(&mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.camera {
+            monitor.refresh(&self.system);
+        }
+
+        if let Some(ref mut monitor) = self.usb {
+            monitor.refresh();
+        }
+
+        if let Some(ref mut monitor) = self.peripherals {
+            monitor.refresh();
+        }
+
+        if let Some(ref mut monitor) = self.audio {
+            monitor.refresh(&mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.displays {
+            monitor.refresh(&self.system);
+        }
+
+        if let Some(ref mut monitor) = self.fan {
+            monitor.refresh();
+        }
+
+        if let Some(ref mut monitor) = self.boost {
+            monitor.refresh(&self.throttle);
+        }
+
+        if let Some(ref mut monitor) = self.traffic {
+            monitor.sample(self.refresh_interval_secs);
+        }
+
+        if let Some(ref monitor) = self.tls {
+            monitor.refresh(&mut self.alerts);
+        }
+
+        disks::check_inode_alerts(
+            &self.system,
+            &self.disk_filter,
+            &mut self.alerts,
+            self.inode_alert_percent,
+        );
+
+        if let Some(ref mut monitor) = self.network_mounts {
+            monitor.refresh(&mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.growth {
+            monitor.refresh(&self.system, &mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.interfaces {
+            monitor.refresh(&self.system, &mut self.alerts);
+        }
+
+        if let Some(ref monitor) = self.connectivity {
+            monitor.refresh(&mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.fds {
+            monitor.refresh(&self.system, &mut self.alerts);
+        }
+
+        if let Some(ref mut tracker) = self.uptime {
+            tracker.refresh(&self.system);
+        }
+
+        if let Some(ref mut monitor) = self.kernel_log {
+            monitor.refresh(&mut self.alerts);
+        }
+
+        if let Some(ref mut monitor) = self.journal {
+            monitor.refresh();
+        }
+
+        // Update sensor data if available, taking everything queued since
+        // the last refresh so the sensor thread does not have to drop readings
         if let Some(ref manager) = self.sensor_manager {
-            if let Some(result) = manager.try_receive_update() {
+            while let Some(result) = manager.try_receive_update() {
                 match result {
                     Ok(data) => {
                         self.last_sensor_data = data;
+                        if let Some(ref mut rolling) = self.rolling {
+                            rolling.push_sensor(data.acceleration);
+                        }
                     }
+                    // Counted in the sensor stats instead
+                    Err(sensors::SensorError::Timeout) => {}
                     Err(e) => {
                         eprintln!("Sensor error: {}", e);
                     }
                 }
             }
+            for (index, state) in manager.trigger_states().into_iter().enumerate() {
+                let sensor_triggers::Action::Alert(level) = state.trigger.action else {
+                    continue;
+                };
+                let key = format!("sensor-trigger-{}", index);
+                if state.active {
+                    self.alerts.raise(
+                        &key,
+                        level,
+                        "sensor",
+                        format!("Sensor trigger: {}", state.trigger.text),
+                    );
+                } else {
+                    self.alerts.clear(&key);
+                }
+            }
+        }
+
+        if let Some(ref led) = self.status_led {
+            led.show(self.alerts.highest_level());
+        }
+
+        if self.display_output.is_some() {
+            let stats = output::Stats::collect(self);
+            if let Some(ref mut display) = self.display_output {
+                if let Err(e) = display.show(&stats) {
+                    log::debug!("Display output: {}", e);
+                }
+            }
+        }
+
+        if let Some(ref store) = self.metric_store {
+            if let Err(e) = history::record_resources(store, self) {
+                log::debug!("Could not record metrics: {}", e);
+            }
+        }
+
+        if let Some(ref mut reports) = self.reports {
+            reports.tick();
+        }
+
+        if self.statsd.is_some() {
+            let measurements = metrics::collect(self);
+            if let Some(ref mut sink) = self.statsd {
+                sink.send(&measurements);
+            }
+        }
+    }
+}
+
+// Whether the settings picked out by `key` differ from the previous config;
+// without one, on startup, everything counts as changed
+fn changed<T: PartialEq>(
+    old: Option<&MonitorConfig>,
+    config: &MonitorConfig,
+    key: impl Fn(&MonitorConfig) -> T,
+) -> bool {
+    old.is_none_or(|old| key(old) != key(config))
+}
+
+// Same as System::refresh_all, except network mounts are left to
+// disks::NetworkMountMonitor so a hung server cannot block the refresh
+fn refresh_system(system: &mut System) {
+    system.refresh_system();
+    system.refresh_processes();
+    disks::refresh_local_disks(system);
+    system.refresh_networks();
+}
+
+// Set by SIGTERM and SIGINT so the daemon leaves its loop and drops the fan
+// controller, which puts the fan back to full speed
+static STOP_DAEMON: AtomicBool = AtomicBool::new(false);
+
+#[cfg(target_os = "linux")]
+fn stop_daemon_on_signals() {
+    use nix::sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal};
+
+    extern "C" fn stop(_signal: nix::libc::c_int) {
+        STOP_DAEMON.store(true, Ordering::Relaxed);
+    }
+
+    let action = SigAction::new(
+        SigHandler::Handler(stop),
+        SaFlags::SA_RESTART,
+        SigSet::empty(),
+    );
+    for signal in [Signal::SIGTERM, Signal::SIGINT] {
+        // SAFETY: the handler only stores to an atomic
+        if let Err(e) = unsafe { sigaction(signal, &action) } {
+            log::warn!("Cannot handle {}: {}", signal, e);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn stop_daemon_on_signals() {}
+
+// Handle "hercules daemon": refresh in the background without drawing, so
+// alerts, metric recording, the watchdog and scheduled reports keep running
+fn run_daemon() -> Result<()> {
+    let config: MonitorConfig = config::ConfigManager::new()?.get_config().into();
+    let mut res = SystemResources::new(&config);
+    if let Some(ref mut reports) = res.reports {
+        reports.set_echo(true);
+    }
+    let mut fan = if config.fan_control {
+        let curve = pwmfan::parse_curve(&config.fan_control_curve)?;
+        Some(pwmfan::FanController::new(
+            config.fan_control_pin,
+            config.fan_control_frequency_hz,
+            curve,
+            config.fan_control_hysteresis,
+        )?)
+    } else {
+        None
+    };
+    println!(
+        "Hercules daemon running, refreshing every {} ms",
+        config.update_interval_ms
+    );
+    stop_daemon_on_signals();
+    while !STOP_DAEMON.load(Ordering::Relaxed) {
+        if let Some(ref mut fan) = fan {
+            fan.update(res.throttle.temperature());
         }
+        thread::sleep(Duration::from_millis(config.update_interval_ms));
+        res.refresh();
     }
+    println!("Hercules daemon stopped");
+    Ok(())
 }
 
 // Main entry point
 fn main() -> Result<()> {
-    env_logger::init();
+    // Log lines carry the same timestamps as the rest of the output
+    env_logger::Builder::from_default_env()
+        .format(|buf, record| {
+            writeln!(
+                buf,
+                "[{} {:<5} {}] {}",
+                clock::now(),
+                record.level(),
+                record.target(),
+                record.args()
+            )
+        })
+        .init();
 
     // Handle special CLI commands first
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    // --plain and --config apply to every subcommand, so take them out before
+    // they parse their own flags
+    if args.iter().any(|arg| arg == "--plain") {
+        theme::set_plain(true);
+        args.retain(|arg| arg != "--plain");
+    }
+    if let Some(index) = args
+        .iter()
+        .position(|arg| arg == "--config" || arg.starts_with("--config="))
+    {
+        let arg = args.remove(index);
+        let path = match arg.strip_prefix("--config=") {
+            Some(path) => path.to_string(),
+            None if index < args.len() => args.remove(index),
+            None => return Err(anyhow!("--config needs a file path")),
+        };
+        config::ConfigManager::set_config_path(Path::new(&path))?;
+    }
+
+    // Every subcommand prints colored output, so pick the theme up front
+    if let Ok(config_manager) = config::ConfigManager::new() {
+        if let Err(e) = theme::set_theme(&config_manager.get_config().theme) {
+            eprintln!("{}, using the default theme", e);
+        }
+        if let Err(e) = i18n::set_language(&config_manager.get_config().language) {
+            eprintln!("{}, using English", e);
+        }
+        if let Err(e) = units::set_units(&config_manager.get_config().units) {
+            eprintln!("{}, using binary units", e);
+        }
+        units::set_celsius(config_manager.get_config().sensor_config.use_celsius);
+        let sensor_config = &config_manager.get_config().sensor_config;
+        if let Err(e) =
+            units::set_sensor_units(&sensor_config.accel_units, &sensor_config.gyro_units)
+        {
+            eprintln!("{}, using m/s² and °/s", e);
+        }
+        clock::set_format(&config_manager.get_config().timestamp_format);
+        if let Err(e) = clock::set_timezone(&config_manager.get_config().timezone) {
+            eprintln!("{}, using local time", e);
+        }
+    } else {
+        i18n::set_language("auto").ok();
+    }
+
+    // "hercules snapshot" prints every enabled panel once without the banner, for
+    // cron jobs and MOTD scripts. It takes the same flags as a normal run.
+    let snapshot = args.get(1).is_some_and(|arg| arg == "snapshot");
+    if snapshot {
+        args.remove(1);
+        // "--html out.html" or "--svg out.svg" saves the snapshot to a file instead
+        for (flag, format) in [
+            ("--html", export::Format::Html),
+            ("--svg", export::Format::Svg),
+        ] {
+            if let Some(index) = args.iter().position(|arg| arg == flag) {
+                if index + 1 >= args.len() {
+                    return Err(anyhow!("{} needs a file path", flag));
+                }
+                let path = args.remove(index + 1);
+                args.remove(index);
+                return export::run_export(format, &path, &args[1..]);
+            }
+        }
+    }
 
     // Handle configuration commands with exact syntax: "hercules conf <property> -> <new_value>"
-    if args.len() >= 2 {
+    if args.len() >= 2 && !snapshot {
         match args[1].as_str() {
             "conf" => {
                 if args.len() == 2 {
@@ -149,6 +1187,45 @@ fn main() -> Result<()> {
             "conf-reset" => {
                 return config::ConfigManager::reset_config();
             }
+            "watch" => {
+                return process::run_watch(&args[1..]);
+            }
+            "proc" => {
+                return process::run_inspect(&args[1..]);
+            }
+            "speedtest" => {
+                return speedtest::run_speedtest(&args[1..]);
+            }
+            "check" => {
+                return check::run_check(&args[1..]);
+            }
+            "bench" => {
+                return bench::run_bench(&args[1..]);
+            }
+            "history" => {
+                return history::run_history(&args[1..]);
+            }
+            "du" => {
+                return du::run_du(&args[1..]);
+            }
+            "lan-scan" => {
+                return lan::run_lan_scan(&args[1..]);
+            }
+            "portscan" => {
+                return portscan::run_portscan(&args[1..]);
+            }
+            "record" => {
+                return record::run_record(&args[1..]);
+            }
+            "report" => {
+                return report::run_report(&args[1..]);
+            }
+            "daemon" => {
+                return run_daemon();
+            }
+            "replay" => {
+                return record::run_replay(&args[1..]);
+            }
             // Handle shorthand commands
             "installer" => {
                 installer::prompt_install();
@@ -165,9 +1242,19 @@ fn main() -> Result<()> {
                 return display_compact_mode(&resources, config.show_sensors);
             }
             "sensors" => {
-                // Run with sensors enabled
                 let config_manager = config::ConfigManager::new()?;
                 let file_config = config_manager.get_config();
+                match args.get(2).map(String::as_str) {
+                    Some("list") => return sensors::run_list(&file_config.sensor_config),
+                    Some("setup-permissions") => {
+                        return sensor_permissions::run_setup(
+                            &file_config.sensor_config.devices,
+                            &args[3..],
+                        )
+                    }
+                    _ => {}
+                }
+                // Run with sensors enabled
                 let mut config: MonitorConfig = file_config.into();
                 config.show_sensors = true;
                 config.sensor_config.enabled = true;
@@ -177,7 +1264,7 @@ fn main() -> Result<()> {
                 if config.show_compact_mode {
                     return display_compact_mode(&resources, true);
                 } else {
-                    monitor_resources(&resources, &config)?;
+                    monitor_resources(&resources, &config, None)?;
                     return monitor_sensors(&resources);
                 }
             }
@@ -186,7 +1273,7 @@ fn main() -> Result<()> {
     }
 
     // Set up clap for command line argument handling
-    let matches = Command::new("Hercules")
+    let mut command = Command::new("Hercules")
         .version("0.1.0")
         .author("Hercules Team")
         .about("System Resource Monitor")
@@ -211,43 +1298,137 @@ fn main() -> Result<()> {
                 .help("Enable gyroscope and accelerometer monitoring via USB")
                 .action(ArgAction::SetTrue),
         )
-        .get_matches();
-
-    // Check both command line arguments and direct "compact" argument
-    let use_compact_mode = matches.get_flag("compact") || env::args().any(|arg| arg == "compact");
-
-    let use_installer = matches.get_flag("installer") || env::args().any(|arg| arg == "installer");
-    let use_sensors = matches.get_flag("sensors") || env::args().any(|arg| arg == "sensors");
-
-    println!("{}", "HERCULES - System Resource Monitor".bold().green());
-    println!("{}", "==================================".green());
-    println!("Use 'hercules compact' or 'hercules --compact' for compact display");
-    println!("Use 'hercules sensors' or 'hercules --sensors' to enable gyro/accelerometer");
-    println!("Use 'hercules conf' to view configuration");
-    println!("Use 'hercules conf <property> -> <value>' to change settings");
-    println!();
-
-    // Load configuration from file, then override with command line args
-    let config_manager = config::ConfigManager::new()?;
-    let file_config = config_manager.get_config();
-    let mut config: MonitorConfig = file_config.into();
-
-    // Override with command line arguments
-    if use_compact_mode {
-        config.show_compact_mode = true;
+        .arg(
+            Arg::new("plain")
+                .long("plain")
+                .help("Plain ASCII output without colors, box drawing or emoji")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .value_name("PATH")
+                .help("Use this config file instead of the default location"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .long("verbose")
+                .short('v')
+                .help("Show more detail in each panel, -vv for even more")
+                .action(ArgAction::Count),
+        )
+        .arg(
+            Arg::new("quiet")
+                .long("quiet")
+                .short('q')
+                .help("Show only the headline figures of each panel")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("verbose"),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .value_name("MS")
+                .help("Refresh interval in milliseconds")
+                .value_parser(clap::value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("processes")
+                .long("processes")
+                .value_name("N")
+                .help("Show the top N processes")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("once")
+                .long("once")
+                .help("Print a single snapshot and exit")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Print metrics once for a Telegraf or collectd exec plugin and exit")
+                .value_parser(metrics::EXEC_FORMATS),
+        )
+        .arg(
+            Arg::new("count")
+                .long("count")
+                .value_name("N")
+                .help("Exit after N refreshes and print a summary")
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with("once"),
+        )
+        .arg(
+            Arg::new("duration")
+                .long("duration")
+                .value_name("TIME")
+                .help("Exit after this long, e.g. 90s, 5m or 1h, and print a summary")
+                .value_parser(|value: &str| {
+                    summary::parse_duration(value).map_err(|e| e.to_string())
+                })
+                .conflicts_with("once"),
+        );
+
+    // Every config property can be overridden for one run, e.g. --max-processes 20,
+    // and every show_* panel can be hidden with --no-*
+    for (property, help) in config::ConfigManager::properties() {
+        command = command.arg(
+            Arg::new(property)
+                .long(property.replace('_', "-"))
+                .value_name("VALUE")
+                .help(help)
+                .help_heading("Config Overrides"),
+        );
+        if let Some(panel) = property.strip_prefix("show_") {
+            command = command.arg(
+                Arg::new(format!("no_{}", panel))
+                    .long(format!("no-{}", panel.replace('_', "-")))
+                    .help(format!("Same as --{} false", property.replace('_', "-")))
+                    .help_heading("Config Overrides")
+                    .action(ArgAction::SetTrue),
+            );
+        }
     }
-    if use_installer {
-        config.show_installer = true;
+    let matches = command.get_matches_from(&args);
+    let exec_format = matches.get_one::<String>("format");
+    verbosity::set_level(if matches.get_flag("quiet") {
+        -1
+    } else {
+        matches.get_count("verbose") as i8
+    });
+
+    if !snapshot && exec_format.is_none() {
+        println!("{}", "HERCULES - System Resource Monitor".bold().good());
+        println!("{}", "==================================".good());
+        println!("Use 'hercules compact' or 'hercules --compact' for compact display");
+        println!("Use 'hercules sensors' or 'hercules --sensors' to enable gyro/accelerometer");
+        println!("Use 'hercules snapshot' to print everything once and exit");
+        println!("Use 'hercules conf' to view configuration");
+        println!("Use 'hercules conf <property> -> <value>' to change settings");
+        println!();
     }
-    if use_sensors {
-        config.show_sensors = true;
-        config.sensor_config.enabled = true;
-        config.sensor_config.update_interval_ms = config.update_interval_ms / 10;
+
+    let mut config = load_monitor_config(&matches)?;
+    if snapshot {
+        config.continuous = false;
+        config.show_installer = false;
     }
 
     // Create shared system resources
     let resources = Arc::new(Mutex::new(SystemResources::new(&config)));
 
+    if let Some(format) = exec_format {
+        let mut res = resources
+            .lock()
+            .map_err(|e| anyhow!("Failed to lock resources: {}", e))?;
+        // Rates and CPU usage need a measured interval between two refreshes
+        thread::sleep(System::MINIMUM_CPU_UPDATE_INTERVAL);
+        res.refresh();
+        return metrics::print_exec(format, &res);
+    }
+
     // If continuous monitoring, clear screen and show live stats
     if config.continuous {
         // Handle installer if requested
@@ -256,7 +1437,11 @@ fn main() -> Result<()> {
         }
 
         // Create progress bar for visual effect
-        let pb = ProgressBar::new_spinner();
+        let pb = if theme::interactive() {
+            ProgressBar::new_spinner()
+        } else {
+            ProgressBar::hidden()
+        };
         pb.set_style(
             ProgressStyle::default_spinner()
                 .tick_chars("⠁⠂⠄⡀⢀⠠⠐⠈")
@@ -264,20 +1449,86 @@ fn main() -> Result<()> {
                 .unwrap(),
         );
 
-        loop {
+        // Pick up edits to the config file without restarting
+        let watcher = config::ConfigManager::new()
+            .and_then(|manager| reload::ConfigWatcher::new(manager.config_path()))
+            .map_err(|e| eprintln!("Config hot reload disabled: {}", e))
+            .ok();
+        let mut status_notice: Option<(Instant, ColoredString)> = None;
+
+        // --count and --duration end the run and print min/avg/max per metric
+        let count_limit = matches.get_one::<usize>("count").copied();
+        let duration_limit = matches.get_one::<Duration>("duration").copied();
+        let mut run_summary =
+            (count_limit.is_some() || duration_limit.is_some()).then(summary::RunSummary::new);
+
+        // On a terminal, keys scroll, search and sort the process table
+        let keys = input::KeyReader::start();
+        let mut keymap = keymap::Keymap::new(&config.keys)?;
+        let mut process_table = (keys.is_some() && !config.show_compact_mode).then(|| {
+            process_table::ProcessTable::new(
+                config.process_sort,
+                config.max_processes,
+                keymap.clone(),
+            )
+        });
+        // The `?` overlay replaces the panels until the next key press
+        let mut show_help = false;
+        // Set while redrawing for a key press, so the refresh keeps its schedule
+        let mut next_refresh: Option<Instant> = None;
+
+        'monitor: loop {
+            if watcher.as_ref().is_some_and(|watcher| watcher.changed()) {
+                let reloaded = load_monitor_config(&matches)
+                    .and_then(|reloaded| Ok((keymap::Keymap::new(&reloaded.keys)?, reloaded)));
+                match reloaded {
+                    Ok((reloaded_keymap, reloaded)) => {
+                        keymap = reloaded_keymap;
+                        if let Some(ref mut table) = process_table {
+                            table.keymap = keymap.clone();
+                        }
+                        let previous = std::mem::replace(&mut config, reloaded);
+                        if let Ok(mut res) = resources.lock() {
+                            res.apply_config(Some(&previous), &config);
+                        }
+                        status_notice = Some((Instant::now(), "config reloaded".good()));
+                    }
+                    Err(e) => {
+                        status_notice = Some((
+                            Instant::now(),
+                            format!("config reload failed, keeping previous settings: {}", e)
+                                .critical(),
+                        ));
+                    }
+                }
+            }
+            let notice = status_notice
+                .as_ref()
+                .filter(|(at, _)| at.elapsed() < NOTICE_DURATION)
+                .map(|(_, message)| message);
             // Clear screen and reset cursor
-            print!("\x1B[2J\x1B[1;1H");
-            io::stdout().flush().unwrap();
+            if theme::interactive() {
+                print!("\x1B[2J\x1B[1;1H");
+                io::stdout().flush().unwrap();
+            }
 
-            let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+            let timestamp = clock::now();
 
-            if config.show_compact_mode {
+            if show_help {
+                keymap::print_help(&keymap);
+            } else if config.show_compact_mode {
+                if let Some(message) = notice {
+                    println!("{}", message);
+                }
                 display_compact_mode(&resources, config.show_sensors)?;
             } else {
-                println!("{} {}", "HERCULES".bold().green(), timestamp.cyan());
-                println!("{}", "==================================".green());
+                println!("{} {}", "HERCULES".bold().good(), timestamp.value());
+                println!("{}", "==================================".good());
+                if let Some(message) = notice {
+                    println!("{}", message);
+                }
 
-                if let Err(e) = monitor_resources(&resources, &config) {
+                if let Err(e) = monitor_resources(&resources, &config, process_table.as_mut()) {
                     eprintln!("Error monitoring resources: {}", e);
                     break;
                 }
@@ -293,13 +1544,95 @@ fn main() -> Result<()> {
             pb.set_message(format!("Updated at {}", timestamp));
             pb.tick();
 
-            thread::sleep(Duration::from_millis(config.update_interval_ms));
+            let due = match next_refresh.take() {
+                Some(due) => due,
+                None => {
+                    let mut interval = Duration::from_millis(config.update_interval_ms);
+                    if let Some(ref mut run_summary) = run_summary {
+                        if let Ok(res) = resources.lock() {
+                            record_summary(&res, run_summary);
+                        }
+                        if count_limit.is_some_and(|count| run_summary.refreshes() >= count) {
+                            break;
+                        }
+                        if let Some(limit) = duration_limit {
+                            let remaining = limit.saturating_sub(run_summary.elapsed());
+                            if remaining.is_zero() {
+                                break;
+                            }
+                            interval = interval.min(remaining);
+                        }
+                    }
+                    Instant::now() + interval
+                }
+            };
+
+            match keys {
+                Some(ref keys) => {
+                    while let Some(key) = keys.next_key(due) {
+                        if show_help {
+                            show_help = false;
+                            next_refresh = Some(due);
+                            continue 'monitor;
+                        }
+                        // While a prompt is open, keys are typed into it
+                        if let Some(table) = process_table
+                            .as_mut()
+                            .filter(|table| table.prompt.is_some())
+                        {
+                            if let Some(prompt) = table.handle_prompt_key(&key) {
+                                status_notice = Some((Instant::now(), apply_prompt(&prompt)));
+                            }
+                            next_refresh = Some(due);
+                            continue 'monitor;
+                        }
+                        // While searching, keys are typed into the search
+                        if let Some(table) = process_table.as_mut().filter(|table| table.searching)
+                        {
+                            if table.handle_search_key(&key) {
+                                next_refresh = Some(due);
+                                continue 'monitor;
+                            }
+                            continue;
+                        }
+                        let redraw = match keymap.action(&key) {
+                            Some(keymap::Action::Quit) => break 'monitor,
+                            Some(keymap::Action::Help) => {
+                                show_help = true;
+                                true
+                            }
+                            Some(
+                                action @ (keymap::Action::CopyText | keymap::Action::CopyJson),
+                            ) => {
+                                let json = action == keymap::Action::CopyJson;
+                                status_notice =
+                                    Some((Instant::now(), copy_snapshot(json, &resources, &args)));
+                                true
+                            }
+                            Some(action) => process_table
+                                .as_mut()
+                                .is_some_and(|table| table.handle_action(action)),
+                            None => false,
+                        };
+                        if redraw {
+                            next_refresh = Some(due);
+                            continue 'monitor;
+                        }
+                    }
+                }
+                None => thread::sleep(due.saturating_duration_since(Instant::now())),
+            }
 
             // Refresh resources data
             if let Ok(mut res) = resources.lock() {
                 res.refresh();
             }
         }
+
+        if let Some(run_summary) = run_summary {
+            pb.finish_and_clear();
+            run_summary.print();
+        }
     } else {
         // One-time display of system information
         if config.show_installer {
@@ -310,7 +1643,7 @@ fn main() -> Result<()> {
         if config.show_compact_mode {
             display_compact_mode(&resources, config.show_sensors)?;
         } else {
-            monitor_resources(&resources, &config)?;
+            monitor_resources(&resources, &config, None)?;
 
             if config.show_sensors {
                 monitor_sensors(&resources)?;
@@ -321,72 +1654,214 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-// Function to display compact mode with ASCII art
-fn display_compact_mode(resources: &Arc<Mutex<SystemResources>>, show_sensors: bool) -> Result<()> {
-    let res = resources
-        .lock()
-        .map_err(|e| anyhow!("Failed to lock resources: {}", e))?;
+// Load the config file and apply command line overrides on top, so reloads
+// keep the flags the program was started with
+fn load_monitor_config(matches: &ArgMatches) -> Result<MonitorConfig> {
+    // Check both command line arguments and direct "compact" argument
+    let use_compact_mode = matches.get_flag("compact") || env::args().any(|arg| arg == "compact");
 
-    // Get system info
-    let hostname = res
-        .system
-        .host_name()
-        .unwrap_or_else(|| "Unknown".to_string());
-    let os_name = res.system.name().unwrap_or_else(|| "Unknown".to_string());
-    let kernel_version = res
-        .system
-        .kernel_version()
-        .unwrap_or_else(|| "Unknown".to_string());
-
-    // CPU info
-    let global_cpu_usage = res.system.global_cpu_info().cpu_usage();
-    let cpu_count = res.system.cpus().len();
+    let use_installer = matches.get_flag("installer") || env::args().any(|arg| arg == "installer");
+    let use_sensors = matches.get_flag("sensors") || env::args().any(|arg| arg == "sensors");
 
-    // Memory info
-    let total_mem = res.system.total_memory();
-    let used_mem = res.system.used_memory();
-    let total_gb = total_mem as f64 / 1_073_741_824.0; // Convert to GB
-    let used_gb = used_mem as f64 / 1_073_741_824.0;
-    let mem_percent = if total_mem > 0 {
-        (used_mem as f64 / total_mem as f64) * 100.0
-    } else {
-        0.0
-    };
+    let config_manager = config::ConfigManager::new()?;
+    let mut file_config = config_manager.get_config().clone();
+    for (property, _) in config::ConfigManager::properties() {
+        if let Some(value) = matches.get_one::<String>(property) {
+            config::ConfigManager::set_property(&mut file_config, property, value)
+                .map_err(|e| anyhow!("Invalid --{}: {}", property.replace('_', "-"), e))?;
+        }
+        if let Some(panel) = property.strip_prefix("show_") {
+            if matches.get_flag(&format!("no_{}", panel)) {
+                config::ConfigManager::set_property(&mut file_config, property, "false")?;
+            }
+        }
+    }
+    if let Some(&interval) = matches.get_one::<u64>("interval") {
+        file_config.update_interval_ms = interval;
+    }
+    if let Some(&count) = matches.get_one::<usize>("processes") {
+        file_config.show_processes = true;
+        file_config.max_processes = count;
+    }
+    if matches.get_flag("once") {
+        file_config.continuous = false;
+    }
+    // A run limit only makes sense when refreshing
+    if matches.contains_id("count") || matches.contains_id("duration") {
+        file_config.continuous = true;
+    }
+    // An unknown theme keeps the current one, it was already reported at startup
+    theme::set_theme(&file_config.theme).ok();
+    units::set_units(&file_config.units).ok();
+    units::set_celsius(file_config.sensor_config.use_celsius);
+    units::set_sensor_units(
+        &file_config.sensor_config.accel_units,
+        &file_config.sensor_config.gyro_units,
+    )
+    .ok();
+    // The timezone only changes at startup, see clock::set_timezone
+    clock::set_format(&file_config.timestamp_format);
+    let mut config: MonitorConfig = (&file_config).into();
+
+    // Override with command line arguments
+    if use_compact_mode {
+        config.show_compact_mode = true;
+    }
+    if use_installer {
+        config.show_installer = true;
+    }
+    if use_sensors {
+        config.show_sensors = true;
+        config.sensor_config.enabled = true;
+        config.sensor_config.update_interval_ms = config.update_interval_ms / 10;
+    }
 
-    // Network info
-    let elapsed = res.last_update.elapsed().as_secs_f64();
+    Ok(config)
+}
 
-    // Calculate total network rates across all interfaces
-    let mut total_received = 0;
-    let mut total_transmitted = 0;
+// Sample the headline metrics for the end-of-run summary
+fn record_summary(res: &SystemResources, run_summary: &mut summary::RunSummary) {
+    let system = &res.system;
+    run_summary.record("CPU", "%", system.global_cpu_info().cpu_usage() as f64);
+    if system.total_memory() > 0 {
+        let memory = system.used_memory() as f64 / system.total_memory() as f64 * 100.0;
+        run_summary.record("Memory", "%", memory);
+    }
+    if system.total_swap() > 0 {
+        let swap = system.used_swap() as f64 / system.total_swap() as f64 * 100.0;
+        run_summary.record("Swap", "%", swap);
+    }
+    run_summary.record("Load (1m)", "", system.load_average().one);
+
+    let seconds = res.refresh_interval_secs;
+    if seconds > 0.0 {
+        let (received, transmitted) = system
+            .networks()
+            .into_iter()
+            .fold((0, 0), |(rx, tx), (_, network)| {
+                (rx + network.received(), tx + network.transmitted())
+            });
+        run_summary.record("Net receive", units::RATE, received as f64 / seconds);
+        run_summary.record("Net transmit", units::RATE, transmitted as f64 / seconds);
+    }
 
-    for (_, data) in res.system.networks() {
-        total_received += data.received();
-        total_transmitted += data.transmitted();
+    if res.throttle.average_mhz() > 0 {
+        run_summary.record("CPU clock", " MHz", res.throttle.average_mhz() as f64);
     }
+    if let Some(temp) = res.throttle.temperature() {
+        run_summary.record("CPU temperature", units::TEMPERATURE, temp as f64);
+    }
+    run_summary.finish_refresh();
+}
 
-    // Calculate rates (bytes/sec)
-    let total_recv_rate = if elapsed > 0.0 {
-        (total_received - res.last_net_receive) as f64 / elapsed
+// Copies the current state to the clipboard, returning the notice to show
+fn copy_snapshot(
+    json: bool,
+    resources: &Arc<Mutex<SystemResources>>,
+    args: &[String],
+) -> ColoredString {
+    let text = if json {
+        resources
+            .lock()
+            .map(|res| metrics::json(&res))
+            .map_err(|e| anyhow!("Failed to lock resources: {}", e))
     } else {
-        0.0
+        clipboard::plain_snapshot(&args[1..])
     };
+    match text.and_then(|text| clipboard::copy(&text)) {
+        Ok(target) => format!(
+            "copied {} to the {}",
+            if json { "metrics as JSON" } else { "snapshot" },
+            target
+        )
+        .good(),
+        Err(e) => format!("copy failed: {}", e).critical(),
+    }
+}
 
-    let total_transmit_rate = if elapsed > 0.0 {
-        (total_transmitted - res.last_net_transmit) as f64 / elapsed
-    } else {
-        0.0
+// Renice or pin the process a prompt was opened for, and say how it went
+fn apply_prompt(prompt: &process_table::Prompt) -> ColoredString {
+    let result = match prompt.kind {
+        process_table::PromptKind::Nice => prompt
+            .text
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("'{}' is not a nice value", prompt.text.trim()))
+            .and_then(|nice| process_control::renice(prompt.pid, nice))
+            .map(|()| {
+                format!(
+                    "set nice of {} ({}) to {}",
+                    prompt.name,
+                    prompt.pid,
+                    prompt.text.trim()
+                )
+            }),
+        process_table::PromptKind::Affinity => {
+            process_control::set_affinity(prompt.pid, &prompt.text).map(|()| {
+                format!(
+                    "pinned {} ({}) to CPUs {}",
+                    prompt.name,
+                    prompt.pid,
+                    process_control::affinity(prompt.pid).unwrap_or_default()
+                )
+            })
+        }
     };
+    match result {
+        Ok(done) => done.good(),
+        Err(e) => e.to_string().critical(),
+    }
+}
+
+// How long notices such as "config reloaded" stay on screen
+const NOTICE_DURATION: Duration = Duration::from_secs(3);
+
+// Width of the CPU and Raspberry Pi art in compact mode
+const ART_WIDTH: usize = 23;
+
+// Function to display compact mode with ASCII art
+fn display_compact_mode(resources: &Arc<Mutex<SystemResources>>, show_sensors: bool) -> Result<()> {
+    let res = resources
+        .lock()
+        .map_err(|e| anyhow!("Failed to lock resources: {}", e))?;
 
     // Get sensor data if enabled
     let sensor_data = res.last_sensor_data;
-    let has_sensor_data = show_sensors
-        && (sensor_data.acceleration[0] != 0.0
-            || sensor_data.acceleration[1] != 0.0
-            || sensor_data.acceleration[2] != 0.0
-            || sensor_data.gyro[0] != 0.0
-            || sensor_data.gyro[1] != 0.0
-            || sensor_data.gyro[2] != 0.0);
+    let has_sensor_data = sensor_data.acceleration[0] != 0.0
+        || sensor_data.acceleration[1] != 0.0
+        || sensor_data.acceleration[2] != 0.0
+        || sensor_data.gyro[0] != 0.0
+        || sensor_data.gyro[1] != 0.0
+        || sensor_data.gyro[2] != 0.0;
+
+    print_compact(
+        &record::Sample::collect(&res),
+        res.pi.as_ref(),
+        show_sensors.then_some(has_sensor_data),
+    );
+
+    // Display sensor data in compact mode if enabled
+    if show_sensors {
+        print_compact_sensors(&res, has_sensor_data);
+    }
+
+    Ok(())
+}
+
+// The compact view of one sample. `sensors` says whether sensor data arrived,
+// None when sensors are off. Replay has no board info to pass as `pi`.
+fn print_compact(sample: &record::Sample, pi: Option<&pi::PiInfo>, sensors: Option<bool>) {
+    let hostname = &sample.host;
+    let global_cpu_usage = sample.cpu.usage;
+    let cpu_count = sample.cpu.cores.len();
+
+    // Memory info
+    let mem_scale = units::Scale::of(sample.memory.total as f64);
+    let mem_percent = sample.memory.percent();
+
+    // Network totals across all interfaces, already per second
+    let total_recv_rate: f64 = sample.networks.iter().map(|net| net.rx_rate).sum();
+    let total_transmit_rate: f64 = sample.networks.iter().map(|net| net.tx_rate).sum();
 
     // ASCII art for CPU
     let cpu_art = [
@@ -403,8 +1878,8 @@ fn display_compact_mode(resources: &Arc<Mutex<SystemResources>>, show_sensors: b
     ];
 
     // Output in neofetch style
-    let timestamp = Local::now().format("%H:%M:%S").to_string();
-    let uptime = match res.system.uptime() {
+    let timestamp = clock::time(&sample.time());
+    let uptime = match sample.uptime {
         uptime if uptime < 60 => format!("{}s", uptime),
         uptime if uptime < 3600 => format!("{}m {}s", uptime / 60, uptime % 60),
         uptime => format!("{}h {}m", uptime / 3600, (uptime % 3600) / 60),
@@ -421,247 +1896,561 @@ fn display_compact_mode(resources: &Arc<Mutex<SystemResources>>, show_sensors: b
         "red"
     };
 
+    // Size the frame and bars to the terminal instead of a fixed 47 columns
+    let width = graphs::terminal_width().clamp(24, 160);
+    let rule = "─".repeat(width - 2);
+    let frame_top = || println!("{}", theme::ascii(&format!("╭{}╮", rule)).value());
+    let frame_bottom = || println!("{}", theme::ascii(&format!("╰{}╯", rule)).value());
+    let usage_bar = |percent: f64, cells: usize| {
+        let filled = (percent.clamp(0.0, 100.0) / 100.0 * cells as f64).round() as usize;
+        format!(
+            "[{}{}]",
+            theme::ascii(&"█".repeat(filled)).critical(),
+            theme::ascii(&"░".repeat(cells - filled)).value()
+        )
+    };
+
     // Draw header
-    println!(
-        "{}",
-        "╭─────────────────────────────────────────────╮".cyan()
-    );
+    frame_top();
     println!(
         "{} {} {} {}",
-        "│".cyan(),
-        "HERCULES".bold().green(),
-        timestamp.cyan(),
-        format!("(up: {})", uptime).yellow()
+        theme::ascii("│").value(),
+        "HERCULES".bold().good(),
+        timestamp.value(),
+        format!("(up: {})", uptime).warning()
     );
-    if show_sensors {
+    if let Some(has_sensor_data) = sensors {
         println!(
             "{} {} {}",
-            "│".cyan(),
-            "🔬 SENSORS ENABLED".bold().bright_blue(),
-            if has_sensor_data {
+            theme::ascii("│").value(),
+            theme::ascii("🔬 SENSORS ENABLED").bold().highlight(),
+            theme::ascii(if has_sensor_data {
                 "📡 ACTIVE"
             } else {
                 "⚠️  NO DATA"
-            }
-            .yellow()
+            })
+            .warning()
         );
     }
-    println!(
-        "{}",
-        "╰─────────────────────────────────────────────╯".cyan()
-    );
+    frame_bottom();
 
-    // Memory bar (10 chars)
-    let mem_bar_width = 10;
-    let mem_filled = ((mem_percent as f64) / 100.0 * (mem_bar_width as f64)).round() as usize;
-    let mem_bar = format!(
-        "[{}{}]",
-        "█".repeat(mem_filled).red(),
-        "░".repeat(mem_bar_width - mem_filled).cyan()
-    );
+    // The art needs 25 columns beside the info, so narrow terminals drop it.
+    // The CPU and MEM bars get whatever is left after their "CPU: 100.0% " label.
+    let show_art = width >= ART_WIDTH + 2 + 32;
+    let info_width = if show_art {
+        width - ART_WIDTH - 2
+    } else {
+        width
+    };
+    let bar_width = info_width.saturating_sub(14).clamp(5, 40);
+    let mem_bar = usage_bar(mem_percent, bar_width);
+    let cpu_bar = usage_bar(global_cpu_usage as f64, bar_width);
+
+    let mut info = vec![
+        format!("{}@{}", "user".warning(), hostname.emphasis()),
+        format!("{}", theme::ascii(&"─".repeat(hostname.len() + 6)).value()),
+        format!("{}: {}", "OS".warning(), sample.os.emphasis()),
+        format!("{}: {}", "Kernel".warning(), sample.kernel.emphasis()),
+        format!(
+            "{}: {} {}",
+            "CPU".warning(),
+            cpu_count.to_string().emphasis(),
+            "cores".emphasis()
+        ),
+        format!(
+            "{}: {}% {}",
+            "CPU".warning(),
+            format!("{:.1}", global_cpu_usage).emphasis(),
+            cpu_bar
+        ),
+        format!(
+            "{}: {:.1}/{:.1} {}",
+            "RAM".warning(),
+            mem_scale.apply(sample.memory.used as f64),
+            mem_scale.apply(sample.memory.total as f64),
+            mem_scale.name
+        ),
+        format!(
+            "{}: {}% {}",
+            "MEM".warning(),
+            format!("{:.1}", mem_percent).emphasis(),
+            mem_bar
+        ),
+        format!(
+            "{}: {}",
+            theme::ascii("▼").good(),
+            units::format_rate(total_recv_rate)
+        ),
+        format!(
+            "{}: {}",
+            theme::ascii("▲").critical(),
+            units::format_rate(total_transmit_rate)
+        ),
+    ];
 
-    // CPU bar (10 chars)
-    let cpu_bar_width = 10;
-    let cpu_filled = ((global_cpu_usage as f64) / 100.0 * (cpu_bar_width as f64)).round() as usize;
-    let cpu_bar = format!(
-        "[{}{}]",
-        "█".repeat(cpu_filled).red(),
-        "░".repeat(cpu_bar_width - cpu_filled).cyan()
-    );
+    if verbosity::verbose() {
+        // After the CPU cores line
+        info.insert(
+            5,
+            format!(
+                "{}: {} MHz",
+                "Clock".warning(),
+                sample.cpu.average_mhz.to_string().emphasis()
+            ),
+        );
+        if let Some([one, five, fifteen]) = sample.cpu.load {
+            info.push(format!(
+                "{}: {:.2} {:.2} {:.2}",
+                "Load".warning(),
+                one,
+                five,
+                fifteen
+            ));
+        }
+    }
 
-    // Draw main content with colored CPU art
-    for (i, line) in cpu_art.iter().enumerate() {
-        let colored_line = match cpu_color {
-            "cyan" => line.cyan(),
-            "blue" => line.blue(),
-            "yellow" => line.yellow(),
-            _ => line.red(),
-        };
+    // On a Raspberry Pi the board replaces the CPU art and is named after the kernel
+    let art: &[&str] = match pi {
+        Some(board) => {
+            let mut board_lines =
+                vec![format!("{}: {}", "Model".warning(), board.model.emphasis())];
+            if let Some(ref revision) = board.revision {
+                let memory = match board.memory_mb {
+                    Some(mb) if mb >= 1024 => format!(" ({} GB)", mb / 1024),
+                    Some(mb) => format!(" ({} MB)", mb),
+                    None => String::new(),
+                };
+                board_lines.push(format!(
+                    "{}: {}{}",
+                    "Revision".warning(),
+                    revision.emphasis(),
+                    memory
+                ));
+            }
+            if let Some(ref firmware) = board.firmware {
+                board_lines.push(format!("{}: {}", "Firmware".warning(), firmware.value()));
+            }
+            if let Some(ref bootloader) = board.bootloader {
+                board_lines.push(format!(
+                    "{}: {}",
+                    "Bootloader".warning(),
+                    bootloader.value()
+                ));
+            }
+            if let Some(ref latest) = board.eeprom_update {
+                board_lines.push(format!(
+                    "{}: {}",
+                    "EEPROM Update".warning(),
+                    latest.critical()
+                ));
+            }
+            info.splice(4..4, board_lines);
+            &pi::LOGO
+        }
+        None => &cpu_art,
+    };
 
-        let info = match i {
-            0 => format!("{}@{}", "user".yellow(), hostname.bright_white()),
-            1 => format!("{}", "─".repeat(hostname.len() + 6).cyan()),
-            2 => format!("{}: {}", "OS".yellow(), os_name.bright_white()),
-            3 => format!("{}: {}", "Kernel".yellow(), kernel_version.bright_white()),
-            4 => format!(
-                "{}: {} {}",
-                "CPU".yellow(),
-                cpu_count.to_string().bright_white(),
-                "cores".bright_white()
-            ),
-            5 => format!(
-                "{}: {}% {}",
-                "CPU".yellow(),
-                format!("{:.1}", global_cpu_usage).bright_white(),
-                cpu_bar
-            ),
-            6 => format!("{}: {:.1}/{:.1} GB", "RAM".yellow(), used_gb, total_gb),
-            7 => format!(
-                "{}: {}% {}",
-                "MEM".yellow(),
-                format!("{:.1}", mem_percent).bright_white(),
-                mem_bar
-            ),
-            8 => format!("{}: {:.1} KB/s", "▼".green(), total_recv_rate / 1024.0),
-            9 => format!("{}: {:.1} KB/s", "▲".red(), total_transmit_rate / 1024.0),
-            _ => String::new(),
+    // Draw main content with colored art
+    let blank = " ".repeat(ART_WIDTH);
+    for i in 0..art.len().max(info.len()) {
+        let info_line = info.get(i).map(String::as_str).unwrap_or("");
+        if !show_art {
+            if !info_line.is_empty() {
+                println!("{}", info_line);
+            }
+            continue;
+        }
+
+        let line = theme::ascii(art.get(i).copied().unwrap_or(&blank));
+        let colored_line = match cpu_color {
+            "cyan" => line.value(),
+            "blue" => line.primary(),
+            "yellow" => line.warning(),
+            _ => line.critical(),
         };
 
-        println!("{}  {}", colored_line, info);
+        println!("{}  {}", colored_line, info_line);
     }
 
-    // Draw CPU core usage as a compact bar graph
-    println!(
-        "\n{}",
-        "╭─────────────────────────────────────────────╮".cyan()
-    );
-    println!("{} {}", "│".cyan(), "CPU Cores:".bold().yellow());
-    println!("{}", "│".cyan());
-
-    // Display CPU core usage in a compact graphical format
-    let core_bar_width = 12;
-    for i in 0..res.system.cpus().len() {
-        let cpu = &res.system.cpus()[i];
-        let usage = cpu.cpu_usage();
-        let filled = ((usage as f64) / 100.0 * (core_bar_width as f64)).round() as usize;
-        let bar = format!(
-            "[{}{}]",
-            "█".repeat(filled).red(),
-            "░".repeat(core_bar_width - filled).cyan()
+    // Draw CPU core usage as a compact bar graph, quiet mode leaves it out
+    if !verbosity::quiet() {
+        println!();
+        frame_top();
+        println!(
+            "{} {}",
+            theme::ascii("│").value(),
+            "CPU Cores:".bold().warning()
         );
-
-        if i % 2 == 0 {
-            print!("│  Core {:2}: {:5.1}% {}  ", i, usage, bar);
-        } else {
-            println!("Core {:2}: {:5.1}% {}", i, usage, bar);
+        println!("{}", theme::ascii("│").value());
+
+        // As many columns as fit with a bar of at least 8 cells, the last row may be short.
+        // Each cell is "Core NN: 100.0% [...]" followed by a 2-space gap.
+        const CORE_LABEL_WIDTH: usize = 18;
+        let usable = width.saturating_sub(3);
+        let cpus = &sample.cpu.cores;
+        let columns = (usable / (CORE_LABEL_WIDTH + 8 + 2)).clamp(1, cpus.len().max(1));
+        let core_bar_width = (usable / columns)
+            .saturating_sub(CORE_LABEL_WIDTH + 2)
+            .clamp(4, 40);
+        for (row, chunk) in cpus.chunks(columns).enumerate() {
+            let cells: Vec<String> = chunk
+                .iter()
+                .enumerate()
+                .map(|(column, cpu)| {
+                    let usage = cpu.usage;
+                    format!(
+                        "Core {:2}: {:5.1}% {}",
+                        row * columns + column,
+                        usage,
+                        usage_bar(usage as f64, core_bar_width)
+                    )
+                })
+                .collect();
+            println!("{}  {}", theme::ascii("│").value(), cells.join("  "));
         }
+        frame_bottom();
     }
-    // Make sure we end with a newline
-    if res.system.cpus().len() % 2 != 0 {
-        println!();
-    }
+}
+
+// The sensor box under the compact view
+fn print_compact_sensors(res: &SystemResources, has_sensor_data: bool) {
+    let sensor_data = res.last_sensor_data;
+    let width = graphs::terminal_width().clamp(24, 160);
+    let rule = "─".repeat(width - 2);
+    println!();
+    println!("{}", theme::ascii(&format!("╭{}╮", rule)).value());
     println!(
-        "{}",
-        "╰─────────────────────────────────────────────╯".cyan()
+        "{} {}",
+        theme::ascii("│").value(),
+        "Sensor Data:".bold().highlight()
     );
+    println!("{}", theme::ascii("│").value());
 
-    // Display sensor data in compact mode if enabled
-    if show_sensors {
+    if has_sensor_data {
+        // Compact sensor display
+        println!(
+            "{}",
+            theme::ascii(&format!(
+                "│  🚀 Accel: X:{:6.2} Y:{:6.2} Z:{:6.2} {}",
+                units::acceleration(sensor_data.acceleration[0] as f64),
+                units::acceleration(sensor_data.acceleration[1] as f64),
+                units::acceleration(sensor_data.acceleration[2] as f64),
+                units::acceleration_suffix()
+            ))
+        );
         println!(
-            "\n{}",
-            "╭─────────────────────────────────────────────╮".cyan()
+            "{}",
+            theme::ascii(&format!(
+                "│  🌀 Gyro:  X:{:6.2} Y:{:6.2} Z:{:6.2} {}",
+                units::angular_rate(sensor_data.gyro[0] as f64),
+                units::angular_rate(sensor_data.gyro[1] as f64),
+                units::angular_rate(sensor_data.gyro[2] as f64),
+                units::angular_rate_suffix()
+            ))
         );
-        println!("{} {}", "│".cyan(), "Sensor Data:".bold().bright_blue());
-        println!("{}", "│".cyan());
 
-        if has_sensor_data {
-            // Compact sensor display
-            println!(
-                "│  🚀 Accel: X:{:6.2} Y:{:6.2} Z:{:6.2} m/s²",
-                sensor_data.acceleration[0],
-                sensor_data.acceleration[1],
-                sensor_data.acceleration[2]
-            );
+        if sensor_data.orientation[0] != 0.0
+            || sensor_data.orientation[1] != 0.0
+            || sensor_data.orientation[2] != 0.0
+        {
             println!(
-                "│  🌀 Gyro:  X:{:6.1} Y:{:6.1} Z:{:6.1} °/s",
-                sensor_data.gyro[0], sensor_data.gyro[1], sensor_data.gyro[2]
-            );
-
-            if sensor_data.orientation[0] != 0.0
-                || sensor_data.orientation[1] != 0.0
-                || sensor_data.orientation[2] != 0.0
-            {
-                println!(
+                "{}",
+                theme::ascii(&format!(
                     "│  📐 Orient: R:{:5.1} P:{:5.1} Y:{:5.1} °",
                     sensor_data.orientation[0],
                     sensor_data.orientation[1],
                     sensor_data.orientation[2]
-                );
-            }
+                ))
+            );
+        }
+        if let Some([w, x, y, z]) = sensor_data.quaternion {
+            println!(
+                "{}",
+                theme::ascii(&format!(
+                    "│  🧭 Quat:  W:{:6.3} X:{:6.3} Y:{:6.3} Z:{:6.3}",
+                    w, x, y, z
+                ))
+            );
+        }
 
-            if sensor_data.temperature != 0.0 {
-                println!("│  🌡️  Temp:  {:.1}°C", sensor_data.temperature);
-            }
+        if sensor_data.temperature != 0.0 {
+            println!(
+                "{}",
+                theme::ascii(&format!(
+                    "│  🌡️  Temp:  {}",
+                    units::format_temperature(sensor_data.temperature as f64)
+                ))
+            );
+        }
 
-            // Simple orientation visualization
-            let roll_char = match sensor_data.orientation[0] {
-                r if r > 30.0 => "↗️",
-                r if r > 10.0 => "↗",
-                r if r < -30.0 => "↙️",
-                r if r < -10.0 => "↙",
-                _ => "→",
-            };
-            let pitch_char = match sensor_data.orientation[1] {
-                p if p > 30.0 => "⬆️",
-                p if p > 10.0 => "⬆",
-                p if p < -30.0 => "⬇️",
-                p if p < -10.0 => "⬇",
-                _ => "➡️",
-            };
-            println!("│  📱 Position: {} {}", roll_char, pitch_char);
+        let activity = sensor_data.activity;
+        let line = format!(
+            "│  🚶 Motion: {} ({} steps, {} impacts)",
+            activity.state.name(),
+            activity.steps,
+            activity.impacts
+        );
+        if activity.state == activity::MotionState::Impact {
+            println!("{}", theme::ascii(&line).critical());
         } else {
-            println!("│  ⚠️  No sensor data available");
-            println!("│     Check USB connection or run with --sensors");
+            println!("{}", theme::ascii(&line));
+        }
+
+        if let Some(motion) = sensor_data.motion {
+            println!(
+                "{}",
+                theme::ascii(&format!(
+                    "│  🏃 Vel:   X:{:6.2} Y:{:6.2} Z:{:6.2} m/s",
+                    motion.velocity[0], motion.velocity[1], motion.velocity[2]
+                ))
+            );
+            println!(
+                "{}",
+                theme::ascii(&format!(
+                    "│  📏 Disp:  X:{:6.3} Y:{:6.3} Z:{:6.3} m",
+                    motion.displacement[0], motion.displacement[1], motion.displacement[2]
+                ))
+            );
+            let state = if motion.at_rest {
+                "at rest".to_string()
+            } else {
+                format!("moving for {:.1} s", motion.moving_secs)
+            };
+            let line = format!(
+                "│  🎯 Confidence: {:3.0}% ({})",
+                motion.confidence * 100.0,
+                state
+            );
+            match motion.confidence {
+                c if c >= 0.6 => println!("{}", theme::ascii(&line).good()),
+                c if c >= 0.25 => println!("{}", theme::ascii(&line).warning()),
+                _ => println!("{}", theme::ascii(&line).critical()),
+            }
         }
 
+        // Wireframe of the sensor turned to its current orientation
+        let quaternion = sensor_data
+            .quaternion
+            .unwrap_or_else(|| fusion::from_euler(sensor_data.orientation));
+        println!(
+            "{}",
+            theme::ascii("│  📱 Position (top corners O, bottom +):")
+        );
+        for row in cube::render(quaternion) {
+            println!("{}     {}", theme::ascii("│").value(), row);
+        }
+        if let Some(ref manager) = res.sensor_manager {
+            let stats = manager.stats();
+            let drops = stats.drop_percent();
+            let line = format!(
+                "│  ⏱️  Rate:  {:.1} Hz of {:.1} Hz, {:.1}% drops ({} timeouts, {} dropped)",
+                stats.samples_per_sec,
+                manager.target_rate(),
+                drops,
+                stats.timeouts,
+                stats.dropped
+            );
+            if drops >= 5.0 || stats.samples_per_sec < manager.target_rate() * 0.9 {
+                println!("{}", theme::ascii(&line).warning());
+            } else {
+                println!("{}", theme::ascii(&line));
+            }
+        }
+        if verbosity::very_verbose() && sensor_data.raw_len > 0 {
+            println!(
+                "{}  Raw: {}",
+                theme::ascii("│").value(),
+                sensor_data.raw_hex()
+            );
+        }
+    } else {
+        println!("{}", theme::ascii("│  ⚠️  No sensor data available"));
         println!(
             "{}",
-            "╰─────────────────────────────────────────────╯".cyan()
+            theme::ascii("│     Check USB connection or run with --sensors")
         );
     }
 
-    Ok(())
+    println!("{}", theme::ascii(&format!("╰{}╯", rule)).value());
 }
 
 // Main function for monitoring all resources
 fn monitor_resources(
     resources: &Arc<Mutex<SystemResources>>,
     config: &MonitorConfig,
+    process_table: Option<&mut process_table::ProcessTable>,
 ) -> Result<()> {
     let res = resources
         .lock()
         .map_err(|e| anyhow!("Failed to lock resources: {}", e))?;
 
+    // The `/` search typed into the process table filters the other lists too
+    let search = process_table
+        .as_ref()
+        .map(|table| table.search.clone())
+        .unwrap_or_default();
+    if !config.show_processes {
+        if let Some(ref table) = process_table {
+            if let Some(status) = search_status(table) {
+                println!("{}", status);
+            }
+        }
+    }
+
+    let sample = record::Sample::collect(&res);
+
     if config.show_cpu {
-        monitor_cpu(&res)?;
+        monitor_cpu(Some(&res), &sample.cpu, res.history.as_ref())?;
     }
 
     if config.show_memory {
-        monitor_memory(&res)?;
+        monitor_memory(Some(&res), &sample.memory, res.history.as_ref())?;
+    }
+
+    if let Some(ref monitor) = res.fan {
+        fan::monitor_fan(monitor, res.throttle.temperature())?;
+    }
+
+    if let Some(ref monitor) = res.boost {
+        boost::monitor_boost(monitor)?;
+    }
+
+    if config.show_numa {
+        numa::monitor_numa(&res.system, config.numa_collapse_single_node)?;
     }
 
     if config.show_disk {
-        monitor_disks(&res)?;
+        monitor_disks(Some(&res), &sample.disks, &search)?;
+    }
+
+    if let Some(ref monitor) = res.disk_io {
+        diskio::monitor_disk_io(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.growth {
+        growth::monitor_growth(monitor)?;
     }
 
     if config.show_network {
-        monitor_network(&res)?;
+        monitor_network(Some(&res), &sample.networks, res.history.as_ref(), &search)?;
     }
 
-    if config.show_processes {
-        monitor_processes(&res, config.max_processes)?;
+    if let Some(ref monitor) = res.traffic {
+        traffic::monitor_traffic(monitor)?;
     }
 
-    Ok(())
-}
+    if let Some(ref monitor) = res.ping {
+        ping::monitor_ping(monitor)?;
+    }
 
-// Function to monitor and display sensor data
-#[allow(dead_code)]
-fn monitor_sensors(resources: &Arc<Mutex<SystemResources>>) -> Result<()> {
-    if let Ok(res) = resources.lock() {
-        let sensor_data = res.last_sensor_data;
+    if let Some(ref monitor) = res.connectivity {
+        connectivity::monitor_connectivity(monitor)?;
+    }
 
-        println!("{}", "\n=== Gyroscope & Accelerometer Data ===".cyan());
+    if let Some(ref monitor) = res.vpn {
+        vpn::monitor_vpn(monitor)?;
+    }
 
-        // Format and display sensor readings
-        println!(
-            "Acceleration (m/s²): X: {:.2}, Y: {:.2}, Z: {:.2}",
-            sensor_data.acceleration[0], sensor_data.acceleration[1], sensor_data.acceleration[2]
-        );
+    if let Some(ref monitor) = res.tls {
+        tls::monitor_tls(monitor)?;
+    }
 
+    if config.show_processes {
+        monitor_processes(
+            &res,
+            config.max_processes,
+            config.process_sort,
+            config.process_memory_detail,
+            process_table,
+        )?;
+    }
+
+    if let Some(ref watchdog) = res.watchdog {
+        watchdog::monitor_watchdog(watchdog)?;
+    }
+
+    if config.show_connections {
+        sockets::monitor_connections(&res.system, &config.connections_filter, &search)?;
+    }
+
+    if let Some(ref monitor) = res.listening_ports {
+        sockets::monitor_listening_ports(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.firewall {
+        firewall::monitor_firewall(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.containers {
+        containers::monitor_containers(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.fds {
+        fds::monitor_fds(monitor)?;
+    }
+
+    if let Some(ref tracker) = res.uptime {
+        uptime::monitor_uptime(tracker, &res.system)?;
+    }
+
+    if let Some(ref monitor) = res.camera {
+        camera::monitor_camera(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.usb {
+        usb::monitor_usb(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.peripherals {
+        bluetooth::monitor_peripherals(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.audio {
+        audio::monitor_audio(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.displays {
+        displays::monitor_displays(monitor)?;
+    }
+
+    if let Some(ref monitor) = res.kernel_log {
+        kmsg::monitor_kernel_log(monitor, config.kernel_log_lines)?;
+    }
+
+    if let Some(ref monitor) = res.journal {
+        journal::monitor_journal(monitor, config.journal_lines)?;
+    }
+
+    if !res.alerts.is_empty() {
+        alerts::monitor_alerts(&res.alerts)?;
+    }
+
+    Ok(())
+}
+
+// Function to monitor and display sensor data
+#[allow(dead_code)]
+fn monitor_sensors(resources: &Arc<Mutex<SystemResources>>) -> Result<()> {
+    if let Ok(res) = resources.lock() {
+        let sensor_data = res.last_sensor_data;
+
+        println!("{}", "\n=== Gyroscope & Accelerometer Data ===".value());
+
+        // Format and display sensor readings
         println!(
-            "Gyroscope (deg/s):   X: {:.2}, Y: {:.2}, Z: {:.2}",
-            sensor_data.gyro[0], sensor_data.gyro[1], sensor_data.gyro[2]
+            "Acceleration ({}): X: {:.2}, Y: {:.2}, Z: {:.2}",
+            units::acceleration_suffix(),
+            units::acceleration(sensor_data.acceleration[0] as f64),
+            units::acceleration(sensor_data.acceleration[1] as f64),
+            units::acceleration(sensor_data.acceleration[2] as f64)
+        );
+
+        println!(
+            "Gyroscope ({}):   X: {:.2}, Y: {:.2}, Z: {:.2}",
+            units::angular_rate_suffix(),
+            units::angular_rate(sensor_data.gyro[0] as f64),
+            units::angular_rate(sensor_data.gyro[1] as f64),
+            units::angular_rate(sensor_data.gyro[2] as f64)
         );
 
         if sensor_data.orientation[0] != 0.0
@@ -675,7 +2464,28 @@ fn monitor_sensors(resources: &Arc<Mutex<SystemResources>>) -> Result<()> {
         }
 
         if sensor_data.temperature != 0.0 {
-            println!("Temperature:        {:.1}°C", sensor_data.temperature);
+            println!(
+                "Temperature:        {}",
+                units::format_temperature(sensor_data.temperature as f64)
+            );
+        }
+
+        if let Some(ref rolling) = res.rolling {
+            rolling.sensor.print("Acceleration magnitude", |v| {
+                format!(
+                    "{:.2} {}",
+                    units::acceleration(v),
+                    units::acceleration_suffix()
+                )
+            });
+        }
+
+        if verbosity::very_verbose() && sensor_data.raw_len > 0 {
+            println!(
+                "Raw report ({} bytes): {}",
+                sensor_data.raw_len,
+                sensor_data.raw_hex().value()
+            );
         }
 
         // Display a visualization of the orientation
@@ -707,197 +2517,1001 @@ fn visualize_orientation(sensor_data: &sensors::SensorData) {
         "⬜" // default
     };
 
-    println!("Current orientation: {}", orientation_char);
+    println!("Current orientation: {}", theme::ascii(orientation_char));
 }
 
-// CPU monitoring function
-fn monitor_cpu(res: &SystemResources) -> Result<()> {
-    println!("\n{}", "CPU USAGE".bold().blue());
-    println!("{}", "----------".blue());
+// CPU monitoring function. `res` is None when replaying a recording, which
+// leaves out what only the live system can tell.
+fn monitor_cpu(
+    res: Option<&SystemResources>,
+    cpu: &record::CpuSample,
+    history: Option<&graphs::ResourceHistory>,
+) -> Result<()> {
+    let heading = i18n::tr("heading-cpu");
+    println!("\n{}", heading.bold().primary());
+    println!("{}", i18n::underline(heading).primary());
 
     // Global CPU info
-    let global_cpu_usage = res.system.global_cpu_info().cpu_usage();
-    println!(
-        "Global CPU Usage: {}%",
-        format!("{:.1}", global_cpu_usage).yellow()
-    );
+    if let Some(steal) = cpu.steal {
+        // Stolen time is not in the usage figure, so point it out next to it
+        println!(
+            "{}: {}% ({})",
+            i18n::tr("cpu-global-usage"),
+            format!("{:.1}", cpu.usage).warning(),
+            i18n::tr_args(
+                "cpu-stolen",
+                &[("percent", &format!("{:.1}", steal).critical())]
+            )
+        );
+    } else {
+        println!(
+            "{}: {}%",
+            i18n::tr("cpu-global-usage"),
+            format!("{:.1}", cpu.usage).warning()
+        );
+    }
+
+    let rolling = res
+        .and_then(|res| res.rolling.as_ref())
+        .filter(|_| !verbosity::quiet());
+    if let Some(rolling) = rolling {
+        rolling.cpu.print("  Rolling", |v| format!("{:.1}%", v));
+    }
+
+    if let Some(ref reason) = cpu.throttled {
+        println!(
+            "{} {}",
+            i18n::tr("cpu-throttled").critical().bold(),
+            format!("({})", reason).critical()
+        );
+    }
+
+    if let Some(res) = res {
+        monitor_cpu_details(res);
+    }
+
+    // Load average is relative to the number of cores that can share it
+    if let Some([one, five, fifteen]) = cpu.load {
+        let cores = cpu.cores.len().max(1) as f64;
+        let color_load = |value: f64| {
+            let text = format!("{:.2}", value);
+            if value / cores >= 1.0 {
+                text.critical()
+            } else if value / cores >= 0.7 {
+                text.warning()
+            } else {
+                text.good()
+            }
+        };
+        println!(
+            "{}: {} {} {} (1/5/15 min)",
+            i18n::tr("cpu-load-average"),
+            color_load(one),
+            color_load(five),
+            color_load(fifteen)
+        );
+    }
+
+    let scheduler = res
+        .map(|res| &res.scheduler)
+        .filter(|_| !verbosity::quiet());
+    if let Some((scheduler, stats)) =
+        scheduler.and_then(|scheduler| Some((scheduler, scheduler.stats()?)))
+    {
+        let blocked = format!("{}", stats.procs_blocked);
+        println!(
+            "{}: {} {}, {} {} - {}: {}/s",
+            i18n::tr("cpu-tasks"),
+            format!("{}", stats.procs_running).value(),
+            i18n::tr("cpu-tasks-running"),
+            if stats.procs_blocked > 0 {
+                blocked.warning()
+            } else {
+                blocked.value()
+            },
+            i18n::tr("cpu-tasks-blocked"),
+            i18n::tr("cpu-context-switches"),
+            format!("{:.0}", scheduler.context_switch_rate()).value()
+        );
+    }
 
     // Per-core CPU info
-    for (i, cpu) in res.system.cpus().iter().enumerate() {
+    for (i, core) in cpu.cores.iter().enumerate() {
+        if verbosity::quiet() {
+            break;
+        }
         println!(
-            "  Core #{}: {}% - {} MHz",
+            "  {} #{}: {}% - {} MHz",
+            i18n::tr("cpu-core"),
             i,
-            format!("{:.1}", cpu.cpu_usage()).yellow(),
-            format!("{:.0}", cpu.frequency()).cyan()
+            format!("{:.1}", core.usage).warning(),
+            format!("{}", core.mhz).value()
+        );
+    }
+
+    if let Some(history) = history {
+        history.print_chart(
+            i18n::tr("usage-history"),
+            &history.cpu,
+            Some(100.0),
+            Role::Primary,
+            |v| format!("{:.0}%", v),
         );
     }
 
     Ok(())
 }
 
+// Under-voltage, processor, environment and steal time lines of the live CPU panel
+fn monitor_cpu_details(res: &SystemResources) {
+    if let Some(events) = res
+        .throttle
+        .under_voltage_events()
+        .filter(|events| *events > 0)
+    {
+        println!(
+            "{}: {}",
+            i18n::tr("cpu-under-voltage"),
+            events.to_string().critical()
+        );
+    }
+
+    if verbosity::verbose() {
+        let brand = res.system.global_cpu_info().brand().trim().to_string();
+        let base = res
+            .throttle
+            .base_mhz()
+            .map(|mhz| format!(" (base {} MHz)", mhz))
+            .unwrap_or_default();
+        println!(
+            "{}: {} - {} MHz {}{}",
+            i18n::tr("cpu-processor"),
+            brand.value(),
+            res.throttle.average_mhz().to_string().value(),
+            i18n::tr("cpu-average"),
+            base
+        );
+    }
+
+    // Detection relies on Linux interfaces, elsewhere it would always say bare metal
+    if cfg!(target_os = "linux") && !verbosity::quiet() {
+        println!(
+            "{}: {}",
+            i18n::tr("cpu-environment"),
+            res.virt.describe().value()
+        );
+    }
+    if res.virt.is_virtual_machine() && !verbosity::quiet() {
+        let steal = res.scheduler.steal_percent();
+        let steal_text = format!("{:.1}", steal);
+        println!(
+            "{}: {}%",
+            i18n::tr("cpu-steal-time"),
+            if steal >= 5.0 {
+                steal_text.critical()
+            } else if steal >= 1.0 {
+                steal_text.warning()
+            } else {
+                steal_text.good()
+            }
+        );
+    }
+}
+
 // Memory monitoring function
-fn monitor_memory(res: &SystemResources) -> Result<()> {
-    println!("\n{}", "MEMORY USAGE".bold().magenta());
-    println!("{}", "------------".magenta());
+fn monitor_memory(
+    res: Option<&SystemResources>,
+    memory: &record::MemorySample,
+    history: Option<&graphs::ResourceHistory>,
+) -> Result<()> {
+    let heading = i18n::tr("heading-memory");
+    println!("\n{}", heading.bold().accent());
+    println!("{}", i18n::underline(heading).accent());
 
     // Virtual memory
-    let total_mem = res.system.total_memory();
-    let used_mem = res.system.used_memory();
-    let total_gb = total_mem as f64 / 1_073_741_824.0; // Convert to GB
-    let used_gb = used_mem as f64 / 1_073_741_824.0;
-    let percent = if total_mem > 0 {
-        (used_mem as f64 / total_mem as f64) * 100.0
-    } else {
-        0.0
-    };
+    let total_mem = memory.total;
+    let used_mem = memory.used;
+    let scale = units::Scale::of(total_mem as f64);
+    let percent = memory.percent();
 
     println!(
-        "Memory: {}/{} GB ({}% used)",
-        format!("{:.2}", used_gb).yellow(),
-        format!("{:.2}", total_gb).green(),
-        format!("{:.1}", percent).red()
+        "{}: {}/{} {} ({}% {})",
+        i18n::tr("memory-memory"),
+        format!("{:.2}", scale.apply(used_mem as f64)).warning(),
+        format!("{:.2}", scale.apply(total_mem as f64)).good(),
+        scale.name,
+        format!("{:.1}", percent).critical(),
+        i18n::tr("memory-used")
     );
+    let rolling = res
+        .and_then(|res| res.rolling.as_ref())
+        .filter(|_| !verbosity::quiet());
+    if let Some(rolling) = rolling {
+        rolling.memory.print("  Rolling", |v| format!("{:.1}%", v));
+    }
 
     // Swap memory
-    let total_swap = res.system.total_swap();
-    let used_swap = res.system.used_swap();
-    let total_swap_gb = total_swap as f64 / 1_073_741_824.0;
-    let used_swap_gb = used_swap as f64 / 1_073_741_824.0;
-    let swap_percent = if total_swap > 0 {
-        (used_swap as f64 / total_swap as f64) * 100.0
-    } else {
-        0.0
-    };
+    let total_swap = memory.swap_total;
+    let used_swap = memory.swap_used;
+    let swap_scale = units::Scale::of(total_swap as f64);
+    let swap_percent = memory.swap_percent();
 
     println!(
-        "Swap: {}/{} GB ({}% used)",
-        format!("{:.2}", used_swap_gb).yellow(),
-        format!("{:.2}", total_swap_gb).green(),
-        format!("{:.1}", swap_percent).red()
+        "{}: {}/{} {} ({}% {})",
+        i18n::tr("memory-swap"),
+        format!("{:.2}", swap_scale.apply(used_swap as f64)).warning(),
+        format!("{:.2}", swap_scale.apply(total_swap as f64)).good(),
+        swap_scale.name,
+        format!("{:.1}", swap_percent).critical(),
+        i18n::tr("memory-used")
     );
 
+    if let Some(res) = res {
+        monitor_memory_details(res);
+    }
+
+    if let Some(history) = history {
+        history.print_chart(
+            i18n::tr("usage-history"),
+            &history.memory,
+            Some(100.0),
+            Role::Accent,
+            |v| format!("{:.0}%", v),
+        );
+    }
+
     Ok(())
 }
 
+// Page cache, Pi memory split and hugepage lines of the live memory panel
+fn monitor_memory_details(res: &SystemResources) {
+    // Hugepages and THP only exist on Linux
+    if let Some(meminfo) = meminfo::MemInfo::read().filter(|_| !verbosity::quiet()) {
+        if verbosity::verbose() {
+            // /proc/meminfo counts in KiB
+            let size =
+                |key: &str| units::format_bytes(meminfo.get(key).unwrap_or(0) as f64 * 1024.0);
+            println!(
+                "Available: {} - Cached: {} - Buffers: {} - Dirty: {}",
+                size("MemAvailable").good(),
+                size("Cached").value(),
+                size("Buffers").value(),
+                size("Dirty").warning()
+            );
+        }
+
+        // On a Pi, "used" leaves out what the firmware keeps for the GPU
+        if let Some(ref board) = res.pi {
+            if let Some(split) = board.memory_split {
+                let mb = |mb: u64| units::format_bytes(mb as f64 * 1_048_576.0);
+                println!(
+                    "GPU Split: ARM {} - GPU {}",
+                    mb(split.arm_mb).good(),
+                    mb(split.gpu_mb).warning()
+                );
+            }
+            if let Some(cma) = meminfo::Cma::from_meminfo(&meminfo) {
+                let scale = units::Scale::of(cma.total_kb as f64 * 1024.0);
+                let percent = cma.used_kb() as f64 / cma.total_kb as f64 * 100.0;
+                println!(
+                    "CMA: {}/{} {} ({}% used)",
+                    format!("{:.1}", scale.apply(cma.used_kb() as f64 * 1024.0)).warning(),
+                    format!("{:.1}", scale.apply(cma.total_kb as f64 * 1024.0)).good(),
+                    scale.name,
+                    format!("{:.1}", percent).critical()
+                );
+            }
+        }
+
+        if let Some(huge) = meminfo::HugePages::from_meminfo(&meminfo) {
+            if huge.total > 0 {
+                println!(
+                    "HugePages: {}/{} used ({} reserved, {} surplus) - {} kB pages",
+                    format!("{}", huge.total - huge.free).warning(),
+                    format!("{}", huge.total).good(),
+                    huge.reserved,
+                    huge.surplus,
+                    huge.page_size_kb
+                );
+            } else {
+                println!("HugePages: {}", "none allocated".value());
+            }
+        }
+
+        if let Some(enabled) = meminfo::thp_setting("enabled") {
+            let defrag = meminfo::thp_setting("defrag").unwrap_or_else(|| "-".to_string());
+            let anon = meminfo.get("AnonHugePages").unwrap_or(0) as f64 * 1024.0;
+            println!(
+                "Transparent HugePages: {} (defrag: {}) - {} in use",
+                enabled.value(),
+                defrag,
+                units::format_bytes(anon).warning()
+            );
+        }
+    }
+}
+
 // Disk monitoring function
-fn monitor_disks(res: &SystemResources) -> Result<()> {
-    println!("\n{}", "DISK USAGE".bold().cyan());
-    println!("{}", "----------".cyan());
+fn monitor_disks(
+    res: Option<&SystemResources>,
+    disks: &[record::DiskSample],
+    search: &str,
+) -> Result<()> {
+    let heading = i18n::tr("heading-disk");
+    println!("\n{}", heading.bold().value());
+    println!("{}", i18n::underline(heading).value());
 
     // Disks from sysinfo
     println!("Disks:");
-    for disk in res.system.disks() {
-        let total_gb = disk.total_space() as f64 / 1_073_741_824.0;
-        let available_gb = disk.available_space() as f64 / 1_073_741_824.0;
-        let used_gb = total_gb - available_gb;
-        let percent = if total_gb > 0.0 {
-            (used_gb / total_gb) * 100.0
+    let mut shown = 0;
+    for disk in disks {
+        let mount_points = disk.mount_points.join(", ");
+        if !search::matches(&disk.name, search) && !search::matches(&mount_points, search) {
+            continue;
+        }
+        shown += 1;
+        let total = disk.total as f64;
+        let used = total - disk.available as f64;
+        let scale = units::Scale::of(total);
+        let percent = if total > 0.0 {
+            (used / total) * 100.0
         } else {
             0.0
         };
 
+        let file_system = if verbosity::verbose() {
+            format!(" [{}]", disk.file_system)
+        } else {
+            String::new()
+        };
         println!(
-            "  {}: {}/{} GB ({}% used) - Mount: {}",
-            disk.name().to_string_lossy().yellow(),
-            format!("{:.2}", used_gb).red(),
-            format!("{:.2}", total_gb).green(),
-            format!("{:.1}", percent).red(),
-            disk.mount_point().to_string_lossy().cyan()
+            "  {}{}: {}/{} {} ({}% used) - Mount: {}",
+            search::highlight(&disk.name, search, |text| text.warning()),
+            file_system,
+            format!("{:.2}", scale.apply(used)).critical(),
+            format!("{:.2}", scale.apply(total)).good(),
+            scale.name,
+            format!("{:.1}", percent).critical(),
+            search::highlight(&mount_points, search, |text| text.value())
         );
+
+        // Inode counts are read from the live filesystem
+        let Some(res) = res.filter(|_| !verbosity::quiet()) else {
+            continue;
+        };
+        if let Some(inodes) = disk
+            .mount_points
+            .first()
+            .and_then(|mount| disks::inode_usage(Path::new(mount)))
+        {
+            let inode_percent = format!("{:.1}", inodes.percent());
+            let inode_percent =
+                if res.inode_alert_percent > 0.0 && inodes.percent() >= res.inode_alert_percent {
+                    inode_percent.critical().bold()
+                } else {
+                    inode_percent.good()
+                };
+            println!(
+                "    Inodes: {}/{} ({}% used)",
+                inodes.used, inodes.total, inode_percent
+            );
+        }
+    }
+
+    if shown == 0 && !search.is_empty() {
+        println!("  No matching disks");
+    }
+
+    if let Some(monitor) = res.and_then(|res| res.network_mounts.as_ref()) {
+        disks::monitor_network_mounts(monitor)?;
     }
 
     Ok(())
 }
 
 // Network monitoring function
-fn monitor_network(res: &SystemResources) -> Result<()> {
-    println!("\n{}", "NETWORK USAGE".bold().green());
-    println!("{}", "-------------".green());
+fn monitor_network(
+    res: Option<&SystemResources>,
+    networks: &[record::NetSample],
+    history: Option<&graphs::ResourceHistory>,
+    search: &str,
+) -> Result<()> {
+    let heading = i18n::tr("heading-network");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
 
     // Network interfaces from sysinfo
     println!("Network Interfaces:");
 
-    let elapsed = res.last_update.elapsed().as_secs_f64();
-
-    for (interface_name, data) in res.system.networks() {
-        let received = data.received();
-        let transmitted = data.transmitted();
-
-        // Calculate rates (bytes/sec)
-        let recv_rate = if elapsed > 0.0 {
-            ((received - res.last_net_receive) as f64 / elapsed) as u64
-        } else {
-            0
-        };
-
-        let transmit_rate = if elapsed > 0.0 {
-            ((transmitted - res.last_net_transmit) as f64 / elapsed) as u64
-        } else {
-            0
-        };
+    let mut shown = 0;
+    for net in networks {
+        if !search::matches(&net.interface, search) {
+            continue;
+        }
+        shown += 1;
 
-        println!("  {}:", interface_name.yellow());
         println!(
-            "    Total Received: {} bytes",
-            format!("{}", received).cyan()
+            "  {}:",
+            search::highlight(&net.interface, search, |text| text.warning())
         );
+        if let Some(details) = res
+            .and_then(|res| res.interfaces.as_ref())
+            .and_then(|monitor| monitor.get(&net.interface))
+        {
+            display_interface_details(details);
+        }
+        if !verbosity::quiet() {
+            println!(
+                "    Total Received: {}",
+                units::format_bytes(net.received as f64).value()
+            );
+            println!(
+                "    Total Transmitted: {}",
+                units::format_bytes(net.transmitted as f64).value()
+            );
+        }
         println!(
-            "    Total Transmitted: {} bytes",
-            format!("{}", transmitted).cyan()
+            "    Receive Rate: {}",
+            units::format_rate(net.rx_rate).good()
         );
         println!(
-            "    Receive Rate: {} KB/s",
-            format!("{:.2}", recv_rate as f64 / 1024.0).green()
+            "    Transmit Rate: {}",
+            units::format_rate(net.tx_rate).good()
         );
-        println!(
-            "    Transmit Rate: {} KB/s",
-            format!("{:.2}", transmit_rate as f64 / 1024.0).green()
+
+        if verbosity::verbose() {
+            println!(
+                "    Packets: {} received, {} sent - Errors: {}",
+                net.packets_received.to_string().value(),
+                net.packets_transmitted.to_string().value(),
+                if net.errors > 0 {
+                    net.errors.to_string().critical()
+                } else {
+                    net.errors.to_string().good()
+                }
+            );
+        }
+
+        if res.is_some() {
+            if let Some(wifi) = wifi::wifi_info(&net.interface) {
+                display_wifi_info(&wifi);
+            }
+        }
+    }
+
+    if shown == 0 && !search.is_empty() {
+        println!("  No matching interfaces");
+    }
+
+    let rolling = res
+        .and_then(|res| res.rolling.as_ref())
+        .filter(|_| !verbosity::quiet());
+    if let Some(rolling) = rolling {
+        rolling.net_rx.print("Receive", units::format_rate);
+        rolling.net_tx.print("Transmit", units::format_rate);
+    }
+
+    if let Some(history) = history {
+        history.print_chart(
+            "Receive History",
+            &history.net_rx,
+            None,
+            Role::Good,
+            units::format_rate,
+        );
+        history.print_chart(
+            "Transmit History",
+            &history.net_tx,
+            None,
+            Role::Warning,
+            units::format_rate,
         );
     }
 
     Ok(())
 }
 
+// Link state, addresses, MAC, MTU and speed of one interface
+fn display_interface_details(details: &netinfo::InterfaceDetails) {
+    let state = match details.up {
+        Some(true) => "UP".good(),
+        Some(false) => "DOWN".critical(),
+        None => "UNKNOWN".value(),
+    };
+    let mut link = Vec::new();
+    if let Some(speed) = details.speed_mbps {
+        link.push(format!("{} Mb/s", speed));
+    }
+    if let Some(ref duplex) = details.duplex {
+        link.push(format!("{} duplex", duplex));
+    }
+    if let Some(mtu) = details.mtu {
+        link.push(format!("MTU {}", mtu));
+    }
+    println!(
+        "    State: {}{}{}",
+        state,
+        if details.flapped {
+            " (FLAPPED)".critical()
+        } else {
+            "".normal()
+        },
+        if link.is_empty() {
+            String::new()
+        } else {
+            format!(" - {}", link.join(", "))
+        }
+    );
+    if !details.addresses.is_empty() {
+        println!("    Addresses: {}", details.addresses.join(", ").value());
+    }
+    if let Some(mac) = details.mac.as_ref().filter(|_| !verbosity::quiet()) {
+        println!("    MAC: {}", mac.value());
+    }
+}
+
+fn display_wifi_info(wifi: &wifi::WifiInfo) {
+    if !wifi.is_connected() {
+        println!("    Wi-Fi: {}", "not connected".warning());
+        return;
+    }
+
+    println!(
+        "    SSID: {}",
+        wifi.ssid.as_deref().unwrap_or_default().value()
+    );
+    if let Some(dbm) = wifi.signal_dbm {
+        let signal = format!("{} dBm", dbm);
+        let signal = if dbm >= -60 {
+            signal.good()
+        } else if dbm >= -70 {
+            signal.warning()
+        } else {
+            signal.critical()
+        };
+        match wifi.link_quality {
+            Some(quality) => println!("    Signal: {} (Link Quality {}%)", signal, quality),
+            None => println!("    Signal: {}", signal),
+        }
+    }
+    if let Some(bitrate) = wifi.bitrate_mbps {
+        println!("    Bitrate: {} Mbit/s", format!("{:.1}", bitrate).value());
+    }
+    match (wifi.channel, wifi.frequency_mhz) {
+        (Some(channel), Some(freq)) => println!("    Channel: {} ({} MHz)", channel, freq),
+        (Some(channel), None) => println!("    Channel: {}", channel),
+        _ => {}
+    }
+}
+
 // Process monitoring function
-fn monitor_processes(res: &SystemResources, max_processes: usize) -> Result<()> {
-    println!("\n{}", "TOP PROCESSES".bold().yellow());
-    println!("{}", "-------------".yellow());
+fn monitor_processes(
+    res: &SystemResources,
+    max_processes: usize,
+    sort: config::ProcessSort,
+    memory_detail: bool,
+    mut table: Option<&mut process_table::ProcessTable>,
+) -> Result<()> {
+    let heading = i18n::tr("heading-processes");
+    println!("\n{}", heading.bold().warning());
+    println!("{}", i18n::underline(heading).warning());
 
     // Get processes from sysinfo
     let mut processes: Vec<_> = res.system.processes().iter().collect();
 
-    // Sort by CPU usage (descending)
+    // On a terminal the keyboard picks the sort, search and user filter
+    let mut sort = sort;
+    let mut ascending = false;
+    if let Some(ref mut table) = table {
+        sort = table.sort;
+        ascending = table.ascending;
+        table.page = max_processes.max(1);
+        let own_user = sysinfo::get_current_pid()
+            .ok()
+            .and_then(|pid| res.system.process(pid))
+            .and_then(|process| process.user_id().cloned());
+        processes.retain(|(_, process)| {
+            table.matches(process.name())
+                && (!table.only_mine
+                    || own_user.is_none()
+                    || process.user_id() == own_user.as_ref())
+        });
+    }
+
+    sort_processes(res, &mut processes, sort, ascending, &HashMap::new());
+
+    let rows = match table {
+        Some(ref mut table) => table.visible(processes.len()),
+        None => 0..processes.len().min(max_processes),
+    };
+    let first_row = rows.start;
+
+    // smaps_rollup is comparatively expensive, so it is only read in detailed
+    // mode and for the rows on screen, which are then ordered by PSS
+    let details = visible_memory_details(&processes[rows.clone()], memory_detail);
+    sort_processes(res, &mut processes[rows.clone()], sort, ascending, &details);
+    if let Some(ref mut table) = table {
+        table.selected_process = processes
+            .get(table.selected)
+            .map(|(pid, process)| (pid.as_u32(), process.name().to_string()));
+    }
+    let samples: Vec<_> = processes[rows.clone()]
+        .iter()
+        .map(|(pid, process)| record::ProcessSample::new(res, process, details.get(&pid.as_u32())))
+        .collect();
+    print_process_table(
+        &samples,
+        memory_detail,
+        res.process_net.is_some(),
+        table.as_deref().map(|table| (table, first_row)),
+    );
+
+    if let Some(ref table) = table {
+        print_process_table_status(table, first_row, rows.len(), processes.len());
+    }
+
+    if let Some(ref tracker) = res.process_net {
+        let search = table.as_ref().map_or("", |table| table.search.as_str());
+        print_top_connections(tracker, &res.system, search);
+    }
+
+    Ok(())
+}
+
+// PSS/USS of the given processes, keyed by PID; empty unless `enabled`
+fn visible_memory_details(
+    processes: &[(&sysinfo::Pid, &sysinfo::Process)],
+    enabled: bool,
+) -> HashMap<u32, process::MemoryDetail> {
+    if !enabled {
+        return HashMap::new();
+    }
+    processes
+        .iter()
+        .filter_map(|(pid, _)| {
+            process::memory_detail(pid.as_u32()).map(|detail| (pid.as_u32(), detail))
+        })
+        .collect()
+}
+
+// Order processes by the given column, largest first unless `ascending`.
+// The memory sort uses PSS where `details` has it and RSS otherwise.
+fn sort_processes(
+    res: &SystemResources,
+    processes: &mut [(&sysinfo::Pid, &sysinfo::Process)],
+    sort: config::ProcessSort,
+    ascending: bool,
+    details: &HashMap<u32, process::MemoryDetail>,
+) {
     processes.sort_by(|a, b| {
-        b.1.cpu_usage()
-            .partial_cmp(&a.1.cpu_usage())
-            .unwrap_or(std::cmp::Ordering::Equal)
+        let key = |process: &sysinfo::Process| -> f64 {
+            let disk = process.disk_usage();
+            match sort {
+                config::ProcessSort::Cpu => process.cpu_usage() as f64,
+                // PSS attributes shared pages fairly, so prefer it when available
+                config::ProcessSort::Memory => details
+                    .get(&process.pid().as_u32())
+                    .map(|detail| detail.pss)
+                    .unwrap_or_else(|| process.memory())
+                    as f64,
+                config::ProcessSort::DiskRead => disk.read_bytes as f64,
+                config::ProcessSort::DiskWrite => disk.written_bytes as f64,
+                config::ProcessSort::DiskTotal => (disk.read_bytes + disk.written_bytes) as f64,
+                config::ProcessSort::Network => res
+                    .process_net
+                    .as_ref()
+                    .map(|tracker| {
+                        let rate = tracker.rate_for(process.pid().as_u32());
+                        rate.rx + rate.tx
+                    })
+                    .unwrap_or(0.0),
+            }
+        };
+        let order = key(b.1)
+            .partial_cmp(&key(a.1))
+            .unwrap_or(std::cmp::Ordering::Equal);
+        if ascending {
+            order.reverse()
+        } else {
+            order
+        }
     });
+}
 
-    println!(
-        "{:<6} {:<20} {:<10} {:<10} {:<10}",
-        "PID", "NAME", "CPU%", "MEM MB", "STATUS"
-    );
+// Width of the name column when there is room, and the least it is squeezed to
+const PROCESS_NAME_WIDTH: usize = 20;
+const PROCESS_NAME_MIN_WIDTH: usize = 10;
+
+// Process table columns besides PID, NAME, CPU% and MEM, which always show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProcessColumn {
+    Read,
+    Write,
+    Pss,
+    Uss,
+    Rx,
+    Tx,
+    Status,
+}
 
-    for (i, (pid, process)) in processes.iter().enumerate() {
-        if i >= max_processes {
+impl ProcessColumn {
+    // Dropped in this order when the terminal is too narrow for all of them
+    const DROP_ORDER: [ProcessColumn; 7] = [
+        ProcessColumn::Uss,
+        ProcessColumn::Pss,
+        ProcessColumn::Tx,
+        ProcessColumn::Rx,
+        ProcessColumn::Write,
+        ProcessColumn::Read,
+        ProcessColumn::Status,
+    ];
+
+    fn title(self, kilo: &units::Scale, mega: &units::Scale) -> String {
+        match self {
+            ProcessColumn::Read => format!("READ {}/s", kilo.name),
+            ProcessColumn::Write => format!("WRITE {}/s", kilo.name),
+            ProcessColumn::Pss => format!("PSS {}", mega.name),
+            ProcessColumn::Uss => format!("USS {}", mega.name),
+            ProcessColumn::Rx => format!("RX {}/s", kilo.name),
+            ProcessColumn::Tx => format!("TX {}/s", kilo.name),
+            ProcessColumn::Status => "STATUS".to_string(),
+        }
+    }
+
+    fn sort(self) -> Option<config::ProcessSort> {
+        match self {
+            ProcessColumn::Read => Some(config::ProcessSort::DiskRead),
+            ProcessColumn::Write => Some(config::ProcessSort::DiskWrite),
+            ProcessColumn::Rx => Some(config::ProcessSort::Network),
+            _ => None,
+        }
+    }
+}
+
+// Header and rows of the process table, with as many columns as fit the
+// terminal and long names cut to what is left. With an interactive `table`,
+// whose first row is given alongside, the sorted column, search matches and
+// the selected process are marked.
+fn print_process_table(
+    rows: &[record::ProcessSample],
+    memory_detail: bool,
+    show_net: bool,
+    table: Option<(&process_table::ProcessTable, usize)>,
+) {
+    // Columns keep one unit so rows can be compared at a glance
+    let kilo = units::Scale::fixed(1);
+    let mega = units::Scale::fixed(2);
+    let cpu_title = "CPU%".to_string();
+    let mem_title = format!("MEM {}", mega.name);
+    let width = |title: &str| title.len().max(10);
+
+    let mut columns = vec![ProcessColumn::Read, ProcessColumn::Write];
+    if memory_detail {
+        columns.extend([ProcessColumn::Pss, ProcessColumn::Uss]);
+    }
+    if show_net {
+        columns.extend([ProcessColumn::Rx, ProcessColumn::Tx]);
+    }
+    columns.push(ProcessColumn::Status);
+
+    // Narrow terminals lose optional columns, but never the sorted one
+    let sort = table.map(|(table, _)| table.sort);
+    let used = |columns: &[ProcessColumn]| {
+        6 + 1
+            + 1
+            + width(&cpu_title)
+            + 1
+            + width(&mem_title)
+            + columns
+                .iter()
+                .map(|column| 1 + width(&column.title(&kilo, &mega)))
+                .sum::<usize>()
+    };
+    let terminal_width = graphs::terminal_width();
+    while used(&columns) + PROCESS_NAME_MIN_WIDTH > terminal_width {
+        let Some(dropped) = ProcessColumn::DROP_ORDER.into_iter().find(|column| {
+            columns.contains(column) && column.sort().is_none_or(|s| sort != Some(s))
+        }) else {
             break;
+        };
+        columns.retain(|&column| column != dropped);
+    }
+    let name_width = terminal_width
+        .saturating_sub(used(&columns))
+        .clamp(PROCESS_NAME_MIN_WIDTH, PROCESS_NAME_WIDTH);
+
+    // The sorted column is marked in the header while the table is interactive
+    let header = |title: &str, column_sort: Option<config::ProcessSort>| match table {
+        Some((table, _)) if column_sort == Some(table.sort) => {
+            let arrow = theme::ascii(if table.ascending { "▲" } else { "▼" }).into_owned();
+            format!("{:<1$}", format!("{}{}", title, arrow), width(title))
+                .emphasis()
+                .to_string()
+        }
+        _ => format!("{:<1$}", title, width(title)),
+    };
+    let mut cells = vec![
+        format!("{:<6}", "PID"),
+        format!("{:<1$}", "NAME", name_width),
+        header(&cpu_title, Some(config::ProcessSort::Cpu)),
+        header(&mem_title, Some(config::ProcessSort::Memory)),
+    ];
+    cells.extend(
+        columns
+            .iter()
+            .map(|column| header(&column.title(&kilo, &mega), column.sort())),
+    );
+    println!("{}", cells.join(" "));
+
+    for (row, process) in rows.iter().enumerate() {
+        let name = if process.name.chars().count() > name_width {
+            let mut short: String = process.name.chars().take(name_width - 1).collect();
+            short.push('~');
+            short
+        } else {
+            process.name.clone()
+        };
+
+        // Padded before highlighting, which adds escape codes
+        let name = format!("{:<1$}", name, name_width);
+        let name = match table {
+            Some((table, _)) => search::highlight(&name, &table.search, |text| text.normal()),
+            None => name,
+        };
+
+        // The selected process is the one renice and affinity apply to
+        let pid_cell = format!("{:<6}", process.pid);
+        let pid_cell = if table.is_some_and(|(table, first_row)| first_row + row == table.selected)
+        {
+            pid_cell.reversed()
+        } else {
+            pid_cell.normal()
+        };
+
+        let mut cells = vec![
+            pid_cell.to_string(),
+            name,
+            format!("{:<1$.1}", process.cpu, width(&cpu_title)),
+            format!(
+                "{:<1$.1}",
+                mega.apply(process.memory as f64),
+                width(&mem_title)
+            ),
+        ];
+        let [rx, tx] = process.net.unwrap_or_default();
+        for &column in &columns {
+            let width = width(&column.title(&kilo, &mega));
+            // Other users' processes need root to read smaps_rollup
+            let detail = |value: Option<u64>| match value {
+                Some(bytes) => format!("{:<1$.1}", mega.apply(bytes as f64), width),
+                None => format!("{:<1$}", "-", width),
+            };
+            cells.push(match column {
+                ProcessColumn::Read => format!("{:<1$.1}", kilo.apply(process.read_rate), width),
+                ProcessColumn::Write => format!("{:<1$.1}", kilo.apply(process.write_rate), width),
+                ProcessColumn::Pss => detail(process.pss),
+                ProcessColumn::Uss => detail(process.uss),
+                ProcessColumn::Rx => format!("{:<1$.1}", kilo.apply(rx), width),
+                ProcessColumn::Tx => format!("{:<1$.1}", kilo.apply(tx), width),
+                ProcessColumn::Status => format!("{:<1$}", process.status, width),
+            });
         }
+        println!("{}", cells.join(" "));
+    }
+}
 
-        let name = process.name();
-        let cpu_usage = process.cpu_usage();
-        let memory_usage = process.memory() as f64 / 1_048_576.0; // Convert to MB
-        let status = format!("{:?}", process.status());
+// Connections listed under the process table when capturing packets
+const TOP_CONNECTIONS: usize = 5;
 
+// Busiest connections from packet capture, or why capture is not running
+fn print_top_connections(tracker: &sockets::ProcessNetTracker, system: &System, search: &str) {
+    if let Some(error) = tracker.capture_error() {
         println!(
-            "{:<6} {:<20} {:<10.1} {:<10.1} {:<10}",
-            pid.as_u32(),
-            if name.len() > 20 { &name[0..17] } else { name },
-            cpu_usage,
-            memory_usage,
-            status
+            "{}",
+            format!("Packet capture unavailable: {}", error).warning()
         );
+        return;
+    }
+    if !tracker.capturing() || verbosity::quiet() {
+        return;
     }
 
-    Ok(())
+    println!("Top Connections:");
+    let busy: Vec<_> = tracker
+        .connections()
+        .iter()
+        .filter(|connection| connection.rate.rx + connection.rate.tx >= 1.0)
+        .map(|connection| {
+            let remote = format!("{:<40}", connection.flow.remote.to_string());
+            let owner = format!("{:<24}", sockets::owner_name(system, connection.pid));
+            (connection, remote, owner)
+        })
+        .filter(|(_, remote, owner)| {
+            search::matches(remote, search) || search::matches(owner, search)
+        })
+        .take(TOP_CONNECTIONS)
+        .collect();
+    if busy.is_empty() {
+        println!("  {}", "no traffic".value());
+    }
+    for (connection, remote, owner) in busy {
+        println!(
+            "  {:<4} {:<6} {} {} {} in, {} out",
+            connection.flow.protocol.as_str(),
+            connection.flow.local_port,
+            search::highlight(&remote, search, |text| text.normal()),
+            search::highlight(&owner, search, |text| text.normal()),
+            units::format_rate(connection.rate.rx).good(),
+            units::format_rate(connection.rate.tx).good()
+        );
+    }
+}
+
+// Position, active filters and key hints under the interactive process table
+fn print_process_table_status(
+    table: &process_table::ProcessTable,
+    first_row: usize,
+    shown: usize,
+    total: usize,
+) {
+    let position = if shown == 0 {
+        "no matching processes".to_string()
+    } else {
+        format!("{}-{} of {}", first_row + 1, first_row + shown, total)
+    };
+    let mut status = vec![position.value().to_string()];
+    status.extend(search_status(table));
+    if let Some(ref prompt) = table.prompt {
+        let label = match prompt.kind {
+            process_table::PromptKind::Nice => "nice",
+            process_table::PromptKind::Affinity => "CPUs",
+        };
+        status.push(
+            format!(
+                "{} of {} ({}): {}_",
+                label, prompt.name, prompt.pid, prompt.text
+            )
+            .highlight()
+            .to_string(),
+        );
+    }
+    if table.only_mine {
+        status.push("my processes".highlight().to_string());
+    }
+    println!("{}", status.join(" | "));
+    let hint = if let Some(ref prompt) = table.prompt {
+        match prompt.kind {
+            process_table::PromptKind::Nice => {
+                "-20 (highest priority) to 19, Enter to apply, Esc to cancel"
+            }
+            process_table::PromptKind::Affinity => "CPUs like 0-3,6, Enter to apply, Esc to cancel",
+        }
+        .to_string()
+    } else if table.searching {
+        "type to search processes, disks, interfaces and connections, Enter to keep, Esc to clear"
+            .to_string()
+    } else {
+        let keys = |action| table.keymap.keys(action);
+        format!(
+            "{} search, {} renice, {} affinity, {} help, {} quit",
+            keys(keymap::Action::Search),
+            keys(keymap::Action::Renice),
+            keys(keymap::Action::Affinity),
+            keys(keymap::Action::Help),
+            keys(keymap::Action::Quit)
+        )
+    };
+    println!("{}", theme::ascii(&hint).as_ref().dimmed());
+}
+
+// "search: ssh_" while typing, "search: ssh" once kept
+fn search_status(table: &process_table::ProcessTable) -> Option<String> {
+    if table.searching {
+        Some(format!("search: {}_", table.search).highlight().to_string())
+    } else if !table.search.is_empty() {
+        Some(format!("search: {}", table.search).highlight().to_string())
+    } else {
+        None
+    }
 }
 //funny comment
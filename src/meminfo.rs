@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+// Fields from /proc/meminfo; sizes are in kB, HugePages_* are page counts
+#[derive(Debug, Clone, Default)]
+pub struct MemInfo {
+    fields: HashMap<String, u64>,
+}
+
+impl MemInfo {
+    #[cfg(target_os = "linux")]
+    pub fn read() -> Option<Self> {
+        let content = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let fields = content
+            .lines()
+            .filter_map(|line| {
+                let (key, rest) = line.split_once(':')?;
+                let value = rest.split_whitespace().next()?.parse().ok()?;
+                Some((key.trim().to_string(), value))
+            })
+            .collect();
+        Some(MemInfo { fields })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn read() -> Option<Self> {
+        None
+    }
+
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.fields.get(key).copied()
+    }
+}
+
+// Static hugepage pool counters
+#[derive(Debug, Clone, Copy)]
+pub struct HugePages {
+    pub total: u64,
+    pub free: u64,
+    pub reserved: u64,
+    pub surplus: u64,
+    pub page_size_kb: u64,
+}
+
+impl HugePages {
+    pub fn from_meminfo(meminfo: &MemInfo) -> Option<Self> {
+        Some(HugePages {
+            total: meminfo.get("HugePages_Total")?,
+            free: meminfo.get("HugePages_Free")?,
+            reserved: meminfo.get("HugePages_Rsvd").unwrap_or(0),
+            surplus: meminfo.get("HugePages_Surp").unwrap_or(0),
+            page_size_kb: meminfo.get("Hugepagesize")?,
+        })
+    }
+}
+
+// Contiguous memory pool the kernel keeps for camera, video and display
+// buffers, in kB
+#[derive(Debug, Clone, Copy)]
+pub struct Cma {
+    pub total_kb: u64,
+    pub free_kb: u64,
+}
+
+impl Cma {
+    pub fn from_meminfo(meminfo: &MemInfo) -> Option<Self> {
+        Some(Cma {
+            total_kb: meminfo.get("CmaTotal").filter(|total| *total > 0)?,
+            free_kb: meminfo.get("CmaFree").unwrap_or(0),
+        })
+    }
+
+    pub fn used_kb(&self) -> u64 {
+        self.total_kb.saturating_sub(self.free_kb)
+    }
+}
+
+// Selected transparent hugepage mode, e.g. "madvise" from "always [madvise] never"
+#[cfg(target_os = "linux")]
+pub fn thp_setting(name: &str) -> Option<String> {
+    let path = format!("/sys/kernel/mm/transparent_hugepage/{}", name);
+    let content = std::fs::read_to_string(path).ok()?;
+    let start = content.find('[')? + 1;
+    let end = content[start..].find(']')? + start;
+    Some(content[start..end].to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn thp_setting(_name: &str) -> Option<String> {
+    None
+}
@@ -0,0 +1,298 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use sysinfo::{CpuExt, NetworkExt, SystemExt};
+
+use crate::units;
+use crate::SystemResources;
+
+// A group of readings sharing the same tags, e.g. one disk or one interface.
+// This is the common shape behind every machine-readable output.
+pub struct Measurement {
+    pub name: &'static str,
+    pub tags: Vec<(&'static str, String)>,
+    pub fields: Vec<(&'static str, f64)>,
+}
+
+impl Measurement {
+    fn new(name: &'static str) -> Self {
+        Measurement {
+            name,
+            tags: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    fn tag(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.tags.push((key, value.into()));
+        self
+    }
+
+    fn field(mut self, key: &'static str, value: f64) -> Self {
+        if value.is_finite() {
+            self.fields.push((key, value));
+        }
+        self
+    }
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total > 0 {
+        part as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+// Everything Hercules measures, flattened into measurements
+pub fn collect(res: &SystemResources) -> Vec<Measurement> {
+    let system = &res.system;
+    let mut measurements = Vec::new();
+
+    measurements.push(
+        Measurement::new("cpu")
+            .tag("cpu", "cpu-total")
+            .field("usage_percent", system.global_cpu_info().cpu_usage() as f64)
+            .field("frequency_mhz", res.throttle.average_mhz() as f64)
+            .field("throttled", res.throttle.reason().is_some() as u8 as f64),
+    );
+    for (i, cpu) in system.cpus().iter().enumerate() {
+        measurements.push(
+            Measurement::new("cpu")
+                .tag("cpu", format!("cpu{}", i))
+                .field("usage_percent", cpu.cpu_usage() as f64)
+                .field("frequency_mhz", cpu.frequency() as f64),
+        );
+    }
+
+    measurements.push(
+        Measurement::new("mem")
+            .field("total_bytes", system.total_memory() as f64)
+            .field("used_bytes", system.used_memory() as f64)
+            .field("available_bytes", system.available_memory() as f64)
+            .field(
+                "used_percent",
+                percent(system.used_memory(), system.total_memory()),
+            )
+            .field("swap_total_bytes", system.total_swap() as f64)
+            .field("swap_used_bytes", system.used_swap() as f64),
+    );
+
+    let load = system.load_average();
+    measurements.push(
+        Measurement::new("system")
+            .field("load1", load.one)
+            .field("load5", load.five)
+            .field("load15", load.fifteen)
+            .field("uptime_seconds", system.uptime() as f64)
+            .field("processes", system.processes().len() as f64),
+    );
+
+    if let Some(temp) = res.throttle.temperature() {
+        measurements.push(Measurement::new("temp").field("cpu_celsius", temp as f64));
+    }
+
+    for mount in res.disk_filter.mounts(system) {
+        let used = mount.total_space.saturating_sub(mount.available_space);
+        measurements.push(
+            Measurement::new("disk")
+                .tag("path", mount.mount_point().to_string_lossy())
+                .tag("device", mount.name.clone())
+                .field("total_bytes", mount.total_space as f64)
+                .field("used_bytes", used as f64)
+                .field("free_bytes", mount.available_space as f64)
+                .field("used_percent", percent(used, mount.total_space)),
+        );
+    }
+
+    let seconds = res.refresh_interval_secs;
+    for (interface, network) in system.networks() {
+        let rate = |bytes: u64| {
+            if seconds > 0.0 {
+                bytes as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+        measurements.push(
+            Measurement::new("net")
+                .tag("interface", interface.clone())
+                .field("rx_bytes_per_sec", rate(network.received()))
+                .field("tx_bytes_per_sec", rate(network.transmitted()))
+                .field("rx_bytes_total", network.total_received() as f64)
+                .field("tx_bytes_total", network.total_transmitted() as f64),
+        );
+    }
+
+    // IMU readings, only while a sensor is actually delivering data
+    let sensor = &res.last_sensor_data;
+    if res.sensor_manager.is_some() && (sensor.acceleration != [0.0; 3] || sensor.gyro != [0.0; 3])
+    {
+        let (accel_units, gyro_units) = units::sensor_unit_names();
+        let mut imu = Measurement::new("imu")
+            .tag("accel_units", accel_units)
+            .tag("gyro_units", gyro_units)
+            .tag("motion", sensor.activity.state.name())
+            .field(
+                "accel_x",
+                units::acceleration(sensor.acceleration[0] as f64),
+            )
+            .field(
+                "accel_y",
+                units::acceleration(sensor.acceleration[1] as f64),
+            )
+            .field(
+                "accel_z",
+                units::acceleration(sensor.acceleration[2] as f64),
+            )
+            .field("gyro_x", units::angular_rate(sensor.gyro[0] as f64))
+            .field("gyro_y", units::angular_rate(sensor.gyro[1] as f64))
+            .field("gyro_z", units::angular_rate(sensor.gyro[2] as f64))
+            .field("roll", sensor.orientation[0] as f64)
+            .field("pitch", sensor.orientation[1] as f64)
+            .field("yaw", sensor.orientation[2] as f64)
+            .field("temperature", sensor.temperature as f64)
+            .field("steps", sensor.activity.steps as f64)
+            .field("impacts", sensor.activity.impacts as f64);
+        if let Some([w, x, y, z]) = sensor.quaternion {
+            imu = imu
+                .field("quat_w", w as f64)
+                .field("quat_x", x as f64)
+                .field("quat_y", y as f64)
+                .field("quat_z", z as f64);
+        }
+        measurements.push(imu);
+    }
+
+    measurements
+}
+
+// Output formats for external collectors, selected with --format
+pub const EXEC_FORMATS: [&str; 2] = ["influx-exec", "collectd-exec"];
+
+pub fn print_exec(format: &str, res: &SystemResources) -> Result<()> {
+    let measurements = collect(res);
+    let output = match format {
+        "influx-exec" => influx_lines(&measurements),
+        "collectd-exec" => {
+            // collectd passes these to exec plugins
+            let hostname = std::env::var("COLLECTD_HOSTNAME")
+                .ok()
+                .or_else(|| res.system.host_name())
+                .unwrap_or_else(|| "localhost".to_string());
+            let interval = std::env::var("COLLECTD_INTERVAL")
+                .ok()
+                .and_then(|value| value.parse::<f64>().ok());
+            collectd_putvals(&measurements, &hostname, interval)
+        }
+        _ => return Err(anyhow!("Unknown output format '{}'", format)),
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+// InfluxDB line protocol, as read by Telegraf's exec input with data_format = "influx":
+// measurement,tag=value field=1.5,other=2 1700000000000000000
+fn influx_lines(measurements: &[Measurement]) -> String {
+    let escape = |text: &str| {
+        text.replace('\\', "\\\\")
+            .replace(',', "\\,")
+            .replace('=', "\\=")
+            .replace(' ', "\\ ")
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+
+    let mut out = String::new();
+    for measurement in measurements.iter().filter(|m| !m.fields.is_empty()) {
+        out.push_str(measurement.name);
+        for (key, value) in &measurement.tags {
+            if !value.is_empty() {
+                out.push_str(&format!(",{}={}", key, escape(value)));
+            }
+        }
+        let fields = measurement
+            .fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(",");
+        out.push_str(&format!(" {} {}\n", fields, timestamp));
+    }
+    out
+}
+
+// collectd exec plugin commands, one gauge per field:
+// PUTVAL "host/hercules-disk-root/gauge-used_percent" interval=10 N:42.5
+fn collectd_putvals(measurements: &[Measurement], hostname: &str, interval: Option<f64>) -> String {
+    // Identifier parts may not contain slashes, and "/" itself becomes "root"
+    let sanitize = |text: &str| {
+        let trimmed = text.trim_matches('/');
+        if trimmed.is_empty() {
+            "root".to_string()
+        } else {
+            trimmed.replace(['/', ' ', '"'], "_")
+        }
+    };
+    let options = interval
+        .map(|seconds| format!(" interval={}", seconds))
+        .unwrap_or_default();
+
+    let mut out = String::new();
+    for measurement in measurements {
+        let mut instance = measurement.name.to_string();
+        // The first tag identifies the instance, e.g. the mount point or interface
+        if let Some((_, value)) = measurement.tags.first() {
+            instance.push('-');
+            instance.push_str(&sanitize(value));
+        }
+        for (key, value) in &measurement.fields {
+            out.push_str(&format!(
+                "PUTVAL \"{}/hercules-{}/gauge-{}\"{} N:{}\n",
+                sanitize(hostname),
+                instance,
+                key,
+                options,
+                value
+            ));
+        }
+    }
+    out
+}
+
+// One JSON document with every measurement, for pasting into a ticket:
+// {"host": ..., "timestamp": ..., "measurements": [{"name", "tags", "fields"}]}
+pub fn json(res: &SystemResources) -> String {
+    let measurements: Vec<serde_json::Value> = collect(res)
+        .iter()
+        .map(|measurement| {
+            let tags: serde_json::Map<String, serde_json::Value> = measurement
+                .tags
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone().into()))
+                .collect();
+            let fields: serde_json::Map<String, serde_json::Value> = measurement
+                .fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), (*value).into()))
+                .collect();
+            serde_json::json!({
+                "name": measurement.name,
+                "tags": tags,
+                "fields": fields,
+            })
+        })
+        .collect();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let document = serde_json::json!({
+        "host": res.system.host_name().unwrap_or_default(),
+        "timestamp": timestamp,
+        "measurements": measurements,
+    });
+    serde_json::to_string_pretty(&document).unwrap_or_default()
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use sysinfo::{NetworkExt, System, SystemExt};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+
+// Link details for one interface; fields are None where the platform does not report them
+#[derive(Debug, Clone, Default)]
+pub struct InterfaceDetails {
+    pub mac: Option<String>,
+    // Addresses in CIDR notation, e.g. 192.168.1.20/24
+    pub addresses: Vec<String>,
+    pub mtu: Option<u32>,
+    pub speed_mbps: Option<u32>,
+    pub duplex: Option<String>,
+    pub up: Option<bool>,
+    // Link went down and/or up since the previous refresh
+    pub flapped: bool,
+    carrier_changes: Option<u64>,
+}
+
+// Interface addresses and link state, refreshed with the rest of the monitor
+pub struct InterfaceMonitor {
+    interfaces: HashMap<String, InterfaceDetails>,
+}
+
+impl InterfaceMonitor {
+    pub fn new() -> Self {
+        InterfaceMonitor {
+            interfaces: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, interface: &str) -> Option<&InterfaceDetails> {
+        self.interfaces.get(interface)
+    }
+
+    pub fn refresh(&mut self, system: &System, alerts: &mut AlertEngine) {
+        let mut addresses = interface_addresses();
+        let mut interfaces = HashMap::new();
+        for (name, data) in system.networks() {
+            let mac = data.mac_address();
+            let mut details = link_details(name);
+            details.mac = (!mac.is_unspecified()).then(|| mac.to_string());
+            details.addresses = addresses.remove(name).unwrap_or_default();
+
+            // The kernel counts carrier changes, which also catches a flap
+            // that went down and back up between two refreshes
+            if let Some(previous) = self.interfaces.get(name) {
+                details.flapped = match (previous.carrier_changes, details.carrier_changes) {
+                    (Some(before), Some(now)) => now != before,
+                    _ => previous.up.is_some() && previous.up != details.up,
+                };
+            }
+
+            let key = format!("link:{}", name);
+            if details.flapped {
+                alerts.raise(
+                    &key,
+                    AlertLevel::Warning,
+                    "network",
+                    format!(
+                        "{} link changed state, now {}",
+                        name,
+                        if details.up == Some(false) {
+                            "down"
+                        } else {
+                            "up"
+                        }
+                    ),
+                );
+            } else {
+                alerts.clear(&key);
+            }
+            interfaces.insert(name.clone(), details);
+        }
+        self.interfaces = interfaces;
+    }
+}
+
+// MTU, speed, duplex and state from /sys/class/net/<interface>
+#[cfg(target_os = "linux")]
+fn link_details(interface: &str) -> InterfaceDetails {
+    let base = std::path::Path::new("/sys/class/net").join(interface);
+    let read = |file: &str| {
+        std::fs::read_to_string(base.join(file))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+
+    InterfaceDetails {
+        mtu: read("mtu").and_then(|value| value.parse().ok()),
+        // -1 or an error while the link is down or for virtual interfaces
+        speed_mbps: read("speed")
+            .and_then(|value| value.parse::<i64>().ok())
+            .filter(|speed| *speed > 0 && *speed < u32::MAX as i64)
+            .map(|speed| speed as u32),
+        duplex: read("duplex").filter(|duplex| duplex != "unknown"),
+        // "unknown" is common for loopback and tunnels; carrier tells those apart
+        up: match read("operstate").as_deref() {
+            Some("up") => Some(true),
+            Some("down") | Some("lowerlayerdown") | Some("notpresent") => Some(false),
+            _ => read("carrier").map(|carrier| carrier == "1"),
+        },
+        carrier_changes: read("carrier_changes").and_then(|value| value.parse().ok()),
+        ..InterfaceDetails::default()
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn link_details(_interface: &str) -> InterfaceDetails {
+    InterfaceDetails::default()
+}
+
+// IPv4 and IPv6 addresses per interface
+#[cfg(target_os = "linux")]
+fn interface_addresses() -> HashMap<String, Vec<String>> {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    let mut addresses: HashMap<String, Vec<String>> = HashMap::new();
+    let Ok(interfaces) = nix::ifaddrs::getifaddrs() else {
+        return addresses;
+    };
+    for interface in interfaces {
+        let Some(address) = interface.address else {
+            continue;
+        };
+        let netmask = interface.netmask;
+        let (ip, prefix) = if let Some(v4) = address.as_sockaddr_in() {
+            let prefix = netmask
+                .as_ref()
+                .and_then(|mask| mask.as_sockaddr_in())
+                .map(|mask| mask.ip().count_ones());
+            (IpAddr::V4(Ipv4Addr::from(v4.ip())), prefix)
+        } else if let Some(v6) = address.as_sockaddr_in6() {
+            let prefix = netmask
+                .as_ref()
+                .and_then(|mask| mask.as_sockaddr_in6())
+                .map(|mask| u128::from(mask.ip()).count_ones());
+            (IpAddr::V6(v6.ip()), prefix)
+        } else {
+            continue;
+        };
+        let text = match prefix {
+            Some(prefix) => format!("{}/{}", ip, prefix),
+            None => ip.to_string(),
+        };
+        addresses
+            .entry(interface.interface_name)
+            .or_default()
+            .push(text);
+    }
+    addresses
+}
+
+#[cfg(not(target_os = "linux"))]
+fn interface_addresses() -> HashMap<String, Vec<String>> {
+    HashMap::new()
+}
+
+// IPv4 address and prefix length of every interface that is up, loopback excluded
+#[cfg(target_os = "linux")]
+pub fn ipv4_networks() -> Vec<(String, std::net::Ipv4Addr, u32)> {
+    use nix::net::if_::InterfaceFlags;
+
+    let Ok(interfaces) = nix::ifaddrs::getifaddrs() else {
+        return Vec::new();
+    };
+    interfaces
+        .filter(|interface| {
+            interface.flags.contains(InterfaceFlags::IFF_UP)
+                && !interface.flags.contains(InterfaceFlags::IFF_LOOPBACK)
+        })
+        .filter_map(|interface| {
+            let address = interface.address?.as_sockaddr_in()?.ip();
+            let prefix = interface.netmask?.as_sockaddr_in()?.ip().count_ones();
+            Some((
+                interface.interface_name,
+                std::net::Ipv4Addr::from(address),
+                prefix,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn ipv4_networks() -> Vec<(String, std::net::Ipv4Addr, u32)> {
+    Vec::new()
+}
+
+// Every address assigned to this host, loopback included
+#[cfg(target_os = "linux")]
+pub fn local_addresses() -> std::collections::HashSet<std::net::IpAddr> {
+    let Ok(interfaces) = nix::ifaddrs::getifaddrs() else {
+        return Default::default();
+    };
+    interfaces
+        .filter_map(|interface| {
+            let address = interface.address?;
+            if let Some(v4) = address.as_sockaddr_in() {
+                Some(std::net::IpAddr::V4(std::net::Ipv4Addr::from(v4.ip())))
+            } else {
+                address
+                    .as_sockaddr_in6()
+                    .map(|v6| std::net::IpAddr::V6(v6.ip()))
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn local_addresses() -> std::collections::HashSet<std::net::IpAddr> {
+    Default::default()
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use colored::*;
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::graphs;
+use crate::i18n;
+use crate::theme::Themed;
+use crate::units;
+
+// Memory and CPUs belonging to one NUMA node
+#[derive(Debug, Clone)]
+pub struct NumaNode {
+    pub id: usize,
+    pub cpus: Vec<usize>,
+    pub mem_total_kb: u64,
+    pub mem_free_kb: u64,
+}
+
+impl NumaNode {
+    pub fn mem_used_kb(&self) -> u64 {
+        self.mem_total_kb.saturating_sub(self.mem_free_kb)
+    }
+}
+
+// NUMA topology from /sys/devices/system/node; empty when unavailable
+#[cfg(target_os = "linux")]
+pub fn numa_nodes() -> Vec<NumaNode> {
+    let Ok(entries) = std::fs::read_dir("/sys/devices/system/node") else {
+        return Vec::new();
+    };
+
+    let mut nodes: Vec<NumaNode> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let id = name.strip_prefix("node")?.parse().ok()?;
+            read_node(id, &entry.path())
+        })
+        .collect();
+    nodes.sort_by_key(|node| node.id);
+    nodes
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn numa_nodes() -> Vec<NumaNode> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn read_node(id: usize, path: &std::path::Path) -> Option<NumaNode> {
+    let cpulist = std::fs::read_to_string(path.join("cpulist")).unwrap_or_default();
+    let meminfo = std::fs::read_to_string(path.join("meminfo")).ok()?;
+
+    // Lines look like "Node 0 MemTotal:       16314424 kB"
+    let field = |key: &str| {
+        meminfo.lines().find_map(|line| {
+            let (_, rest) = line.split_once(key)?;
+            rest.split_whitespace().next()?.parse::<u64>().ok()
+        })
+    };
+
+    Some(NumaNode {
+        id,
+        cpus: parse_cpu_list(&cpulist),
+        mem_total_kb: field("MemTotal:")?,
+        mem_free_kb: field("MemFree:").unwrap_or(0),
+    })
+}
+
+// Expand a kernel cpu list such as "0-3,8-11"
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter(|part| !part.is_empty())
+        .flat_map(|part| match part.split_once('-') {
+            Some((start, end)) => {
+                let start = start.parse().unwrap_or(0);
+                let end = end.parse().unwrap_or(start);
+                (start..=end).collect::<Vec<_>>()
+            }
+            None => part.parse().ok().into_iter().collect(),
+        })
+        .collect()
+}
+
+// NUMA panel: memory and CPU load per node. A single node only repeats the
+// memory and CPU panels, so it collapses to one line when asked to.
+pub fn monitor_numa(system: &System, collapse_single_node: bool) -> Result<()> {
+    let nodes = numa_nodes();
+
+    let heading = i18n::tr("heading-numa");
+    println!("\n{}", heading.bold().accent());
+    println!("{}", i18n::underline(heading).accent());
+
+    if nodes.is_empty() {
+        println!("  No NUMA topology information available");
+        return Ok(());
+    }
+    if nodes.len() == 1 && collapse_single_node {
+        println!("  Single NUMA node ({} CPUs)", nodes[0].cpus.len());
+        return Ok(());
+    }
+
+    let cpus = system.cpus();
+    for node in &nodes {
+        let usages: Vec<f32> = node
+            .cpus
+            .iter()
+            .filter_map(|&cpu| cpus.get(cpu).map(|cpu| cpu.cpu_usage()))
+            .collect();
+        let cpu_usage = if usages.is_empty() {
+            0.0
+        } else {
+            usages.iter().sum::<f32>() as f64 / usages.len() as f64
+        };
+        let (used, total) = (
+            node.mem_used_kb() as f64 * 1024.0,
+            node.mem_total_kb as f64 * 1024.0,
+        );
+        let scale = units::Scale::of(total);
+        let mem_percent = if node.mem_total_kb > 0 {
+            node.mem_used_kb() as f64 / node.mem_total_kb as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        println!(
+            "  Node {}: Memory {}/{} {} ({}%) {}",
+            node.id,
+            format!("{:.2}", scale.apply(used)).critical(),
+            format!("{:.2}", scale.apply(total)).good(),
+            scale.name,
+            format!("{:.1}", mem_percent).warning(),
+            graphs::bar(mem_percent, 20).accent()
+        );
+        println!(
+            "          CPU {}% across {} CPUs {}",
+            format!("{:.1}", cpu_usage).warning(),
+            node.cpus.len(),
+            graphs::bar(cpu_usage, 20).primary()
+        );
+    }
+
+    Ok(())
+}
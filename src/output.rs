@@ -0,0 +1,200 @@
+use std::net::Ipv4Addr;
+
+use anyhow::{anyhow, Result};
+use log::debug;
+use sysinfo::{CpuExt, NetworkExt, SystemExt};
+
+use crate::lcd1602::Lcd1602;
+use crate::netinfo;
+use crate::process::format_duration;
+use crate::ssd1306::Ssd1306;
+use crate::units;
+use crate::{MonitorConfig, SystemResources};
+
+// Backends for display_backend, besides the terminal
+pub const BACKENDS: [&str; 3] = ["none", "ssd1306", "lcd1602"];
+
+pub fn validate_backend(name: &str) -> Result<()> {
+    if BACKENDS.contains(&name) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Unknown display backend '{}'. Use: {}",
+            name,
+            BACKENDS.join(", ")
+        ))
+    }
+}
+
+// The few numbers that fit on a small attached display
+#[derive(Debug, Clone)]
+pub struct Stats {
+    pub hostname: String,
+    pub cpu_percent: f32,
+    pub memory_percent: f64,
+    pub temperature: Option<f32>,
+    pub address: Option<Ipv4Addr>,
+    pub load_one: f64,
+    pub uptime_secs: u64,
+    // Usage of the root filesystem
+    pub disk_percent: Option<f64>,
+    // Bytes per second over all interfaces but loopback
+    pub receive_rate: f64,
+    pub transmit_rate: f64,
+}
+
+impl Stats {
+    pub fn collect(res: &SystemResources) -> Self {
+        let system = &res.system;
+        let memory_percent = if system.total_memory() == 0 {
+            0.0
+        } else {
+            system.used_memory() as f64 / system.total_memory() as f64 * 100.0
+        };
+        let disk_percent = res
+            .disk_filter
+            .mounts(system)
+            .into_iter()
+            .find(|mount| mount.mount_point() == std::path::Path::new("/"))
+            .filter(|mount| mount.total_space > 0)
+            .map(|mount| {
+                let used = mount.total_space.saturating_sub(mount.available_space);
+                used as f64 / mount.total_space as f64 * 100.0
+            });
+        let seconds = res.refresh_interval_secs;
+        let rate = |bytes: u64| {
+            if seconds > 0.0 {
+                bytes as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+        let (received, transmitted) = system
+            .networks()
+            .into_iter()
+            .filter(|(name, _)| name.as_str() != "lo")
+            .fold((0, 0), |(rx, tx), (_, network)| {
+                (rx + network.received(), tx + network.transmitted())
+            });
+        Stats {
+            hostname: system.host_name().unwrap_or_default(),
+            cpu_percent: system.global_cpu_info().cpu_usage(),
+            memory_percent,
+            temperature: res.throttle.temperature(),
+            address: netinfo::ipv4_networks()
+                .into_iter()
+                .map(|(_, address, _)| address)
+                .next(),
+            load_one: system.load_average().one,
+            uptime_secs: system.uptime(),
+            disk_percent,
+            receive_rate: rate(received),
+            transmit_rate: rate(transmitted),
+        }
+    }
+}
+
+// One screen of a character display: two lines of up to 16 characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Page {
+    Cpu,
+    Memory,
+    Temperature,
+    Network,
+    Traffic,
+    Uptime,
+}
+
+impl Page {
+    pub const NAMES: [&'static str; 6] = [
+        "cpu",
+        "memory",
+        "temperature",
+        "network",
+        "traffic",
+        "uptime",
+    ];
+
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "cpu" => Ok(Page::Cpu),
+            "memory" | "mem" => Ok(Page::Memory),
+            "temperature" | "temp" => Ok(Page::Temperature),
+            "network" | "ip" => Ok(Page::Network),
+            "traffic" => Ok(Page::Traffic),
+            "uptime" => Ok(Page::Uptime),
+            _ => Err(anyhow!(
+                "Unknown display page '{}'. Use: {}",
+                name,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+
+    pub fn lines(self, stats: &Stats) -> [String; 2] {
+        match self {
+            Page::Cpu => [
+                format!("CPU  {:5.1}%", stats.cpu_percent),
+                format!("Load {:.2}", stats.load_one),
+            ],
+            Page::Memory => [
+                format!("Mem  {:5.1}%", stats.memory_percent),
+                match stats.disk_percent {
+                    Some(percent) => format!("Disk {:5.1}%", percent),
+                    None => String::new(),
+                },
+            ],
+            Page::Temperature => [
+                "CPU Temperature".to_string(),
+                stats
+                    .temperature
+                    .map(|temp| units::format_temperature(temp as f64))
+                    .unwrap_or_else(|| "no sensor".to_string()),
+            ],
+            Page::Network => [
+                stats.hostname.clone(),
+                stats
+                    .address
+                    .map(|address| address.to_string())
+                    .unwrap_or_else(|| "no network".to_string()),
+            ],
+            Page::Traffic => [
+                format!("RX {}", units::format_rate(stats.receive_rate)),
+                format!("TX {}", units::format_rate(stats.transmit_rate)),
+            ],
+            Page::Uptime => ["Uptime".to_string(), format_duration(stats.uptime_secs)],
+        }
+    }
+}
+
+// "cpu,memory,network"
+pub fn parse_pages(names: &[String]) -> Result<Vec<Page>> {
+    names.iter().map(|name| Page::parse(name)).collect()
+}
+
+// A display that shows Stats on every refresh
+pub trait DisplayOutput: Send {
+    fn show(&mut self, stats: &Stats) -> Result<()>;
+}
+
+// The display set by display_backend, None for "none"
+pub fn open(config: &MonitorConfig) -> Result<Option<Box<dyn DisplayOutput>>> {
+    let bus = config.display_i2c_bus;
+    let address = config.display_i2c_address;
+    let output: Box<dyn DisplayOutput> = match config.display_backend.as_str() {
+        "none" | "" => return Ok(None),
+        "ssd1306" => Box::new(Ssd1306::open(bus, address)?),
+        "lcd1602" => Box::new(Lcd1602::open(
+            bus,
+            address,
+            parse_pages(&config.display_pages)?,
+            config.display_page_secs,
+        )?),
+        backend => return Err(anyhow!("Unknown display backend '{}'", backend)),
+    };
+    debug!(
+        "Display output: {} on I2C bus {}",
+        config.display_backend, bus
+    );
+    Ok(Some(output))
+}
@@ -0,0 +1,170 @@
+// Raspberry Pi board identification
+#[derive(Debug, Clone)]
+pub struct PiInfo {
+    // e.g. "Raspberry Pi 4 Model B Rev 1.4"
+    pub model: String,
+    // Board revision code from /proc/cpuinfo, e.g. "c03114"
+    pub revision: Option<String>,
+    pub memory_mb: Option<u64>,
+    // Firmware split between the ARM cores and the GPU
+    pub memory_split: Option<MemorySplit>,
+    // VideoCore firmware build date, e.g. "Mar 17 2023 10:50:39"
+    pub firmware: Option<String>,
+    // Bootloader EEPROM build date, Pi 4 and later only
+    pub bootloader: Option<String>,
+    // Newer bootloader release reported by rpi-eeprom-update, when checked
+    pub eeprom_update: Option<String>,
+}
+
+// Memory the firmware hands to the ARM cores and reserves for the GPU, in MB
+#[derive(Debug, Clone, Copy)]
+pub struct MemorySplit {
+    pub arm_mb: u64,
+    pub gpu_mb: u64,
+}
+
+// `check_eeprom_update` also runs rpi-eeprom-update, which takes a moment
+#[cfg(target_os = "linux")]
+pub fn detect(check_eeprom_update: bool) -> Option<PiInfo> {
+    let (model, revision) = board_identity()?;
+    let memory_mb = revision.as_deref().and_then(revision_memory_mb);
+
+    Some(PiInfo {
+        model,
+        revision,
+        memory_mb,
+        memory_split: memory_split(),
+        firmware: vcgencmd_first_line(&["version"]),
+        bootloader: vcgencmd_first_line(&["bootloader_version"]),
+        eeprom_update: if check_eeprom_update {
+            eeprom_update()
+        } else {
+            None
+        },
+    })
+}
+
+// Both `vcgencmd version` and `vcgencmd bootloader_version` start with the
+// build date, followed by the git hash and build details
+#[cfg(target_os = "linux")]
+fn vcgencmd_first_line(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("vcgencmd")
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let line = text.lines().next()?.trim();
+    // Boards without a bootloader EEPROM answer with an error
+    if line.is_empty() || line.contains("error") {
+        return None;
+    }
+    Some(line.to_string())
+}
+
+// rpi-eeprom-update exits with 1 when an update is available and lists
+// "LATEST: Wed 11 Jan 17:40:52 UTC 2023 (1673458852)" for the bootloader
+#[cfg(target_os = "linux")]
+fn eeprom_update() -> Option<String> {
+    let output = std::process::Command::new("rpi-eeprom-update")
+        .output()
+        .ok()?;
+    if output.status.code() != Some(1) {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let latest = text
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("LATEST:"))
+        .map(|latest| {
+            // Drop the epoch seconds in parentheses
+            latest
+                .split(" (")
+                .next()
+                .unwrap_or(latest)
+                .trim()
+                .to_string()
+        });
+    Some(latest.unwrap_or_else(|| "newer release".to_string()))
+}
+
+// Device tree model and the revision code from /proc/cpuinfo, e.g.
+// "c03114", None off a Pi
+#[cfg(target_os = "linux")]
+pub fn board_identity() -> Option<(String, Option<String>)> {
+    // The device tree string is NUL terminated
+    let model = std::fs::read_to_string("/proc/device-tree/model").ok()?;
+    let model = model.trim_end_matches('\0').trim().to_string();
+    if !model.starts_with("Raspberry Pi") {
+        return None;
+    }
+
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let revision = cpuinfo
+        .lines()
+        .find(|line| line.starts_with("Revision"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, value)| value.trim().to_string());
+    Some((model, revision))
+}
+
+// The split is fixed at boot, so it is read once with `vcgencmd get_mem`
+#[cfg(target_os = "linux")]
+fn memory_split() -> Option<MemorySplit> {
+    let get_mem = |part: &str| -> Option<u64> {
+        let output = std::process::Command::new("vcgencmd")
+            .args(["get_mem", part])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // "arm=948M"
+        let text = String::from_utf8_lossy(&output.stdout);
+        text.trim()
+            .strip_prefix(part)?
+            .strip_prefix('=')?
+            .strip_suffix('M')?
+            .parse()
+            .ok()
+    };
+    Some(MemorySplit {
+        arm_mb: get_mem("arm")?,
+        gpu_mb: get_mem("gpu")?,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn board_identity() -> Option<(String, Option<String>)> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn detect(_check_eeprom_update: bool) -> Option<PiInfo> {
+    None
+}
+
+// New-style revision codes (bit 23 set) encode the RAM size in bits 20-22
+fn revision_memory_mb(revision: &str) -> Option<u64> {
+    let code = u32::from_str_radix(revision, 16).ok()?;
+    if code & (1 << 23) == 0 {
+        return None;
+    }
+    Some(256 << ((code >> 20) & 0x7))
+}
+
+// Logo for compact mode, same footprint as the CPU art
+pub const LOGO: [&str; 10] = [
+    r"     .~~.   .~~.       ",
+    r"    '. \ ' ' / .'      ",
+    r"     .~ .~~~..~.       ",
+    r"    : .~.'~'.~. :      ",
+    r"   ~ (   ) (   ) ~     ",
+    r"  ( : '~'.~.'~' : )    ",
+    r"   ~ .~ (   ) ~. ~     ",
+    r"    (  : '~' :  )      ",
+    r"     '~ .~~~. ~'       ",
+    r"         '~'           ",
+];
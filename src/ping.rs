@@ -0,0 +1,218 @@
+use std::collections::VecDeque;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+use colored::*;
+use log::{debug, info};
+
+use crate::graphs;
+use crate::i18n;
+use crate::theme::Themed;
+
+// Number of probes kept per target for loss and sparkline calculations
+const PING_HISTORY_LEN: usize = 60;
+
+// Rolling probe results for one target; None marks a lost probe
+#[derive(Debug, Clone, Default)]
+pub struct PingStats {
+    pub target: String,
+    pub history: VecDeque<Option<f64>>,
+}
+
+impl PingStats {
+    pub fn last(&self) -> Option<f64> {
+        self.history.back().copied().flatten()
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        let replies: Vec<f64> = self.history.iter().flatten().copied().collect();
+        if replies.is_empty() {
+            None
+        } else {
+            Some(replies.iter().sum::<f64>() / replies.len() as f64)
+        }
+    }
+
+    pub fn loss_percent(&self) -> f64 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        let lost = self.history.iter().filter(|probe| probe.is_none()).count();
+        lost as f64 / self.history.len() as f64 * 100.0
+    }
+}
+
+// Pings each configured target from its own background thread
+pub struct PingMonitor {
+    stats: Vec<Arc<Mutex<PingStats>>>,
+}
+
+impl PingMonitor {
+    pub fn start(targets: &[String], interval_ms: u64) -> Self {
+        // Probing faster than once a second only adds noise
+        let interval = Duration::from_millis(interval_ms.max(1000));
+        let mut stats = Vec::new();
+
+        for target in targets {
+            let shared = Arc::new(Mutex::new(PingStats {
+                target: target.clone(),
+                history: VecDeque::with_capacity(PING_HISTORY_LEN),
+            }));
+            // The thread only holds a weak reference, so it ends once the
+            // monitor is dropped, e.g. when a config reload replaces it
+            let weak = Arc::downgrade(&shared);
+            stats.push(shared);
+
+            let target = target.clone();
+            thread::spawn(move || {
+                info!("Starting ping monitor for {}", target);
+                loop {
+                    let host = resolve_target(&target);
+                    let result = host.as_deref().and_then(ping_once);
+
+                    let Some(shared) = weak.upgrade() else {
+                        debug!("Stopping ping monitor for {}", target);
+                        return;
+                    };
+                    if let Ok(mut stats) = shared.lock() {
+                        if stats.history.len() == PING_HISTORY_LEN {
+                            stats.history.pop_front();
+                        }
+                        stats.history.push_back(result);
+                    }
+                    drop(shared);
+
+                    thread::sleep(interval);
+                }
+            });
+        }
+
+        PingMonitor { stats }
+    }
+
+    pub fn snapshot(&self) -> Vec<PingStats> {
+        self.stats
+            .iter()
+            .filter_map(|stats| stats.lock().ok().map(|stats| stats.clone()))
+            .collect()
+    }
+}
+
+// "gateway" is a shorthand for the current default route
+fn resolve_target(target: &str) -> Option<String> {
+    if target.eq_ignore_ascii_case("gateway") {
+        default_gateway()
+    } else {
+        Some(target.to_string())
+    }
+}
+
+// Default IPv4 gateway from the kernel routing table
+#[cfg(target_os = "linux")]
+pub fn default_gateway() -> Option<String> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    routes.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() > 2 && fields[1] == "00000000" {
+            let gateway = u32::from_str_radix(fields[2], 16).ok()?;
+            Some(std::net::Ipv4Addr::from(gateway.to_le_bytes()).to_string())
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn default_gateway() -> Option<String> {
+    None
+}
+
+// Send a single echo request with the system ping tool and return the RTT in ms
+fn ping_once(host: &str) -> Option<f64> {
+    let output = if cfg!(windows) {
+        Command::new("ping")
+            .args(["-n", "1", "-w", "1000", host])
+            .output()
+    } else {
+        Command::new("ping")
+            .args(["-c", "1", "-W", "1", host])
+            .output()
+    };
+
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            debug!("Failed to run ping: {}", e);
+            return None;
+        }
+    };
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_ping_time(&String::from_utf8_lossy(&output.stdout))
+}
+
+// Extract the round-trip time from "time=12.3 ms" (Unix) or "time<1ms" (Windows)
+fn parse_ping_time(output: &str) -> Option<f64> {
+    let start = output.find("time=").map(|i| i + 5).or_else(|| {
+        // Sub-millisecond replies on Windows
+        output.find("time<").map(|i| i + 5)
+    })?;
+    let value: String = output[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    value.parse().ok()
+}
+
+// Latency panel shown alongside network throughput
+pub fn monitor_ping(monitor: &PingMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-latency");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    println!(
+        "{:<20} {:<10} {:<10} {:<8} HISTORY",
+        "TARGET", "LAST ms", "AVG ms", "LOSS%"
+    );
+
+    for stats in monitor.snapshot() {
+        let format_ms = |value: Option<f64>| {
+            value
+                .map(|ms| format!("{:.1}", ms))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let loss = stats.loss_percent();
+        let loss_text = format!("{:.0}", loss);
+        let loss_colored = if loss == 0.0 {
+            loss_text.good()
+        } else if loss < 20.0 {
+            loss_text.warning()
+        } else {
+            loss_text.critical()
+        };
+
+        // Lost probes are drawn at full height so they stand out
+        let peak = stats.history.iter().flatten().cloned().fold(0.0, f64::max);
+        let series: Vec<f64> = stats
+            .history
+            .iter()
+            .map(|probe| probe.unwrap_or(peak))
+            .collect();
+
+        println!(
+            "{:<20} {:<10} {:<10} {:<8} {}",
+            stats.target,
+            format_ms(stats.last()),
+            format_ms(stats.average()),
+            loss_colored,
+            graphs::sparkline(&series, None).value()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, Command};
+use colored::*;
+use sysinfo::{System, SystemExt};
+
+use crate::config::ConfigManager;
+use crate::i18n;
+use crate::netinfo;
+use crate::sockets::{self, Protocol};
+use crate::theme::{self, Themed};
+
+// Connections in flight at once; refused ports answer immediately
+const SCAN_THREADS: usize = 128;
+
+// What was found for one port
+struct PortReport {
+    port: u16,
+    // Addresses the port is bound to according to the socket table
+    bound: Vec<IpAddr>,
+    owner: String,
+    local: bool,
+    external: Vec<Ipv4Addr>,
+}
+
+impl PortReport {
+    fn bound_to_all(&self) -> bool {
+        self.bound.iter().any(|ip| ip.is_unspecified())
+    }
+}
+
+// Handle "hercules portscan --self [--ports 1-65535] [--timeout 300]"
+pub fn run_portscan(args: &[String]) -> Result<()> {
+    let matches = Command::new("portscan")
+        .about("Scan this host's own addresses for open TCP ports and compare them with the listening sockets")
+        .arg(
+            Arg::new("self")
+                .long("self")
+                .help("Scan localhost and every address of this host")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ports")
+                .long("ports")
+                .short('p')
+                .value_name("RANGE")
+                .help("Ports to scan, e.g. 1-1024 or 22,80,443")
+                .default_value("1-65535"),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .value_name("MS")
+                .help("Connect timeout per port in milliseconds")
+                .default_value("300")
+                .value_parser(clap::value_parser!(u64)),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|e| e.exit());
+
+    if !matches.get_flag("self") {
+        return Err(anyhow!(
+            "Only self-audits are supported; run 'hercules portscan --self'"
+        ));
+    }
+    let ports = parse_ports(matches.get_one::<String>("ports").unwrap())?;
+    let timeout = Duration::from_millis(*matches.get_one::<u64>("timeout").unwrap());
+    let allowlist = ConfigManager::new()?.get_config().port_allowlist.clone();
+
+    let external: Vec<Ipv4Addr> = netinfo::ipv4_networks()
+        .into_iter()
+        .map(|(_, address, _)| address)
+        .collect();
+
+    if theme::interactive() {
+        eprintln!(
+            "Scanning {} ports on 127.0.0.1{}...",
+            ports.len(),
+            external
+                .iter()
+                .map(|address| format!(", {}", address))
+                .collect::<String>()
+        );
+    }
+    let open_local = scan(Ipv4Addr::LOCALHOST, &ports, timeout);
+    let open_external: Vec<(Ipv4Addr, BTreeSet<u16>)> = external
+        .iter()
+        .map(|&address| (address, scan(address, &ports, timeout)))
+        .collect();
+
+    // Listening TCP sockets in the scanned range, by port
+    let mut system = System::new();
+    system.refresh_processes();
+    let owners = sockets::socket_owners();
+    let mut reports: BTreeMap<u16, PortReport> = BTreeMap::new();
+    for entry in sockets::socket_table()
+        .into_iter()
+        .filter(|entry| entry.protocol == Protocol::Tcp && entry.is_listening())
+        .filter(|entry| ports.contains(&entry.local.port()))
+    {
+        let report = reports
+            .entry(entry.local.port())
+            .or_insert_with(|| PortReport {
+                port: entry.local.port(),
+                bound: Vec::new(),
+                owner: sockets::owner_name(&system, owners.get(&entry.inode).copied()),
+                local: false,
+                external: Vec::new(),
+            });
+        if !report.bound.contains(&entry.local.ip()) {
+            report.bound.push(entry.local.ip());
+        }
+    }
+
+    for &port in &open_local {
+        open_port(&mut reports, port).local = true;
+    }
+    for (address, open) in &open_external {
+        for &port in open {
+            open_port(&mut reports, port).external.push(*address);
+        }
+    }
+
+    let heading = i18n::tr("heading-port-audit");
+    println!("{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+    if reports.is_empty() {
+        println!("No open or listening TCP ports in the scanned range");
+        return Ok(());
+    }
+    println!(
+        "{:<6} {:<24} {:<20} {:<6} {:<16} STATUS",
+        "PORT", "BOUND", "PROCESS", "LOCAL", "EXTERNAL"
+    );
+
+    let mut unexpected = 0;
+    for report in reports.values() {
+        let status = if !report.external.is_empty() {
+            if allowlist.contains(&report.port) {
+                "allowed".good()
+            } else {
+                unexpected += 1;
+                "UNEXPECTED".critical().bold()
+            }
+        } else if report.bound.is_empty() {
+            // Answered, but no socket here owns it: another network namespace
+            // such as a container, or a NAT rule
+            "not in socket table".warning()
+        } else if report.bound_to_all() {
+            "filtered".value()
+        } else if report.local {
+            "local only".good()
+        } else {
+            "not reachable".value()
+        };
+        let bound = if report.bound.is_empty() {
+            "-".to_string()
+        } else {
+            report
+                .bound
+                .iter()
+                .map(|ip| ip.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let external = if report.external.is_empty() {
+            "-".to_string()
+        } else {
+            report
+                .external
+                .iter()
+                .map(|ip| ip.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        println!(
+            "{:<6} {:<24} {:<20} {:<6} {:<16} {}",
+            report.port,
+            bound,
+            report.owner,
+            if report.local { "open" } else { "-" },
+            external,
+            status
+        );
+    }
+
+    println!();
+    if unexpected > 0 {
+        if allowlist.is_empty() {
+            println!(
+                "{}",
+                "Set port_allowlist to the ports you expect to be reachable, e.g. hercules conf port_allowlist -> 22,80".value()
+            );
+        }
+        return Err(anyhow!(
+            "{} externally reachable port{} not on port_allowlist",
+            unexpected,
+            if unexpected == 1 { " is" } else { "s are" }
+        ));
+    }
+    println!(
+        "{}",
+        theme::ascii("✓ Every externally reachable port is on port_allowlist").good()
+    );
+    Ok(())
+}
+
+// The report for a port that accepted a connection, added if no socket listed it
+fn open_port(reports: &mut BTreeMap<u16, PortReport>, port: u16) -> &mut PortReport {
+    reports.entry(port).or_insert_with(|| PortReport {
+        port,
+        bound: Vec::new(),
+        owner: "-".to_string(),
+        local: false,
+        external: Vec::new(),
+    })
+}
+
+// "1-1024", "22,80,443" or a mix of both
+fn parse_ports(spec: &str) -> Result<Vec<u16>> {
+    let mut ports = BTreeSet::new();
+    for part in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let invalid = || anyhow!("Invalid port range '{}'", part);
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let start: u16 = start.trim().parse().map_err(|_| invalid())?;
+                let end: u16 = end.trim().parse().map_err(|_| invalid())?;
+                if start == 0 || start > end {
+                    return Err(invalid());
+                }
+                ports.extend(start..=end);
+            }
+            None => {
+                let port: u16 = part.parse().map_err(|_| invalid())?;
+                if port == 0 {
+                    return Err(invalid());
+                }
+                ports.insert(port);
+            }
+        }
+    }
+    if ports.is_empty() {
+        return Err(anyhow!("No ports to scan"));
+    }
+    Ok(ports.into_iter().collect())
+}
+
+// Ports on `address` that accept a TCP connection
+fn scan(address: Ipv4Addr, ports: &[u16], timeout: Duration) -> BTreeSet<u16> {
+    let next = AtomicUsize::new(0);
+    let open = Mutex::new(BTreeSet::new());
+    thread::scope(|scope| {
+        for _ in 0..SCAN_THREADS.min(ports.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&port) = ports.get(index) else {
+                    return;
+                };
+                let target = SocketAddr::new(IpAddr::V4(address), port);
+                if TcpStream::connect_timeout(&target, timeout).is_ok() {
+                    if let Ok(mut open) = open.lock() {
+                        open.insert(port);
+                    }
+                }
+            });
+        }
+    });
+    open.into_inner().unwrap_or_default()
+}
@@ -0,0 +1,430 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::{Local, TimeZone};
+use clap::{Arg, Command};
+use colored::*;
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt, UserExt};
+
+use crate::clock;
+use crate::config;
+use crate::graphs;
+use crate::i18n;
+use crate::theme::{self, Themed};
+use crate::units;
+
+// Number of samples kept for the watch sparklines
+const WATCH_HISTORY_LEN: usize = 60;
+
+// Handle "hercules watch --pid <PID>" / "hercules watch --name <NAME>"
+pub fn run_watch(args: &[String]) -> Result<()> {
+    let matches = Command::new("watch")
+        .about("Show a focused live panel for a single process")
+        .arg(
+            Arg::new("pid")
+                .long("pid")
+                .short('p')
+                .help("Process ID to watch")
+                .value_parser(clap::value_parser!(u32))
+                .conflicts_with("name")
+                .required_unless_present("name"),
+        )
+        .arg(
+            Arg::new("name")
+                .long("name")
+                .short('n')
+                .help("Process name to watch (first match wins)"),
+        )
+        .get_matches_from(args);
+
+    let config_manager = config::ConfigManager::new()?;
+    let interval = Duration::from_millis(config_manager.get_config().update_interval_ms);
+
+    let mut system = System::new_all();
+    system.refresh_processes();
+
+    let pid = match matches.get_one::<u32>("pid") {
+        Some(&pid) => Pid::from_u32(pid),
+        None => {
+            let name = matches
+                .get_one::<String>("name")
+                .ok_or_else(|| anyhow!("Either --pid or --name is required"))?;
+            find_process_by_name(&system, name)
+                .ok_or_else(|| anyhow!("No running process matches '{}'", name))?
+        }
+    };
+
+    if system.process(pid).is_none() {
+        return Err(anyhow!("No process with PID {}", pid.as_u32()));
+    }
+
+    let mut cpu_history: VecDeque<f64> = VecDeque::with_capacity(WATCH_HISTORY_LEN);
+    let mut mem_history: VecDeque<f64> = VecDeque::with_capacity(WATCH_HISTORY_LEN);
+    let mut last_refresh = Instant::now();
+
+    loop {
+        thread::sleep(interval);
+        system.refresh_processes();
+        let elapsed = last_refresh.elapsed().as_secs_f64();
+        last_refresh = Instant::now();
+
+        let Some(process) = system.process(pid) else {
+            println!(
+                "\n{} Process {} has exited",
+                "!".critical().bold(),
+                pid.as_u32()
+            );
+            return Ok(());
+        };
+
+        push_sample(&mut cpu_history, process.cpu_usage() as f64);
+        push_sample(&mut mem_history, process.memory() as f64);
+
+        // Clear screen and reset cursor
+        if theme::interactive() {
+            print!("\x1B[2J\x1B[1;1H");
+            io::stdout().flush()?;
+        }
+
+        display_watch_panel(&system, process, elapsed, &cpu_history, &mem_history);
+    }
+}
+
+fn display_watch_panel(
+    system: &System,
+    process: &sysinfo::Process,
+    elapsed: f64,
+    cpu_history: &VecDeque<f64>,
+    mem_history: &VecDeque<f64>,
+) {
+    let pid = process.pid();
+    let timestamp = clock::now();
+
+    println!(
+        "{} {} {}",
+        "HERCULES WATCH".bold().good(),
+        format!("{} ({})", process.name(), pid.as_u32()).warning(),
+        timestamp.value()
+    );
+    println!("{}", "==================================".good());
+
+    let disk = process.disk_usage();
+    let per_second = |bytes: u64| {
+        if elapsed > 0.0 {
+            bytes as f64 / elapsed
+        } else {
+            0.0
+        }
+    };
+
+    println!(
+        "CPU:      {}%",
+        format!("{:.1}", process.cpu_usage()).warning()
+    );
+    println!(
+        "RSS:      {}",
+        units::format_bytes(process.memory() as f64).warning()
+    );
+    println!(
+        "Threads:  {}",
+        thread_count(pid.as_u32())
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "n/a".to_string())
+            .value()
+    );
+    println!(
+        "FDs:      {}",
+        fd_count(pid.as_u32())
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "n/a".to_string())
+            .value()
+    );
+    println!(
+        "Disk I/O: read {}, write {}",
+        units::format_rate(per_second(disk.read_bytes)).good(),
+        units::format_rate(per_second(disk.written_bytes)).good()
+    );
+    println!("Status:   {:?}", process.status());
+
+    let cpu_values: Vec<f64> = cpu_history.iter().cloned().collect();
+    let mem_values: Vec<f64> = mem_history.iter().cloned().collect();
+    let cpu_ceiling = 100.0 * system.cpus().len().max(1) as f64;
+    println!();
+    println!(
+        "CPU history: {}",
+        graphs::sparkline(&cpu_values, Some(cpu_ceiling)).warning()
+    );
+    println!(
+        "RSS history: {}",
+        graphs::sparkline(&mem_values, None).accent()
+    );
+
+    // Direct children of the watched process
+    let mut children: Vec<_> = system
+        .processes()
+        .values()
+        .filter(|child| child.parent() == Some(pid))
+        .collect();
+    children.sort_by_key(|child| child.pid().as_u32());
+
+    let heading = i18n::tr("heading-child-processes");
+    println!("\n{}", heading.bold().warning());
+    println!("{}", i18n::underline(heading).warning());
+    if children.is_empty() {
+        println!("  (none)");
+    } else {
+        for child in children {
+            println!(
+                "  {:<6} {:<20} {:>5.1}% {:>11}",
+                child.pid().as_u32(),
+                child.name(),
+                child.cpu_usage(),
+                units::format_bytes(child.memory() as f64)
+            );
+        }
+    }
+}
+
+// Handle "hercules proc <PID>": print everything we know about one process
+pub fn run_inspect(args: &[String]) -> Result<()> {
+    let matches = Command::new("proc")
+        .about("Show detailed information about a process")
+        .arg(
+            Arg::new("pid")
+                .help("Process ID to inspect")
+                .value_parser(clap::value_parser!(u32))
+                .required(true),
+        )
+        .arg(
+            Arg::new("no-env")
+                .long("no-env")
+                .help("Do not print the process environment")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .get_matches_from(args);
+
+    let pid = Pid::from_u32(*matches.get_one::<u32>("pid").expect("pid is required"));
+
+    let mut system = System::new_all();
+    system.refresh_processes();
+    system.refresh_users_list();
+
+    let process = system
+        .process(pid)
+        .ok_or_else(|| anyhow!("No process with PID {}", pid.as_u32()))?;
+
+    display_process_details(&system, process, !matches.get_flag("no-env"));
+    Ok(())
+}
+
+pub fn display_process_details(system: &System, process: &sysinfo::Process, show_env: bool) {
+    let pid = process.pid();
+
+    println!(
+        "{} {}",
+        "PROCESS".bold().warning(),
+        format!("{} ({})", process.name(), pid.as_u32()).emphasis()
+    );
+    println!("{}", "-------".warning());
+
+    let cmdline = if process.cmd().is_empty() {
+        format!("[{}]", process.name())
+    } else {
+        process.cmd().join(" ")
+    };
+    let owner = process
+        .user_id()
+        .map(|uid| {
+            system
+                .get_user_by_id(uid)
+                .map(|user| user.name().to_string())
+                .unwrap_or_else(|| format!("{:?}", uid))
+        })
+        .unwrap_or_else(|| "Unknown".to_string());
+    let started = Local
+        .timestamp_opt(process.start_time() as i64, 0)
+        .single()
+        .map(|time| clock::format(&time))
+        .unwrap_or_else(|| "Unknown".to_string());
+    let parent = process
+        .parent()
+        .map(|ppid| {
+            let name = system
+                .process(ppid)
+                .map(|parent| parent.name().to_string())
+                .unwrap_or_default();
+            format!("{} {}", ppid.as_u32(), name)
+        })
+        .unwrap_or_else(|| "none".to_string());
+    let optional = |value: Option<usize>| {
+        value
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "n/a".to_string())
+    };
+
+    println!("{:<12} {}", "Command:".value(), cmdline);
+    println!("{:<12} {}", "Executable:".value(), process.exe().display());
+    println!("{:<12} {}", "CWD:".value(), process.cwd().display());
+    println!("{:<12} {}", "Owner:".value(), owner);
+    println!("{:<12} {}", "Parent:".value(), parent);
+    println!("{:<12} {:?}", "Status:".value(), process.status());
+    println!(
+        "{:<12} {} (running {})",
+        "Started:".value(),
+        started,
+        format_duration(process.run_time())
+    );
+    println!("{:<12} {:.1}%", "CPU:".value(), process.cpu_usage());
+    println!(
+        "{:<12} {}",
+        "RSS:".value(),
+        units::format_bytes(process.memory() as f64)
+    );
+    if let Some(detail) = memory_detail(pid.as_u32()) {
+        println!(
+            "{:<12} {} (USS {})",
+            "PSS:".value(),
+            units::format_bytes(detail.pss as f64),
+            units::format_bytes(detail.uss as f64)
+        );
+    }
+    println!(
+        "{:<12} {}",
+        "Threads:".value(),
+        optional(thread_count(pid.as_u32()))
+    );
+    println!(
+        "{:<12} {}",
+        "Open files:".value(),
+        optional(fd_count(pid.as_u32()))
+    );
+
+    if show_env {
+        let heading = i18n::tr("heading-environment");
+        println!("\n{}", heading.bold().warning());
+        println!("{}", i18n::underline(heading).warning());
+        if process.environ().is_empty() {
+            println!("  (unavailable - try running with elevated privileges)");
+        } else {
+            let mut environ: Vec<_> = process.environ().iter().collect();
+            environ.sort();
+            for variable in environ {
+                println!("  {}", variable);
+            }
+        }
+    }
+}
+
+// Format a number of seconds as "1d 2h 3m 4s", omitting leading zero units
+pub fn format_duration(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let hours = (seconds % 86_400) / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, secs)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+fn push_sample(history: &mut VecDeque<f64>, value: f64) {
+    if history.len() == WATCH_HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+// Prefer an exact name match, then fall back to a substring match
+pub fn find_process_by_name(system: &System, name: &str) -> Option<Pid> {
+    let needle = name.to_lowercase();
+    let mut candidates: Vec<_> = system.processes().values().collect();
+    candidates.sort_by_key(|process| process.pid().as_u32());
+
+    candidates
+        .iter()
+        .find(|process| process.name().to_lowercase() == needle)
+        .or_else(|| {
+            candidates
+                .iter()
+                .find(|process| process.name().to_lowercase().contains(&needle))
+        })
+        .map(|process| process.pid())
+}
+
+// Number of threads in a process, from /proc/<pid>/status
+#[cfg(target_os = "linux")]
+pub fn thread_count(pid: u32) -> Option<usize> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Threads:"))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn thread_count(_pid: u32) -> Option<usize> {
+    None
+}
+
+// Proportional (PSS) and unique (USS) set sizes in bytes. Unlike RSS these
+// do not count shared pages in full for every process mapping them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryDetail {
+    pub pss: u64,
+    pub uss: u64,
+}
+
+// Read PSS/USS from /proc/<pid>/smaps_rollup (Linux 4.14 and later). The
+// kernel walks the process's page tables for it, so only read it for the
+// processes actually shown.
+#[cfg(target_os = "linux")]
+pub fn memory_detail(pid: u32) -> Option<MemoryDetail> {
+    let content = std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid)).ok()?;
+
+    let mut detail = MemoryDetail::default();
+    for line in content.lines() {
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(kb) = rest
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        match key {
+            "Pss" => detail.pss += kb * 1024,
+            "Private_Clean" | "Private_Dirty" => detail.uss += kb * 1024,
+            _ => {}
+        }
+    }
+    Some(detail)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn memory_detail(_pid: u32) -> Option<MemoryDetail> {
+    None
+}
+
+// Number of open file descriptors, from /proc/<pid>/fd
+#[cfg(target_os = "linux")]
+pub fn fd_count(pid: u32) -> Option<usize> {
+    std::fs::read_dir(format!("/proc/{}/fd", pid))
+        .ok()
+        .map(|entries| entries.count())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn fd_count(_pid: u32) -> Option<usize> {
+    None
+}
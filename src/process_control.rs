@@ -0,0 +1,183 @@
+#[cfg(target_os = "linux")]
+use std::fs;
+
+use anyhow::{anyhow, Result};
+#[cfg(target_os = "linux")]
+use nix::errno::Errno;
+#[cfg(target_os = "linux")]
+use nix::sched::{sched_getaffinity, sched_setaffinity, CpuSet};
+#[cfg(target_os = "linux")]
+use nix::unistd::Pid;
+
+// Current nice value, from field 19 of /proc/<pid>/stat
+#[cfg(target_os = "linux")]
+pub fn nice(pid: u32) -> Option<i32> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name may contain spaces, so count fields after its ")"
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(16)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn nice(_pid: u32) -> Option<i32> {
+    None
+}
+
+// Current affinity as a CPU list, e.g. "0-3"
+#[cfg(target_os = "linux")]
+pub fn affinity(pid: u32) -> Option<String> {
+    let set = sched_getaffinity(Pid::from_raw(pid as i32)).ok()?;
+    let cpus: Vec<usize> = (0..CpuSet::count())
+        .filter(|&cpu| set.is_set(cpu).unwrap_or(false))
+        .collect();
+    Some(format_cpu_list(&cpus))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn affinity(_pid: u32) -> Option<String> {
+    None
+}
+
+// Sets the nice value of every thread of the process; on Linux
+// setpriority only changes the one thread it is given
+#[cfg(target_os = "linux")]
+pub fn renice(pid: u32, nice: i32) -> Result<()> {
+    if !(-20..=19).contains(&nice) {
+        return Err(anyhow!("Nice values go from -20 to 19"));
+    }
+    for tid in threads(pid) {
+        // SAFETY: setpriority only reads its integer arguments
+        let result = unsafe { nix::libc::setpriority(nix::libc::PRIO_PROCESS, tid, nice) };
+        match Errno::last() {
+            _ if result == 0 => {}
+            // A thread that exited in the meantime
+            Errno::ESRCH if tid != pid => {}
+            errno => return Err(change_error(errno, pid)),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn renice(_pid: u32, _nice: i32) -> Result<()> {
+    Err(anyhow!("Renicing is only supported on Linux"))
+}
+
+// Pins every thread of the process to the CPUs in a taskset-style list
+// such as "0-3,6"
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: u32, list: &str) -> Result<()> {
+    let cpus = parse_cpu_list(list)?;
+    let online = fs::read_to_string("/sys/devices/system/cpu/online")
+        .ok()
+        .and_then(|online| parse_cpu_list(online.trim()).ok())
+        .unwrap_or_default();
+    if let Some(cpu) = cpus
+        .iter()
+        .find(|&&cpu| cpu >= CpuSet::count() || !(online.is_empty() || online.contains(&cpu)))
+    {
+        return Err(anyhow!(
+            "CPU {} is not online, this machine has CPUs {}",
+            cpu,
+            format_cpu_list(&online)
+        ));
+    }
+
+    let mut set = CpuSet::new();
+    for &cpu in &cpus {
+        set.set(cpu)?;
+    }
+    for tid in threads(pid) {
+        match sched_setaffinity(Pid::from_raw(tid as i32), &set) {
+            Ok(()) => {}
+            Err(Errno::ESRCH) if tid != pid => {}
+            Err(errno) => return Err(change_error(errno, pid)),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity(_pid: u32, _list: &str) -> Result<()> {
+    Err(anyhow!("CPU affinity is only supported on Linux"))
+}
+
+// The process's threads from /proc/<pid>/task, or just the PID if they
+// cannot be listed
+#[cfg(target_os = "linux")]
+fn threads(pid: u32) -> Vec<u32> {
+    let mut tids: Vec<u32> = fs::read_dir(format!("/proc/{}/task", pid))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    if tids.is_empty() {
+        tids.push(pid);
+    }
+    tids
+}
+
+// The kernel does the permission checks; explain what they mean
+#[cfg(target_os = "linux")]
+fn change_error(errno: Errno, pid: u32) -> anyhow::Error {
+    match errno {
+        Errno::EPERM => anyhow!(
+            "Process {} belongs to another user, changing it needs root or CAP_SYS_NICE",
+            pid
+        ),
+        Errno::EACCES => anyhow!(
+            "Lowering the nice value of process {} needs root or CAP_SYS_NICE",
+            pid
+        ),
+        Errno::ESRCH => anyhow!("Process {} is gone", pid),
+        _ => anyhow!("Cannot change process {}: {}", pid, errno.desc()),
+    }
+}
+
+// "0-3,6" -> [0, 1, 2, 3, 6]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cpu_list(list: &str) -> Result<Vec<usize>> {
+    let invalid = || anyhow!("Invalid CPU list '{}', use e.g. 0-3,6", list);
+    let mut cpus = Vec::new();
+    for part in list.split(',').map(str::trim) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (first.trim(), last.trim()),
+            None => (part, part),
+        };
+        let first: usize = first.parse().map_err(|_| invalid())?;
+        let last: usize = last.parse().map_err(|_| invalid())?;
+        if first > last {
+            return Err(invalid());
+        }
+        cpus.extend(first..=last);
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+// [0, 1, 2, 3, 6] -> "0-3,6"
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == cpu => *last = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{}-{}", first, last)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,186 @@
+use console::Key;
+
+use crate::config::ProcessSort;
+use crate::keymap::{Action, Keymap};
+use crate::process_control;
+use crate::search;
+
+// What the prompt under the table asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    Nice,
+    Affinity,
+}
+
+// A value being typed for the selected process, prefilled with the current one
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub pid: u32,
+    pub name: String,
+    pub text: String,
+}
+
+// View state of the process panel when the monitor runs on a terminal:
+// scroll position, selected row, sort column and direction, search and user
+// filter. The search also filters the disk, network and connection panels.
+pub struct ProcessTable {
+    pub sort: ProcessSort,
+    pub ascending: bool,
+    pub offset: usize,
+    // Row of the selection in the filtered and sorted list
+    pub selected: usize,
+    // PID and name on the selected row when the table was last drawn
+    pub selected_process: Option<(u32, String)>,
+    pub search: String,
+    pub searching: bool,
+    pub prompt: Option<Prompt>,
+    pub only_mine: bool,
+    // Rows on one page, for PageUp/PageDown
+    pub page: usize,
+    // For the key hints under the table
+    pub keymap: Keymap,
+}
+
+impl ProcessTable {
+    pub fn new(sort: ProcessSort, page: usize, keymap: Keymap) -> Self {
+        ProcessTable {
+            sort,
+            ascending: false,
+            offset: 0,
+            selected: 0,
+            selected_process: None,
+            search: String::new(),
+            searching: false,
+            prompt: None,
+            only_mine: false,
+            page: page.max(1),
+            keymap,
+        }
+    }
+
+    // Apply a key typed into the search. Returns true when the table needs to be redrawn.
+    pub fn handle_search_key(&mut self, key: &Key) -> bool {
+        match key {
+            Key::Char(c) if !c.is_control() => self.search.push(*c),
+            Key::Backspace => {
+                self.search.pop();
+            }
+            Key::Enter => self.searching = false,
+            Key::Escape => {
+                self.search.clear();
+                self.searching = false;
+            }
+            _ => return false,
+        }
+        // The matches change, so start again from the top
+        self.top();
+        true
+    }
+
+    // Apply a key typed into the renice or affinity prompt. Returns the
+    // prompt once Enter submits it.
+    pub fn handle_prompt_key(&mut self, key: &Key) -> Option<Prompt> {
+        let prompt = self.prompt.as_mut()?;
+        match key {
+            Key::Char(c) if !c.is_control() => prompt.text.push(*c),
+            Key::Backspace => {
+                prompt.text.pop();
+            }
+            Key::Enter => return self.prompt.take(),
+            Key::Escape => self.prompt = None,
+            _ => {}
+        }
+        None
+    }
+
+    // Apply an action from the keymap. Returns true when the table needs to be redrawn.
+    pub fn handle_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::ScrollUp => self.selected = self.selected.saturating_sub(1),
+            Action::ScrollDown => self.selected += 1,
+            Action::PageUp => self.selected = self.selected.saturating_sub(self.page),
+            Action::PageDown => self.selected += self.page,
+            Action::Top => self.top(),
+            Action::Bottom => self.selected = usize::MAX,
+            Action::Search => {
+                self.searching = true;
+                self.search.clear();
+                self.top();
+            }
+            Action::ClearSearch if !self.search.is_empty() => {
+                self.search.clear();
+                self.top();
+            }
+            Action::OnlyMine => {
+                self.only_mine = !self.only_mine;
+                self.top();
+            }
+            Action::Renice | Action::Affinity => {
+                let Some((pid, name)) = self.selected_process.clone() else {
+                    return false;
+                };
+                let (kind, current) = if action == Action::Renice {
+                    (
+                        PromptKind::Nice,
+                        process_control::nice(pid).map(|nice| nice.to_string()),
+                    )
+                } else {
+                    (PromptKind::Affinity, process_control::affinity(pid))
+                };
+                self.prompt = Some(Prompt {
+                    kind,
+                    pid,
+                    name,
+                    text: current.unwrap_or_default(),
+                });
+            }
+            Action::ReverseSort => self.ascending = !self.ascending,
+            action => match sort_for_action(action) {
+                // Pressing the key of the current column flips the direction
+                Some(sort) if sort == self.sort => self.ascending = !self.ascending,
+                Some(sort) => {
+                    self.sort = sort;
+                    self.ascending = false;
+                    self.top();
+                }
+                None => return false,
+            },
+        }
+        true
+    }
+
+    fn top(&mut self) {
+        self.offset = 0;
+        self.selected = 0;
+    }
+
+    // Keep the selection inside the list and on the page, and return the rows to show
+    pub fn visible(&mut self, rows: usize) -> std::ops::Range<usize> {
+        self.selected = self.selected.min(rows.saturating_sub(1));
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + self.page {
+            self.offset = self.selected + 1 - self.page;
+        }
+        self.offset = self.offset.min(rows.saturating_sub(self.page));
+        self.offset..(self.offset + self.page).min(rows)
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        search::matches(name, &self.search)
+    }
+}
+
+// Sort column for each sort action
+fn sort_for_action(action: Action) -> Option<ProcessSort> {
+    match action {
+        Action::SortCpu => Some(ProcessSort::Cpu),
+        Action::SortMemory => Some(ProcessSort::Memory),
+        Action::SortDiskRead => Some(ProcessSort::DiskRead),
+        Action::SortDiskWrite => Some(ProcessSort::DiskWrite),
+        Action::SortDiskTotal => Some(ProcessSort::DiskTotal),
+        Action::SortNetwork => Some(ProcessSort::Network),
+        _ => None,
+    }
+}
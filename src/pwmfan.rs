@@ -0,0 +1,246 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use log::{debug, info, warn};
+
+use crate::hardware::{self, Board};
+use crate::units;
+
+// GPIO pins wired to the SoC's hardware PWM channels
+const PWM_PINS: [u8; 4] = [12, 13, 18, 19];
+
+// One point of the fan curve: run at `percent` from `celsius` up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurvePoint {
+    pub celsius: f64,
+    pub percent: f64,
+}
+
+// "40:0,50:30,60:60,70:100", temperatures rising
+pub fn parse_curve(value: &str) -> Result<Vec<CurvePoint>> {
+    let points = value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let invalid = || anyhow!("Invalid fan curve point '{}', expected °C:percent", item);
+            let (celsius, percent) = item.split_once(':').ok_or_else(invalid)?;
+            let celsius = celsius.trim().parse::<f64>().map_err(|_| invalid())?;
+            let percent = percent
+                .trim()
+                .trim_end_matches('%')
+                .parse::<f64>()
+                .map_err(|_| invalid())?;
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(anyhow!("Fan speed in '{}' must be 0-100%", item));
+            }
+            Ok(CurvePoint { celsius, percent })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if points.is_empty() {
+        return Err(anyhow!(
+            "The fan curve needs at least one point, e.g. 50:0,70:100"
+        ));
+    }
+    if points
+        .windows(2)
+        .any(|pair| pair[0].celsius >= pair[1].celsius)
+    {
+        return Err(anyhow!(
+            "Fan curve temperatures must rise, e.g. 40:0,50:30,70:100"
+        ));
+    }
+    Ok(points)
+}
+
+pub fn validate_pin(pin: u8) -> Result<()> {
+    if PWM_PINS.contains(&pin) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "GPIO {} has no hardware PWM. Use one of: 12, 13, 18, 19",
+            pin
+        ))
+    }
+}
+
+// Speed for `celsius`, interpolated between the curve points
+fn curve_percent(curve: &[CurvePoint], celsius: f64) -> f64 {
+    let (Some(first), Some(last)) = (curve.first(), curve.last()) else {
+        return 100.0;
+    };
+    if celsius <= first.celsius {
+        return first.percent;
+    }
+    if celsius >= last.celsius {
+        return last.percent;
+    }
+    curve
+        .windows(2)
+        .find(|pair| celsius < pair[1].celsius)
+        .map(|pair| {
+            let span = pair[1].celsius - pair[0].celsius;
+            let position = (celsius - pair[0].celsius) / span;
+            pair[0].percent + (pair[1].percent - pair[0].percent) * position
+        })
+        .unwrap_or(last.percent)
+}
+
+// Drives a PWM fan on a GPIO pin from the CPU temperature. Uses the kernel's
+// PWM sysfs interface, so the pin needs the pwm or pwm-2chan overlay in
+// config.txt, and writing it needs root (or the gpio group on Raspberry Pi OS).
+pub struct FanController {
+    pin: u8,
+    channel: PathBuf,
+    period_ns: u64,
+    curve: Vec<CurvePoint>,
+    hysteresis: f64,
+    // Temperature the current speed was picked for
+    reference: Option<f64>,
+    percent: Option<f64>,
+}
+
+impl FanController {
+    pub fn new(
+        pin: u8,
+        frequency_hz: u64,
+        curve: Vec<CurvePoint>,
+        hysteresis: f64,
+    ) -> Result<Self> {
+        validate_pin(pin)?;
+        if frequency_hz == 0 {
+            return Err(anyhow!("fan_control_frequency_hz must be above 0"));
+        }
+        let channel = export_channel(pin)?;
+        let period_ns = 1_000_000_000 / frequency_hz;
+
+        // The duty cycle has to fit the period before the period can shrink
+        write(&channel.join("duty_cycle"), 0)?;
+        write(&channel.join("period"), period_ns)?;
+        write(&channel.join("enable"), 1)?;
+        info!(
+            "Fan control on GPIO {} ({}) at {} Hz",
+            pin,
+            channel.display(),
+            frequency_hz
+        );
+
+        Ok(FanController {
+            pin,
+            channel,
+            period_ns,
+            curve,
+            hysteresis: hysteresis.max(0.0),
+            reference: None,
+            percent: None,
+        })
+    }
+
+    // Pick the speed for `temperature`. Rising temperatures apply at once;
+    // falling ones only once they drop `hysteresis` degrees below the one the
+    // current speed was set for, so the fan does not hunt around a curve point.
+    pub fn update(&mut self, temperature: Option<f32>) {
+        let Some(celsius) = temperature.map(f64::from) else {
+            // Without a reading, err on the side of cooling
+            self.set_percent(100.0);
+            return;
+        };
+        let reference = match self.reference {
+            Some(reference) if celsius < reference && celsius > reference - self.hysteresis => {
+                reference
+            }
+            _ => celsius,
+        };
+        self.reference = Some(reference);
+        self.set_percent(curve_percent(&self.curve, reference));
+    }
+
+    fn set_percent(&mut self, percent: f64) {
+        if self
+            .percent
+            .is_some_and(|current| (current - percent).abs() < 0.5)
+        {
+            return;
+        }
+        let duty = (self.period_ns as f64 * percent / 100.0).round() as u64;
+        match write(&self.channel.join("duty_cycle"), duty) {
+            Ok(()) => {
+                debug!(
+                    "Fan on GPIO {} at {:.0}% ({})",
+                    self.pin,
+                    percent,
+                    self.reference
+                        .map(units::format_temperature)
+                        .unwrap_or_else(|| "no temperature".to_string())
+                );
+                self.percent = Some(percent);
+            }
+            Err(e) => warn!("Fan control: {}", e),
+        }
+    }
+}
+
+impl Drop for FanController {
+    // Leave the fan at full speed rather than stopped once nothing controls it
+    fn drop(&mut self) {
+        let _ = write(&self.channel.join("duty_cycle"), self.period_ns);
+    }
+}
+
+fn write(path: &Path, value: u64) -> Result<()> {
+    fs::write(path, value.to_string())
+        .map_err(|e| anyhow!("cannot write {}: {}", path.display(), e))
+}
+
+// The PWM chip and channel behind `pin`. Up to the Pi 4 the SoC has two
+// channels, GPIO 12/18 on 0 and 13/19 on 1; the Pi 5's RP1 has four.
+fn export_channel(pin: u8) -> Result<PathBuf> {
+    let pi5 = matches!(
+        hardware::detect().board,
+        Board::Pi5 | Board::Pi500 | Board::Cm5
+    );
+    let channel: u64 = match (pi5, pin) {
+        (true, 12) => 0,
+        (true, 13) => 1,
+        (true, 18) => 2,
+        (true, 19) => 3,
+        (false, 12 | 18) => 0,
+        _ => 1,
+    };
+
+    let chips = fs::read_dir("/sys/class/pwm")
+        .map_err(|_| anyhow!("no PWM chip found, enable dtoverlay=pwm-2chan in config.txt"))?;
+    let mut chips: Vec<PathBuf> = chips.flatten().map(|entry| entry.path()).collect();
+    chips.sort();
+    let chip = chips
+        .into_iter()
+        .find(|chip| {
+            fs::read_to_string(chip.join("npwm"))
+                .ok()
+                .and_then(|npwm| npwm.trim().parse::<u64>().ok())
+                .is_some_and(|npwm| npwm > channel)
+        })
+        .ok_or_else(|| {
+            anyhow!(
+                "no PWM chip with channel {} for GPIO {}, enable dtoverlay=pwm-2chan in config.txt",
+                channel,
+                pin
+            )
+        })?;
+
+    let path = chip.join(format!("pwm{}", channel));
+    if !path.exists() {
+        write(&chip.join("export"), channel)?;
+        // The channel directory appears shortly after the export
+        for _ in 0..20 {
+            if path.join("enable").exists() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+    Ok(path)
+}
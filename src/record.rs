@@ -0,0 +1,589 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, TimeZone};
+use clap::{Arg, ArgAction, Command};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use sysinfo::{CpuExt, NetworkExt, PidExt, ProcessExt, SystemExt};
+
+use crate::alerts::{self, Alert, AlertEngine, AlertLevel};
+use crate::config::ConfigManager;
+use crate::graphs::ResourceHistory;
+use crate::theme::{self, Themed};
+use crate::{clock, i18n, process, summary, units, MonitorConfig, SystemResources};
+
+// Version 1 recordings held rendered text instead of samples
+const VERSION: u32 = 2;
+
+// First line of a .hrec file
+#[derive(Serialize, Deserialize)]
+struct Header {
+    format: String,
+    version: u32,
+    host: String,
+    started: String,
+    #[serde(default)]
+    interval_ms: u64,
+}
+
+// One refresh, stamped with its offset from the start of the recording
+#[derive(Serialize, Deserialize)]
+struct Tick {
+    ms: u64,
+    sample: Sample,
+}
+
+// What the panels show for one refresh, before any formatting. The live
+// monitor draws from it too, so a replay goes through the same panels.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Sample {
+    pub time: i64,
+    pub host: String,
+    pub os: String,
+    pub kernel: String,
+    pub uptime: u64,
+    pub cpu: CpuSample,
+    pub memory: MemorySample,
+    pub disks: Vec<DiskSample>,
+    pub networks: Vec<NetSample>,
+    #[serde(default)]
+    pub processes: Vec<ProcessSample>,
+    #[serde(default)]
+    pub alerts: Vec<AlertSample>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CpuSample {
+    pub usage: f32,
+    // Only set on a VM with noticeable steal time
+    pub steal: Option<f64>,
+    pub throttled: Option<String>,
+    pub average_mhz: u64,
+    // 1, 5 and 15 minutes; Windows has no load average
+    pub load: Option<[f64; 3]>,
+    pub cores: Vec<CoreSample>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoreSample {
+    pub usage: f32,
+    pub mhz: u64,
+}
+
+// Bytes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemorySample {
+    pub used: u64,
+    pub total: u64,
+    pub swap_used: u64,
+    pub swap_total: u64,
+}
+
+impl MemorySample {
+    pub fn percent(&self) -> f64 {
+        percent(self.used, self.total)
+    }
+
+    pub fn swap_percent(&self) -> f64 {
+        percent(self.swap_used, self.swap_total)
+    }
+}
+
+fn percent(used: u64, total: u64) -> f64 {
+    if total > 0 {
+        used as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskSample {
+    pub name: String,
+    pub file_system: String,
+    pub mount_points: Vec<String>,
+    pub total: u64,
+    pub available: u64,
+}
+
+// Traffic since the previous refresh, and the same as bytes per second
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetSample {
+    pub interface: String,
+    pub received: u64,
+    pub transmitted: u64,
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+    pub packets_received: u64,
+    pub packets_transmitted: u64,
+    pub errors: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProcessSample {
+    pub pid: u32,
+    pub name: String,
+    pub cpu: f32,
+    // Bytes, and bytes per second for disk and network
+    pub memory: u64,
+    pub read_rate: f64,
+    pub write_rate: f64,
+    pub pss: Option<u64>,
+    pub uss: Option<u64>,
+    pub net: Option<[f64; 2]>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertSample {
+    pub key: String,
+    pub level: String,
+    pub source: String,
+    pub message: String,
+    pub raised_at: i64,
+}
+
+impl Sample {
+    // Everything but the process list, which depends on the sort and how many rows show
+    pub fn collect(res: &SystemResources) -> Sample {
+        let system = &res.system;
+        let steal = res.scheduler.steal_percent();
+        let seconds = res.refresh_interval_secs;
+        let per_second = |bytes: u64| {
+            if seconds > 0.0 {
+                bytes as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+        Sample {
+            time: Local::now().timestamp(),
+            host: system.host_name().unwrap_or_else(|| "Unknown".to_string()),
+            os: system.name().unwrap_or_else(|| "Unknown".to_string()),
+            kernel: system
+                .kernel_version()
+                .unwrap_or_else(|| "Unknown".to_string()),
+            uptime: system.uptime(),
+            cpu: CpuSample {
+                usage: system.global_cpu_info().cpu_usage(),
+                steal: (res.virt.is_virtual_machine() && steal >= 0.1).then_some(steal),
+                throttled: res.throttle.reason().map(str::to_string),
+                average_mhz: res.throttle.average_mhz(),
+                load: (!cfg!(windows)).then(|| {
+                    let load = system.load_average();
+                    [load.one, load.five, load.fifteen]
+                }),
+                cores: system
+                    .cpus()
+                    .iter()
+                    .map(|cpu| CoreSample {
+                        usage: cpu.cpu_usage(),
+                        mhz: cpu.frequency(),
+                    })
+                    .collect(),
+            },
+            memory: MemorySample {
+                used: system.used_memory(),
+                total: system.total_memory(),
+                swap_used: system.used_swap(),
+                swap_total: system.total_swap(),
+            },
+            disks: res
+                .disk_filter
+                .mounts(system)
+                .into_iter()
+                .map(|disk| DiskSample {
+                    name: disk.name,
+                    file_system: disk.file_system,
+                    mount_points: disk
+                        .mount_points
+                        .iter()
+                        .map(|mount| mount.to_string_lossy().into_owned())
+                        .collect(),
+                    total: disk.total_space,
+                    available: disk.available_space,
+                })
+                .collect(),
+            networks: system
+                .networks()
+                .into_iter()
+                .map(|(interface, data)| NetSample {
+                    interface: interface.clone(),
+                    received: data.received(),
+                    transmitted: data.transmitted(),
+                    rx_rate: per_second(data.received()),
+                    tx_rate: per_second(data.transmitted()),
+                    packets_received: data.total_packets_received(),
+                    packets_transmitted: data.total_packets_transmitted(),
+                    errors: data.total_errors_on_received() + data.total_errors_on_transmitted(),
+                })
+                .collect(),
+            processes: Vec::new(),
+            alerts: res
+                .alerts
+                .active_by_key()
+                .map(|(key, alert)| AlertSample {
+                    key: key.clone(),
+                    level: alert.level.as_str().to_string(),
+                    source: alert.source.clone(),
+                    message: alert.message.clone(),
+                    raised_at: alert.raised_at.timestamp(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn time(&self) -> DateTime<Local> {
+        Local
+            .timestamp_opt(self.time, 0)
+            .single()
+            .unwrap_or_else(Local::now)
+    }
+}
+
+impl ProcessSample {
+    pub fn new(
+        res: &SystemResources,
+        process: &sysinfo::Process,
+        detail: Option<&process::MemoryDetail>,
+    ) -> ProcessSample {
+        // Disk counters are deltas since the last refresh
+        let interval = res.refresh_interval_secs;
+        let per_second = |bytes: u64| {
+            if interval > 0.0 {
+                bytes as f64 / interval
+            } else {
+                0.0
+            }
+        };
+        let pid = process.pid().as_u32();
+        let disk = process.disk_usage();
+        ProcessSample {
+            pid,
+            name: process.name().to_string(),
+            cpu: process.cpu_usage(),
+            memory: process.memory(),
+            read_rate: per_second(disk.read_bytes),
+            write_rate: per_second(disk.written_bytes),
+            pss: detail.map(|detail| detail.pss),
+            uss: detail.map(|detail| detail.uss),
+            net: res.process_net.as_ref().map(|tracker| {
+                let rate = tracker.rate_for(pid);
+                [rate.rx, rate.tx]
+            }),
+            status: format!("{:?}", process.status()),
+        }
+    }
+}
+
+// Charts and alerts carried from one replayed sample to the next, the way
+// SystemResources carries them for the live monitor
+struct Player {
+    history: Option<ResourceHistory>,
+    alerts: AlertEngine,
+}
+
+impl Player {
+    fn new(config: &MonitorConfig) -> Self {
+        Player {
+            history: config
+                .show_graphs
+                .then(|| ResourceHistory::new(config.graph_height)),
+            alerts: AlertEngine::new(),
+        }
+    }
+
+    fn show(&mut self, sample: &Sample, config: &MonitorConfig) -> Result<()> {
+        if let Some(ref mut history) = self.history {
+            let rx = sample.networks.iter().map(|net| net.rx_rate).sum();
+            let tx = sample.networks.iter().map(|net| net.tx_rate).sum();
+            history.push(sample.cpu.usage as f64, sample.memory.percent(), rx, tx);
+        }
+        self.alerts.restore(
+            sample
+                .alerts
+                .iter()
+                .map(|alert| {
+                    let level = match alert.level.as_str() {
+                        "CRIT" => AlertLevel::Critical,
+                        _ => AlertLevel::Warning,
+                    };
+                    let raised_at = Local
+                        .timestamp_opt(alert.raised_at, 0)
+                        .single()
+                        .unwrap_or_else(Local::now);
+                    (
+                        alert.key.clone(),
+                        Alert {
+                            level,
+                            source: alert.source.clone(),
+                            message: alert.message.clone(),
+                            raised_at,
+                        },
+                    )
+                })
+                .collect(),
+        );
+
+        if theme::interactive() {
+            print!("\x1B[2J\x1B[1;1H");
+        }
+        if config.show_compact_mode {
+            crate::print_compact(sample, None, None);
+        } else {
+            println!(
+                "{} {}",
+                "HERCULES".bold().good(),
+                clock::format(&sample.time()).value()
+            );
+            println!("{}", "==================================".good());
+            if config.show_cpu {
+                crate::monitor_cpu(None, &sample.cpu, self.history.as_ref())?;
+            }
+            if config.show_memory {
+                crate::monitor_memory(None, &sample.memory, self.history.as_ref())?;
+            }
+            if config.show_disk {
+                crate::monitor_disks(None, &sample.disks, "")?;
+            }
+            if config.show_network {
+                crate::monitor_network(None, &sample.networks, self.history.as_ref(), "")?;
+            }
+            if config.show_processes && !sample.processes.is_empty() {
+                let heading = i18n::tr("heading-processes");
+                println!("\n{}", heading.bold().warning());
+                println!("{}", i18n::underline(heading).warning());
+                let rows = sample.processes.len().min(config.max_processes);
+                let show_net = sample.processes.iter().any(|row| row.net.is_some());
+                crate::print_process_table(
+                    &sample.processes[..rows],
+                    config.process_memory_detail,
+                    show_net,
+                    None,
+                );
+            }
+            if !self.alerts.is_empty() {
+                alerts::monitor_alerts(&self.alerts)?;
+            }
+        }
+        io::stdout().flush()?;
+        Ok(())
+    }
+}
+
+// Handle "hercules record out.hrec [--interval MS] [--duration 10m] [--processes N]"
+pub fn run_record(args: &[String]) -> Result<()> {
+    let matches = Command::new("record")
+        .about("Record what the monitor shows on every refresh for 'hercules replay'")
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Recording to write, e.g. out.hrec")
+                .required(true),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .value_name("MS")
+                .help("Refresh interval in milliseconds (default: update_interval_ms)")
+                .value_parser(clap::value_parser!(u64).range(100..)),
+        )
+        .arg(
+            Arg::new("duration")
+                .long("duration")
+                .value_name("DURATION")
+                .help("Stop after this long, e.g. 30s or 10m (default: until Ctrl+C)")
+                .value_parser(summary::parse_duration),
+        )
+        .arg(
+            Arg::new("processes")
+                .long("processes")
+                .value_name("COUNT")
+                .help("Top processes to keep per refresh (default: max_processes)")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|e| e.exit());
+
+    let path = matches.get_one::<String>("file").unwrap();
+    let mut config: MonitorConfig = ConfigManager::new()?.get_config().into();
+    if let Some(&interval) = matches.get_one::<u64>("interval") {
+        config.update_interval_ms = interval;
+    }
+    let duration = matches.get_one::<Duration>("duration").copied();
+    let processes = matches
+        .get_one::<usize>("processes")
+        .copied()
+        .unwrap_or(config.max_processes);
+
+    let mut writer =
+        BufWriter::new(File::create(path).map_err(|e| anyhow!("Cannot create {}: {}", path, e))?);
+    let mut res = SystemResources::new(&config);
+    let header = Header {
+        format: "hercules-record".to_string(),
+        version: VERSION,
+        host: res.system.host_name().unwrap_or_default(),
+        started: Local::now().to_rfc3339(),
+        interval_ms: config.update_interval_ms,
+    };
+    writeln!(writer, "{}", serde_json::to_string(&header)?)?;
+
+    eprintln!("Recording to {}, press Ctrl+C to stop", path.value());
+    let interval = Duration::from_millis(config.update_interval_ms);
+    let started = Instant::now();
+    let mut player = Player::new(&config);
+    let mut ticks = 0;
+    loop {
+        thread::sleep(interval);
+        res.refresh();
+        let mut sample = Sample::collect(&res);
+        sample.processes = top_processes(&res, &config, processes);
+        player.show(&sample, &config)?;
+
+        let tick = Tick {
+            ms: started.elapsed().as_millis() as u64,
+            sample,
+        };
+        // Flushed per refresh so stopping with Ctrl+C still leaves a usable file
+        writeln!(writer, "{}", serde_json::to_string(&tick)?)?;
+        writer.flush()?;
+        ticks += 1;
+        if duration.is_some_and(|duration| started.elapsed() >= duration) {
+            break;
+        }
+    }
+
+    eprintln!(
+        "Recorded {} refreshes over {:.0}s to {}",
+        ticks.to_string().value(),
+        started.elapsed().as_secs_f64(),
+        path.value()
+    );
+    Ok(())
+}
+
+// The first `count` processes in the configured sort order
+fn top_processes(
+    res: &SystemResources,
+    config: &MonitorConfig,
+    count: usize,
+) -> Vec<ProcessSample> {
+    let mut processes: Vec<_> = res.system.processes().iter().collect();
+    let sort = config.process_sort;
+    crate::sort_processes(res, &mut processes, sort, false, &HashMap::new());
+    processes.truncate(count);
+    let details = crate::visible_memory_details(&processes, config.process_memory_detail);
+    crate::sort_processes(res, &mut processes, sort, false, &details);
+    processes
+        .into_iter()
+        .map(|(pid, process)| ProcessSample::new(res, process, details.get(&pid.as_u32())))
+        .collect()
+}
+
+// Handle "hercules replay out.hrec [--speed 2] [--step] [--compact] [--units si]"
+pub fn run_replay(args: &[String]) -> Result<()> {
+    let matches = Command::new("replay")
+        .about("Play back a recording made with 'hercules record' using the current settings")
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .help("Recording to play")
+                .required(true),
+        )
+        .arg(
+            Arg::new("speed")
+                .long("speed")
+                .value_name("FACTOR")
+                .help("Playback speed, e.g. 2 for twice as fast")
+                .default_value("1")
+                .value_parser(clap::value_parser!(f64)),
+        )
+        .arg(
+            Arg::new("step")
+                .long("step")
+                .help("Wait for Enter before each refresh instead of keeping the recorded timing")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .help("Show the recording in compact mode")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("units")
+                .long("units")
+                .value_name("UNITS")
+                .help("Byte units for this replay (default: the units setting)"),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|e| e.exit());
+
+    let path = matches.get_one::<String>("file").unwrap();
+    let speed = *matches.get_one::<f64>("speed").unwrap();
+    if speed <= 0.0 {
+        return Err(anyhow!("--speed must be greater than 0"));
+    }
+    let step = matches.get_flag("step");
+    let mut config: MonitorConfig = ConfigManager::new()?.get_config().into();
+    if matches.get_flag("compact") {
+        config.show_compact_mode = true;
+    }
+    if let Some(name) = matches.get_one::<String>("units") {
+        units::set_units(name)?;
+    }
+
+    let file = File::open(path).map_err(|e| anyhow!("Cannot open {}: {}", path, e))?;
+    let mut lines = BufReader::new(file).lines();
+    let header = lines
+        .next()
+        .and_then(|line| line.ok())
+        .and_then(|line| serde_json::from_str::<Header>(&line).ok())
+        .filter(|header| header.format == "hercules-record")
+        .ok_or_else(|| anyhow!("{} is not a Hercules recording", path))?;
+    if header.version != VERSION {
+        return Err(anyhow!(
+            "{} is a version {} recording, this Hercules replays version {}; record it again",
+            path,
+            header.version,
+            VERSION
+        ));
+    }
+
+    eprintln!(
+        "Replaying {} recorded on {} at {}",
+        path.value(),
+        header.host.value(),
+        header.started.value()
+    );
+    let mut player = Player::new(&config);
+    let mut previous_ms = None;
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tick: Tick = serde_json::from_str(&line)?;
+        if step {
+            if previous_ms.is_some() {
+                eprint!("Enter for the next refresh");
+                io::stderr().flush()?;
+                let mut input = String::new();
+                if io::stdin().read_line(&mut input)? == 0 {
+                    break;
+                }
+            }
+        } else if let Some(previous) = previous_ms {
+            let gap = tick.ms.saturating_sub(previous) as f64 / speed;
+            thread::sleep(Duration::from_millis(gap as u64));
+        }
+        previous_ms = Some(tick.ms);
+        player.show(&tick.sample, &config)?;
+    }
+    Ok(())
+}
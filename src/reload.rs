@@ -0,0 +1,53 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+use anyhow::{anyhow, Result};
+use log::debug;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+// Reports changes to the config file while monitoring continuously
+pub struct ConfigWatcher {
+    // Dropping the watcher stops the notifications
+    _watcher: RecommendedWatcher,
+    events: Receiver<()>,
+}
+
+impl ConfigWatcher {
+    pub fn new(config_path: &Path) -> Result<Self> {
+        // Editors often save by replacing the file, which a watch on the file
+        // itself would not survive, so watch its directory and filter by path
+        let directory = config_path
+            .parent()
+            .ok_or_else(|| anyhow!("Config file has no parent directory"))?;
+        let target: PathBuf = config_path.to_path_buf();
+        let (sender, events) = mpsc::channel();
+
+        let mut watcher = notify::recommended_watcher(
+            move |result: notify::Result<notify::Event>| match result {
+                Ok(event) if event.kind.is_modify() || event.kind.is_create() => {
+                    if event.paths.iter().any(|path| path == &target) {
+                        let _ = sender.send(());
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => debug!("Config watch error: {}", e),
+            },
+        )?;
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        Ok(ConfigWatcher {
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    // True if the file changed since the last call. A single save can fire
+    // several events, so all pending ones are drained together.
+    pub fn changed(&self) -> bool {
+        let mut changed = false;
+        while self.events.try_recv().is_ok() {
+            changed = true;
+        }
+        changed
+    }
+}
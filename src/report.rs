@@ -0,0 +1,336 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Local, Timelike};
+use clap::{Arg, ArgAction, Command};
+use log::{info, warn};
+use sysinfo::{System, SystemExt};
+
+use crate::clock;
+use crate::config::ConfigManager;
+use crate::history::HistoryStore;
+use crate::summary::parse_duration;
+use crate::units;
+
+// How far back the previous run of a schedule is looked for
+const MAX_PERIOD_MINUTES: i64 = 31 * 24 * 60;
+
+// Events listed at the end of a report
+const REPORT_EVENTS: usize = 10;
+
+// A five-field cron expression: minute, hour, day of month, month, day of week
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    // Cron matches either day field when both are restricted
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Schedule {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(anyhow!(
+                "Invalid schedule '{}': expected 5 fields, e.g. \"0 8 * * *\"",
+                expression
+            ));
+        };
+        // Sunday is both 0 and 7
+        let mut weekdays = parse_field(weekday, 0, 7)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Schedule {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days: parse_field(day, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+
+    pub fn matches(&self, time: &DateTime<Local>) -> bool {
+        let bit = |set: u64, value: u32| set & (1 << value) != 0;
+        let day = bit(self.days, time.day());
+        let weekday = bit(self.weekdays, time.weekday().num_days_from_sunday());
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+        bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+            && day_matches
+    }
+
+    // The last minute before `time` the schedule fired, within a month
+    fn previous(&self, time: DateTime<Local>) -> Option<DateTime<Local>> {
+        (1..=MAX_PERIOD_MINUTES)
+            .map(|minutes| time - chrono::Duration::minutes(minutes))
+            .find(|candidate| self.matches(candidate))
+    }
+}
+
+// "*", "5", "1-5", "*/15", "0-30/10" or a comma-separated list of those
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64> {
+    let invalid = || anyhow!("Invalid schedule field '{}'", field);
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (
+                    start.parse().map_err(|_| invalid())?,
+                    end.parse().map_err(|_| invalid())?,
+                ),
+                // "5/15" runs from 5 to the end of the range
+                None => {
+                    let start = range.parse().map_err(|_| invalid())?;
+                    (start, if part.contains('/') { max } else { start })
+                }
+            },
+        };
+        if step == 0 || start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+// Runs the report schedule from the refresh loop and sends each report
+// through the notification channels on a background thread
+pub struct ReportScheduler {
+    schedule: Schedule,
+    webhook: Option<String>,
+    // Also print reports to stdout, for daemon mode
+    echo: bool,
+    // The minute up to which the schedule has been checked
+    checked: DateTime<Local>,
+}
+
+impl ReportScheduler {
+    pub fn new(expression: &str, webhook: &str) -> Result<Self> {
+        Ok(ReportScheduler {
+            schedule: Schedule::parse(expression)?,
+            webhook: (!webhook.is_empty()).then(|| webhook.to_string()),
+            echo: false,
+            checked: start_of_minute(Local::now()),
+        })
+    }
+
+    pub fn set_echo(&mut self, echo: bool) {
+        self.echo = echo;
+    }
+
+    pub fn tick(&mut self) {
+        let now = start_of_minute(Local::now());
+        // After a suspend, only the last day is caught up on
+        let mut minute = self.checked.max(now - chrono::Duration::days(1));
+        let mut due = false;
+        while minute < now {
+            minute += chrono::Duration::minutes(1);
+            due |= self.schedule.matches(&minute);
+        }
+        self.checked = now;
+        if !due {
+            return;
+        }
+
+        let since = self
+            .schedule
+            .previous(now)
+            .unwrap_or(now - chrono::Duration::days(1));
+        let webhook = self.webhook.clone();
+        let echo = self.echo;
+        thread::spawn(move || match build_report(since, now) {
+            Ok(report) => dispatch(&report, webhook.as_deref(), echo),
+            Err(e) => warn!("Could not build the scheduled report: {}", e),
+        });
+    }
+}
+
+fn start_of_minute(time: DateTime<Local>) -> DateTime<Local> {
+    time.with_second(0)
+        .and_then(|time| time.with_nanosecond(0))
+        .unwrap_or(time)
+}
+
+// Summary of the history database between two times, as plain text
+pub fn build_report(since: DateTime<Local>, until: DateTime<Local>) -> Result<String> {
+    let store = HistoryStore::open()?;
+    let mut system = System::new();
+    system.refresh_system();
+    let host = system
+        .host_name()
+        .unwrap_or_else(|| "this host".to_string());
+
+    let mut lines = vec![
+        format!("Hercules report for {}", host),
+        format!("{} to {}", clock::format(&since), clock::format(&until)),
+        String::new(),
+    ];
+
+    let (start, end) = (since.timestamp(), until.timestamp());
+    let metrics: [(&str, &str, &str); 7] = [
+        ("cpu", "CPU", "%"),
+        ("memory", "Memory", "%"),
+        ("swap", "Swap", "%"),
+        ("load", "Load (1m)", ""),
+        ("temp", "Temperature", units::TEMPERATURE),
+        ("net_rx", "Network in", units::RATE),
+        ("net_tx", "Network out", units::RATE),
+    ];
+    let mut any_samples = false;
+    for (metric, label, unit) in metrics {
+        let Some(bucket) = store.summarize(metric, start, end)? else {
+            continue;
+        };
+        any_samples = true;
+        lines.push(format!(
+            "{:<14} avg {:>12}   max {:>12}",
+            label,
+            units::format_metric(bucket.avg, unit),
+            units::format_metric(bucket.max, unit)
+        ));
+    }
+    if !any_samples {
+        lines.push(
+            "No samples recorded in this period. Enable record_metrics to collect them."
+                .to_string(),
+        );
+    }
+
+    let boots: Vec<_> = store
+        .recent_boots(100)?
+        .into_iter()
+        .filter(|boot| boot.boot_time >= start && boot.boot_time < end)
+        .collect();
+    if let Some(latest) = boots.first() {
+        lines.push(format!(
+            "Reboots: {} (last at {})",
+            boots.len(),
+            clock::format_timestamp(latest.boot_time)
+        ));
+    }
+
+    let events = store.events(start)?;
+    let events: Vec<_> = events.into_iter().filter(|(ts, _, _)| *ts < end).collect();
+    if !events.is_empty() {
+        let mut kinds: BTreeMap<&str, usize> = BTreeMap::new();
+        for (_, kind, _) in &events {
+            *kinds.entry(kind.as_str()).or_default() += 1;
+        }
+        lines.push(format!(
+            "Events: {}",
+            kinds
+                .iter()
+                .map(|(kind, count)| format!("{} {}", count, kind))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        for (ts, kind, message) in events.iter().rev().take(REPORT_EVENTS).rev() {
+            lines.push(format!(
+                "  {} {}: {}",
+                clock::format_timestamp(*ts),
+                kind,
+                message
+            ));
+        }
+    }
+
+    Ok(lines.join("\n"))
+}
+
+// Saves the report next to the config file and sends it to the webhook
+fn dispatch(report: &str, webhook: Option<&str>, echo: bool) {
+    info!("Scheduled report:\n{}", report);
+    if echo {
+        println!("{}\n", report);
+    }
+    match ConfigManager::get_config_dir() {
+        Ok(dir) => {
+            if let Err(e) = fs::write(dir.join("last_report.txt"), report) {
+                warn!("Could not save the report: {}", e);
+            }
+        }
+        Err(e) => warn!("Could not save the report: {}", e),
+    }
+    if let Some(url) = webhook {
+        if let Err(e) = send_webhook(url, report) {
+            warn!("Could not send the report to {}: {}", url, e);
+        }
+    }
+}
+
+// Posts {"text": report}, which Slack, Mattermost and most chat webhooks accept
+fn send_webhook(url: &str, report: &str) -> Result<()> {
+    ureq::post(url)
+        .timeout(Duration::from_secs(15))
+        .set("Content-Type", "application/json")
+        .send_string(&serde_json::json!({ "text": report }).to_string())?;
+    Ok(())
+}
+
+// Handle "hercules report [--last 24h] [--send]"
+pub fn run_report(args: &[String]) -> Result<()> {
+    let matches = Command::new("report")
+        .about("Summarize the history database, as the scheduled report does")
+        .arg(
+            Arg::new("last")
+                .long("last")
+                .short('l')
+                .value_name("TIME")
+                .help("Period to cover, e.g. 24h or 7d")
+                .default_value("24h"),
+        )
+        .arg(
+            Arg::new("send")
+                .long("send")
+                .help("Also send the report to notify_webhook")
+                .action(ArgAction::SetTrue),
+        )
+        .try_get_matches_from(args)
+        .unwrap_or_else(|e| e.exit());
+
+    let last = parse_duration(matches.get_one::<String>("last").unwrap())?;
+    let until = Local::now();
+    let since = until - chrono::Duration::seconds(last.as_secs() as i64);
+    let report = build_report(since, until)?;
+    println!("{}", report);
+
+    if matches.get_flag("send") {
+        let webhook = ConfigManager::new()?.get_config().notify_webhook.clone();
+        if webhook.is_empty() {
+            return Err(anyhow!(
+                "No notification channel configured; set notify_webhook first"
+            ));
+        }
+        send_webhook(&webhook, &report)?;
+        println!("\nSent to {}", webhook);
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::theme::Themed;
+
+// Windows reported for every series, shortest first
+const WINDOWS: [(&str, Duration); 3] = [
+    ("1m", Duration::from_secs(60)),
+    ("5m", Duration::from_secs(300)),
+    ("15m", Duration::from_secs(900)),
+];
+
+// Timestamped samples covering the longest window
+#[derive(Default)]
+pub struct Series {
+    samples: VecDeque<(Instant, f64)>,
+}
+
+impl Series {
+    fn push(&mut self, now: Instant, value: f64) {
+        let longest = WINDOWS[WINDOWS.len() - 1].1;
+        while self
+            .samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > longest)
+        {
+            self.samples.pop_front();
+        }
+        if value.is_finite() {
+            self.samples.push_back((now, value));
+        }
+    }
+
+    // Min, average and max of the samples taken within `window`
+    fn window(&self, window: Duration) -> Option<(f64, f64, f64)> {
+        let now = Instant::now();
+        let (mut min, mut max, mut sum, mut count) = (f64::MAX, f64::MIN, 0.0, 0);
+        for (_, value) in self
+            .samples
+            .iter()
+            .rev()
+            .take_while(|(at, _)| now.duration_since(*at) <= window)
+        {
+            min = min.min(*value);
+            max = max.max(*value);
+            sum += value;
+            count += 1;
+        }
+        (count > 0).then(|| (min, sum / count as f64, max))
+    }
+
+    fn covered(&self) -> Duration {
+        match (self.samples.front(), self.samples.back()) {
+            (Some((first, _)), Some((last, _))) => last.duration_since(*first),
+            _ => Duration::ZERO,
+        }
+    }
+
+    // "1m 3/12/80  5m 2/10/80  15m 2/9/95" for min/avg/max. A window is only
+    // listed once the samples reach past the one before it, so early on 15m
+    // does not just repeat 1m. Needs two samples to say anything.
+    pub fn print(&self, label: &str, format: impl Fn(f64) -> String) {
+        if self.samples.len() < 2 {
+            return;
+        }
+        let covered = self.covered();
+        let parts: Vec<String> = WINDOWS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i == 0 || covered > WINDOWS[i - 1].1)
+            .filter_map(|(_, (name, window))| {
+                let (min, avg, max) = self.window(*window)?;
+                Some(format!(
+                    "{} {}/{}/{}",
+                    name,
+                    format(min).good(),
+                    format(avg).value(),
+                    format(max).warning()
+                ))
+            })
+            .collect();
+        println!("{} (min/avg/max): {}", label, parts.join("  "));
+    }
+}
+
+// Rolling statistics so spikes between refreshes still show up
+#[derive(Default)]
+pub struct RollingStats {
+    pub cpu: Series,
+    pub memory: Series,
+    pub net_rx: Series,
+    pub net_tx: Series,
+    pub sensor: Series,
+}
+
+impl RollingStats {
+    // Percentages for CPU and memory, bytes per second for the network
+    pub fn push(&mut self, cpu: f64, memory: f64, net_rx: f64, net_tx: f64) {
+        let now = Instant::now();
+        self.cpu.push(now, cpu);
+        self.memory.push(now, memory);
+        self.net_rx.push(now, net_rx);
+        self.net_tx.push(now, net_tx);
+    }
+
+    // Magnitude of the acceleration vector in m/s²
+    pub fn push_sensor(&mut self, acceleration: [f32; 3]) {
+        let magnitude = acceleration
+            .iter()
+            .map(|axis| (*axis as f64).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        self.sensor.push(Instant::now(), magnitude);
+    }
+}
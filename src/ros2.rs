@@ -0,0 +1,91 @@
+// ROS 2 publishing of IMU readings as sensor_msgs/Imu. Only built with
+// `cargo build --features ros2`, which needs a sourced ROS 2 install.
+
+use anyhow::Result;
+
+#[cfg(feature = "ros2")]
+use crate::fusion;
+use crate::sensors::SensorData;
+
+#[cfg(feature = "ros2")]
+pub struct ImuPublisher {
+    // The node has to outlive its publisher
+    _node: r2r::Node,
+    publisher: r2r::Publisher<r2r::sensor_msgs::msg::Imu>,
+    clock: r2r::Clock,
+    frame_id: String,
+}
+
+#[cfg(feature = "ros2")]
+impl ImuPublisher {
+    pub fn new(topic: &str, frame_id: &str) -> Result<Self> {
+        let context = r2r::Context::create()?;
+        let mut node = r2r::Node::create(context, "hercules", "")?;
+        let publisher = node.create_publisher::<r2r::sensor_msgs::msg::Imu>(
+            topic,
+            r2r::QosProfile::sensor_data(),
+        )?;
+        log::info!("Publishing IMU data on ROS 2 topic {}", topic);
+        Ok(ImuPublisher {
+            _node: node,
+            publisher,
+            clock: r2r::Clock::create(r2r::ClockType::SystemTime)?,
+            frame_id: frame_id.to_string(),
+        })
+    }
+
+    pub fn publish(&mut self, data: &SensorData) {
+        use r2r::geometry_msgs::msg::{Quaternion, Vector3};
+
+        let stamp = match self.clock.get_now() {
+            Ok(now) => r2r::Clock::to_builtin_time(&now),
+            Err(e) => {
+                log::debug!("ROS 2 clock: {}", e);
+                return;
+            }
+        };
+        // ROS wants rad/s; SensorData has deg/s
+        let [w, x, y, z] = data
+            .quaternion
+            .unwrap_or_else(|| fusion::from_euler(data.orientation))
+            .map(|v| v as f64);
+        let message = r2r::sensor_msgs::msg::Imu {
+            header: r2r::std_msgs::msg::Header {
+                stamp,
+                frame_id: self.frame_id.clone(),
+            },
+            orientation: Quaternion { x, y, z, w },
+            // All zeros is "covariance unknown" in sensor_msgs/Imu
+            orientation_covariance: vec![0.0; 9],
+            angular_velocity: Vector3 {
+                x: (data.gyro[0] as f64).to_radians(),
+                y: (data.gyro[1] as f64).to_radians(),
+                z: (data.gyro[2] as f64).to_radians(),
+            },
+            angular_velocity_covariance: vec![0.0; 9],
+            linear_acceleration: Vector3 {
+                x: data.acceleration[0] as f64,
+                y: data.acceleration[1] as f64,
+                z: data.acceleration[2] as f64,
+            },
+            linear_acceleration_covariance: vec![0.0; 9],
+        };
+        if let Err(e) = self.publisher.publish(&message) {
+            log::debug!("ROS 2 publish failed: {}", e);
+        }
+    }
+}
+
+#[cfg(not(feature = "ros2"))]
+pub struct ImuPublisher;
+
+#[cfg(not(feature = "ros2"))]
+impl ImuPublisher {
+    pub fn new(_topic: &str, _frame_id: &str) -> Result<Self> {
+        Err(anyhow::anyhow!(
+            "this build has no ROS 2 support, rebuild with `cargo build --features ros2`"
+        ))
+    }
+
+    pub fn publish(&mut self, _data: &SensorData) {}
+}
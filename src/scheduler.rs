@@ -0,0 +1,104 @@
+// Scheduler counters from /proc/stat
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SchedulerStats {
+    pub procs_running: u64,
+    pub procs_blocked: u64,
+    pub context_switches: u64,
+    // Aggregate CPU time in clock ticks, and the part stolen by a hypervisor
+    pub cpu_ticks: u64,
+    pub steal_ticks: u64,
+}
+
+#[cfg(target_os = "linux")]
+pub fn read_scheduler_stats() -> Option<SchedulerStats> {
+    let content = std::fs::read_to_string("/proc/stat").ok()?;
+    let mut stats = SchedulerStats::default();
+
+    for line in content.lines() {
+        // "cpu  user nice system idle iowait irq softirq steal guest guest_nice";
+        // guest time is already included in user
+        if let Some(times) = line.strip_prefix("cpu ") {
+            let times: Vec<u64> = times
+                .split_whitespace()
+                .filter_map(|v| v.parse().ok())
+                .collect();
+            stats.cpu_ticks = times.iter().take(8).sum();
+            stats.steal_ticks = times.get(7).copied().unwrap_or(0);
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let key = fields.next();
+        let value = fields.next().and_then(|v| v.parse::<u64>().ok());
+        match (key, value) {
+            (Some("procs_running"), Some(value)) => stats.procs_running = value,
+            (Some("procs_blocked"), Some(value)) => stats.procs_blocked = value,
+            (Some("ctxt"), Some(value)) => stats.context_switches = value,
+            _ => {}
+        }
+    }
+
+    Some(stats)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_scheduler_stats() -> Option<SchedulerStats> {
+    None
+}
+
+// Tracks run-queue length and the context switch rate between refreshes
+pub struct SchedulerMonitor {
+    stats: Option<SchedulerStats>,
+    context_switch_rate: f64,
+    steal_percent: f64,
+}
+
+impl SchedulerMonitor {
+    pub fn new() -> Self {
+        let stats = read_scheduler_stats();
+        // Until there is a second sample, report steal averaged since boot
+        let steal_percent = stats
+            .filter(|stats| stats.cpu_ticks > 0)
+            .map(|stats| stats.steal_ticks as f64 / stats.cpu_ticks as f64 * 100.0)
+            .unwrap_or(0.0);
+
+        SchedulerMonitor {
+            stats,
+            context_switch_rate: 0.0,
+            steal_percent,
+        }
+    }
+
+    pub fn refresh(&mut self, elapsed_secs: f64) {
+        let current = read_scheduler_stats();
+        if let (Some(previous), Some(current)) = (self.stats, current) {
+            if elapsed_secs > 0.0 {
+                self.context_switch_rate = current
+                    .context_switches
+                    .saturating_sub(previous.context_switches)
+                    as f64
+                    / elapsed_secs;
+            }
+            let ticks = current.cpu_ticks.saturating_sub(previous.cpu_ticks);
+            if ticks > 0 {
+                self.steal_percent =
+                    current.steal_ticks.saturating_sub(previous.steal_ticks) as f64 / ticks as f64
+                        * 100.0;
+            }
+        }
+        self.stats = current;
+    }
+
+    pub fn stats(&self) -> Option<SchedulerStats> {
+        self.stats
+    }
+
+    pub fn context_switch_rate(&self) -> f64 {
+        self.context_switch_rate
+    }
+
+    // Share of CPU time taken by the hypervisor over the last interval
+    pub fn steal_percent(&self) -> f64 {
+        self.steal_percent
+    }
+}
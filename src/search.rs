@@ -0,0 +1,44 @@
+use colored::*;
+
+use crate::theme::Themed;
+
+// Case-insensitive substring match of the `/` search; an empty search matches everything
+pub fn matches(text: &str, query: &str) -> bool {
+    query.is_empty() || text.to_lowercase().contains(&query.to_lowercase())
+}
+
+// `text` colored by `paint`, with every match of the search in the highlight color
+pub fn highlight(text: &str, query: &str, paint: impl Fn(&str) -> ColoredString) -> String {
+    let lower = text.to_lowercase();
+    let query = query.to_lowercase();
+    // Lowercasing can change byte offsets outside ASCII; leave such text unmarked
+    if query.is_empty() || lower.len() != text.len() {
+        return paint(text).to_string();
+    }
+
+    let mut out = String::new();
+    let mut push = |part: &str, marked: bool| {
+        // Empty parts would only add escape codes
+        if part.is_empty() {
+            return;
+        }
+        let part = if marked {
+            part.highlight().bold()
+        } else {
+            paint(part)
+        };
+        out.push_str(&part.to_string());
+    };
+    let mut rest = 0;
+    for (start, _) in lower.match_indices(&query) {
+        let end = start + query.len();
+        if !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            continue;
+        }
+        push(&text[rest..start], false);
+        push(&text[start..end], true);
+        rest = end;
+    }
+    push(&text[rest..], false);
+    out
+}
@@ -0,0 +1,114 @@
+use std::thread;
+use std::time::Duration;
+
+use crate::sensors::SensorDevice;
+use anyhow::{anyhow, Result};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+// A HID sensor described in the config file, for dongles the built-in list
+// does not know:
+//
+//   [[sensor_config.devices]]
+//   name = "IMU dongle"
+//   vendor_id = 0x1234
+//   product_id = 0x5678
+//   report_id = 3
+//   init = ["feature 03 01", "sleep 20"]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SensorDescriptor {
+    pub name: String,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    // For devices with numbered reports: only reports with this ID carry
+    // readings, and the ID byte is dropped before they are parsed
+    pub report_id: Option<u8>,
+    // Sent in order after opening, to start the device streaming
+    pub init: Vec<String>,
+}
+
+impl SensorDescriptor {
+    pub fn validate(&self) -> Result<()> {
+        for step in &self.init {
+            InitStep::parse(step).map_err(|e| anyhow!("Sensor device '{}': {}", self.name, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn initialize(&self, device: &SensorDevice) -> Result<()> {
+        for step in &self.init {
+            InitStep::parse(step)?.run(device)?;
+        }
+        Ok(())
+    }
+}
+
+// One line of a descriptor's init sequence. Bytes are hex and start with the
+// report ID, 00 for devices without numbered reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum InitStep {
+    // "feature 03 01": send a feature report
+    Feature(Vec<u8>),
+    // "output 01 80": write an output report
+    Output(Vec<u8>),
+    // "get-feature 05 40": read feature report 05, 0x40 bytes long; some
+    // devices switch modes when a report is read
+    GetFeature(u8, usize),
+    // "sleep 50": wait this many milliseconds
+    Sleep(u64),
+}
+
+impl InitStep {
+    fn parse(step: &str) -> Result<Self> {
+        let mut words = step.split_whitespace();
+        let kind = words.next().unwrap_or_default().to_lowercase();
+        let args: Vec<&str> = words.collect();
+        let bytes = || -> Result<Vec<u8>> {
+            if args.is_empty() {
+                return Err(anyhow!("'{}' needs at least a report ID", step));
+            }
+            args.iter()
+                .map(|byte| {
+                    u8::from_str_radix(byte.trim_start_matches("0x"), 16)
+                        .map_err(|_| anyhow!("Invalid hex byte '{}' in '{}'", byte, step))
+                })
+                .collect()
+        };
+        match kind.as_str() {
+            "feature" => Ok(InitStep::Feature(bytes()?)),
+            "output" => Ok(InitStep::Output(bytes()?)),
+            "get-feature" => {
+                let bytes = bytes()?;
+                let length = bytes.get(1).map(|&len| len as usize).unwrap_or(64);
+                Ok(InitStep::GetFeature(bytes[0], length.max(1)))
+            }
+            "sleep" => args
+                .first()
+                .and_then(|ms| ms.parse().ok())
+                .map(InitStep::Sleep)
+                .ok_or_else(|| anyhow!("'{}' needs milliseconds", step)),
+            _ => Err(anyhow!(
+                "Unknown init step '{}'. Use: feature, output, get-feature, sleep",
+                step
+            )),
+        }
+    }
+
+    fn run(&self, device: &SensorDevice) -> Result<()> {
+        debug!("Sensor init: {:?}", self);
+        match self {
+            InitStep::Feature(bytes) => device.send_feature_report(bytes)?,
+            InitStep::Output(bytes) => {
+                device.write(bytes)?;
+            }
+            InitStep::GetFeature(report_id, length) => {
+                let mut buf = vec![0u8; length + 1];
+                buf[0] = *report_id;
+                device.get_feature_report(&mut buf)?;
+            }
+            InitStep::Sleep(ms) => thread::sleep(Duration::from_millis(*ms)),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+
+use crate::sensors::SensorData;
+
+// Longest moving average or median window, in samples
+const MAX_WINDOW: usize = 64;
+
+// Smoothing for sensor_filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind {
+    None,
+    // Mean of the last N samples
+    Average,
+    // First order low-pass: each sample moves the output part of the way
+    Exponential,
+    // Middle of the last N samples, which drops single-sample spikes
+    Median,
+}
+
+impl FilterKind {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "none" | "off" | "" => Ok(FilterKind::None),
+            "average" | "mean" | "moving-average" => Ok(FilterKind::Average),
+            "exponential" | "ema" | "lowpass" | "low-pass" => Ok(FilterKind::Exponential),
+            "median" => Ok(FilterKind::Median),
+            _ => Err(anyhow!(
+                "Invalid sensor filter '{}'. Use: none, average, exponential, median",
+                name
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FilterKind::None => "none",
+            FilterKind::Average => "average",
+            FilterKind::Exponential => "exponential",
+            FilterKind::Median => "median",
+        }
+    }
+}
+
+// One axis of one channel
+#[derive(Default)]
+struct Axis {
+    history: VecDeque<f32>,
+    value: Option<f32>,
+}
+
+impl Axis {
+    fn window(&mut self, sample: f32, size: usize) {
+        self.history.push_back(sample);
+        while self.history.len() > size {
+            self.history.pop_front();
+        }
+    }
+
+    fn filter(&mut self, kind: FilterKind, sample: f32, window: usize, alpha: f32) -> f32 {
+        match kind {
+            FilterKind::None => sample,
+            FilterKind::Average => {
+                self.window(sample, window);
+                self.history.iter().sum::<f32>() / self.history.len() as f32
+            }
+            FilterKind::Median => {
+                self.window(sample, window);
+                let mut sorted: Vec<f32> = self.history.iter().copied().collect();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                sorted[sorted.len() / 2]
+            }
+            FilterKind::Exponential => {
+                let value = match self.value {
+                    Some(previous) => previous + alpha * (sample - previous),
+                    None => sample,
+                };
+                self.value = Some(value);
+                value
+            }
+        }
+    }
+}
+
+// Acceleration or gyro, with its own cutoff; 0 Hz leaves it unfiltered
+struct Channel {
+    cutoff_hz: f64,
+    axes: [Axis; 3],
+}
+
+impl Channel {
+    fn new(cutoff_hz: f64) -> Self {
+        Channel {
+            cutoff_hz,
+            axes: Default::default(),
+        }
+    }
+
+    fn apply(&mut self, kind: FilterKind, values: &mut [f32; 3], sample_secs: f64) {
+        if self.cutoff_hz <= 0.0 || sample_secs <= 0.0 {
+            return;
+        }
+        // A moving average of N samples has its -3 dB point near
+        // 0.443 * rate / N; the median window is sized the same way
+        let window = (0.443 / (self.cutoff_hz * sample_secs)).round() as usize;
+        let window = window.clamp(1, MAX_WINDOW);
+        let rc = 1.0 / (2.0 * PI * self.cutoff_hz);
+        let alpha = (sample_secs / (rc + sample_secs)) as f32;
+        for (axis, value) in self.axes.iter_mut().zip(values.iter_mut()) {
+            *value = axis.filter(kind, *value, window, alpha);
+        }
+    }
+}
+
+// Smooths readings in the sensor thread, before they are shown, streamed or
+// checked by alerts
+pub struct SensorFilter {
+    kind: FilterKind,
+    acceleration: Channel,
+    gyro: Channel,
+    // Time between readings, starting from the configured interval
+    sample_secs: f64,
+    last: Option<Instant>,
+}
+
+impl SensorFilter {
+    pub fn new(
+        kind: FilterKind,
+        accel_cutoff_hz: f64,
+        gyro_cutoff_hz: f64,
+        interval_ms: u64,
+    ) -> Self {
+        SensorFilter {
+            kind,
+            acceleration: Channel::new(accel_cutoff_hz),
+            gyro: Channel::new(gyro_cutoff_hz),
+            sample_secs: interval_ms as f64 / 1000.0,
+            last: None,
+        }
+    }
+
+    pub fn apply(&mut self, data: &mut SensorData) {
+        if self.kind == FilterKind::None {
+            return;
+        }
+        // Readings come slower than the interval when the device is slow,
+        // so the rate is taken from the timestamps, smoothed over ~10 samples
+        if let Some(last) = self.last {
+            let elapsed = data.timestamp.saturating_duration_since(last).as_secs_f64();
+            if elapsed > 0.0 {
+                self.sample_secs += (elapsed - self.sample_secs) * 0.1;
+            }
+        }
+        self.last = Some(data.timestamp);
+        self.acceleration
+            .apply(self.kind, &mut data.acceleration, self.sample_secs);
+        self.gyro.apply(self.kind, &mut data.gyro, self.sample_secs);
+    }
+}
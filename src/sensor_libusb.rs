@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use log::debug;
+use rusb::{
+    Context, Device, DeviceHandle, Direction, Recipient, RequestType, TransferType, UsbContext,
+};
+
+// USB interface class of HID devices
+const HID_CLASS: u8 = 3;
+
+// HID class requests and report types
+const GET_REPORT: u8 = 0x01;
+const SET_REPORT: u8 = 0x09;
+const OUTPUT_REPORT: u16 = 2;
+const FEATURE_REPORT: u16 = 3;
+
+const CONTROL_TIMEOUT: Duration = Duration::from_millis(1000);
+
+// One HID interface of a USB device, as "hercules sensors list" shows it
+#[derive(Debug, Clone)]
+pub struct UsbHidInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub serial: Option<String>,
+    pub product: Option<String>,
+    pub manufacturer: Option<String>,
+    pub interface: u8,
+    // "usb:<bus>-<address>:<interface>"
+    pub path: String,
+    device: Device<Context>,
+}
+
+// Every HID interface libusb can see. Strings need the device opened, so
+// they are missing where permissions do not allow that.
+pub fn list() -> Result<Vec<UsbHidInfo>> {
+    let mut found = Vec::new();
+    let context = Context::new()?;
+    for device in context.devices()?.iter() {
+        let Ok(descriptor) = device.device_descriptor() else {
+            continue;
+        };
+        let Ok(config) = device.active_config_descriptor() else {
+            continue;
+        };
+        let handle = device.open().ok();
+        let read = |read: &dyn Fn(&DeviceHandle<Context>) -> rusb::Result<String>| {
+            handle.as_ref().and_then(|handle| read(handle).ok())
+        };
+        let serial = read(&|handle| handle.read_serial_number_string_ascii(&descriptor));
+        let product = read(&|handle| handle.read_product_string_ascii(&descriptor));
+        let manufacturer = read(&|handle| handle.read_manufacturer_string_ascii(&descriptor));
+        for interface in config.interfaces() {
+            let is_hid = interface
+                .descriptors()
+                .any(|setting| setting.class_code() == HID_CLASS);
+            if !is_hid {
+                continue;
+            }
+            found.push(UsbHidInfo {
+                vendor_id: descriptor.vendor_id(),
+                product_id: descriptor.product_id(),
+                serial: serial.clone(),
+                product: product.clone(),
+                manufacturer: manufacturer.clone(),
+                interface: interface.number(),
+                path: format!(
+                    "usb:{}-{}:{}",
+                    device.bus_number(),
+                    device.address(),
+                    interface.number()
+                ),
+                device: device.clone(),
+            });
+        }
+    }
+    Ok(found)
+}
+
+// A claimed HID interface, read and written the way hidapi does it so the
+// report parsing and init steps work unchanged
+pub struct UsbHidDevice {
+    handle: DeviceHandle<Context>,
+    interface: u8,
+    input: u8,
+    output: Option<u8>,
+}
+
+impl UsbHidDevice {
+    pub fn open(info: &UsbHidInfo) -> Result<Self> {
+        let handle = info.device.open()?;
+        // Takes the interface from the kernel's HID driver while it is open
+        if let Err(e) = handle.set_auto_detach_kernel_driver(true) {
+            debug!("libusb cannot detach kernel drivers here: {}", e);
+        }
+        handle.claim_interface(info.interface)?;
+
+        let config = info.device.active_config_descriptor()?;
+        let endpoints: Vec<_> = config
+            .interfaces()
+            .filter(|interface| interface.number() == info.interface)
+            .flat_map(|interface| interface.descriptors())
+            .flat_map(|setting| {
+                setting
+                    .endpoint_descriptors()
+                    .filter(|endpoint| endpoint.transfer_type() == TransferType::Interrupt)
+                    .map(|endpoint| (endpoint.direction(), endpoint.address()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let endpoint = |wanted: Direction| {
+            endpoints
+                .iter()
+                .find(|(direction, _)| *direction == wanted)
+                .map(|&(_, address)| address)
+        };
+        let input = endpoint(Direction::In)
+            .ok_or_else(|| anyhow!("{} has no interrupt IN endpoint", info.path))?;
+        Ok(UsbHidDevice {
+            handle,
+            interface: info.interface,
+            input,
+            output: endpoint(Direction::Out),
+        })
+    }
+
+    // Like hidapi, a timeout is not an error but an empty read
+    pub fn read_timeout(&self, buf: &mut [u8], timeout_ms: i32) -> Result<usize> {
+        let timeout = Duration::from_millis(timeout_ms.max(1) as u64);
+        match self.handle.read_interrupt(self.input, buf, timeout) {
+            Ok(size) => Ok(size),
+            Err(rusb::Error::Timeout) => Ok(0),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // `data` starts with the report ID, 0 for devices without numbered
+    // reports, which is then not sent
+    pub fn write(&self, data: &[u8]) -> Result<usize> {
+        let (&report_id, payload) = data
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty output report"))?;
+        let report = if report_id == 0 { payload } else { data };
+        match self.output {
+            Some(endpoint) => Ok(self
+                .handle
+                .write_interrupt(endpoint, report, CONTROL_TIMEOUT)?),
+            // Without an OUT endpoint output reports go over the control pipe
+            None => {
+                self.set_report(OUTPUT_REPORT, report_id, report)?;
+                Ok(data.len())
+            }
+        }
+    }
+
+    pub fn send_feature_report(&self, data: &[u8]) -> Result<()> {
+        let (&report_id, payload) = data
+            .split_first()
+            .ok_or_else(|| anyhow!("Empty feature report"))?;
+        let report = if report_id == 0 { payload } else { data };
+        self.set_report(FEATURE_REPORT, report_id, report)
+    }
+
+    // `buf[0]` holds the report ID to read; the report is returned with it
+    pub fn get_feature_report(&self, buf: &mut [u8]) -> Result<usize> {
+        let report_id = *buf
+            .first()
+            .ok_or_else(|| anyhow!("Empty feature report buffer"))?;
+        let request_type =
+            rusb::request_type(Direction::In, RequestType::Class, Recipient::Interface);
+        let value = FEATURE_REPORT << 8 | report_id as u16;
+        if report_id == 0 {
+            let size = self.handle.read_control(
+                request_type,
+                GET_REPORT,
+                value,
+                self.interface as u16,
+                &mut buf[1..],
+                CONTROL_TIMEOUT,
+            )?;
+            Ok(size + 1)
+        } else {
+            Ok(self.handle.read_control(
+                request_type,
+                GET_REPORT,
+                value,
+                self.interface as u16,
+                buf,
+                CONTROL_TIMEOUT,
+            )?)
+        }
+    }
+
+    fn set_report(&self, report_type: u16, report_id: u8, report: &[u8]) -> Result<()> {
+        let request_type =
+            rusb::request_type(Direction::Out, RequestType::Class, Recipient::Interface);
+        self.handle.write_control(
+            request_type,
+            SET_REPORT,
+            report_type << 8 | report_id as u16,
+            self.interface as u16,
+            report,
+            CONTROL_TIMEOUT,
+        )?;
+        Ok(())
+    }
+}
+
+impl Drop for UsbHidDevice {
+    fn drop(&mut self) {
+        // Hands the interface back to the kernel driver
+        let _ = self.handle.release_interface(self.interface);
+    }
+}
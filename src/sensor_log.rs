@@ -0,0 +1,105 @@
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use crossbeam_channel::{unbounded, Sender};
+use log::{debug, warn};
+
+use crate::sensor_stream;
+use crate::sensors::SensorData;
+
+const HEADER: &str = "unix_ms,accel_x,accel_y,accel_z,gyro_x,gyro_y,gyro_z,\
+    roll,pitch,yaw,temperature,quat_w,quat_x,quat_y,quat_z";
+
+// Appends every sensor reading to a CSV file. Readings are collected in
+// memory and written in batches by a separate thread, so the sensor thread
+// never waits on the disk and an SD card sees a few large writes instead of
+// one small write per reading.
+pub struct SensorLog {
+    buffer: Vec<SensorData>,
+    batch_size: usize,
+    flush_interval: Duration,
+    last_flush: Instant,
+    sender: Sender<Vec<SensorData>>,
+}
+
+impl SensorLog {
+    pub fn new(path: &str, batch_size: usize, flush_secs: u64) -> Result<Self> {
+        let path = PathBuf::from(path);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let empty = file.metadata()?.len() == 0;
+        let (sender, receiver) = unbounded::<Vec<SensorData>>();
+
+        // Readings carry an Instant, so they are dated from this pair
+        let anchor = (Instant::now(), SystemTime::now());
+        thread::spawn(move || {
+            let mut writer = BufWriter::new(file);
+            if empty {
+                if let Err(e) = writeln!(writer, "{}", HEADER) {
+                    warn!("Sensor log {}: {}", path.display(), e);
+                }
+            }
+            for batch in receiver {
+                let result = batch
+                    .iter()
+                    .try_for_each(|data| writeln!(writer, "{}", line(data, anchor)))
+                    .and_then(|_| writer.flush());
+                match result {
+                    Ok(()) => debug!("Wrote {} sensor readings", batch.len()),
+                    Err(e) => warn!("Sensor log {}: {}", path.display(), e),
+                }
+            }
+        });
+
+        Ok(SensorLog {
+            buffer: Vec::with_capacity(batch_size),
+            batch_size: batch_size.max(1),
+            flush_interval: Duration::from_secs(flush_secs.max(1)),
+            last_flush: Instant::now(),
+            sender,
+        })
+    }
+
+    pub fn push(&mut self, data: SensorData) {
+        self.buffer.push(data);
+        if self.buffer.len() >= self.batch_size || self.last_flush.elapsed() >= self.flush_interval
+        {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        self.last_flush = Instant::now();
+        if !self.buffer.is_empty() {
+            let batch = std::mem::replace(&mut self.buffer, Vec::with_capacity(self.batch_size));
+            // Only fails once the writer thread is gone
+            self.sender.send(batch).ok();
+        }
+    }
+}
+
+impl Drop for SensorLog {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+fn line(data: &SensorData, (instant, time): (Instant, SystemTime)) -> String {
+    let stamp = if data.timestamp >= instant {
+        time + data.timestamp.duration_since(instant)
+    } else {
+        time - instant.duration_since(data.timestamp)
+    };
+    let mut line = stamp
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        .to_string();
+    for value in sensor_stream::values(data) {
+        line.push_str(&format!(",{:.5}", value));
+    }
+    line
+}
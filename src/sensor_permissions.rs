@@ -0,0 +1,89 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+
+use crate::sensor_devices::SensorDescriptor;
+use crate::sensors::SUPPORTED_SENSORS;
+
+const RULES_PATH: &str = "/etc/udev/rules.d/70-hercules-sensors.rules";
+
+// Udev rules that let non-root users open the built-in and configured
+// sensors. uaccess covers whoever is logged in at the seat, plugdev covers
+// SSH sessions on a Pi, whose default user is in that group.
+pub fn rules(devices: &[SensorDescriptor]) -> String {
+    let mut ids: Vec<(u16, u16, &str)> = SUPPORTED_SENSORS.to_vec();
+    for device in devices {
+        let ids_match = |&(vendor_id, product_id, _): &(u16, u16, &str)| {
+            (vendor_id, product_id) == (device.vendor_id, device.product_id)
+        };
+        if !ids.iter().any(ids_match) {
+            ids.push((device.vendor_id, device.product_id, &device.name));
+        }
+    }
+
+    let access = r#"MODE="0660", GROUP="plugdev", TAG+="uaccess""#;
+    let mut rules = String::from("# Written by `hercules sensors setup-permissions`\n");
+    for (vendor_id, product_id, name) in ids {
+        rules.push_str(&format!("\n# {}\n", name));
+        // USB devices carry their IDs as attributes of a parent device
+        rules.push_str(&format!(
+            "SUBSYSTEM==\"hidraw\", ATTRS{{idVendor}}==\"{:04x}\", ATTRS{{idProduct}}==\"{:04x}\", {}\n",
+            vendor_id, product_id, access
+        ));
+        // For the libusb backend
+        rules.push_str(&format!(
+            "SUBSYSTEM==\"usb\", ATTRS{{idVendor}}==\"{:04x}\", ATTRS{{idProduct}}==\"{:04x}\", {}\n",
+            vendor_id, product_id, access
+        ));
+        // Bluetooth devices only have them in the HID device name
+        rules.push_str(&format!(
+            "KERNEL==\"hidraw*\", KERNELS==\"*:{:04X}:{:04X}.*\", {}\n",
+            vendor_id, product_id, access
+        ));
+    }
+    rules
+}
+
+// Writes the rules and has udev apply them to devices already plugged in
+pub fn install(devices: &[SensorDescriptor]) -> Result<()> {
+    if !cfg!(target_os = "linux") {
+        return Err(anyhow!("Sensor permissions are only needed on Linux"));
+    }
+    fs::write(RULES_PATH, rules(devices)).map_err(|e| match e.kind() {
+        ErrorKind::PermissionDenied => anyhow!(
+            "Cannot write {}: run `sudo hercules sensors setup-permissions`",
+            RULES_PATH
+        ),
+        _ => anyhow!("Cannot write {}: {}", RULES_PATH, e),
+    })?;
+
+    for args in [
+        &["control", "--reload-rules"][..],
+        &["trigger", "--action=add", "--subsystem-match=hidraw"],
+        &["trigger", "--action=add", "--subsystem-match=usb"],
+    ] {
+        let status = Command::new("udevadm")
+            .args(args)
+            .status()
+            .map_err(|e| anyhow!("Cannot run udevadm: {}", e))?;
+        if !status.success() {
+            return Err(anyhow!("udevadm {} failed with {}", args.join(" "), status));
+        }
+    }
+    Ok(())
+}
+
+// "hercules sensors setup-permissions [--print]"
+pub fn run_setup(devices: &[SensorDescriptor], args: &[String]) -> Result<()> {
+    if args.iter().any(|arg| arg == "--print") {
+        print!("{}", rules(devices));
+        return Ok(());
+    }
+    install(devices)?;
+    println!("Wrote {} and reloaded udev.", RULES_PATH);
+    println!("Replug the sensor, then run hercules without sudo.");
+    println!("Over SSH, your user needs to be in the plugdev group.");
+    Ok(())
+}
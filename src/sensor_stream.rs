@@ -0,0 +1,121 @@
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use log::debug;
+
+use crate::sensors::SensorData;
+use crate::units;
+
+// OSC address of the IMU message
+const OSC_ADDRESS: &str = "/hercules/imu";
+
+// Wire formats for sensor_stream_format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    // One OSC message of fourteen float32 arguments
+    Osc,
+    // One comma separated text line per packet
+    Csv,
+}
+
+impl StreamFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "osc" => Ok(StreamFormat::Osc),
+            "csv" | "udp" => Ok(StreamFormat::Csv),
+            _ => Err(anyhow!(
+                "Invalid sensor stream format '{}'. Use: osc, csv",
+                name
+            )),
+        }
+    }
+}
+
+// Sends every sensor reading over UDP as it is read, so other programs can
+// consume the IMU feed at its full rate. Both formats carry acceleration x/y/z,
+// gyro x/y/z, roll/pitch/yaw, temperature and the orientation quaternion
+// w/x/y/z, in that order; CSV lines start with the milliseconds since the
+// stream started.
+pub struct SensorStream {
+    socket: UdpSocket,
+    format: StreamFormat,
+    started: Instant,
+}
+
+impl SensorStream {
+    // `target` is "host:port"
+    pub fn new(target: &str, format: StreamFormat) -> Result<Self> {
+        let address = target
+            .to_socket_addrs()
+            .map_err(|e| anyhow!("Invalid sensor stream target '{}': {}", target, e))?
+            .next()
+            .ok_or_else(|| anyhow!("Could not resolve sensor stream target {}", target))?;
+        // Bind to the same address family as the receiver
+        let local = if address.is_ipv6() {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(address)?;
+        Ok(SensorStream {
+            socket,
+            format,
+            started: Instant::now(),
+        })
+    }
+
+    pub fn send(&self, data: &SensorData) {
+        let values = values(data);
+        let packet = match self.format {
+            StreamFormat::Osc => osc_message(OSC_ADDRESS, &values),
+            StreamFormat::Csv => {
+                let elapsed = data.timestamp.saturating_duration_since(self.started);
+                let mut line = elapsed.as_millis().to_string();
+                for value in values {
+                    line.push_str(&format!(",{:.5}", value));
+                }
+                line.push('\n');
+                line.into_bytes()
+            }
+        };
+        // Nobody listening is normal for UDP, so failures are only logged
+        if let Err(e) = self.socket.send(&packet) {
+            debug!("Sensor stream send failed: {}", e);
+        }
+    }
+}
+
+// Acceleration and gyro in the configured sensor units
+pub fn values(data: &SensorData) -> [f32; 14] {
+    let mut values = [0.0; 14];
+    for axis in 0..3 {
+        values[axis] = units::acceleration(data.acceleration[axis] as f64) as f32;
+        values[3 + axis] = units::angular_rate(data.gyro[axis] as f64) as f32;
+    }
+    values[6..9].copy_from_slice(&data.orientation);
+    values[9] = data.temperature;
+    values[10..14].copy_from_slice(&data.quaternion.unwrap_or([1.0, 0.0, 0.0, 0.0]));
+    values
+}
+
+// OSC strings are NUL terminated and padded to a multiple of four bytes
+fn osc_string(buffer: &mut Vec<u8>, text: &str) {
+    buffer.extend_from_slice(text.as_bytes());
+    buffer.push(0);
+    while !buffer.len().is_multiple_of(4) {
+        buffer.push(0);
+    }
+}
+
+// An OSC 1.0 message with float32 arguments, big endian
+fn osc_message(address: &str, values: &[f32]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(64 + values.len() * 4);
+    osc_string(&mut buffer, address);
+    osc_string(&mut buffer, &format!(",{}", "f".repeat(values.len())));
+    for value in values {
+        buffer.extend_from_slice(&value.to_be_bytes());
+    }
+    buffer
+}
@@ -0,0 +1,267 @@
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+
+use crate::alerts::AlertLevel;
+use crate::sensors::SensorData;
+use crate::units::STANDARD_GRAVITY;
+
+// What a trigger looks at, in m/s², deg/s and degrees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quantity {
+    AccelMagnitude,
+    GyroMagnitude,
+    // Angle between the sensor's z axis and vertical
+    Tilt,
+    Roll,
+    Pitch,
+    Yaw,
+    Accel(usize),
+    Gyro(usize),
+    Temperature,
+}
+
+impl Quantity {
+    const NAMES: &'static str = "accel_magnitude, gyro_magnitude, tilt, roll, pitch, yaw, \
+        accel_x/y/z, gyro_x/y/z, temperature";
+
+    fn parse(name: &str) -> Result<Self> {
+        Ok(match name {
+            "accel_magnitude" | "acceleration" => Quantity::AccelMagnitude,
+            "gyro_magnitude" | "rotation" => Quantity::GyroMagnitude,
+            "tilt" => Quantity::Tilt,
+            "roll" => Quantity::Roll,
+            "pitch" => Quantity::Pitch,
+            "yaw" => Quantity::Yaw,
+            "accel_x" => Quantity::Accel(0),
+            "accel_y" => Quantity::Accel(1),
+            "accel_z" => Quantity::Accel(2),
+            "gyro_x" => Quantity::Gyro(0),
+            "gyro_y" => Quantity::Gyro(1),
+            "gyro_z" => Quantity::Gyro(2),
+            "temperature" | "temp" => Quantity::Temperature,
+            _ => {
+                return Err(anyhow!(
+                    "Unknown trigger quantity '{}'. Use: {}",
+                    name,
+                    Self::NAMES
+                ))
+            }
+        })
+    }
+
+    fn value(self, data: &SensorData) -> f32 {
+        let magnitude = |v: [f32; 3]| v.iter().map(|a| a * a).sum::<f32>().sqrt();
+        match self {
+            Quantity::AccelMagnitude => magnitude(data.acceleration),
+            Quantity::GyroMagnitude => magnitude(data.gyro),
+            Quantity::Tilt => {
+                let [roll, pitch, _] = data.orientation.map(f32::to_radians);
+                (roll.cos() * pitch.cos())
+                    .clamp(-1.0, 1.0)
+                    .acos()
+                    .to_degrees()
+            }
+            Quantity::Roll => data.orientation[0],
+            Quantity::Pitch => data.orientation[1],
+            Quantity::Yaw => data.orientation[2],
+            Quantity::Accel(axis) => data.acceleration[axis],
+            Quantity::Gyro(axis) => data.gyro[axis],
+            Quantity::Temperature => data.temperature,
+        }
+    }
+}
+
+// The threshold's unit, converted to what Quantity::value returns
+fn threshold(number: &str, unit: &str) -> Result<f32> {
+    let value: f32 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid trigger threshold '{}'", number))?;
+    Ok(match unit {
+        "" | "m/s2" | "m/s²" | "deg/s" | "°/s" | "deg" | "°" | "c" | "°c" => value,
+        "g" => value * STANDARD_GRAVITY as f32,
+        "rad/s" | "rad" => value.to_degrees(),
+        _ => return Err(anyhow!("Unknown trigger unit '{}'", unit)),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Alert(AlertLevel),
+    // Shell command, started without waiting for it
+    Run(String),
+}
+
+// `gyro_magnitude > 200 deg/s -> run "/usr/local/bin/capture.sh"`
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    pub text: String,
+    quantity: Quantity,
+    above: bool,
+    threshold: f32,
+    pub action: Action,
+}
+
+impl Trigger {
+    pub fn parse(text: &str) -> Result<Self> {
+        let (condition, action) = text
+            .split_once("->")
+            .ok_or_else(|| anyhow!("Trigger '{}' needs '-> action'", text))?;
+        let condition = condition.trim().to_lowercase();
+        let (name, above, rest) = [(">=", true), ("<=", false), (">", true), ("<", false)]
+            .iter()
+            .find_map(|&(op, above)| {
+                condition
+                    .split_once(op)
+                    .map(|(name, rest)| (name.trim(), above, rest.trim()))
+            })
+            .ok_or_else(|| anyhow!("Trigger '{}' needs a > or < comparison", text))?;
+        // "45deg" and "45 deg" both work
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+            .unwrap_or(rest.len());
+        let (number, unit) = rest.split_at(split);
+
+        let action = action.trim();
+        let action = match action.split_once(char::is_whitespace) {
+            Some(("run", command)) => Action::Run(command.trim().trim_matches('"').to_string()),
+            _ => match action.to_lowercase().as_str() {
+                "alert" | "alert warning" => Action::Alert(AlertLevel::Warning),
+                "alert critical" => Action::Alert(AlertLevel::Critical),
+                _ => {
+                    return Err(anyhow!(
+                        "Unknown trigger action '{}'. Use: alert, alert critical, run \"command\"",
+                        action
+                    ))
+                }
+            },
+        };
+
+        Ok(Trigger {
+            text: text.trim().to_string(),
+            quantity: Quantity::parse(name)?,
+            above,
+            threshold: threshold(number.trim(), unit.trim())?,
+            action,
+        })
+    }
+
+    fn holds(&self, data: &SensorData) -> bool {
+        let value = self.quantity.value(data);
+        if self.above {
+            value > self.threshold
+        } else {
+            value < self.threshold
+        }
+    }
+}
+
+// Checks that every trigger in the list parses
+pub fn validate(triggers: &[String]) -> Result<()> {
+    for trigger in triggers {
+        Trigger::parse(trigger)?;
+    }
+    Ok(())
+}
+
+// A trigger and whether it has fired
+#[derive(Debug, Clone)]
+pub struct TriggerState {
+    pub trigger: Trigger,
+    pub active: bool,
+    since: Option<Instant>,
+}
+
+// Evaluates triggers on every reading in the sensor thread. A condition has
+// to hold for the debounce time before the trigger fires, and has to stop
+// holding for as long before it can fire again, so a noisy reading near the
+// threshold does not run the action over and over.
+pub struct TriggerEngine {
+    states: Arc<Mutex<Vec<TriggerState>>>,
+    debounce: Duration,
+}
+
+impl TriggerEngine {
+    pub fn new(triggers: &[String], debounce_ms: u64) -> Result<Self> {
+        let states = triggers
+            .iter()
+            .map(|text| {
+                Ok(TriggerState {
+                    trigger: Trigger::parse(text)?,
+                    active: false,
+                    since: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(TriggerEngine {
+            states: Arc::new(Mutex::new(states)),
+            debounce: Duration::from_millis(debounce_ms),
+        })
+    }
+
+    // Shared with SensorManager, which turns alert triggers into alerts
+    pub fn states(&self) -> Arc<Mutex<Vec<TriggerState>>> {
+        self.states.clone()
+    }
+
+    pub fn evaluate(&self, data: &SensorData) {
+        let Ok(mut states) = self.states.lock() else {
+            return;
+        };
+        for state in states.iter_mut() {
+            // `since` is when the condition last changed away from `active`
+            if state.trigger.holds(data) == state.active {
+                state.since = None;
+                continue;
+            }
+            let since = *state.since.get_or_insert(data.timestamp);
+            if data.timestamp.saturating_duration_since(since) < self.debounce {
+                continue;
+            }
+            state.active = !state.active;
+            state.since = None;
+            if state.active {
+                info!("Sensor trigger fired: {}", state.trigger.text);
+                if let Action::Run(ref command) = state.trigger.action {
+                    run(command);
+                }
+            }
+        }
+    }
+}
+
+// Starts the command through the shell without waiting for it, the same way
+// the watchdog runs its restart commands
+fn run(command: &str) {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let child = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            warn!("Sensor trigger '{}' failed: {}", command, e);
+            return;
+        }
+    };
+    // Reap the child so it does not linger as a zombie
+    let command = command.to_string();
+    thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            warn!("Sensor trigger '{}' exited with {}", command, status)
+        }
+        Err(e) => warn!("Sensor trigger '{}' failed: {}", command, e),
+        _ => {}
+    });
+}
@@ -1,23 +1,54 @@
 use std::error::Error;
+use std::ffi::CString;
 use std::fmt;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
-use crossbeam_channel::{bounded, Receiver};
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
 use hidapi::{HidApi, HidDevice};
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 
+use crate::activity::{Activity, Classifier};
+use crate::controllers::Controller;
+use crate::dead_reckoning::{DeadReckoning, Motion};
+use crate::fusion::{OrientationFilter, Quaternion};
+use crate::ros2::ImuPublisher;
+use crate::sensor_devices::SensorDescriptor;
+use crate::sensor_filter::{FilterKind, SensorFilter};
+use crate::sensor_libusb::{self, UsbHidDevice, UsbHidInfo};
+use crate::sensor_log::SensorLog;
+use crate::sensor_stream::{SensorStream, StreamFormat};
+use crate::sensor_triggers::{TriggerEngine, TriggerState};
+use crate::units;
+
 // Common sensor data structure
 #[derive(Debug, Clone, Copy)]
 pub struct SensorData {
     pub timestamp: Instant,
-    pub acceleration: [f32; 3], // x, y, z in m/s²
-    pub gyro: [f32; 3],         // x, y, z in deg/s
-    pub orientation: [f32; 3],  // roll, pitch, yaw in degrees
-    pub temperature: f32,       // in °C
+    pub acceleration: [f32; 3],         // x, y, z in m/s²
+    pub gyro: [f32; 3],                 // x, y, z in deg/s
+    pub orientation: [f32; 3],          // roll, pitch, yaw in degrees
+    pub quaternion: Option<Quaternion>, // w, x, y, z; None until fused or read
+    pub motion: Option<Motion>,         // with sensor_dead_reckoning only
+    pub activity: Activity,             // still, walking, ...; step and impact counts
+    pub temperature: f32,               // in °C
+    // The HID report the values were parsed from, for debugging new devices
+    pub raw: [u8; 64],
+    pub raw_len: usize,
+}
+
+impl SensorData {
+    // Raw report as hex bytes, empty until something has been read
+    pub fn raw_hex(&self) -> String {
+        self.raw[..self.raw_len]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 impl Default for SensorData {
@@ -27,18 +58,58 @@ impl Default for SensorData {
             acceleration: [0.0; 3],
             gyro: [0.0; 3],
             orientation: [0.0; 3],
+            quaternion: None,
+            motion: None,
+            activity: Activity::default(),
             temperature: 0.0,
+            raw: [0; 64],
+            raw_len: 0,
         }
     }
 }
 
 // Sensor configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SensorConfig {
     pub enabled: bool,
     pub update_interval_ms: u64,
     #[allow(dead_code)]
     pub use_celsius: bool,
+    // "host:port" to stream every reading to over UDP, empty for none
+    pub stream_target: String,
+    // "osc" or "csv"
+    pub stream_format: String,
+    // Smoothing applied in the sensor thread: none, average, exponential or
+    // median, with a cutoff per channel (0 leaves that channel raw)
+    pub filter: String,
+    pub filter_accel_cutoff_hz: f64,
+    pub filter_gyro_cutoff_hz: f64,
+    // HID sensors beyond the built-in list, see SensorDescriptor
+    pub devices: Vec<SensorDescriptor>,
+    // CSV file every reading is appended to, empty for none; written in
+    // batches of log_batch_size readings or every log_flush_secs
+    pub log_path: String,
+    pub log_batch_size: usize,
+    pub log_flush_secs: u64,
+    // "<quantity> <op> <threshold> -> <action>" rules checked on every reading
+    pub triggers: Vec<String>,
+    // How long a trigger condition must hold, or stop holding, to count
+    pub trigger_debounce_ms: u64,
+    // Experimental: integrate acceleration into velocity and displacement
+    pub dead_reckoning: bool,
+    // Units shown and exported: "m/s2" or "g", "deg/s" or "rad/s"
+    pub accel_units: String,
+    pub gyro_units: String,
+    // Publish readings as sensor_msgs/Imu (needs the ros2 build feature)
+    pub ros2: bool,
+    pub ros2_topic: String,
+    pub ros2_frame_id: String,
+    // Picks one of several matching devices; empty and -1 match any
+    pub serial: String,
+    pub interface: i32,
+    // "hidraw" or "libusb", see HidBackend
+    pub hid_backend: String,
 }
 
 impl Default for SensorConfig {
@@ -47,6 +118,58 @@ impl Default for SensorConfig {
             enabled: false,
             update_interval_ms: 100,
             use_celsius: true,
+            stream_target: String::new(),
+            stream_format: "osc".to_string(),
+            filter: "none".to_string(),
+            filter_accel_cutoff_hz: 5.0,
+            filter_gyro_cutoff_hz: 10.0,
+            devices: Vec::new(),
+            log_path: String::new(),
+            log_batch_size: 500,
+            log_flush_secs: 30,
+            triggers: Vec::new(),
+            trigger_debounce_ms: 200,
+            dead_reckoning: false,
+            accel_units: "m/s2".to_string(),
+            gyro_units: "deg/s".to_string(),
+            ros2: false,
+            ros2_topic: "/imu/data".to_string(),
+            ros2_frame_id: "imu_link".to_string(),
+            serial: String::new(),
+            interface: -1,
+            hid_backend: "hidraw".to_string(),
+        }
+    }
+}
+
+impl SensorConfig {
+    // Whether a device passes the serial number and interface filters
+    fn selects(&self, info: &HidInfo) -> bool {
+        (self.serial.is_empty() || info.serial.as_deref() == Some(self.serial.as_str()))
+            && (self.interface < 0 || info.interface == self.interface)
+    }
+}
+
+// How well the sensor thread keeps up with update_interval_ms
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SensorStats {
+    // Readings per second over the last second
+    pub samples_per_sec: f64,
+    pub samples: u64,
+    // Reads that returned no report in time
+    pub timeouts: u64,
+    // Readings thrown away because the display had not taken the previous ones
+    pub dropped: u64,
+}
+
+impl SensorStats {
+    // Timeouts and dropped readings as a share of all read attempts
+    pub fn drop_percent(&self) -> f64 {
+        let attempts = self.samples + self.timeouts;
+        if attempts == 0 {
+            0.0
+        } else {
+            (self.timeouts + self.dropped) as f64 / attempts as f64 * 100.0
         }
     }
 }
@@ -55,12 +178,12 @@ impl Default for SensorConfig {
 #[derive(Debug)]
 pub enum SensorError {
     NotFound,
+    Timeout,
     #[allow(dead_code)]
     ConnectionFailed(String),
     ReadError(String),
     #[allow(dead_code)]
     Disconnected,
-    #[allow(dead_code)]
     InitializationFailed(String),
 }
 
@@ -68,6 +191,7 @@ impl fmt::Display for SensorError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             SensorError::NotFound => write!(f, "No compatible sensor found"),
+            SensorError::Timeout => write!(f, "Timed out waiting for sensor data"),
             SensorError::ConnectionFailed(s) => write!(f, "Failed to connect to sensor: {}", s),
             SensorError::ReadError(s) => write!(f, "Failed to read from sensor: {}", s),
             SensorError::Disconnected => write!(f, "Sensor disconnected"),
@@ -80,11 +204,188 @@ impl fmt::Display for SensorError {
 
 impl Error for SensorError {}
 
+// Built-in sensors by vendor_id, product_id, and description
+pub const SUPPORTED_SENSORS: [(u16, u16, &str); 6] = [
+    // MPU-6050 based USB adapters
+    (0x16c0, 0x0486, "MPU-6050"),
+    // Common IMU adapters
+    (0x2341, 0x8036, "Arduino Leonardo"), // Arduino with IMU shield
+    (0x1b4f, 0x9206, "SparkFun 9DoF"),    // SparkFun 9DoF sensor
+    // Mainstream gaming controllers with gyro (for testing)
+    (0x054c, 0x05c4, "Sony DualShock 4"), // PS4 controller, first revision
+    (0x054c, 0x09cc, "Sony DualShock 4"), // PS4 controller
+    (0x057e, 0x2009, "Nintendo Switch Pro Controller"),
+];
+
+// How sensors are reached. hidraw goes through hidapi and the kernel's HID
+// driver, which also sees Bluetooth devices; on macOS and Windows hidapi uses
+// the system HID API instead. libusb claims USB devices directly, for
+// kernels without hidraw or devices the HID driver does not bind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HidBackend {
+    Hidraw,
+    Libusb,
+}
+
+impl HidBackend {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "hidraw" => Ok(HidBackend::Hidraw),
+            "libusb" => Ok(HidBackend::Libusb),
+            _ => Err(anyhow!(
+                "Unknown HID backend '{}'. Use: hidraw, libusb",
+                name
+            )),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            HidBackend::Libusb => "libusb",
+            HidBackend::Hidraw if cfg!(target_os = "macos") => "IOHIDManager",
+            HidBackend::Hidraw if cfg!(target_os = "windows") => "Windows HID",
+            HidBackend::Hidraw => "hidraw",
+        }
+    }
+}
+
+// A HID device as either backend lists it
+pub struct HidInfo {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub serial: Option<String>,
+    pub product: Option<String>,
+    pub manufacturer: Option<String>,
+    pub interface: i32,
+    pub path: String,
+    source: HidSource,
+}
+
+enum HidSource {
+    Hidapi(CString),
+    Libusb(UsbHidInfo),
+}
+
+// The devices the configured backend can see, and what it takes to open them
+struct HidDevices {
+    api: Option<HidApi>,
+    devices: Vec<HidInfo>,
+}
+
+impl HidDevices {
+    fn scan(backend: HidBackend) -> Result<Self> {
+        match backend {
+            HidBackend::Hidraw => {
+                let api =
+                    HidApi::new().map_err(|e| anyhow!("Failed to initialize HID API: {}", e))?;
+                let devices = api
+                    .device_list()
+                    .map(|info| HidInfo {
+                        vendor_id: info.vendor_id(),
+                        product_id: info.product_id(),
+                        serial: info.serial_number().map(str::to_string),
+                        product: info.product_string().map(str::to_string),
+                        manufacturer: info.manufacturer_string().map(str::to_string),
+                        interface: info.interface_number(),
+                        path: info.path().to_string_lossy().into_owned(),
+                        source: HidSource::Hidapi(info.path().to_owned()),
+                    })
+                    .collect();
+                Ok(HidDevices {
+                    api: Some(api),
+                    devices,
+                })
+            }
+            HidBackend::Libusb => {
+                let devices = sensor_libusb::list()
+                    .map_err(|e| anyhow!("Failed to initialize libusb: {}", e))?
+                    .into_iter()
+                    .map(|info| HidInfo {
+                        vendor_id: info.vendor_id,
+                        product_id: info.product_id,
+                        serial: info.serial.clone(),
+                        product: info.product.clone(),
+                        manufacturer: info.manufacturer.clone(),
+                        interface: info.interface as i32,
+                        path: info.path.clone(),
+                        source: HidSource::Libusb(info),
+                    })
+                    .collect();
+                Ok(HidDevices { api: None, devices })
+            }
+        }
+    }
+
+    fn open(&self, info: &HidInfo) -> Result<SensorDevice> {
+        match (&info.source, &self.api) {
+            (HidSource::Hidapi(path), Some(api)) => Ok(SensorDevice::Hidapi(api.open_path(path)?)),
+            (HidSource::Libusb(usb), _) => Ok(SensorDevice::Libusb(UsbHidDevice::open(usb)?)),
+            (HidSource::Hidapi(_), None) => Err(anyhow!("HID API not initialized")),
+        }
+    }
+}
+
+// An open sensor on either backend, with hidapi's report conventions
+pub enum SensorDevice {
+    Hidapi(HidDevice),
+    Libusb(UsbHidDevice),
+}
+
+impl SensorDevice {
+    // Reads one input report; 0 bytes when none arrived in time
+    pub fn read_timeout(&self, buf: &mut [u8], timeout_ms: i32) -> Result<usize> {
+        match self {
+            SensorDevice::Hidapi(device) => Ok(device.read_timeout(buf, timeout_ms)?),
+            SensorDevice::Libusb(device) => device.read_timeout(buf, timeout_ms),
+        }
+    }
+
+    pub fn write(&self, data: &[u8]) -> Result<usize> {
+        match self {
+            SensorDevice::Hidapi(device) => Ok(device.write(data)?),
+            SensorDevice::Libusb(device) => device.write(data),
+        }
+    }
+
+    pub fn send_feature_report(&self, data: &[u8]) -> Result<()> {
+        match self {
+            SensorDevice::Hidapi(device) => Ok(device.send_feature_report(data)?),
+            SensorDevice::Libusb(device) => device.send_feature_report(data),
+        }
+    }
+
+    pub fn get_feature_report(&self, buf: &mut [u8]) -> Result<usize> {
+        match self {
+            SensorDevice::Hidapi(device) => Ok(device.get_feature_report(buf)?),
+            SensorDevice::Libusb(device) => device.get_feature_report(buf),
+        }
+    }
+}
+
+// How readings are pulled out of a device's reports
+#[derive(Debug, Clone, Copy)]
+enum Protocol {
+    // Float or 16-bit layout; with a report ID only those reports are parsed
+    Generic { report_id: Option<u8> },
+    Controller(Controller),
+}
+
+impl Protocol {
+    fn for_ids(vendor_id: u16, product_id: u16) -> Self {
+        match Controller::from_ids(vendor_id, product_id) {
+            Some(controller) => Protocol::Controller(controller),
+            None => Protocol::Generic { report_id: None },
+        }
+    }
+}
+
 // Sensor manager to handle connection and data collection
 pub struct SensorManager {
     data: Arc<Mutex<SensorData>>,
     config: SensorConfig,
     receiver: Option<Receiver<Result<SensorData, SensorError>>>,
+    stats: Arc<Mutex<SensorStats>>,
+    triggers: Option<Arc<Mutex<Vec<TriggerState>>>>,
 }
 
 impl SensorManager {
@@ -93,6 +394,8 @@ impl SensorManager {
             data: Arc::new(Mutex::new(SensorData::default())),
             config,
             receiver: None,
+            stats: Arc::new(Mutex::new(SensorStats::default())),
+            triggers: None,
         }
     }
 
@@ -103,17 +406,69 @@ impl SensorManager {
 
         info!("Starting sensor monitoring");
 
-        // Try to initialize HidApi
-        let api = match HidApi::new() {
-            Ok(api) => api,
+        let backend = HidBackend::parse(&self.config.hid_backend)?;
+        let devices = match HidDevices::scan(backend) {
+            Ok(devices) => devices,
             Err(e) => {
-                error!("Failed to initialize HID API: {}", e);
-                return Err(anyhow!("Failed to initialize HID API: {}", e));
+                error!("{}", e);
+                return Err(e);
             }
         };
 
         // Look for supported devices
-        let device = self.find_supported_sensor(&api)?;
+        for descriptor in &self.config.devices {
+            descriptor.validate()?;
+        }
+        let (device, protocol) = self.find_supported_sensor(&devices)?;
+        if let Protocol::Controller(controller) = protocol {
+            controller.enable_motion(&device);
+        }
+
+        let stream = if self.config.stream_target.is_empty() {
+            None
+        } else {
+            let format = StreamFormat::parse(&self.config.stream_format)?;
+            match SensorStream::new(&self.config.stream_target, format) {
+                Ok(stream) => Some(stream),
+                Err(e) => {
+                    warn!("Sensor streaming disabled: {}", e);
+                    None
+                }
+            }
+        };
+
+        let mut filter = SensorFilter::new(
+            FilterKind::parse(&self.config.filter)?,
+            self.config.filter_accel_cutoff_hz,
+            self.config.filter_gyro_cutoff_hz,
+            self.config.update_interval_ms,
+        );
+        let mut orientation = OrientationFilter::new();
+        let mut log = if self.config.log_path.is_empty() {
+            None
+        } else {
+            match SensorLog::new(
+                &self.config.log_path,
+                self.config.log_batch_size,
+                self.config.log_flush_secs,
+            ) {
+                Ok(log) => Some(log),
+                Err(e) => {
+                    warn!("Sensor log {} disabled: {}", self.config.log_path, e);
+                    None
+                }
+            }
+        };
+        let mut classifier = Classifier::new();
+        let triggers = if self.config.triggers.is_empty() {
+            None
+        } else {
+            let engine =
+                TriggerEngine::new(&self.config.triggers, self.config.trigger_debounce_ms)?;
+            self.triggers = Some(engine.states());
+            Some(engine)
+        };
+        let mut dead_reckoning = self.config.dead_reckoning.then(DeadReckoning::new);
 
         // Create channel for sensor data
         let (sender, receiver) = bounded(10);
@@ -122,21 +477,75 @@ impl SensorManager {
         // Clone necessary data for the thread
         let update_interval = self.config.update_interval_ms;
         let data_clone = self.data.clone();
+        let stats = self.stats.clone();
+        let ros2 = self.config.ros2.then(|| {
+            (
+                self.config.ros2_topic.clone(),
+                self.config.ros2_frame_id.clone(),
+            )
+        });
 
         // Spawn a thread to continuously read sensor data
         thread::spawn(move || {
             let mut last_data = SensorData::default();
+            // The ROS 2 node lives on the thread that publishes
+            let mut publisher = ros2.and_then(|(topic, frame_id)| {
+                ImuPublisher::new(&topic, &frame_id)
+                    .map_err(|e| warn!("ROS 2 publishing disabled: {}", e))
+                    .ok()
+            });
+
+            let mut window_start = Instant::now();
+            let mut window_samples = 0u32;
 
             loop {
-                match read_sensor_data(&device) {
-                    Ok(sensor_data) => {
+                let result = read_sensor_data(&device, protocol);
+                if let Ok(mut stats) = stats.lock() {
+                    match &result {
+                        Ok(_) => {
+                            stats.samples += 1;
+                            window_samples += 1;
+                        }
+                        Err(SensorError::Timeout) => stats.timeouts += 1,
+                        Err(_) => {}
+                    }
+                    let elapsed = window_start.elapsed().as_secs_f64();
+                    if elapsed >= 1.0 {
+                        stats.samples_per_sec = window_samples as f64 / elapsed;
+                        window_start = Instant::now();
+                        window_samples = 0;
+                    }
+                }
+
+                match result {
+                    Ok(mut sensor_data) => {
+                        sensor_data.activity =
+                            classifier.update(sensor_data.timestamp, sensor_data.acceleration);
+                        filter.apply(&mut sensor_data);
+                        orientation.apply(&mut sensor_data);
+                        if let Some(ref mut dead_reckoning) = dead_reckoning {
+                            dead_reckoning.apply(&mut sensor_data);
+                        }
+                        if let Some(ref mut log) = log {
+                            log.push(sensor_data);
+                        }
+                        if let Some(ref triggers) = triggers {
+                            triggers.evaluate(&sensor_data);
+                        }
+                        if let Some(ref stream) = stream {
+                            stream.send(&sensor_data);
+                        }
+                        if let Some(ref mut publisher) = publisher {
+                            publisher.publish(&sensor_data);
+                        }
+
                         // Update the shared data
                         if let Ok(mut data) = data_clone.lock() {
                             *data = sensor_data;
                         }
 
                         // Send the data through the channel
-                        if sender.send(Ok(sensor_data)).is_err() {
+                        if !deliver(&sender, Ok(sensor_data), &stats) {
                             // Receiver dropped, exit thread
                             break;
                         }
@@ -147,7 +556,7 @@ impl SensorManager {
                         error!("Error reading sensor data: {}", e);
 
                         // Send the error through the channel
-                        if sender.send(Err(e)).is_err() {
+                        if !deliver(&sender, Err(e), &stats) {
                             // Receiver dropped, exit thread
                             break;
                         }
@@ -166,6 +575,23 @@ impl SensorManager {
         Ok(())
     }
 
+    pub fn stats(&self) -> SensorStats {
+        self.stats.lock().map(|stats| *stats).unwrap_or_default()
+    }
+
+    // Configured triggers and whether each has fired
+    pub fn trigger_states(&self) -> Vec<TriggerState> {
+        self.triggers
+            .as_ref()
+            .and_then(|states| states.lock().ok().map(|states| states.clone()))
+            .unwrap_or_default()
+    }
+
+    // Readings per second update_interval_ms asks for
+    pub fn target_rate(&self) -> f64 {
+        1000.0 / self.config.update_interval_ms.max(1) as f64
+    }
+
     #[allow(dead_code)]
     pub fn get_latest_data(&self) -> SensorData {
         if let Ok(data) = self.data.lock() {
@@ -183,49 +609,59 @@ impl SensorManager {
         }
     }
 
-    fn find_supported_sensor(&self, api: &HidApi) -> Result<HidDevice, SensorError> {
-        // List of supported sensors by vendor_id, product_id, and description
-        let supported_sensors = [
-            // MPU-6050 based USB adapters
-            (0x16c0, 0x0486, "MPU-6050"),
-            // Common IMU adapters
-            (0x2341, 0x8036, "Arduino Leonardo"), // Arduino with IMU shield
-            (0x1b4f, 0x9206, "SparkFun 9DoF"),    // SparkFun 9DoF sensor
-            // Mainstream gaming controllers with gyro (for testing)
-            (0x054c, 0x09cc, "Sony DualShock 4"), // PS4 controller
-            (0x057e, 0x2009, "Nintendo Switch Pro Controller"),
-        ];
+    fn find_supported_sensor(
+        &self,
+        devices: &HidDevices,
+    ) -> Result<(SensorDevice, Protocol), SensorError> {
+        // Devices described in the config come first, so they can also
+        // override a built-in entry
+        for descriptor in &self.config.devices {
+            debug!(
+                "Looking for configured sensor: {} ({:04x}:{:04x})",
+                descriptor.name, descriptor.vendor_id, descriptor.product_id
+            );
+            if let Some(device) =
+                self.open_matching(devices, descriptor.vendor_id, descriptor.product_id)
+            {
+                info!("Found configured sensor: {}", descriptor.name);
+                descriptor.initialize(&device).map_err(|e| {
+                    SensorError::InitializationFailed(format!("{}: {}", descriptor.name, e))
+                })?;
+                return Ok((
+                    device,
+                    Protocol::Generic {
+                        report_id: descriptor.report_id,
+                    },
+                ));
+            }
+        }
 
         // First try to find exact matches for supported sensors
-        for &(vendor_id, product_id, description) in &supported_sensors {
+        for &(vendor_id, product_id, description) in &SUPPORTED_SENSORS {
             debug!(
                 "Looking for sensor: {} ({:04x}:{:04x})",
                 description, vendor_id, product_id
             );
 
-            if let Ok(device) = api.open(vendor_id, product_id) {
+            if let Some(device) = self.open_matching(devices, vendor_id, product_id) {
                 info!("Found supported sensor: {}", description);
-                return Ok(device);
+                return Ok((device, Protocol::for_ids(vendor_id, product_id)));
             }
         }
 
         // If no exact match found, list all available HID devices for debugging
         debug!("No exact match found, listing all available HID devices");
-        for device_info in api.device_list() {
+        for device_info in &devices.devices {
+            let product_string = device_info.product.as_deref().unwrap_or("Unknown");
+            let manufacturer_string = device_info.manufacturer.as_deref().unwrap_or("Unknown");
             debug!(
                 "HID Device: {:04x}:{:04x} - {} [{}]",
-                device_info.vendor_id(),
-                device_info.product_id(),
-                device_info.product_string().unwrap_or("Unknown"),
-                device_info.manufacturer_string().unwrap_or("Unknown")
+                device_info.vendor_id, device_info.product_id, product_string, manufacturer_string
             );
 
             // Try to detect if it might be an IMU/gyro device by name
-            let product = device_info.product_string().unwrap_or("").to_lowercase();
-            let manufacturer = device_info
-                .manufacturer_string()
-                .unwrap_or("")
-                .to_lowercase();
+            let product = product_string.to_lowercase();
+            let manufacturer = manufacturer_string.to_lowercase();
 
             if product.contains("gyro")
                 || product.contains("accel")
@@ -236,12 +672,12 @@ impl SensorManager {
             {
                 info!(
                     "Found potential IMU device: {} from {}",
-                    device_info.product_string().unwrap_or("Unknown"),
-                    device_info.manufacturer_string().unwrap_or("Unknown")
+                    product_string, manufacturer_string
                 );
 
-                if let Ok(device) = api.open(device_info.vendor_id(), device_info.product_id()) {
-                    return Ok(device);
+                let (vendor_id, product_id) = (device_info.vendor_id, device_info.product_id);
+                if let Some(device) = self.open_matching(devices, vendor_id, product_id) {
+                    return Ok((device, Protocol::for_ids(vendor_id, product_id)));
                 }
             }
         }
@@ -250,78 +686,184 @@ impl SensorManager {
         error!("No supported sensor found");
         Err(SensorError::NotFound)
     }
-}
-
-fn read_sensor_data(device: &HidDevice) -> Result<SensorData, SensorError> {
-    let mut buf = [0u8; 64]; // Common buffer size for HID devices
-
-    // Read data from the device
-    match device.read_timeout(&mut buf, 100) {
-        Ok(size) if size > 0 => {
-            debug!("Read {} bytes from sensor", size);
-
-            // Parse the data based on generic IMU format
-            // This is a simplified implementation - in reality, you'd need specific parsing
-            // for each supported device based on its protocol
-            let mut data = SensorData::default();
-
-            // Example parsing (adjust based on actual device protocol)
-            if size >= 16 {
-                // Acceleration (assuming bytes 0-11 contain accel data as 3 floats)
-                data.acceleration[0] = parse_float(&buf[0..4]);
-                data.acceleration[1] = parse_float(&buf[4..8]);
-                data.acceleration[2] = parse_float(&buf[8..12]);
-
-                // Gyro (assuming bytes 12-23 contain gyro data as 3 floats)
-                if size >= 24 {
-                    data.gyro[0] = parse_float(&buf[12..16]);
-                    data.gyro[1] = parse_float(&buf[16..20]);
-                    data.gyro[2] = parse_float(&buf[20..24]);
-                }
 
-                // Temperature (if available)
-                if size >= 28 {
-                    data.temperature = parse_float(&buf[24..28]);
+    // Opens the first device with these IDs that also has the configured
+    // serial number and interface. Opening by path keeps to the exact
+    // device listed, where HidApi::open would take any with the same IDs.
+    fn open_matching(
+        &self,
+        devices: &HidDevices,
+        vendor_id: u16,
+        product_id: u16,
+    ) -> Option<SensorDevice> {
+        devices
+            .devices
+            .iter()
+            .filter(|info| {
+                info.vendor_id == vendor_id
+                    && info.product_id == product_id
+                    && self.config.selects(info)
+            })
+            .find_map(|info| match devices.open(info) {
+                Ok(device) => Some(device),
+                Err(e) => {
+                    debug!("Cannot open {}: {}", info.path, e);
+                    None
                 }
+            })
+    }
+}
 
-                // Orientation (if available)
-                if size >= 40 {
-                    data.orientation[0] = parse_float(&buf[28..32]);
-                    data.orientation[1] = parse_float(&buf[32..36]);
-                    data.orientation[2] = parse_float(&buf[36..40]);
-                }
-            } else {
-                // Simple data format fallback - try to extract at least some information
-                // This is highly device-specific and may need adjustment
-                if size >= 6 {
-                    // Try to interpret as simple 16-bit per axis format
-                    data.acceleration[0] =
-                        (((buf[0] as i16) << 8) | buf[1] as i16) as f32 / 16384.0;
-                    data.acceleration[1] =
-                        (((buf[2] as i16) << 8) | buf[3] as i16) as f32 / 16384.0;
-                    data.acceleration[2] =
-                        (((buf[4] as i16) << 8) | buf[5] as i16) as f32 / 16384.0;
-
-                    if size >= 12 {
-                        data.gyro[0] = (((buf[6] as i16) << 8) | buf[7] as i16) as f32 / 131.0;
-                        data.gyro[1] = (((buf[8] as i16) << 8) | buf[9] as i16) as f32 / 131.0;
-                        data.gyro[2] = (((buf[10] as i16) << 8) | buf[11] as i16) as f32 / 131.0;
-                    }
-                }
+// Queues a reading without blocking the sensor thread; when the queue is full
+// the reading is counted as dropped. False once the receiver is gone.
+fn deliver(
+    sender: &Sender<Result<SensorData, SensorError>>,
+    message: Result<SensorData, SensorError>,
+    stats: &Mutex<SensorStats>,
+) -> bool {
+    match sender.try_send(message) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) => {
+            if let Ok(mut stats) = stats.lock() {
+                stats.dropped += 1;
             }
+            true
+        }
+        Err(TrySendError::Disconnected(_)) => false,
+    }
+}
 
+// Reports to skip looking for one with motion data before giving up
+const REPORT_READ_ATTEMPTS: usize = 4;
+
+fn read_sensor_data(device: &SensorDevice, protocol: Protocol) -> Result<SensorData, SensorError> {
+    let controller = match protocol {
+        Protocol::Generic { report_id } => return read_generic_sensor_data(device, report_id),
+        Protocol::Controller(controller) => controller,
+    };
+    let mut buf = [0u8; 64];
+    for _ in 0..REPORT_READ_ATTEMPTS {
+        let size = device
+            .read_timeout(&mut buf, 100)
+            .map_err(|e| SensorError::ReadError(e.to_string()))?;
+        if size == 0 {
+            return Err(SensorError::Timeout);
+        }
+        if let Some(mut data) = controller.parse(&buf[..size]) {
+            data.raw = buf;
+            data.raw_len = size;
             data.timestamp = Instant::now();
-            Ok(data)
+            return Ok(data);
         }
-        Ok(_) => {
+    }
+    Err(SensorError::ReadError(format!(
+        "no motion data from {:?}",
+        controller
+    )))
+}
+
+fn read_generic_sensor_data(
+    device: &SensorDevice,
+    report_id: Option<u8>,
+) -> Result<SensorData, SensorError> {
+    let mut buf = [0u8; 64]; // Common buffer size for HID devices
+    for _ in 0..REPORT_READ_ATTEMPTS {
+        let size = device
+            .read_timeout(&mut buf, 100)
+            .map_err(|e| SensorError::ReadError(e.to_string()))?;
+        if size == 0 {
             warn!("Read 0 bytes from sensor");
-            Err(SensorError::ReadError("Zero bytes read".to_string()))
+            return Err(SensorError::Timeout);
         }
-        Err(e) => {
-            error!("Failed to read from sensor: {}", e);
-            Err(SensorError::ReadError(e.to_string()))
+        debug!("Read {} bytes from sensor", size);
+
+        let raw = buf;
+        let payload = match report_id {
+            None => size,
+            Some(id) if buf[0] == id => {
+                buf.copy_within(1..size, 0);
+                size - 1
+            }
+            // Another numbered report, e.g. buttons or status
+            Some(_) => continue,
+        };
+        let mut data = parse_generic_report(&buf, payload);
+        data.raw = raw;
+        data.raw_len = size;
+        data.timestamp = Instant::now();
+        return Ok(data);
+    }
+    Err(SensorError::ReadError(format!(
+        "no report {:#04x} from sensor",
+        report_id.unwrap_or_default()
+    )))
+}
+
+// Parse the data based on generic IMU format
+// This is a simplified implementation - in reality, you'd need specific parsing
+// for each supported device based on its protocol
+fn parse_generic_report(buf: &[u8; 64], size: usize) -> SensorData {
+    let mut data = SensorData::default();
+
+    // Example parsing (adjust based on actual device protocol)
+    if size >= 16 {
+        // Acceleration (assuming bytes 0-11 contain accel data as 3 floats)
+        data.acceleration[0] = parse_float(&buf[0..4]);
+        data.acceleration[1] = parse_float(&buf[4..8]);
+        data.acceleration[2] = parse_float(&buf[8..12]);
+
+        // Gyro (assuming bytes 12-23 contain gyro data as 3 floats)
+        if size >= 24 {
+            data.gyro[0] = parse_float(&buf[12..16]);
+            data.gyro[1] = parse_float(&buf[16..20]);
+            data.gyro[2] = parse_float(&buf[20..24]);
+        }
+
+        // Temperature (if available)
+        if size >= 28 {
+            data.temperature = parse_float(&buf[24..28]);
+        }
+
+        // Orientation (if available)
+        if size >= 40 {
+            data.orientation[0] = parse_float(&buf[28..32]);
+            data.orientation[1] = parse_float(&buf[32..36]);
+            data.orientation[2] = parse_float(&buf[36..40]);
+        }
+
+        // Quaternion w, x, y, z (if available); anything that is not
+        // close to unit length is taken as some other payload
+        if size >= 56 {
+            let quaternion = [
+                parse_float(&buf[40..44]),
+                parse_float(&buf[44..48]),
+                parse_float(&buf[48..52]),
+                parse_float(&buf[52..56]),
+            ];
+            let norm = quaternion.iter().map(|v| v * v).sum::<f32>().sqrt();
+            if (0.9..1.1).contains(&norm) {
+                data.quaternion = Some(quaternion.map(|v| v / norm));
+            }
+        }
+    } else {
+        // Simple data format fallback - try to extract at least some information
+        // This is highly device-specific and may need adjustment
+        if size >= 6 {
+            // Try to interpret as simple 16-bit per axis format
+            // MPU-6050 style: 16384 LSB per g at ±2 g
+            let g = units::STANDARD_GRAVITY as f32;
+            data.acceleration[0] = (((buf[0] as i16) << 8) | buf[1] as i16) as f32 / 16384.0 * g;
+            data.acceleration[1] = (((buf[2] as i16) << 8) | buf[3] as i16) as f32 / 16384.0 * g;
+            data.acceleration[2] = (((buf[4] as i16) << 8) | buf[5] as i16) as f32 / 16384.0 * g;
+
+            if size >= 12 {
+                data.gyro[0] = (((buf[6] as i16) << 8) | buf[7] as i16) as f32 / 131.0;
+                data.gyro[1] = (((buf[8] as i16) << 8) | buf[9] as i16) as f32 / 131.0;
+                data.gyro[2] = (((buf[10] as i16) << 8) | buf[11] as i16) as f32 / 131.0;
+            }
         }
     }
+    data
 }
 
 // Helper function to convert 4 bytes to a float
@@ -352,3 +894,59 @@ pub fn initialize_sensors(config: SensorConfig) -> Result<SensorManager> {
 
     Ok(manager)
 }
+
+// "hercules sensors list": every HID device, marked with whether Hercules
+// knows it and whether the serial/interface filters let it through, to find
+// the values for sensor_serial and sensor_interface
+pub fn run_list(config: &SensorConfig) -> Result<()> {
+    let backend = HidBackend::parse(&config.hid_backend)?;
+    let devices = HidDevices::scan(backend)?;
+    println!("HID backend: {}", backend.describe());
+
+    for info in &devices.devices {
+        let ids = (info.vendor_id, info.product_id);
+        let known = config
+            .devices
+            .iter()
+            .find(|d| (d.vendor_id, d.product_id) == ids)
+            .map(|d| format!("configured: {}", d.name))
+            .or_else(|| {
+                SUPPORTED_SENSORS
+                    .iter()
+                    .find(|&&(vendor_id, product_id, _)| (vendor_id, product_id) == ids)
+                    .map(|&(_, _, name)| format!("supported: {}", name))
+            });
+        println!(
+            "{:04x}:{:04x}  {} [{}]",
+            ids.0,
+            ids.1,
+            info.product.as_deref().unwrap_or("Unknown"),
+            info.manufacturer.as_deref().unwrap_or("Unknown")
+        );
+        println!(
+            "    serial {:?}, interface {}, path {}",
+            info.serial.as_deref().unwrap_or(""),
+            info.interface,
+            info.path
+        );
+        if let Some(known) = known {
+            let selected = if config.selects(info) {
+                ""
+            } else {
+                ", skipped by sensor_serial/sensor_interface"
+            };
+            println!("    {}{}", known, selected);
+        }
+    }
+    if devices.devices.is_empty() {
+        match backend {
+            HidBackend::Hidraw => {
+                println!("No HID devices found. Check permissions on /dev/hidraw* or run as root.")
+            }
+            HidBackend::Libusb => println!(
+                "No HID devices found. Check permissions on /dev/bus/usb/* or run as root."
+            ),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,622 @@
+use std::collections::{HashMap, HashSet};
+#[cfg(target_os = "linux")]
+use std::fs;
+use std::net::SocketAddr;
+#[cfg(target_os = "linux")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+use anyhow::Result;
+use colored::*;
+#[cfg(target_os = "linux")]
+use log::debug;
+use log::warn;
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::capture::{Flow, PacketCapture};
+use crate::i18n;
+use crate::search;
+use crate::theme::Themed;
+
+// Connections panel stops listing after this many rows
+const MAX_CONNECTION_ROWS: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl Protocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+        }
+    }
+}
+
+// One row of /proc/net/{tcp,tcp6,udp,udp6}
+#[derive(Debug, Clone)]
+pub struct SocketEntry {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    pub state: &'static str,
+    pub inode: u64,
+}
+
+impl SocketEntry {
+    pub fn is_listening(&self) -> bool {
+        match self.protocol {
+            Protocol::Tcp => self.state == "LISTEN",
+            // Unconnected UDP sockets are bound and waiting, the UDP analogue of LISTEN
+            Protocol::Udp => self.remote.ip().is_unspecified(),
+        }
+    }
+}
+
+// Receive/transmit rate for a single process in bytes/sec
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NetRate {
+    pub rx: f64,
+    pub tx: f64,
+}
+
+// Live rate of one connection, from packet capture
+#[derive(Debug, Clone)]
+pub struct ConnectionRate {
+    pub flow: Flow,
+    pub pid: Option<u32>,
+    pub rate: NetRate,
+}
+
+// Per-process network accounting built from socket ownership and TCP byte
+// counters, or from packet capture when that is enabled and permitted
+pub struct ProcessNetTracker {
+    last_counters: HashMap<u64, (u64, u64)>, // socket inode -> (received, sent)
+    rates: HashMap<u32, NetRate>,            // pid -> rate
+    primed: bool,
+    capture: Option<PacketCapture>,
+    capture_error: Option<String>,
+    connections: Vec<ConnectionRate>,
+}
+
+impl ProcessNetTracker {
+    pub fn new(capture: bool) -> Self {
+        let (capture, capture_error) = if capture {
+            match PacketCapture::start() {
+                Ok(capture) => (Some(capture), None),
+                Err(e) => {
+                    warn!("Falling back to TCP counters: {}", e);
+                    (None, Some(e.to_string()))
+                }
+            }
+        } else {
+            (None, None)
+        };
+        ProcessNetTracker {
+            last_counters: HashMap::new(),
+            rates: HashMap::new(),
+            primed: false,
+            capture,
+            capture_error,
+            connections: Vec::new(),
+        }
+    }
+
+    // Take a new sample and recompute per-process rates over `elapsed_secs`
+    pub fn sample(&mut self, elapsed_secs: f64) {
+        if self.capture.is_some() {
+            self.sample_capture(elapsed_secs);
+            return;
+        }
+
+        let counters = tcp_byte_counters();
+        let owners = socket_owners();
+
+        let mut rates: HashMap<u32, NetRate> = HashMap::new();
+        if self.primed && elapsed_secs > 0.0 {
+            for (inode, &(received, sent)) in &counters {
+                let Some(&pid) = owners.get(inode) else {
+                    continue;
+                };
+                // Sockets opened since the last sample count from zero
+                let (last_received, last_sent) =
+                    self.last_counters.get(inode).copied().unwrap_or((0, 0));
+                let rate = rates.entry(pid).or_default();
+                rate.rx += received.saturating_sub(last_received) as f64 / elapsed_secs;
+                rate.tx += sent.saturating_sub(last_sent) as f64 / elapsed_secs;
+            }
+        }
+
+        self.last_counters = counters;
+        self.rates = rates;
+        self.primed = true;
+    }
+
+    // Attribute captured flows to sockets: connected sockets by their full
+    // address pair, anything else (servers, unconnected UDP) by local port
+    fn sample_capture(&mut self, elapsed_secs: f64) {
+        let Some(ref capture) = self.capture else {
+            return;
+        };
+        let flows = capture.take();
+        if elapsed_secs <= 0.0 {
+            return;
+        }
+
+        let owners = socket_owners();
+        let mut connected = HashMap::new();
+        let mut bound = HashMap::new();
+        for entry in socket_table() {
+            if entry.remote.ip().is_unspecified() {
+                bound.insert((entry.protocol, entry.local.port()), entry.inode);
+            } else {
+                connected.insert(
+                    (entry.protocol, entry.local.port(), entry.remote),
+                    entry.inode,
+                );
+            }
+        }
+
+        let mut rates: HashMap<u32, NetRate> = HashMap::new();
+        let mut connections = Vec::new();
+        for (flow, (received, sent)) in flows {
+            let pid = connected
+                .get(&(flow.protocol, flow.local_port, flow.remote))
+                .or_else(|| bound.get(&(flow.protocol, flow.local_port)))
+                .and_then(|inode| owners.get(inode))
+                .copied();
+            let rate = NetRate {
+                rx: received as f64 / elapsed_secs,
+                tx: sent as f64 / elapsed_secs,
+            };
+            if let Some(pid) = pid {
+                let total = rates.entry(pid).or_default();
+                total.rx += rate.rx;
+                total.tx += rate.tx;
+            }
+            connections.push(ConnectionRate { flow, pid, rate });
+        }
+        connections.sort_by(|a, b| (b.rate.rx + b.rate.tx).total_cmp(&(a.rate.rx + a.rate.tx)));
+
+        self.rates = rates;
+        self.connections = connections;
+    }
+
+    pub fn rate_for(&self, pid: u32) -> NetRate {
+        self.rates.get(&pid).copied().unwrap_or_default()
+    }
+
+    pub fn capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    // Why packet capture was asked for but is not running
+    pub fn capture_error(&self) -> Option<&str> {
+        self.capture_error.as_deref()
+    }
+
+    // Busiest connections first; empty unless capturing
+    pub fn connections(&self) -> &[ConnectionRate] {
+        &self.connections
+    }
+}
+
+// Map socket inodes to the pid holding them open, from /proc/<pid>/fd links
+#[cfg(target_os = "linux")]
+pub fn socket_owners() -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+
+        // Processes owned by other users are unreadable without root; skip them quietly
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+
+        for fd in fds.filter_map(Result::ok) {
+            if let Ok(target) = fs::read_link(fd.path()) {
+                let target = target.to_string_lossy();
+                if let Some(inode) = target
+                    .strip_prefix("socket:[")
+                    .and_then(|rest| rest.strip_suffix(']'))
+                    .and_then(|inode| inode.parse::<u64>().ok())
+                {
+                    owners.insert(inode, pid);
+                }
+            }
+        }
+    }
+
+    owners
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn socket_owners() -> HashMap<u64, u32> {
+    HashMap::new()
+}
+
+// Cumulative (received, sent) bytes per TCP socket inode, read from the kernel's
+// tcp_info via `ss`. UDP sockets carry no byte counters and are not included.
+#[cfg(target_os = "linux")]
+fn tcp_byte_counters() -> HashMap<u64, (u64, u64)> {
+    let mut counters = HashMap::new();
+
+    let output = match Command::new("ss").args(["-tieHn"]).output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            debug!("ss exited with status: {}", output.status);
+            return counters;
+        }
+        Err(e) => {
+            debug!("Failed to run ss: {}", e);
+            return counters;
+        }
+    };
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut current_inode: Option<u64> = None;
+
+    for line in text.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            // Socket summary line carries the inode
+            current_inode = find_field(line, "ino:").and_then(|v| v.parse().ok());
+            continue;
+        }
+
+        // Indented detail line carries tcp_info counters for the preceding socket
+        if let Some(inode) = current_inode.take() {
+            let received = find_field(line, "bytes_received:")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            let sent = find_field(line, "bytes_acked:")
+                .or_else(|| find_field(line, "bytes_sent:"))
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            counters.insert(inode, (received, sent));
+        }
+    }
+
+    counters
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tcp_byte_counters() -> HashMap<u64, (u64, u64)> {
+    HashMap::new()
+}
+
+// Extract the value of a `key:value` token from an ss output line
+#[cfg(target_os = "linux")]
+fn find_field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.split_whitespace()
+        .find_map(|token| token.strip_prefix(key))
+}
+
+// Kernel TCP state codes from include/net/tcp_states.h
+#[cfg(target_os = "linux")]
+fn tcp_state_name(code: u8) -> &'static str {
+    match code {
+        0x01 => "ESTABLISHED",
+        0x02 => "SYN_SENT",
+        0x03 => "SYN_RECV",
+        0x04 => "FIN_WAIT1",
+        0x05 => "FIN_WAIT2",
+        0x06 => "TIME_WAIT",
+        0x07 => "CLOSE",
+        0x08 => "CLOSE_WAIT",
+        0x09 => "LAST_ACK",
+        0x0A => "LISTEN",
+        0x0B => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+// Read every TCP and UDP socket (IPv4 and IPv6) known to the kernel
+#[cfg(target_os = "linux")]
+pub fn socket_table() -> Vec<SocketEntry> {
+    let sources = [
+        ("/proc/net/tcp", Protocol::Tcp),
+        ("/proc/net/tcp6", Protocol::Tcp),
+        ("/proc/net/udp", Protocol::Udp),
+        ("/proc/net/udp6", Protocol::Udp),
+    ];
+
+    let mut entries = Vec::new();
+    for (path, protocol) in sources {
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        // First line is the column header
+        for line in content.lines().skip(1) {
+            if let Some(entry) = parse_socket_line(line, protocol) {
+                entries.push(entry);
+            }
+        }
+    }
+
+    entries
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn socket_table() -> Vec<SocketEntry> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_socket_line(line: &str, protocol: Protocol) -> Option<SocketEntry> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 10 {
+        return None;
+    }
+
+    let local = parse_hex_address(fields[1])?;
+    let remote = parse_hex_address(fields[2])?;
+    let state_code = u8::from_str_radix(fields[3], 16).ok()?;
+    let inode = fields[9].parse().ok()?;
+
+    let state = match protocol {
+        Protocol::Tcp => tcp_state_name(state_code),
+        Protocol::Udp if state_code == 0x01 => "ESTABLISHED",
+        Protocol::Udp => "UNCONN",
+    };
+
+    Some(SocketEntry {
+        protocol,
+        local,
+        remote,
+        state,
+        inode,
+    })
+}
+
+// Decode "0100007F:1F90" style addresses. The kernel prints each 32-bit word of
+// the address in host byte order, so the bytes of every word are reversed.
+#[cfg(target_os = "linux")]
+fn parse_hex_address(field: &str) -> Option<SocketAddr> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = Vec::with_capacity(16);
+    for word in 0..address.len() / 8 {
+        let value = u32::from_str_radix(&address[word * 8..word * 8 + 8], 16).ok()?;
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => {
+            let octets: [u8; 16] = bytes.try_into().ok()?;
+            let v6 = Ipv6Addr::from(octets);
+            // Show IPv4-mapped addresses in their familiar dotted form
+            match v6.to_ipv4_mapped() {
+                Some(v4) => IpAddr::V4(v4),
+                None => IpAddr::V6(v6),
+            }
+        }
+        _ => return None,
+    };
+
+    Some(SocketAddr::new(ip, port))
+}
+
+pub fn owner_name(system: &System, pid: Option<u32>) -> String {
+    match pid {
+        Some(pid) => match system.process(Pid::from_u32(pid)) {
+            Some(process) => format!("{}/{}", pid, process.name()),
+            None => pid.to_string(),
+        },
+        None => "-".to_string(),
+    }
+}
+
+// Connections panel: established/active TCP and connected UDP sockets.
+// `filter` is either a port number or a case-insensitive process name fragment.
+pub fn monitor_connections(system: &System, filter: &str, search: &str) -> Result<()> {
+    let heading = i18n::tr("heading-connections");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    if !cfg!(target_os = "linux") {
+        println!("  Connection listing is only supported on Linux");
+        return Ok(());
+    }
+
+    let owners = socket_owners();
+    let filter = filter.trim().to_lowercase();
+    let port_filter = filter.parse::<u16>().ok();
+
+    let mut rows: Vec<(SocketEntry, String)> = socket_table()
+        .into_iter()
+        .filter(|entry| !entry.is_listening() && entry.state != "CLOSE")
+        .map(|entry| {
+            let owner = owner_name(system, owners.get(&entry.inode).copied());
+            (entry, owner)
+        })
+        .filter(|(entry, owner)| match port_filter {
+            Some(port) => entry.local.port() == port || entry.remote.port() == port,
+            None => filter.is_empty() || owner.to_lowercase().contains(&filter),
+        })
+        // The interactive `/` search narrows the list further
+        .filter(|(entry, owner)| {
+            search::matches(owner, search)
+                || search::matches(&entry.local.to_string(), search)
+                || search::matches(&entry.remote.to_string(), search)
+        })
+        .collect();
+
+    rows.sort_by_key(|(entry, _)| (entry.protocol.as_str(), entry.state, entry.remote));
+
+    if rows.is_empty() {
+        println!("  No matching connections");
+        return Ok(());
+    }
+
+    println!(
+        "{:<5} {:<28} {:<28} {:<12} {:<20}",
+        "PROTO", "LOCAL", "REMOTE", "STATE", "PROCESS"
+    );
+
+    for (entry, owner) in rows.iter().take(MAX_CONNECTION_ROWS) {
+        let state = if entry.state == "ESTABLISHED" {
+            entry.state.good()
+        } else {
+            entry.state.warning()
+        };
+        // Padded before highlighting, which adds escape codes
+        let local = format!("{:<28}", entry.local.to_string());
+        let remote = format!("{:<28}", entry.remote.to_string());
+        println!(
+            "{:<5} {} {} {:<12} {}",
+            entry.protocol.as_str(),
+            search::highlight(&local, search, |text| text.normal()),
+            search::highlight(&remote, search, |text| text.value()),
+            state,
+            search::highlight(owner, search, |text| text.normal())
+        );
+    }
+
+    if rows.len() > MAX_CONNECTION_ROWS {
+        println!("  ... {} more", rows.len() - MAX_CONNECTION_ROWS);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct ListeningPort {
+    pub protocol: Protocol,
+    pub address: SocketAddr,
+    pub owner: String,
+    pub unexpected: bool,
+}
+
+// Tracks listening sockets and flags ports that are not on the allowlist. With an
+// empty allowlist, the set of ports seen at startup becomes the baseline instead.
+pub struct ListeningPortMonitor {
+    allowlist: Vec<u16>,
+    baseline: Option<HashSet<(Protocol, u16)>>,
+    ports: Vec<ListeningPort>,
+}
+
+impl ListeningPortMonitor {
+    pub fn new(allowlist: Vec<u16>) -> Self {
+        ListeningPortMonitor {
+            allowlist,
+            baseline: None,
+            ports: Vec::new(),
+        }
+    }
+
+    pub fn refresh(&mut self, system: &System, alerts: &mut AlertEngine) {
+        let owners = socket_owners();
+        let mut seen = HashSet::new();
+        let mut ports = Vec::new();
+
+        for entry in socket_table().into_iter().filter(SocketEntry::is_listening) {
+            // The same port bound on both IPv4 and IPv6 is listed once
+            if !seen.insert((entry.protocol, entry.local.port())) {
+                continue;
+            }
+            ports.push(ListeningPort {
+                protocol: entry.protocol,
+                address: entry.local,
+                owner: owner_name(system, owners.get(&entry.inode).copied()),
+                unexpected: false,
+            });
+        }
+
+        let baseline = self.baseline.get_or_insert_with(|| seen.clone());
+        for port in &mut ports {
+            let number = port.address.port();
+            port.unexpected = if self.allowlist.is_empty() {
+                !baseline.contains(&(port.protocol, number))
+            } else {
+                !self.allowlist.contains(&number)
+            };
+
+            let key = format!("listening:{}/{}", port.protocol.as_str(), number);
+            if port.unexpected {
+                alerts.raise(
+                    &key,
+                    AlertLevel::Warning,
+                    "ports",
+                    format!(
+                        "Unexpected listening port {}/{} ({})",
+                        port.protocol.as_str(),
+                        number,
+                        port.owner
+                    ),
+                );
+            } else {
+                alerts.clear(&key);
+            }
+        }
+
+        // Ports that stopped listening no longer need an alert
+        for old in &self.ports {
+            if !seen.contains(&(old.protocol, old.address.port())) {
+                alerts.clear(&format!(
+                    "listening:{}/{}",
+                    old.protocol.as_str(),
+                    old.address.port()
+                ));
+            }
+        }
+
+        ports.sort_by_key(|port| (port.protocol.as_str(), port.address.port()));
+        self.ports = ports;
+    }
+
+    pub fn ports(&self) -> &[ListeningPort] {
+        &self.ports
+    }
+}
+
+// Listening ports panel
+pub fn monitor_listening_ports(monitor: &ListeningPortMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-listening-ports");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    if !cfg!(target_os = "linux") {
+        println!("  Listening port monitoring is only supported on Linux");
+        return Ok(());
+    }
+
+    if monitor.ports().is_empty() {
+        println!("  No listening sockets found");
+        return Ok(());
+    }
+
+    println!(
+        "{:<5} {:<28} {:<20} {:<10}",
+        "PROTO", "ADDRESS", "PROCESS", "STATUS"
+    );
+    for port in monitor.ports() {
+        let status = if port.unexpected {
+            "UNEXPECTED".critical().bold()
+        } else {
+            "ok".good()
+        };
+        println!(
+            "{:<5} {:<28} {:<20} {:<10}",
+            port.protocol.as_str(),
+            port.address.to_string(),
+            port.owner,
+            status
+        );
+    }
+
+    Ok(())
+}
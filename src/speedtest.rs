@@ -0,0 +1,200 @@
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgAction, Command};
+use colored::*;
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use log::warn;
+
+use crate::config;
+use crate::history::HistoryStore;
+use crate::i18n;
+use crate::theme::{self, Themed};
+
+// Number of small requests used to estimate latency
+const LATENCY_PROBES: usize = 5;
+
+// Payload size for the upload test
+const UPLOAD_BYTES: usize = 10_000_000;
+
+// Handle "hercules speedtest [--download-url URL] [--upload-url URL] [--no-record]"
+pub fn run_speedtest(args: &[String]) -> Result<()> {
+    let matches = Command::new("speedtest")
+        .about("Measure internet download/upload throughput and latency")
+        .arg(
+            Arg::new("download-url")
+                .long("download-url")
+                .help("URL to download from (overrides speedtest_download_url)"),
+        )
+        .arg(
+            Arg::new("upload-url")
+                .long("upload-url")
+                .help("URL to upload to (overrides speedtest_upload_url)"),
+        )
+        .arg(
+            Arg::new("no-upload")
+                .long("no-upload")
+                .help("Skip the upload test")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("no-record")
+                .long("no-record")
+                .help("Do not save the results to the history database")
+                .action(ArgAction::SetTrue),
+        )
+        .get_matches_from(args);
+
+    let config_manager = config::ConfigManager::new()?;
+    let file_config = config_manager.get_config();
+    let download_url = matches
+        .get_one::<String>("download-url")
+        .cloned()
+        .unwrap_or_else(|| file_config.speedtest_download_url.clone());
+    let upload_url = matches
+        .get_one::<String>("upload-url")
+        .cloned()
+        .unwrap_or_else(|| file_config.speedtest_upload_url.clone());
+    let record = file_config.speedtest_record && !matches.get_flag("no-record");
+
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(5))
+        .timeout(Duration::from_secs(60))
+        .build();
+
+    let heading = i18n::tr("heading-speed-test");
+    println!("{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    let latency = measure_latency(&agent, &download_url)?;
+    println!("Latency:  {} ms", format!("{:.1}", latency).value());
+
+    let download = measure_download(&agent, &download_url)?;
+    println!("Download: {} Mbit/s", format!("{:.2}", download).value());
+
+    let upload = if matches.get_flag("no-upload") {
+        None
+    } else {
+        let upload = measure_upload(&agent, &upload_url)?;
+        println!("Upload:   {} Mbit/s", format!("{:.2}", upload).value());
+        Some(upload)
+    };
+
+    if record {
+        let store = HistoryStore::open()?;
+        store.record("speedtest.latency_ms", latency)?;
+        store.record("speedtest.download_mbps", download)?;
+        if let Some(upload) = upload {
+            store.record("speedtest.upload_mbps", upload)?;
+        }
+        println!("{}", theme::ascii("✓ Results saved to history").good());
+    }
+
+    Ok(())
+}
+
+// Median time for a handful of tiny requests against the download host
+fn measure_latency(agent: &ureq::Agent, url: &str) -> Result<f64> {
+    let probe_url = latency_probe_url(url);
+    let mut samples = Vec::with_capacity(LATENCY_PROBES);
+
+    for _ in 0..LATENCY_PROBES {
+        let start = Instant::now();
+        match agent.get(&probe_url).call() {
+            // Any HTTP reply counts; we only care about the round trip
+            Ok(_) | Err(ureq::Error::Status(_, _)) => {
+                samples.push(start.elapsed().as_secs_f64() * 1000.0)
+            }
+            Err(e) => warn!("Latency probe failed: {}", e),
+        }
+    }
+
+    if samples.is_empty() {
+        return Err(anyhow!("Could not reach {}", probe_url));
+    }
+    samples.sort_by(|a, b| a.total_cmp(b));
+    Ok(samples[samples.len() / 2])
+}
+
+// Cloudflare-style endpoints accept a byte count, so ask for an empty body
+fn latency_probe_url(url: &str) -> String {
+    match url.split_once("bytes=") {
+        Some((base, _)) => format!("{}bytes=0", base),
+        None => url.to_string(),
+    }
+}
+
+fn measure_download(agent: &ureq::Agent, url: &str) -> Result<f64> {
+    let response = agent
+        .get(url)
+        .call()
+        .map_err(|e| anyhow!("Download from {} failed: {}", url, e))?;
+    let total = response
+        .header("Content-Length")
+        .and_then(|len| len.parse::<u64>().ok());
+
+    let pb = match total {
+        Some(total) => {
+            let pb = ProgressBar::new(total);
+            pb.set_style(
+                ProgressStyle::default_bar()
+                    .template("Download {bar:30.cyan} {bytes}/{total_bytes} ({bytes_per_sec})")
+                    .unwrap(),
+            );
+            pb
+        }
+        None => ProgressBar::new_spinner(),
+    };
+    if !theme::interactive() {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
+
+    let mut reader = response.into_reader();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut received = 0u64;
+    let start = Instant::now();
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        received += read as u64;
+        pb.set_position(received);
+    }
+    pb.finish_and_clear();
+
+    Ok(mbit_per_sec(received, start.elapsed()))
+}
+
+fn measure_upload(agent: &ureq::Agent, url: &str) -> Result<f64> {
+    let payload = vec![0u8; UPLOAD_BYTES];
+
+    let pb = ProgressBar::new_spinner();
+    if !theme::interactive() {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    pb.set_message("Uploading...");
+    pb.enable_steady_tick(Duration::from_millis(100));
+
+    let start = Instant::now();
+    let result = agent
+        .post(url)
+        .set("Content-Type", "application/octet-stream")
+        .send_bytes(&payload);
+    let elapsed = start.elapsed();
+    pb.finish_and_clear();
+
+    result.map_err(|e| anyhow!("Upload to {} failed: {}", url, e))?;
+    Ok(mbit_per_sec(payload.len() as u64, elapsed))
+}
+
+fn mbit_per_sec(bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 {
+        bytes as f64 * 8.0 / secs / 1_000_000.0
+    } else {
+        0.0
+    }
+}
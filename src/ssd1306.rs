@@ -0,0 +1,259 @@
+use anyhow::Result;
+
+use crate::i2c::I2cDevice;
+use crate::output::{DisplayOutput, Stats};
+use crate::units;
+
+const WIDTH: usize = 128;
+const HEIGHT: usize = 64;
+// Address of most modules; some are strapped to 0x3D
+const DEFAULT_ADDRESS: u16 = 0x3C;
+
+// Control bytes: what follows is a command stream or display data
+const COMMAND: u8 = 0x00;
+const DATA: u8 = 0x40;
+
+// Power-up sequence for a 128x64 panel with the internal charge pump
+const INIT: [u8; 25] = [
+    0xAE, // display off
+    0xD5, 0x80, // clock divide
+    0xA8, 0x3F, // multiplex ratio: 64 rows
+    0xD3, 0x00, // no display offset
+    0x40, // start line 0
+    0x8D, 0x14, // charge pump on
+    0x20, 0x00, // horizontal addressing
+    0xA1, // columns mirrored, so column 0 is on the left
+    0xC8, // rows scanned bottom up, so page 0 is at the top
+    0xDA, 0x12, // COM pin layout for 64 rows
+    0x81, 0xCF, // contrast
+    0xD9, 0xF1, // pre-charge period
+    0xDB, 0x40, // VCOMH level
+    0xA4, // show RAM contents
+    0xA6, // not inverted
+    0xAF, // display on
+];
+
+// 5x7 glyphs for ASCII 0x20-0x7E, one byte per column, top row in bit 0
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x5F, 0x00, 0x00],
+    [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7F, 0x14, 0x7F, 0x14],
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12],
+    [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x56, 0x20, 0x50],
+    [0x00, 0x05, 0x03, 0x00, 0x00],
+    [0x00, 0x1C, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1C, 0x00],
+    [0x14, 0x08, 0x3E, 0x08, 0x14],
+    [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x50, 0x30, 0x00, 0x00],
+    [0x08, 0x08, 0x08, 0x08, 0x08],
+    [0x00, 0x60, 0x60, 0x00, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3E, 0x51, 0x49, 0x45, 0x3E],
+    [0x00, 0x42, 0x7F, 0x40, 0x00],
+    [0x42, 0x61, 0x51, 0x49, 0x46],
+    [0x21, 0x41, 0x45, 0x4B, 0x31],
+    [0x18, 0x14, 0x12, 0x7F, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39],
+    [0x3C, 0x4A, 0x49, 0x49, 0x30],
+    [0x01, 0x71, 0x09, 0x05, 0x03],
+    [0x36, 0x49, 0x49, 0x49, 0x36],
+    [0x06, 0x49, 0x49, 0x29, 0x1E],
+    [0x00, 0x36, 0x36, 0x00, 0x00],
+    [0x00, 0x56, 0x36, 0x00, 0x00],
+    [0x08, 0x14, 0x22, 0x41, 0x00],
+    [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08],
+    [0x02, 0x01, 0x51, 0x09, 0x06],
+    [0x32, 0x49, 0x79, 0x41, 0x3E],
+    [0x7E, 0x11, 0x11, 0x11, 0x7E],
+    [0x7F, 0x49, 0x49, 0x49, 0x36],
+    [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x22, 0x1C],
+    [0x7F, 0x49, 0x49, 0x49, 0x41],
+    [0x7F, 0x09, 0x09, 0x09, 0x01],
+    [0x3E, 0x41, 0x49, 0x49, 0x7A],
+    [0x7F, 0x08, 0x08, 0x08, 0x7F],
+    [0x00, 0x41, 0x7F, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3F, 0x01],
+    [0x7F, 0x08, 0x14, 0x22, 0x41],
+    [0x7F, 0x40, 0x40, 0x40, 0x40],
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F],
+    [0x7F, 0x04, 0x08, 0x10, 0x7F],
+    [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06],
+    [0x3E, 0x41, 0x51, 0x21, 0x5E],
+    [0x7F, 0x09, 0x19, 0x29, 0x46],
+    [0x46, 0x49, 0x49, 0x49, 0x31],
+    [0x01, 0x01, 0x7F, 0x01, 0x01],
+    [0x3F, 0x40, 0x40, 0x40, 0x3F],
+    [0x1F, 0x20, 0x40, 0x20, 0x1F],
+    [0x3F, 0x40, 0x38, 0x40, 0x3F],
+    [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x07, 0x08, 0x70, 0x08, 0x07],
+    [0x61, 0x51, 0x49, 0x45, 0x43],
+    [0x00, 0x7F, 0x41, 0x41, 0x00],
+    [0x02, 0x04, 0x08, 0x10, 0x20],
+    [0x00, 0x41, 0x41, 0x7F, 0x00],
+    [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x01, 0x02, 0x04, 0x00],
+    [0x20, 0x54, 0x54, 0x54, 0x78],
+    [0x7F, 0x48, 0x44, 0x44, 0x38],
+    [0x38, 0x44, 0x44, 0x44, 0x20],
+    [0x38, 0x44, 0x44, 0x48, 0x7F],
+    [0x38, 0x54, 0x54, 0x54, 0x18],
+    [0x08, 0x7E, 0x09, 0x01, 0x02],
+    [0x0C, 0x52, 0x52, 0x52, 0x3E],
+    [0x7F, 0x08, 0x04, 0x04, 0x78],
+    [0x00, 0x44, 0x7D, 0x40, 0x00],
+    [0x20, 0x40, 0x44, 0x3D, 0x00],
+    [0x7F, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7F, 0x40, 0x00],
+    [0x7C, 0x04, 0x18, 0x04, 0x78],
+    [0x7C, 0x08, 0x04, 0x04, 0x78],
+    [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0x7C, 0x14, 0x14, 0x14, 0x08],
+    [0x08, 0x14, 0x14, 0x18, 0x7C],
+    [0x7C, 0x08, 0x04, 0x04, 0x08],
+    [0x48, 0x54, 0x54, 0x54, 0x20],
+    [0x04, 0x3F, 0x44, 0x40, 0x20],
+    [0x3C, 0x40, 0x40, 0x20, 0x7C],
+    [0x1C, 0x20, 0x40, 0x20, 0x1C],
+    [0x3C, 0x40, 0x30, 0x40, 0x3C],
+    [0x44, 0x28, 0x10, 0x28, 0x44],
+    [0x0C, 0x50, 0x50, 0x50, 0x3C],
+    [0x44, 0x64, 0x54, 0x4C, 0x44],
+    [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x7F, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00],
+    [0x10, 0x08, 0x08, 0x10, 0x08],
+];
+
+const DEGREE: [u8; 5] = [0x00, 0x06, 0x09, 0x09, 0x06];
+
+fn glyph(c: char) -> &'static [u8; 5] {
+    match c {
+        '°' => &DEGREE,
+        ' '..='~' => &FONT[c as usize - 0x20],
+        _ => &FONT['?' as usize - 0x20],
+    }
+}
+
+// A 128x64 monochrome OLED on I2C
+pub struct Ssd1306 {
+    device: I2cDevice,
+    // Eight 8-pixel-high pages of 128 columns, as the controller stores them
+    buffer: [u8; WIDTH * HEIGHT / 8],
+}
+
+impl Ssd1306 {
+    // `address` 0 picks the usual 0x3C
+    pub fn open(bus: u8, address: u16) -> Result<Self> {
+        let address = if address == 0 {
+            DEFAULT_ADDRESS
+        } else {
+            address
+        };
+        let mut device = I2cDevice::open(bus, address)?;
+        let mut init = vec![COMMAND];
+        init.extend_from_slice(&INIT);
+        device.write(&init)?;
+        Ok(Ssd1306 {
+            device,
+            buffer: [0; WIDTH * HEIGHT / 8],
+        })
+    }
+
+    fn clear(&mut self) {
+        self.buffer.fill(0);
+    }
+
+    fn pixel(&mut self, x: usize, y: usize) {
+        if x < WIDTH && y < HEIGHT {
+            self.buffer[y / 8 * WIDTH + x] |= 1 << (y % 8);
+        }
+    }
+
+    // Text in the 5x7 font at 6 pixels per character
+    fn text(&mut self, x: usize, y: usize, text: &str) {
+        for (index, c) in text.chars().enumerate() {
+            for (column, bits) in glyph(c).iter().enumerate() {
+                for row in 0..7 {
+                    if bits & (1 << row) != 0 {
+                        self.pixel(x + index * 6 + column, y + row);
+                    }
+                }
+            }
+        }
+    }
+
+    // Outlined bar filled to `percent`
+    fn bar(&mut self, x: usize, y: usize, width: usize, percent: f64) {
+        let filled = ((width - 2) as f64 * percent.clamp(0.0, 100.0) / 100.0).round() as usize;
+        for dx in 0..width {
+            self.pixel(x + dx, y);
+            self.pixel(x + dx, y + 6);
+        }
+        for dy in 0..7 {
+            self.pixel(x, y + dy);
+            self.pixel(x + width - 1, y + dy);
+        }
+        for dx in 0..filled {
+            for dy in 2..5 {
+                self.pixel(x + 1 + dx, y + dy);
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.device.write(&[
+            COMMAND,
+            0x21,
+            0,
+            WIDTH as u8 - 1,
+            0x22,
+            0,
+            HEIGHT as u8 / 8 - 1,
+        ])?;
+        // Small chunks, since some I2C adapters cap the transfer size
+        for chunk in self.buffer.chunks(32) {
+            let mut data = Vec::with_capacity(chunk.len() + 1);
+            data.push(DATA);
+            data.extend_from_slice(chunk);
+            self.device.write(&data)?;
+        }
+        Ok(())
+    }
+}
+
+impl DisplayOutput for Ssd1306 {
+    fn show(&mut self, stats: &Stats) -> Result<()> {
+        self.clear();
+        let hostname: String = stats.hostname.chars().take(WIDTH / 6).collect();
+        self.text(0, 0, &hostname);
+        for dx in 0..WIDTH {
+            self.pixel(dx, 10);
+        }
+
+        self.text(0, 16, &format!("CPU {:3.0}%", stats.cpu_percent));
+        self.bar(62, 16, 66, stats.cpu_percent as f64);
+        self.text(0, 28, &format!("MEM {:3.0}%", stats.memory_percent));
+        self.bar(62, 28, 66, stats.memory_percent);
+        if let Some(temp) = stats.temperature {
+            self.text(
+                0,
+                40,
+                &format!("TEMP {}", units::format_temperature(temp as f64)),
+            );
+        }
+        let address = stats
+            .address
+            .map(|address| address.to_string())
+            .unwrap_or_else(|| "no network".to_string());
+        self.text(0, 54, &format!("IP {}", address));
+        self.flush()
+    }
+}
@@ -0,0 +1,119 @@
+use std::net::{ToSocketAddrs, UdpSocket};
+
+use anyhow::{anyhow, Result};
+use log::debug;
+
+use crate::metrics::Measurement;
+
+// Stay under a typical path MTU so no packet is fragmented
+const MAX_PACKET_BYTES: usize = 1432;
+
+// Sends every metric as a StatsD gauge over UDP after each refresh
+pub struct StatsdSink {
+    socket: UdpSocket,
+    prefix: String,
+    sample_rate: f64,
+    // Builds up by sample_rate each refresh, a send spends one
+    credit: f64,
+}
+
+impl StatsdSink {
+    pub fn new(host: &str, prefix: &str, sample_rate: f64) -> Result<Self> {
+        let address = host
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("Could not resolve StatsD host {}", host))?;
+        // Bind to the same address family as the server
+        let local = if address.is_ipv6() {
+            "[::]:0"
+        } else {
+            "0.0.0.0:0"
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.connect(address)?;
+        Ok(StatsdSink {
+            socket,
+            prefix: prefix.trim_end_matches('.').to_string(),
+            sample_rate,
+            // Enough that the first refresh is sent
+            credit: 1.0 - sample_rate,
+        })
+    }
+
+    pub fn send(&mut self, measurements: &[Measurement]) {
+        // Spread the sampled refreshes evenly instead of picking them at random
+        self.credit += self.sample_rate;
+        if self.credit < 1.0 {
+            return;
+        }
+        self.credit -= 1.0;
+
+        let rate = if self.sample_rate < 1.0 {
+            format!("|@{}", self.sample_rate)
+        } else {
+            String::new()
+        };
+        let mut packet = String::new();
+        for measurement in measurements {
+            for (field, value) in &measurement.fields {
+                let line = format!(
+                    "{}:{}|g{}\n",
+                    self.metric_name(measurement, field),
+                    value,
+                    rate
+                );
+                if packet.len() + line.len() > MAX_PACKET_BYTES {
+                    self.flush(&mut packet);
+                }
+                packet.push_str(&line);
+            }
+        }
+        self.flush(&mut packet);
+    }
+
+    fn flush(&self, packet: &mut String) {
+        if packet.is_empty() {
+            return;
+        }
+        // Nobody listening is normal for UDP, so failures are only logged
+        if let Err(e) = self.socket.send(packet.trim_end().as_bytes()) {
+            debug!("StatsD send failed: {}", e);
+        }
+        packet.clear();
+    }
+
+    // prefix.measurement[.tag...].field, e.g. hercules.disk.root.used_percent
+    fn metric_name(&self, measurement: &Measurement, field: &str) -> String {
+        let mut parts = Vec::new();
+        if !self.prefix.is_empty() {
+            parts.push(self.prefix.clone());
+        }
+        parts.push(measurement.name.to_string());
+        // The path tag already identifies a disk, the device would only repeat it
+        for (key, value) in &measurement.tags {
+            if *key != "device" {
+                parts.push(sanitize(value));
+            }
+        }
+        parts.push(field.to_string());
+        parts.join(".")
+    }
+}
+
+// Graphite paths only allow letters, digits, '_' and '-'; "/" becomes "root"
+fn sanitize(text: &str) -> String {
+    let trimmed = text.trim_matches('/');
+    if trimmed.is_empty() {
+        return "root".to_string();
+    }
+    trimmed
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
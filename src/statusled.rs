@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use log::{debug, info};
+
+use crate::alerts::AlertLevel;
+
+// One step of the blink patterns: warnings blink at 1 Hz, critical alerts at 5 Hz
+const TICK: Duration = Duration::from_millis(100);
+
+const OK: u8 = 0;
+const WARNING: u8 = 1;
+const CRITICAL: u8 = 2;
+const STOP: u8 = u8::MAX;
+
+// Where the LED is driven: an LED class device such as the Pi's ACT LED or
+// one set up with dtoverlay=gpio-led, or a bare GPIO pin via sysfs
+enum Output {
+    Led {
+        dir: PathBuf,
+        max_brightness: String,
+        // Trigger to hand the LED back to, e.g. "mmc0" for ACT
+        trigger: Option<String>,
+    },
+    Gpio {
+        value: PathBuf,
+    },
+}
+
+impl Output {
+    // "ACT", an LED class name, or "gpio17"
+    fn open(target: &str) -> Result<Self> {
+        if let Some(pin) = target.strip_prefix("gpio") {
+            let pin: u32 = pin
+                .parse()
+                .map_err(|_| anyhow!("Invalid GPIO pin in status_led '{}'", target))?;
+            return open_gpio(pin);
+        }
+
+        // The ACT LED is called led0 on older kernels
+        let candidates: &[&str] = if target.eq_ignore_ascii_case("act") {
+            &["ACT", "led0"]
+        } else {
+            &[target]
+        };
+        let dir = candidates
+            .iter()
+            .map(|name| Path::new("/sys/class/leds").join(name))
+            .find(|dir| dir.exists())
+            .ok_or_else(|| anyhow!("No LED named '{}' in /sys/class/leds", target))?;
+
+        let trigger = fs::read_to_string(dir.join("trigger"))
+            .ok()
+            .and_then(|triggers| current_trigger(&triggers));
+        write(&dir.join("trigger"), "none")?;
+        let max_brightness = fs::read_to_string(dir.join("max_brightness"))
+            .map(|max| max.trim().to_string())
+            .unwrap_or_else(|_| "1".to_string());
+        Ok(Output::Led {
+            dir,
+            max_brightness,
+            trigger,
+        })
+    }
+
+    fn set(&self, on: bool) {
+        let result = match self {
+            Output::Led {
+                dir,
+                max_brightness,
+                ..
+            } => write(
+                &dir.join("brightness"),
+                if on { max_brightness } else { "0" },
+            ),
+            Output::Gpio { value } => write(value, if on { "1" } else { "0" }),
+        };
+        if let Err(e) = result {
+            debug!("Status LED: {}", e);
+        }
+    }
+
+    fn release(&self) {
+        match self {
+            Output::Led {
+                dir,
+                trigger: Some(trigger),
+                ..
+            } => {
+                let _ = write(&dir.join("trigger"), trigger);
+            }
+            _ => self.set(false),
+        }
+    }
+}
+
+// The selected trigger is the one in brackets: "none [mmc0] timer heartbeat"
+fn current_trigger(triggers: &str) -> Option<String> {
+    let start = triggers.find('[')?;
+    let end = triggers[start..].find(']')?;
+    Some(triggers[start + 1..start + end].to_string()).filter(|name| name != "none")
+}
+
+// Legacy sysfs GPIO numbers start at the chip's base, 512 on recent kernels
+fn open_gpio(pin: u32) -> Result<Output> {
+    let base = fs::read_dir("/sys/class/gpio")
+        .map_err(|_| anyhow!("/sys/class/gpio is not available, use dtoverlay=gpio-led instead"))?
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("gpiochip"))
+        .filter(|entry| {
+            fs::read_to_string(entry.path().join("label")).is_ok_and(|label| {
+                label.starts_with("pinctrl-bcm") || label.starts_with("pinctrl-rp1")
+            })
+        })
+        .find_map(|entry| {
+            fs::read_to_string(entry.path().join("base"))
+                .ok()?
+                .trim()
+                .parse::<u32>()
+                .ok()
+        })
+        .unwrap_or(0);
+
+    let gpio = Path::new("/sys/class/gpio").join(format!("gpio{}", base + pin));
+    if !gpio.exists() {
+        write(
+            Path::new("/sys/class/gpio/export"),
+            &(base + pin).to_string(),
+        )?;
+        // The pin directory appears shortly after the export
+        for _ in 0..20 {
+            if gpio.join("direction").exists() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+    write(&gpio.join("direction"), "out")?;
+    Ok(Output::Gpio {
+        value: gpio.join("value"),
+    })
+}
+
+fn write(path: &Path, value: &str) -> Result<()> {
+    fs::write(path, value).map_err(|e| anyhow!("cannot write {}: {}", path.display(), e))
+}
+
+// Shows the worst active alert on an LED: solid when all is well, a slow
+// blink for warnings and a fast one for critical alerts
+pub struct StatusLed {
+    pattern: Arc<AtomicU8>,
+}
+
+impl StatusLed {
+    pub fn start(target: &str) -> Result<Self> {
+        let output = Output::open(target)?;
+        info!("Status LED on {}", target);
+        let pattern = Arc::new(AtomicU8::new(OK));
+        let shared = Arc::clone(&pattern);
+        thread::spawn(move || {
+            let mut tick: u32 = 0;
+            let mut lit = None;
+            loop {
+                let on = match shared.load(Ordering::Relaxed) {
+                    STOP => break,
+                    WARNING => tick % 10 < 5,
+                    CRITICAL => tick.is_multiple_of(2),
+                    _ => true,
+                };
+                if lit != Some(on) {
+                    output.set(on);
+                    lit = Some(on);
+                }
+                tick = tick.wrapping_add(1);
+                thread::sleep(TICK);
+            }
+            output.release();
+        });
+        Ok(StatusLed { pattern })
+    }
+
+    // Switch to the pattern for the most severe active alert
+    pub fn show(&self, level: Option<AlertLevel>) {
+        let pattern = match level {
+            None => OK,
+            Some(AlertLevel::Warning) => WARNING,
+            Some(AlertLevel::Critical) => CRITICAL,
+        };
+        self.pattern.store(pattern, Ordering::Relaxed);
+    }
+}
+
+impl Drop for StatusLed {
+    fn drop(&mut self) {
+        self.pattern.store(STOP, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,130 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+
+use crate::i18n;
+use crate::process::format_duration;
+use crate::theme::Themed;
+use crate::units;
+
+// Parse a run length like "90", "90s", "5m", "2h" or "1h30m". Bare numbers are seconds.
+pub fn parse_duration(text: &str) -> Result<Duration> {
+    let invalid = || anyhow!("Invalid duration '{}'. Use e.g. 90s, 5m, 1h30m", text);
+    let mut total = 0;
+    let mut number = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            _ => return Err(invalid()),
+        };
+        total += number.parse::<u64>().map_err(|_| invalid())? * unit;
+        number.clear();
+    }
+    if !number.is_empty() {
+        total += number.parse::<u64>().map_err(|_| invalid())?;
+    }
+    if total == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
+
+// Running min/avg/max of one metric
+struct Stats {
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: usize,
+}
+
+// Collects samples over a limited run and prints them when it ends
+pub struct RunSummary {
+    started: Instant,
+    refreshes: usize,
+    // Name, unit and stats, in the order first recorded
+    metrics: Vec<(&'static str, &'static str, Stats)>,
+}
+
+impl RunSummary {
+    pub fn new() -> Self {
+        RunSummary {
+            started: Instant::now(),
+            refreshes: 0,
+            metrics: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, name: &'static str, unit: &'static str, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        match self
+            .metrics
+            .iter_mut()
+            .find(|(metric, _, _)| *metric == name)
+        {
+            Some((_, _, stats)) => {
+                stats.min = stats.min.min(value);
+                stats.max = stats.max.max(value);
+                stats.sum += value;
+                stats.count += 1;
+            }
+            None => self.metrics.push((
+                name,
+                unit,
+                Stats {
+                    min: value,
+                    max: value,
+                    sum: value,
+                    count: 1,
+                },
+            )),
+        }
+    }
+
+    // Call once per refresh, after its samples are recorded
+    pub fn finish_refresh(&mut self) {
+        self.refreshes += 1;
+    }
+
+    pub fn refreshes(&self) -> usize {
+        self.refreshes
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn print(&self) {
+        let heading = i18n::tr("heading-run-summary");
+        println!("\n{}", heading.bold().highlight());
+        println!("{}", i18n::underline(heading).highlight());
+        println!(
+            "{} refreshes over {}",
+            self.refreshes.to_string().value(),
+            format_duration(self.elapsed().as_secs())
+        );
+        println!(
+            "  {:<16} {:>12} {:>12} {:>12}",
+            "Metric", "Min", "Avg", "Max"
+        );
+        for (name, unit, stats) in &self.metrics {
+            let format = |value: f64| units::format_metric(value, unit);
+            println!(
+                "  {:<16} {:>12} {:>12} {:>12}",
+                name,
+                format(stats.min).good(),
+                format(stats.sum / stats.count as f64).value(),
+                format(stats.max).warning()
+            );
+        }
+    }
+}
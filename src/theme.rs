@@ -0,0 +1,258 @@
+use std::borrow::Cow;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use anyhow::{anyhow, Result};
+use colored::{Color, ColoredString, Colorize};
+
+// What a piece of text means; themes decide what that looks like
+#[derive(Debug, Clone, Copy)]
+pub enum Role {
+    Good,
+    Warning,
+    Critical,
+    Value,
+    Accent,
+    Primary,
+    Emphasis,
+    Highlight,
+}
+
+// Colors for each role, None leaves the terminal's default color
+pub struct Theme {
+    pub name: &'static str,
+    good: Option<Color>,
+    warning: Option<Color>,
+    critical: Option<Color>,
+    value: Option<Color>,
+    accent: Option<Color>,
+    primary: Option<Color>,
+    emphasis: Option<Color>,
+    highlight: Option<Color>,
+}
+
+const fn rgb(r: u8, g: u8, b: u8) -> Option<Color> {
+    Some(Color::TrueColor { r, g, b })
+}
+
+pub const THEMES: &[Theme] = &[
+    Theme {
+        name: "default",
+        good: Some(Color::Green),
+        warning: Some(Color::Yellow),
+        critical: Some(Color::Red),
+        value: Some(Color::Cyan),
+        accent: Some(Color::Magenta),
+        primary: Some(Color::Blue),
+        emphasis: Some(Color::BrightWhite),
+        highlight: Some(Color::BrightBlue),
+    },
+    Theme {
+        name: "solarized",
+        good: rgb(0x85, 0x99, 0x00),
+        warning: rgb(0xb5, 0x89, 0x00),
+        critical: rgb(0xdc, 0x32, 0x2f),
+        value: rgb(0x2a, 0xa1, 0x98),
+        accent: rgb(0xd3, 0x36, 0x82),
+        primary: rgb(0x26, 0x8b, 0xd2),
+        emphasis: rgb(0x93, 0xa1, 0xa1),
+        highlight: rgb(0x6c, 0x71, 0xc4),
+    },
+    Theme {
+        name: "gruvbox",
+        good: rgb(0xb8, 0xbb, 0x26),
+        warning: rgb(0xfa, 0xbd, 0x2f),
+        critical: rgb(0xfb, 0x49, 0x34),
+        value: rgb(0x8e, 0xc0, 0x7c),
+        accent: rgb(0xd3, 0x86, 0x9b),
+        primary: rgb(0x83, 0xa5, 0x98),
+        emphasis: rgb(0xeb, 0xdb, 0xb2),
+        highlight: rgb(0xfe, 0x80, 0x19),
+    },
+    Theme {
+        name: "monochrome",
+        good: None,
+        warning: None,
+        critical: None,
+        value: None,
+        accent: None,
+        primary: None,
+        emphasis: None,
+        highlight: None,
+    },
+    Theme {
+        name: "high-contrast",
+        good: Some(Color::BrightGreen),
+        warning: Some(Color::BrightYellow),
+        critical: Some(Color::BrightRed),
+        value: Some(Color::BrightCyan),
+        accent: Some(Color::BrightMagenta),
+        primary: Some(Color::BrightBlue),
+        emphasis: Some(Color::BrightWhite),
+        highlight: Some(Color::BrightWhite),
+    },
+];
+
+impl Theme {
+    pub fn find(name: &str) -> Result<&'static Theme> {
+        let name = name.to_lowercase();
+        THEMES
+            .iter()
+            .find(|theme| theme.name == name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "Invalid theme '{}'. Use: {}",
+                    name,
+                    THEMES
+                        .iter()
+                        .map(|theme| theme.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+
+    fn color(&self, role: Role) -> Option<Color> {
+        match role {
+            Role::Good => self.good,
+            Role::Warning => self.warning,
+            Role::Critical => self.critical,
+            Role::Value => self.value,
+            Role::Accent => self.accent,
+            Role::Primary => self.primary,
+            Role::Emphasis => self.emphasis,
+            Role::Highlight => self.highlight,
+        }
+    }
+}
+
+// Index into THEMES of the active theme
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_theme(name: &str) -> Result<()> {
+    let theme = Theme::find(name)?;
+    let index = THEMES
+        .iter()
+        .position(|candidate| std::ptr::eq(candidate, theme))
+        .unwrap_or(0);
+    ACTIVE.store(index, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn current() -> &'static Theme {
+    &THEMES[ACTIVE.load(Ordering::Relaxed)]
+}
+
+// Set by --plain: no ANSI codes and no box drawing or emoji
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+    if plain {
+        colored::control::set_override(false);
+    }
+}
+
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+// Screen clearing and spinners only make sense on an interactive terminal.
+// Colors need no check here: `colored` already honors NO_COLOR and non-TTY stdout.
+pub fn interactive() -> bool {
+    !is_plain() && std::io::stdout().is_terminal()
+}
+
+// In plain mode, swap box drawing, block and arrow glyphs for ASCII and drop emoji
+pub fn ascii(text: &str) -> Cow<'_, str> {
+    if !is_plain() || text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut dropped = false;
+    for c in text.chars() {
+        // Emoji are followed by padding that would be left dangling
+        if c == '\u{FE0F}' || (dropped && c == ' ') {
+            continue;
+        }
+        dropped = false;
+        match c {
+            '─' => out.push('-'),
+            '═' => out.push('='),
+            '│' | '║' => out.push('|'),
+            '╭' | '╮' | '╰' | '╯' | '┌' | '┐' | '└' | '┘' | '╔' | '╗' | '╚' | '╝' | '╩' => {
+                out.push('+')
+            }
+            // Sparkline levels, lowest to highest
+            '▁'..='█' => out.push(b"_.,:-=*#"[c as usize - '▁' as usize] as char),
+            '░' => out.push('-'),
+            '▲' | '⬆' | '↗' => out.push('^'),
+            '▼' | '⬇' | '↙' => out.push('v'),
+            '→' | '➡' => out.push('>'),
+            '⬅' => out.push('<'),
+            '✓' => out.push('*'),
+            // Braille chart cells become a dot wherever anything is drawn
+            '\u{2800}' => out.push(' '),
+            '\u{2801}'..='\u{28FF}' => out.push('.'),
+            '\u{2600}'..='\u{27BF}' | '\u{2B00}'..='\u{2BFF}' | '\u{1F000}'..='\u{1FAFF}' => {
+                dropped = true
+            }
+            _ => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+// Role-based replacements for the fixed colors of `colored::Colorize`
+pub trait Themed: Sized {
+    fn paint(self, role: Role) -> ColoredString;
+
+    fn good(self) -> ColoredString {
+        self.paint(Role::Good)
+    }
+    fn warning(self) -> ColoredString {
+        self.paint(Role::Warning)
+    }
+    fn critical(self) -> ColoredString {
+        self.paint(Role::Critical)
+    }
+    fn value(self) -> ColoredString {
+        self.paint(Role::Value)
+    }
+    fn accent(self) -> ColoredString {
+        self.paint(Role::Accent)
+    }
+    fn primary(self) -> ColoredString {
+        self.paint(Role::Primary)
+    }
+    fn emphasis(self) -> ColoredString {
+        self.paint(Role::Emphasis)
+    }
+    fn highlight(self) -> ColoredString {
+        self.paint(Role::Highlight)
+    }
+}
+
+impl Themed for ColoredString {
+    fn paint(mut self, role: Role) -> ColoredString {
+        match current().color(role) {
+            Some(color) => self.color(color),
+            None => {
+                self.clear_fgcolor();
+                // Without color, problems still need to stand out
+                if matches!(role, Role::Critical) {
+                    self.bold()
+                } else {
+                    self
+                }
+            }
+        }
+    }
+}
+
+impl Themed for &str {
+    fn paint(self, role: Role) -> ColoredString {
+        self.normal().paint(role)
+    }
+}
@@ -0,0 +1,369 @@
+use std::process::Command;
+use std::time::Instant;
+
+use log::debug;
+use sysinfo::{ComponentExt, CpuExt, System, SystemExt};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::history::HistoryStore;
+use crate::process::format_duration;
+use crate::units;
+
+// Consecutive refreshes below base clock before calling it throttling
+const SUSTAINED_SAMPLES: usize = 3;
+
+// Idle cores clock down on purpose, so low clocks only count under load
+const BUSY_CPU_PERCENT: f32 = 50.0;
+
+// Raspberry Pi firmware flags from `vcgencmd get_throttled`
+pub const PI_UNDER_VOLTAGE: u32 = 1 << 0;
+pub const PI_FREQ_CAPPED: u32 = 1 << 1;
+pub const PI_THROTTLED: u32 = 1 << 2;
+pub const PI_SOFT_TEMP_LIMIT: u32 = 1 << 3;
+// Sticky bit: under-voltage has occurred at some point since boot
+pub const PI_UNDER_VOLTAGE_OCCURRED: u32 = 1 << 16;
+
+// Current throttle flags reported by the Pi firmware, None off a Pi
+pub fn pi_throttled_flags() -> Option<u32> {
+    let output = Command::new("vcgencmd")
+        .arg("get_throttled")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // "throttled=0x50005"
+    let text = String::from_utf8_lossy(&output.stdout);
+    let hex = text.trim().strip_prefix("throttled=0x")?;
+    u32::from_str_radix(hex, 16).ok()
+}
+
+// Rated (non-turbo) clock in MHz, which Intel exposes as base_frequency.
+// cpuinfo_max_freq is the boost ceiling, so without base_frequency there is
+// nothing to compare against and the clock check is skipped.
+#[cfg(target_os = "linux")]
+fn base_frequency_mhz() -> Option<u64> {
+    std::fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/base_frequency")
+        .ok()
+        .and_then(|khz| khz.trim().parse::<u64>().ok())
+        .map(|khz| khz / 1000)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn base_frequency_mhz() -> Option<u64> {
+    None
+}
+
+// Hottest CPU sensor, or the hottest sensor overall when none is labelled as one
+pub fn cpu_temperature(system: &System) -> Option<f32> {
+    let is_cpu = |label: &str| {
+        let label = label.to_lowercase();
+        ["cpu", "package", "coretemp", "k10temp", "soc"]
+            .iter()
+            .any(|name| label.contains(name))
+    };
+    let hottest = |cpu_only: bool| {
+        system
+            .components()
+            .iter()
+            .filter(|component| !cpu_only || is_cpu(component.label()))
+            .map(|component| component.temperature())
+            .filter(|temp| temp.is_finite())
+            .reduce(f32::max)
+    };
+    hottest(true).or_else(|| hottest(false))
+}
+
+// An ongoing throttling episode
+struct Episode {
+    started: Instant,
+    min_mhz: u64,
+    max_temp: Option<f32>,
+}
+
+// Watches clocks, temperature and Pi firmware flags for thermal throttling
+pub struct ThrottleMonitor {
+    base_mhz: Option<u64>,
+    has_vcgencmd: bool,
+    // Firmware flags at the last refresh, None off a Pi
+    pi_flags: Option<u32>,
+    below_base_samples: usize,
+    reason: Option<String>,
+    episode: Option<Episode>,
+    store: Option<HistoryStore>,
+    record_episodes: bool,
+    temperature: Option<f32>,
+    average_mhz: u64,
+    // Whether the under-voltage bit was set at the last refresh
+    under_voltage: bool,
+    // Under-voltage events since boot, None until counted from the history
+    under_voltage_events: Option<u32>,
+    // Warning and critical CPU temperatures in °C, no alerts when unset
+    temperature_limits: Option<(f64, f64)>,
+}
+
+impl ThrottleMonitor {
+    // Under-voltage events are always recorded on a Pi; `record_history`
+    // adds throttling episodes
+    pub fn new(record_history: bool) -> Self {
+        let has_vcgencmd = pi_throttled_flags().is_some();
+        let store = if record_history || has_vcgencmd {
+            HistoryStore::open()
+                .map_err(|e| debug!("Throttle history disabled: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        ThrottleMonitor {
+            base_mhz: base_frequency_mhz(),
+            has_vcgencmd,
+            pi_flags: None,
+            below_base_samples: 0,
+            reason: None,
+            episode: None,
+            store,
+            record_episodes: record_history,
+            temperature: None,
+            average_mhz: 0,
+            under_voltage: false,
+            under_voltage_events: None,
+            temperature_limits: None,
+        }
+    }
+
+    // Raise the "temperature" alert at `warn` and `crit` °C
+    pub fn set_temperature_limits(&mut self, warn: f64, crit: f64) {
+        self.temperature_limits = Some((warn, crit));
+    }
+
+    pub fn refresh(&mut self, system: &System, alerts: &mut AlertEngine) {
+        let cpus = system.cpus();
+        self.average_mhz = if cpus.is_empty() {
+            0
+        } else {
+            cpus.iter().map(|cpu| cpu.frequency()).sum::<u64>() / cpus.len() as u64
+        };
+        self.temperature = cpu_temperature(system);
+        let busy = system.global_cpu_info().cpu_usage() >= BUSY_CPU_PERCENT;
+
+        // Below 90% of base clock while busy, for several samples in a row
+        let below_base = matches!(self.base_mhz, Some(base) if base > 0 && self.average_mhz > 0
+            && (self.average_mhz as f64) < base as f64 * 0.9);
+        if below_base && busy {
+            self.below_base_samples += 1;
+        } else {
+            self.below_base_samples = 0;
+        }
+
+        let pi_flags = if self.has_vcgencmd {
+            pi_throttled_flags()
+        } else {
+            None
+        };
+        self.pi_flags = pi_flags;
+
+        self.reason = if let Some(flags) = pi_flags.filter(|flags| flags & 0xF != 0) {
+            let mut reasons = Vec::new();
+            if flags & PI_UNDER_VOLTAGE != 0 {
+                reasons.push("under-voltage");
+            }
+            if flags & PI_SOFT_TEMP_LIMIT != 0 {
+                reasons.push("soft temperature limit");
+            }
+            if flags & (PI_THROTTLED | PI_FREQ_CAPPED) != 0 {
+                reasons.push("firmware throttling");
+            }
+            Some(reasons.join(", "))
+        } else if self.below_base_samples >= SUSTAINED_SAMPLES {
+            let base = self.base_mhz.unwrap_or_default();
+            Some(match self.temperature {
+                Some(temp) => format!(
+                    "{} MHz under load, below {} MHz base at {}",
+                    self.average_mhz,
+                    base,
+                    units::format_temperature(temp as f64)
+                ),
+                None => format!(
+                    "{} MHz under load, below {} MHz base",
+                    self.average_mhz, base
+                ),
+            })
+        } else {
+            None
+        };
+
+        self.check_temperature(alerts);
+        if let Some(flags) = pi_flags {
+            self.track_under_voltage(flags, system.boot_time() as i64, alerts);
+        }
+        self.track_episode(alerts);
+    }
+
+    fn check_temperature(&self, alerts: &mut AlertEngine) {
+        let (Some((warn, crit)), Some(temp)) = (self.temperature_limits, self.temperature) else {
+            return;
+        };
+        let temp = temp as f64;
+        let level = if temp >= crit {
+            AlertLevel::Critical
+        } else if temp >= warn {
+            AlertLevel::Warning
+        } else {
+            alerts.clear("temperature");
+            return;
+        };
+        alerts.raise(
+            "temperature",
+            level,
+            "cpu",
+            format!(
+                "CPU at {} (limit {})",
+                units::format_temperature(temp),
+                units::format_temperature(if level == AlertLevel::Critical {
+                    crit
+                } else {
+                    warn
+                })
+            ),
+        );
+    }
+
+    // Under-voltage is alerted and recorded the moment the bit appears, since
+    // a flaky power supply is the most common cause of SD card corruption
+    fn track_under_voltage(&mut self, flags: u32, boot_time: i64, alerts: &mut AlertEngine) {
+        let active = flags & PI_UNDER_VOLTAGE != 0;
+        let mut events = match self.under_voltage_events {
+            Some(events) => events,
+            None => {
+                // Events from earlier runs since this boot, and at least one
+                // when the firmware saw under-voltage before we started
+                let recorded = self.recorded_under_voltage_events(boot_time);
+                let occurred = u32::from(flags & PI_UNDER_VOLTAGE_OCCURRED != 0 && !active);
+                recorded.max(occurred)
+            }
+        };
+
+        if active && !self.under_voltage {
+            events += 1;
+            let message = format!(
+                "under-voltage detected ({} since boot), check the power supply",
+                events
+            );
+            if let Some(ref store) = self.store {
+                if let Err(e) = store.record_event("under-voltage", &message) {
+                    debug!("Failed to record under-voltage event: {}", e);
+                }
+            }
+            alerts.raise(
+                "under-voltage",
+                AlertLevel::Critical,
+                "power",
+                format!("Under-voltage detected ({} since boot)", events),
+            );
+        } else if !active && self.under_voltage {
+            alerts.clear("under-voltage");
+        }
+
+        self.under_voltage = active;
+        self.under_voltage_events = Some(events);
+    }
+
+    fn recorded_under_voltage_events(&self, boot_time: i64) -> u32 {
+        let Some(ref store) = self.store else {
+            return 0;
+        };
+        match store.events(boot_time) {
+            Ok(events) => events
+                .iter()
+                .filter(|(_, kind, _)| kind == "under-voltage")
+                .count() as u32,
+            Err(e) => {
+                debug!("Failed to read under-voltage events: {}", e);
+                0
+            }
+        }
+    }
+
+    fn track_episode(&mut self, alerts: &mut AlertEngine) {
+        match (&self.reason, self.episode.as_mut()) {
+            (Some(reason), None) => {
+                alerts.raise(
+                    "throttle",
+                    AlertLevel::Warning,
+                    "cpu",
+                    format!("CPU throttled: {}", reason),
+                );
+                self.episode = Some(Episode {
+                    started: Instant::now(),
+                    min_mhz: self.average_mhz,
+                    max_temp: self.temperature,
+                });
+            }
+            (Some(_), Some(episode)) => {
+                episode.min_mhz = episode.min_mhz.min(self.average_mhz);
+                episode.max_temp = match (episode.max_temp, self.temperature) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            (None, Some(_)) => {
+                alerts.clear("throttle");
+                if let Some(episode) = self.episode.take() {
+                    self.log_episode(&episode);
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn log_episode(&self, episode: &Episode) {
+        let Some(store) = self.store.as_ref().filter(|_| self.record_episodes) else {
+            return;
+        };
+        let mut message = format!(
+            "throttled for {}, lowest clock {} MHz",
+            format_duration(episode.started.elapsed().as_secs()),
+            episode.min_mhz
+        );
+        if let Some(temp) = episode.max_temp {
+            message.push_str(&format!(
+                ", peak {}",
+                units::format_temperature(temp as f64)
+            ));
+        }
+        if let Err(e) = store.record_event("throttle", &message) {
+            debug!("Failed to record throttle episode: {}", e);
+        }
+    }
+
+    // Hottest CPU sensor at the last refresh
+    pub fn temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    // Rated clock the throttling check compares against
+    pub fn base_mhz(&self) -> Option<u64> {
+        self.base_mhz
+    }
+
+    // Mean clock across cores at the last refresh
+    pub fn average_mhz(&self) -> u64 {
+        self.average_mhz
+    }
+
+    // Raspberry Pi firmware throttle flags at the last refresh, None off a Pi
+    pub fn pi_flags(&self) -> Option<u32> {
+        self.pi_flags
+    }
+
+    // Under-voltage events since boot, None off a Pi
+    pub fn under_voltage_events(&self) -> Option<u32> {
+        self.under_voltage_events
+    }
+
+    // Why the CPU is currently considered throttled
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
@@ -0,0 +1,414 @@
+use std::io::{ErrorKind, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use colored::*;
+use log::debug;
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::i18n;
+use crate::theme::Themed;
+
+// A handshake that has not finished by then is reported as failed
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How often the idle checker thread looks whether its monitor is gone
+const STOP_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// One certificate in the chain a server presented
+#[derive(Debug, Clone)]
+pub struct Certificate {
+    pub subject: String,
+    pub not_after: DateTime<Utc>,
+}
+
+impl Certificate {
+    pub fn days_left(&self) -> i64 {
+        (self.not_after - Utc::now()).num_days()
+    }
+}
+
+// What one endpoint presented on its last check
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    // "host:port" as configured
+    pub target: String,
+    // Leaf first, then the intermediates
+    pub chain: Vec<Certificate>,
+    // The verification error when the chain is not trusted
+    pub untrusted: Option<String>,
+    pub error: Option<String>,
+    pub checked: DateTime<Local>,
+}
+
+impl Endpoint {
+    // The certificate that expires first; an intermediate can run out before the leaf
+    pub fn earliest(&self) -> Option<&Certificate> {
+        self.chain.iter().min_by_key(|cert| cert.not_after)
+    }
+}
+
+// Checks certificate chains and expiry of the configured endpoints from a
+// background thread every `check_hours`, using the openssl command line tool
+pub struct TlsMonitor {
+    endpoints: Arc<Mutex<Vec<Endpoint>>>,
+    alert_days: i64,
+}
+
+impl TlsMonitor {
+    pub fn start(targets: &[String], alert_days: u64, check_hours: u64) -> Self {
+        let endpoints: Arc<Mutex<Vec<Endpoint>>> = Arc::new(Mutex::new(Vec::new()));
+        // A weak reference lets the thread end once the monitor is dropped,
+        // e.g. when a config reload replaces it
+        let weak = Arc::downgrade(&endpoints);
+        let targets: Vec<String> = targets.iter().map(|target| with_port(target)).collect();
+        let interval = Duration::from_secs(check_hours.max(1) * 3600);
+        thread::spawn(move || loop {
+            for target in &targets {
+                let endpoint = check(target);
+                let Some(shared) = weak.upgrade() else {
+                    return;
+                };
+                let Ok(mut endpoints) = shared.lock() else {
+                    return;
+                };
+                match endpoints.iter_mut().find(|known| known.target == *target) {
+                    Some(known) => *known = endpoint,
+                    None => endpoints.push(endpoint),
+                }
+            }
+            // Checks are hours apart, so look out for the monitor going away meanwhile
+            let started = Instant::now();
+            while started.elapsed() < interval {
+                if weak.strong_count() == 0 {
+                    return;
+                }
+                thread::sleep(STOP_POLL_INTERVAL);
+            }
+        });
+
+        TlsMonitor {
+            endpoints,
+            alert_days: alert_days as i64,
+        }
+    }
+
+    pub fn alert_days(&self) -> i64 {
+        self.alert_days
+    }
+
+    pub fn endpoints(&self) -> Vec<Endpoint> {
+        self.endpoints
+            .lock()
+            .map(|endpoints| endpoints.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn refresh(&self, alerts: &mut AlertEngine) {
+        for endpoint in self.endpoints() {
+            let key = format!("tls:{}", endpoint.target);
+            let Some(earliest) = endpoint.earliest() else {
+                alerts.clear(&key);
+                continue;
+            };
+            let days = earliest.days_left();
+            if earliest.not_after <= Utc::now() {
+                alerts.raise(
+                    &key,
+                    AlertLevel::Critical,
+                    "tls",
+                    format!(
+                        "Certificate {} on {} has expired",
+                        earliest.subject, endpoint.target
+                    ),
+                );
+            } else if let Some(ref reason) = endpoint.untrusted {
+                alerts.raise(
+                    &key,
+                    AlertLevel::Critical,
+                    "tls",
+                    format!(
+                        "Certificate chain on {} is not trusted: {}",
+                        endpoint.target, reason
+                    ),
+                );
+            } else if days < self.alert_days {
+                alerts.raise(
+                    &key,
+                    AlertLevel::Warning,
+                    "tls",
+                    format!(
+                        "Certificate {} on {} expires in {} day{}",
+                        earliest.subject,
+                        endpoint.target,
+                        days,
+                        if days == 1 { "" } else { "s" }
+                    ),
+                );
+            } else {
+                alerts.clear(&key);
+            }
+        }
+    }
+}
+
+// "example.com" becomes "example.com:443"; "[::1]:8443" is left alone
+fn with_port(target: &str) -> String {
+    let target = target.trim();
+    let has_port = match target.rsplit_once(':') {
+        Some((host, port)) => {
+            port.parse::<u16>().is_ok() && (!host.contains(':') || host.ends_with(']'))
+        }
+        None => false,
+    };
+    if has_port {
+        target.to_string()
+    } else if target.contains(':') && !target.starts_with('[') {
+        format!("[{}]:443", target)
+    } else {
+        format!("{}:443", target)
+    }
+}
+
+fn check(target: &str) -> Endpoint {
+    let mut endpoint = Endpoint {
+        target: target.to_string(),
+        chain: Vec::new(),
+        untrusted: None,
+        error: None,
+        checked: Local::now(),
+    };
+    match fetch_chain(target) {
+        Ok((chain, untrusted)) => {
+            endpoint.chain = chain;
+            endpoint.untrusted = untrusted;
+        }
+        Err(e) => {
+            debug!("TLS check of {} failed: {}", target, e);
+            endpoint.error = Some(e.to_string());
+        }
+    }
+    endpoint
+}
+
+// Handshake with `openssl s_client` and read the presented chain and the
+// verification result
+fn fetch_chain(target: &str) -> Result<(Vec<Certificate>, Option<String>)> {
+    let host = target
+        .rsplit_once(':')
+        .map(|(host, _)| host.trim_start_matches('[').trim_end_matches(']'))
+        .unwrap_or(target);
+    let mut args = vec!["s_client", "-connect", target, "-showcerts"];
+    // SNI only applies to names, not addresses
+    if host.parse::<std::net::IpAddr>().is_err() {
+        args.extend(["-servername", host]);
+    }
+    let output = run_openssl(&args, None)?;
+
+    let pems: Vec<String> = output
+        .split("-----BEGIN CERTIFICATE-----")
+        .skip(1)
+        .filter_map(|block| block.split_once("-----END CERTIFICATE-----"))
+        .map(|(body, _)| {
+            format!(
+                "-----BEGIN CERTIFICATE-----{}-----END CERTIFICATE-----\n",
+                body
+            )
+        })
+        .collect();
+    if pems.is_empty() {
+        return Err(anyhow!("{}", handshake_error(&output)));
+    }
+
+    let chain = pems
+        .iter()
+        .map(|pem| parse_certificate(pem))
+        .collect::<Result<Vec<_>>>()?;
+
+    // "Verify return code: 10 (certificate has expired)"
+    let untrusted = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Verify return code: "))
+        .filter(|code| !code.starts_with("0 "))
+        .map(|code| {
+            code.split_once('(')
+                .map(|(_, reason)| reason.trim_end_matches(')'))
+                .unwrap_or(code)
+                .to_string()
+        });
+    Ok((chain, untrusted))
+}
+
+// The reason out of an OpenSSL 3 error line such as
+// "40070DA8C27F0000:error:8000006F:system library:BIO_connect:Connection refused:..."
+fn handshake_error(output: &str) -> String {
+    let Some(line) = output.lines().find(|line| line.contains(":error:")) else {
+        return "no certificate presented".to_string();
+    };
+    let fields: Vec<&str> = line.split(':').collect();
+    fields
+        .iter()
+        .position(|field| *field == "error")
+        .and_then(|index| fields.get(index + 4))
+        .map(|reason| reason.to_string())
+        .unwrap_or_else(|| line.trim().to_string())
+}
+
+fn parse_certificate(pem: &str) -> Result<Certificate> {
+    let output = run_openssl(&["x509", "-noout", "-subject", "-enddate"], Some(pem))?;
+    let mut subject = None;
+    let mut not_after = None;
+    for line in output.lines() {
+        if let Some(value) = line.strip_prefix("subject=") {
+            subject = Some(common_name(value.trim()));
+        } else if let Some(value) = line.strip_prefix("notAfter=") {
+            // "Mar 15 12:00:00 2027 GMT"
+            not_after = NaiveDateTime::parse_from_str(
+                value.trim().trim_end_matches(" GMT"),
+                "%b %e %H:%M:%S %Y",
+            )
+            .ok()
+            .map(|time| Utc.from_utc_datetime(&time));
+        }
+    }
+    Ok(Certificate {
+        subject: subject.unwrap_or_else(|| "unknown".to_string()),
+        not_after: not_after.ok_or_else(|| anyhow!("certificate has no readable expiry date"))?,
+    })
+}
+
+// The CN out of "C = US, O = Let's Encrypt, CN = R3" (or the older
+// "/C=US/O=Let's Encrypt/CN=R3"), falling back to the whole subject
+fn common_name(subject: &str) -> String {
+    subject
+        .split([',', '/'])
+        .find_map(|part| {
+            let (key, value) = part.split_once('=')?;
+            (key.trim() == "CN").then(|| value.trim().to_string())
+        })
+        .unwrap_or_else(|| subject.to_string())
+}
+
+// Runs openssl with `input` on stdin (or an empty stdin so s_client exits after
+// the handshake) and returns stdout followed by stderr, giving up after
+// HANDSHAKE_TIMEOUT
+fn run_openssl(args: &[&str], input: Option<&str>) -> Result<String> {
+    let mut child = Command::new("openssl")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => anyhow!("openssl is not installed"),
+            _ => anyhow!("could not run openssl: {}", e),
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.unwrap_or("").as_bytes())?;
+    }
+    let readers: Vec<_> = [
+        child
+            .stdout
+            .take()
+            .map(|out| Box::new(out) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|err| Box::new(err) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|mut stream| {
+        thread::spawn(move || {
+            let mut output = String::new();
+            let _ = stream.read_to_string(&mut output);
+            output
+        })
+    })
+    .collect();
+
+    let started = Instant::now();
+    while child.try_wait()?.is_none() {
+        if started.elapsed() > HANDSHAKE_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("timed out"));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Ok(readers
+        .into_iter()
+        .filter_map(|reader| reader.join().ok())
+        .collect())
+}
+
+// TLS certificate panel: days left on the first certificate to expire per endpoint
+pub fn monitor_tls(monitor: &TlsMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-tls");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    let endpoints = monitor.endpoints();
+    if endpoints.is_empty() {
+        println!("Checking...");
+        return Ok(());
+    }
+    for endpoint in endpoints {
+        let Some(earliest) = endpoint.earliest() else {
+            println!(
+                "{:<32} {}",
+                endpoint.target,
+                format!(
+                    "check failed: {}",
+                    endpoint.error.as_deref().unwrap_or("unknown error")
+                )
+                .warning()
+            );
+            continue;
+        };
+
+        let days = earliest.days_left();
+        let expiry = format!(
+            "{} ({})",
+            if earliest.not_after <= Utc::now() {
+                "EXPIRED".to_string()
+            } else {
+                format!("{} days", days)
+            },
+            earliest.not_after.format("%Y-%m-%d")
+        );
+        let expiry = if earliest.not_after <= Utc::now() {
+            expiry.critical().bold()
+        } else if days < monitor.alert_days() {
+            expiry.warning()
+        } else {
+            expiry.good()
+        };
+        let trust = match endpoint.untrusted {
+            Some(ref reason) => format!("untrusted: {}", reason).critical(),
+            None => "trusted".good(),
+        };
+        println!("{:<32} {:<24} {}", endpoint.target, expiry, trust);
+
+        // Name the certificate when it is not the leaf that runs out first
+        let leaf = &endpoint.chain[0];
+        if earliest.subject != leaf.subject {
+            println!(
+                "  {} expires first (leaf {})",
+                earliest.subject, leaf.subject
+            );
+        } else {
+            println!(
+                "  {}, chain of {}, checked {}",
+                leaf.subject,
+                endpoint.chain.len(),
+                endpoint.checked.format("%H:%M")
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,353 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::thread;
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::capture::PacketCapture;
+use crate::i18n;
+use crate::lan;
+use crate::netinfo;
+use crate::sockets::{NetRate, Protocol};
+use crate::theme::Themed;
+use crate::units;
+
+// Rows listed per breakdown
+const TOP_ROWS: usize = 5;
+
+// Where the per-flow byte counts come from
+enum Source {
+    Capture(PacketCapture),
+    // Accounting from the connection tracking table, keyed by the original
+    // direction tuple, with the last (received, sent) byte counts seen
+    Conntrack(HashMap<String, (u64, u64)>),
+    // Neither could be used, and why
+    Unavailable(String),
+}
+
+// Traffic summed by transport protocol, by service port and by remote host
+pub struct TrafficMonitor {
+    source: Source,
+    pub by_protocol: Vec<(Protocol, NetRate)>,
+    pub by_port: Vec<((Protocol, u16), NetRate)>,
+    pub by_host: Vec<(IpAddr, NetRate)>,
+    resolver: Resolver,
+}
+
+impl TrafficMonitor {
+    // Packet capture when permitted, otherwise the conntrack table
+    pub fn start() -> Self {
+        let source = match PacketCapture::start() {
+            Ok(capture) => Source::Capture(capture),
+            Err(capture_error) => match conntrack_flows() {
+                Ok(_) => Source::Conntrack(HashMap::new()),
+                Err(conntrack_error) => {
+                    Source::Unavailable(format!("{}; {}", capture_error, conntrack_error))
+                }
+            },
+        };
+        TrafficMonitor {
+            source,
+            by_protocol: Vec::new(),
+            by_port: Vec::new(),
+            by_host: Vec::new(),
+            resolver: Resolver::start(),
+        }
+    }
+
+    pub fn source_name(&self) -> &'static str {
+        match self.source {
+            Source::Capture(_) => "packet capture",
+            Source::Conntrack(_) => "conntrack",
+            Source::Unavailable(_) => "none",
+        }
+    }
+
+    pub fn sample(&mut self, elapsed_secs: f64) {
+        // (protocol, service port, remote host, received, sent)
+        let mut flows: Vec<(Protocol, u16, IpAddr, u64, u64)> = Vec::new();
+        match self.source {
+            Source::Capture(ref capture) => {
+                for (flow, (received, sent)) in capture.take() {
+                    // The service is on whichever side has the lower port
+                    let port = flow.local_port.min(flow.remote.port());
+                    flows.push((flow.protocol, port, flow.remote.ip(), received, sent));
+                }
+            }
+            Source::Conntrack(ref mut last) => {
+                let Ok(entries) = conntrack_flows() else {
+                    return;
+                };
+                let primed = !last.is_empty();
+                let mut seen = HashMap::new();
+                for entry in entries {
+                    // Connections opened since the last sample count from zero,
+                    // except on the first sample, which only sets the baseline
+                    let (old_received, old_sent) = match last.get(&entry.key) {
+                        Some(&counts) => counts,
+                        None if primed => (0, 0),
+                        None => (entry.received, entry.sent),
+                    };
+                    flows.push((
+                        entry.protocol,
+                        entry.port,
+                        entry.remote,
+                        entry.received.saturating_sub(old_received),
+                        entry.sent.saturating_sub(old_sent),
+                    ));
+                    seen.insert(entry.key, (entry.received, entry.sent));
+                }
+                *last = seen;
+            }
+            Source::Unavailable(_) => return,
+        }
+        if elapsed_secs <= 0.0 {
+            return;
+        }
+
+        let mut by_protocol: HashMap<Protocol, NetRate> = HashMap::new();
+        let mut by_port: HashMap<(Protocol, u16), NetRate> = HashMap::new();
+        let mut by_host: HashMap<IpAddr, NetRate> = HashMap::new();
+        for (protocol, port, host, received, sent) in flows {
+            let rx = received as f64 / elapsed_secs;
+            let tx = sent as f64 / elapsed_secs;
+            for rate in [
+                by_protocol.entry(protocol).or_default(),
+                by_port.entry((protocol, port)).or_default(),
+                by_host.entry(host).or_default(),
+            ] {
+                rate.rx += rx;
+                rate.tx += tx;
+            }
+        }
+
+        self.by_protocol = busiest(by_protocol);
+        self.by_port = busiest(by_port);
+        self.by_host = busiest(by_host);
+        self.resolver.collect();
+        for (host, _) in self.by_host.iter().take(TOP_ROWS) {
+            self.resolver.request(*host);
+        }
+    }
+
+    pub fn hostname(&self, host: &IpAddr) -> Option<&str> {
+        self.resolver.lookup(host)
+    }
+}
+
+// Rates with traffic, busiest first
+fn busiest<K>(rates: HashMap<K, NetRate>) -> Vec<(K, NetRate)> {
+    let mut rates: Vec<_> = rates
+        .into_iter()
+        .filter(|(_, rate)| rate.rx + rate.tx > 0.0)
+        .collect();
+    rates.sort_by(|a, b| (b.1.rx + b.1.tx).total_cmp(&(a.1.rx + a.1.tx)));
+    rates
+}
+
+// One connection from /proc/net/nf_conntrack
+struct ConntrackEntry {
+    key: String,
+    protocol: Protocol,
+    port: u16,
+    remote: IpAddr,
+    received: u64,
+    sent: u64,
+}
+
+// TCP and UDP entries with byte counters. The original direction's destination
+// port is the service; the remote side is whichever end is not this host.
+fn conntrack_flows() -> Result<Vec<ConntrackEntry>> {
+    let table = std::fs::read_to_string("/proc/net/nf_conntrack")
+        .map_err(|e| anyhow!("conntrack table unavailable: {}", e))?;
+    let local = netinfo::local_addresses();
+
+    let mut entries = Vec::new();
+    let mut counted = false;
+    for line in table.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let protocol = match fields.get(2) {
+            Some(&"tcp") => Protocol::Tcp,
+            Some(&"udp") => Protocol::Udp,
+            _ => continue,
+        };
+        // Each key appears twice: original direction first, then the reply
+        let values = |key: &str| -> Vec<&str> {
+            fields
+                .iter()
+                .filter_map(|field| field.strip_prefix(key))
+                .collect()
+        };
+        let (sources, destinations, source_ports, ports, bytes) = (
+            values("src="),
+            values("dst="),
+            values("sport="),
+            values("dport="),
+            values("bytes="),
+        );
+        let (Some(source), Some(destination), Some(port)) = (
+            sources.first().and_then(|ip| ip.parse::<IpAddr>().ok()),
+            destinations
+                .first()
+                .and_then(|ip| ip.parse::<IpAddr>().ok()),
+            ports.first().and_then(|port| port.parse::<u16>().ok()),
+        ) else {
+            continue;
+        };
+        let original: u64 = bytes.first().and_then(|b| b.parse().ok()).unwrap_or(0);
+        let reply: u64 = bytes.get(1).and_then(|b| b.parse().ok()).unwrap_or(0);
+        counted |= bytes.len() >= 2;
+
+        let outgoing = local.contains(&source);
+        entries.push(ConntrackEntry {
+            key: format!(
+                "{} {} {} {} {}",
+                protocol.as_str(),
+                source,
+                source_ports.first().unwrap_or(&""),
+                destination,
+                port
+            ),
+            protocol,
+            port,
+            remote: if outgoing { destination } else { source },
+            received: if outgoing { reply } else { original },
+            sent: if outgoing { original } else { reply },
+        });
+    }
+
+    if !entries.is_empty() && !counted {
+        return Err(anyhow!(
+            "conntrack has no byte counters; enable them with 'sysctl -w net.netfilter.nf_conntrack_acct=1'"
+        ));
+    }
+    Ok(entries)
+}
+
+// Reverse DNS off the refresh path: names are looked up on a background
+// thread and cached, so the panel shows the IP until the name arrives
+struct Resolver {
+    requests: Sender<IpAddr>,
+    answers: Receiver<(IpAddr, Option<String>)>,
+    names: HashMap<IpAddr, Option<String>>,
+    pending: HashSet<IpAddr>,
+}
+
+impl Resolver {
+    fn start() -> Self {
+        let (requests, incoming) = crossbeam_channel::unbounded::<IpAddr>();
+        let (replies, answers) = crossbeam_channel::unbounded();
+        thread::spawn(move || {
+            for host in incoming {
+                if replies.send((host, lan::reverse_lookup(host))).is_err() {
+                    return;
+                }
+            }
+        });
+        Resolver {
+            requests,
+            answers,
+            names: HashMap::new(),
+            pending: HashSet::new(),
+        }
+    }
+
+    fn request(&mut self, host: IpAddr) {
+        if !self.names.contains_key(&host) && self.pending.insert(host) {
+            let _ = self.requests.send(host);
+        }
+    }
+
+    // Stores the names answered since the last call
+    fn collect(&mut self) {
+        for (answered, name) in self.answers.try_iter() {
+            self.pending.remove(&answered);
+            self.names.insert(answered, name);
+        }
+    }
+
+    fn lookup(&self, host: &IpAddr) -> Option<&str> {
+        self.names.get(host).and_then(|name| name.as_deref())
+    }
+}
+
+// Well-known service names for the port breakdown
+fn service_name(protocol: Protocol, port: u16) -> Option<&'static str> {
+    Some(match (protocol, port) {
+        (Protocol::Tcp, 22) => "ssh",
+        (_, 53) => "dns",
+        (Protocol::Udp, 67) | (Protocol::Udp, 68) => "dhcp",
+        (Protocol::Tcp, 80) => "http",
+        (Protocol::Udp, 123) => "ntp",
+        (Protocol::Tcp, 139) | (Protocol::Tcp, 445) => "smb",
+        (Protocol::Tcp, 443) => "https",
+        (Protocol::Udp, 443) => "quic",
+        (Protocol::Tcp, 873) => "rsync",
+        (_, 1194) => "openvpn",
+        (Protocol::Tcp, 1883) => "mqtt",
+        (Protocol::Tcp, 2049) => "nfs",
+        (Protocol::Tcp, 3389) => "rdp",
+        (Protocol::Udp, 5353) => "mdns",
+        (Protocol::Tcp, 8883) => "mqtts",
+        (Protocol::Udp, 41641) => "tailscale",
+        (Protocol::Udp, 51820) => "wireguard",
+        _ => return None,
+    })
+}
+
+fn format_rate(rate: &NetRate) -> String {
+    format!(
+        "{} in, {} out",
+        units::format_rate(rate.rx).good(),
+        units::format_rate(rate.tx).good()
+    )
+}
+
+// Traffic breakdown panel: totals per protocol, then the busiest ports and hosts
+pub fn monitor_traffic(monitor: &TrafficMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-traffic");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+    if let Source::Unavailable(ref reason) = monitor.source {
+        println!("{}", format!("Unavailable: {}", reason).warning());
+        return Ok(());
+    }
+    println!("Source: {}", monitor.source_name().value());
+
+    if monitor.by_protocol.is_empty() {
+        println!("No TCP or UDP traffic since the last refresh");
+        return Ok(());
+    }
+    for (protocol, rate) in &monitor.by_protocol {
+        println!(
+            "{:<5} {}",
+            protocol.as_str().to_uppercase(),
+            format_rate(rate)
+        );
+    }
+
+    println!("Top Ports:");
+    for ((protocol, port), rate) in monitor.by_port.iter().take(TOP_ROWS) {
+        let label = format!("{}/{}", port, protocol.as_str());
+        println!(
+            "  {:<10} {:<10} {}",
+            label,
+            service_name(*protocol, *port).unwrap_or("-"),
+            format_rate(rate)
+        );
+    }
+
+    println!("Top Remote Hosts:");
+    for (host, rate) in monitor.by_host.iter().take(TOP_ROWS) {
+        println!(
+            "  {:<40} {:<32} {}",
+            host.to_string(),
+            monitor.hostname(host).unwrap_or("-"),
+            format_rate(rate)
+        );
+    }
+
+    Ok(())
+}
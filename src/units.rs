@@ -0,0 +1,240 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{anyhow, Result};
+
+// Unit tags for metrics kept in bytes per second and in °C, which are
+// converted when shown
+pub const RATE: &str = "B/s";
+pub const TEMPERATURE: &str = "°C";
+
+const BINARY_NAMES: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+const SI_NAMES: [&str; 6] = ["B", "kB", "MB", "GB", "TB", "PB"];
+
+// Binary units (KiB, MiB, GiB) step by 1024, SI units (kB, MB, GB) by 1000
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    Binary,
+    Si,
+}
+
+impl Units {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "binary" | "iec" => Ok(Units::Binary),
+            "si" | "decimal" => Ok(Units::Si),
+            _ => Err(anyhow!("Invalid units '{}'. Use: binary, si", name)),
+        }
+    }
+
+    fn base(self) -> f64 {
+        match self {
+            Units::Binary => 1024.0,
+            Units::Si => 1000.0,
+        }
+    }
+
+    fn names(self) -> &'static [&'static str; 6] {
+        match self {
+            Units::Binary => &BINARY_NAMES,
+            Units::Si => &SI_NAMES,
+        }
+    }
+}
+
+// Set from the units config key; binary until then
+static SI: AtomicBool = AtomicBool::new(false);
+
+pub fn set_units(name: &str) -> Result<()> {
+    SI.store(Units::parse(name)? == Units::Si, Ordering::Relaxed);
+    Ok(())
+}
+
+pub fn current() -> Units {
+    if SI.load(Ordering::Relaxed) {
+        Units::Si
+    } else {
+        Units::Binary
+    }
+}
+
+// Set from sensor_use_celsius; Celsius until then
+static FAHRENHEIT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_celsius(celsius: bool) {
+    FAHRENHEIT.store(!celsius, Ordering::Relaxed);
+}
+
+// Temperatures are read and stored in °C and only converted for display
+pub fn temperature(celsius: f64) -> f64 {
+    if FAHRENHEIT.load(Ordering::Relaxed) {
+        celsius * 9.0 / 5.0 + 32.0
+    } else {
+        celsius
+    }
+}
+
+pub fn temperature_suffix() -> &'static str {
+    if FAHRENHEIT.load(Ordering::Relaxed) {
+        "°F"
+    } else {
+        "°C"
+    }
+}
+
+// 45.04 -> "45.0°C", or "113.1°F"
+pub fn format_temperature(celsius: f64) -> String {
+    format!("{:.1}{}", temperature(celsius), temperature_suffix())
+}
+
+// Standard gravity, to turn m/s² into g
+pub const STANDARD_GRAVITY: f64 = 9.80665;
+
+// Sensor readings are kept in m/s² and deg/s; these pick what they are shown
+// and exported in. Set from sensor_accel_units and sensor_gyro_units.
+static ACCEL_IN_G: AtomicBool = AtomicBool::new(false);
+static GYRO_IN_RADIANS: AtomicBool = AtomicBool::new(false);
+
+// "m/s2" or "g", the config spelling of an accelerometer unit name
+pub fn parse_acceleration_units(name: &str) -> Result<&'static str> {
+    match name.to_lowercase().as_str() {
+        "m/s2" | "m/s²" | "ms2" | "mps2" => Ok("m/s2"),
+        "g" => Ok("g"),
+        _ => Err(anyhow!(
+            "Invalid acceleration units '{}'. Use: m/s2, g",
+            name
+        )),
+    }
+}
+
+// "deg/s" or "rad/s", the config spelling of a gyro unit name
+pub fn parse_rotation_units(name: &str) -> Result<&'static str> {
+    match name.to_lowercase().as_str() {
+        "deg/s" | "deg" | "°/s" | "dps" => Ok("deg/s"),
+        "rad/s" | "rad" => Ok("rad/s"),
+        _ => Err(anyhow!("Invalid gyro units '{}'. Use: deg/s, rad/s", name)),
+    }
+}
+
+pub fn set_sensor_units(acceleration: &str, rotation: &str) -> Result<()> {
+    ACCEL_IN_G.store(
+        parse_acceleration_units(acceleration)? == "g",
+        Ordering::Relaxed,
+    );
+    GYRO_IN_RADIANS.store(
+        parse_rotation_units(rotation)? == "rad/s",
+        Ordering::Relaxed,
+    );
+    Ok(())
+}
+
+pub fn acceleration(meters_per_second: f64) -> f64 {
+    if ACCEL_IN_G.load(Ordering::Relaxed) {
+        meters_per_second / STANDARD_GRAVITY
+    } else {
+        meters_per_second
+    }
+}
+
+pub fn acceleration_suffix() -> &'static str {
+    if ACCEL_IN_G.load(Ordering::Relaxed) {
+        "g"
+    } else {
+        "m/s²"
+    }
+}
+
+// ASCII names of the current sensor units, for exports
+pub fn sensor_unit_names() -> (&'static str, &'static str) {
+    (
+        if ACCEL_IN_G.load(Ordering::Relaxed) {
+            "g"
+        } else {
+            "m/s2"
+        },
+        if GYRO_IN_RADIANS.load(Ordering::Relaxed) {
+            "rad/s"
+        } else {
+            "deg/s"
+        },
+    )
+}
+
+pub fn angular_rate(degrees_per_second: f64) -> f64 {
+    if GYRO_IN_RADIANS.load(Ordering::Relaxed) {
+        degrees_per_second.to_radians()
+    } else {
+        degrees_per_second
+    }
+}
+
+pub fn angular_rate_suffix() -> &'static str {
+    if GYRO_IN_RADIANS.load(Ordering::Relaxed) {
+        "rad/s"
+    } else {
+        "°/s"
+    }
+}
+
+// One unit to show related numbers in, so "used/total" pairs and table
+// columns line up
+#[derive(Debug, Clone, Copy)]
+pub struct Scale {
+    divisor: f64,
+    pub name: &'static str,
+}
+
+impl Scale {
+    // The largest unit that keeps `bytes` at 1 or more
+    pub fn of(bytes: f64) -> Self {
+        let units = current();
+        let mut power = 0;
+        let mut value = bytes.abs();
+        while value >= units.base() && power < units.names().len() - 1 {
+            value /= units.base();
+            power += 1;
+        }
+        Self::fixed(power)
+    }
+
+    // The unit at `power`: 1 for KiB or kB, 2 for MiB or MB, 3 for GiB or GB
+    pub fn fixed(power: usize) -> Self {
+        let units = current();
+        let power = power.min(units.names().len() - 1);
+        Scale {
+            divisor: units.base().powi(power as i32),
+            name: units.names()[power],
+        }
+    }
+
+    pub fn apply(&self, bytes: f64) -> f64 {
+        bytes / self.divisor
+    }
+}
+
+// 1536 -> "1.50 KiB", with three significant digits
+pub fn format_bytes(bytes: f64) -> String {
+    let scale = Scale::of(bytes);
+    let value = scale.apply(bytes);
+    if scale.divisor == 1.0 {
+        format!("{:.0} {}", value, scale.name)
+    } else if value.abs() < 10.0 {
+        format!("{:.2} {}", value, scale.name)
+    } else if value.abs() < 100.0 {
+        format!("{:.1} {}", value, scale.name)
+    } else {
+        format!("{:.0} {}", value, scale.name)
+    }
+}
+
+pub fn format_rate(bytes_per_second: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_second))
+}
+
+// A metric value with its unit, converting the RATE and TEMPERATURE tags
+pub fn format_metric(value: f64, unit: &str) -> String {
+    match unit {
+        RATE => format_rate(value),
+        TEMPERATURE => format_temperature(value),
+        _ => format!("{:.1}{}", value, unit),
+    }
+}
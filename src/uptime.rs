@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::{Local, TimeZone, Utc};
+use colored::*;
+use log::debug;
+use sysinfo::{System, SystemExt};
+
+use crate::clock;
+use crate::history::{BootRecord, HistoryStore};
+use crate::i18n;
+use crate::process::format_duration;
+use crate::theme::Themed;
+
+// How often the current boot's last-seen time is written to the history store
+const BOOT_WRITE_INTERVAL: Duration = Duration::from_secs(60);
+
+// Keeps the boot history in the history store up to date and caches what the
+// uptime panel shows
+pub struct UptimeTracker {
+    store: HistoryStore,
+    boot_history_len: usize,
+    last_write: Option<Instant>,
+    recent_boots: Vec<BootRecord>,
+    record: Option<BootRecord>,
+}
+
+impl UptimeTracker {
+    pub fn new(boot_history_len: usize) -> Result<Self> {
+        Ok(UptimeTracker {
+            store: HistoryStore::open()?,
+            boot_history_len,
+            last_write: None,
+            recent_boots: Vec::new(),
+            record: None,
+        })
+    }
+
+    pub fn refresh(&mut self, system: &System) {
+        if matches!(self.last_write, Some(last) if last.elapsed() < BOOT_WRITE_INTERVAL) {
+            return;
+        }
+        self.last_write = Some(Instant::now());
+
+        if let Err(e) = self.update(system) {
+            debug!("Failed to update boot history: {}", e);
+        }
+    }
+
+    fn update(&mut self, system: &System) -> Result<()> {
+        self.store
+            .record_boot(system.boot_time() as i64, Utc::now().timestamp())?;
+        self.recent_boots = self.store.recent_boots(self.boot_history_len)?;
+        self.record = self.store.uptime_record()?;
+        Ok(())
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| clock::format(&time))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+// Uptime panel: current uptime against the record, plus the latest boots
+pub fn monitor_uptime(tracker: &UptimeTracker, system: &System) -> Result<()> {
+    let heading = i18n::tr("heading-uptime");
+    println!("\n{}", heading.bold().accent());
+    println!("{}", i18n::underline(heading).accent());
+
+    let uptime = system.uptime();
+    println!(
+        "Current Uptime: {} (since {})",
+        format_duration(uptime).value(),
+        format_timestamp(system.boot_time() as i64)
+    );
+
+    if let Some(record) = tracker.record {
+        if record.boot_time == system.boot_time() as i64 {
+            println!(
+                "Record Uptime:  {}",
+                "this boot is the record!".good().bold()
+            );
+        } else {
+            let remaining = record.uptime_secs().saturating_sub(uptime);
+            println!(
+                "Record Uptime:  {} (boot of {}) - {} to go",
+                format_duration(record.uptime_secs()).warning(),
+                format_timestamp(record.boot_time),
+                format_duration(remaining)
+            );
+        }
+    }
+
+    if !tracker.recent_boots.is_empty() {
+        println!("Recent Boots:");
+        for boot in &tracker.recent_boots {
+            println!(
+                "  {}  up {}",
+                format_timestamp(boot.boot_time),
+                format_duration(boot.uptime_secs())
+            );
+        }
+    }
+
+    Ok(())
+}
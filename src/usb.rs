@@ -0,0 +1,247 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use colored::*;
+use log::info;
+
+use crate::clock;
+use crate::i18n;
+use crate::sensor_devices::SensorDescriptor;
+use crate::sensors::SUPPORTED_SENSORS;
+use crate::theme::Themed;
+
+// Attach and detach events kept for the panel
+const USB_EVENT_HISTORY_LEN: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbDevice {
+    // sysfs name, e.g. "1-1.3"; stable for a port, so a replug is recognised
+    pub port: String,
+    pub bus: u32,
+    pub address: u32,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub name: String,
+    // Negotiated speed in Mbit/s, e.g. "12" or "480"
+    pub speed: String,
+}
+
+impl UsbDevice {
+    fn ids(&self) -> String {
+        format!("{:04x}:{:04x}", self.vendor_id, self.product_id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UsbEvent {
+    pub time: DateTime<Local>,
+    pub attached: bool,
+    pub device: UsbDevice,
+}
+
+// Lists USB devices from sysfs and notes what was plugged in or pulled
+// between refreshes
+pub struct UsbMonitor {
+    devices: Vec<UsbDevice>,
+    events: VecDeque<UsbEvent>,
+    // Vendor and product IDs of supported and configured sensors
+    sensors: Vec<(u16, u16)>,
+    error: Option<String>,
+}
+
+impl UsbMonitor {
+    pub fn new(sensor_devices: &[SensorDescriptor]) -> Self {
+        let mut sensors: Vec<(u16, u16)> = SUPPORTED_SENSORS
+            .iter()
+            .map(|&(vendor_id, product_id, _)| (vendor_id, product_id))
+            .collect();
+        sensors.extend(sensor_devices.iter().map(|d| (d.vendor_id, d.product_id)));
+
+        let mut monitor = UsbMonitor {
+            devices: Vec::new(),
+            events: VecDeque::with_capacity(USB_EVENT_HISTORY_LEN),
+            sensors,
+            error: None,
+        };
+        match read_devices() {
+            Ok(devices) => monitor.devices = devices,
+            Err(e) => monitor.error = Some(e),
+        }
+        monitor
+    }
+
+    pub fn refresh(&mut self) {
+        let devices = match read_devices() {
+            Ok(devices) => devices,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        self.error = None;
+
+        let removed: Vec<UsbDevice> = self
+            .devices
+            .iter()
+            .filter(|old| !devices.contains(old))
+            .cloned()
+            .collect();
+        let added: Vec<UsbDevice> = devices
+            .iter()
+            .filter(|new| !self.devices.contains(new))
+            .cloned()
+            .collect();
+        for device in removed {
+            self.push(false, device);
+        }
+        for device in added {
+            self.push(true, device);
+        }
+        self.devices = devices;
+    }
+
+    fn push(&mut self, attached: bool, device: UsbDevice) {
+        info!(
+            "USB device {} on {}: {} {}",
+            if attached { "attached" } else { "detached" },
+            device.port,
+            device.ids(),
+            device.name
+        );
+        if self.events.len() == USB_EVENT_HISTORY_LEN {
+            self.events.pop_front();
+        }
+        self.events.push_back(UsbEvent {
+            time: Local::now(),
+            attached,
+            device,
+        });
+    }
+
+    pub fn devices(&self) -> &[UsbDevice] {
+        &self.devices
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = &UsbEvent> {
+        self.events.iter().rev()
+    }
+
+    fn is_sensor(&self, device: &UsbDevice) -> bool {
+        self.sensors
+            .contains(&(device.vendor_id, device.product_id))
+    }
+}
+
+// Every device under /sys/bus/usb/devices, skipping the interface entries
+// ("1-1.3:1.0"), ordered by bus and address
+#[cfg(target_os = "linux")]
+fn read_devices() -> Result<Vec<UsbDevice>, String> {
+    let entries = fs::read_dir("/sys/bus/usb/devices")
+        .map_err(|e| format!("Cannot read /sys/bus/usb/devices: {}", e))?;
+    let mut devices: Vec<UsbDevice> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().contains(':'))
+        .filter_map(|entry| read_device(&entry.path()))
+        .collect();
+    devices.sort_by_key(|device| (device.bus, device.address));
+    Ok(devices)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_devices() -> Result<Vec<UsbDevice>, String> {
+    Err("USB device listing is only supported on Linux".to_string())
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn read_device(path: &Path) -> Option<UsbDevice> {
+    let attribute = |name: &str| {
+        fs::read_to_string(path.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+    let hex = |name: &str| u16::from_str_radix(&attribute(name)?, 16).ok();
+
+    // Root hubs have no manufacturer worth showing; fall back to the IDs
+    let name = match (attribute("manufacturer"), attribute("product")) {
+        (Some(manufacturer), Some(product)) => format!("{} {}", manufacturer, product),
+        (None, Some(product)) => product,
+        (Some(manufacturer), None) => manufacturer,
+        (None, None) => "Unknown".to_string(),
+    };
+    Some(UsbDevice {
+        port: path.file_name()?.to_string_lossy().into_owned(),
+        bus: attribute("busnum")?.parse().ok()?,
+        address: attribute("devnum")?.parse().ok()?,
+        vendor_id: hex("idVendor")?,
+        product_id: hex("idProduct")?,
+        name,
+        speed: attribute("speed").unwrap_or_default(),
+    })
+}
+
+fn format_speed(mbps: &str) -> String {
+    match mbps {
+        "1.5" => "1.5M low".to_string(),
+        "12" => "12M full".to_string(),
+        "480" => "480M high".to_string(),
+        "" => "?".to_string(),
+        _ => match mbps.parse::<u32>() {
+            Ok(speed) if speed >= 5000 => format!("{}G super", speed / 1000),
+            _ => format!("{}M", mbps),
+        },
+    }
+}
+
+// USB panel: attached devices, sensors highlighted, then recent changes
+pub fn monitor_usb(monitor: &UsbMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-usb");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    if let Some(error) = &monitor.error {
+        println!("  {}", error.critical());
+        return Ok(());
+    }
+
+    for device in monitor.devices() {
+        let name = if monitor.is_sensor(device) {
+            format!("{} (sensor)", device.name).highlight()
+        } else {
+            device.name.normal()
+        };
+        println!(
+            "  {} {:03}:{:03}  {}  {:<12} {}",
+            device.port.warning(),
+            device.bus,
+            device.address,
+            device.ids().value(),
+            format_speed(&device.speed),
+            name
+        );
+    }
+
+    let mut events = monitor.events().peekable();
+    if events.peek().is_some() {
+        println!("  Recent changes:");
+    }
+    for event in events {
+        let change = if event.attached {
+            "attached".good()
+        } else {
+            "detached".warning()
+        };
+        println!(
+            "    {} {} {} {} {}",
+            clock::time(&event.time),
+            change,
+            event.device.port,
+            event.device.ids(),
+            event.device.name
+        );
+    }
+
+    Ok(())
+}
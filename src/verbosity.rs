@@ -0,0 +1,23 @@
+use std::sync::atomic::{AtomicI8, Ordering};
+
+// How much detail the panels print: -1 with -q, 0 by default, 1 with -v, 2 with -vv
+static LEVEL: AtomicI8 = AtomicI8::new(0);
+
+pub fn set_level(level: i8) {
+    LEVEL.store(level.clamp(-1, 2), Ordering::Relaxed);
+}
+
+// Only the headline figures: no per-core, per-interface total or inode lines
+pub fn quiet() -> bool {
+    LEVEL.load(Ordering::Relaxed) < 0
+}
+
+// Adds clock speeds, memory breakdown, per-disk I/O and packet counters
+pub fn verbose() -> bool {
+    LEVEL.load(Ordering::Relaxed) >= 1
+}
+
+// Also dumps raw sensor reports
+pub fn very_verbose() -> bool {
+    LEVEL.load(Ordering::Relaxed) >= 2
+}
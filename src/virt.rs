@@ -0,0 +1,120 @@
+#[cfg(target_os = "linux")]
+use std::path::Path;
+#[cfg(target_os = "linux")]
+use std::process::Command;
+
+// What Hercules is running inside, detected once at startup
+#[derive(Debug, Clone, Default)]
+pub struct VirtInfo {
+    pub hypervisor: Option<String>,
+    pub container: Option<String>,
+}
+
+impl VirtInfo {
+    pub fn is_virtual_machine(&self) -> bool {
+        self.hypervisor.is_some()
+    }
+
+    pub fn describe(&self) -> String {
+        match (&self.container, &self.hypervisor) {
+            (Some(container), Some(hypervisor)) => {
+                format!("Container ({}) on VM ({})", container, hypervisor)
+            }
+            (Some(container), None) => format!("Container ({})", container),
+            (None, Some(hypervisor)) => format!("Virtual machine ({})", hypervisor),
+            (None, None) => "Bare metal".to_string(),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub fn detect() -> VirtInfo {
+    // systemd knows the most hypervisors; fall back to our own probes without it
+    let systemd = |flag: &str| -> Option<String> {
+        let output = Command::new("systemd-detect-virt")
+            .arg(flag)
+            .output()
+            .ok()?;
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if name.is_empty() || name == "none" {
+            None
+        } else {
+            Some(name)
+        }
+    };
+
+    VirtInfo {
+        hypervisor: systemd("--vm").or_else(detect_hypervisor),
+        container: systemd("--container").or_else(detect_container),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn detect() -> VirtInfo {
+    VirtInfo::default()
+}
+
+#[cfg(target_os = "linux")]
+fn detect_hypervisor() -> Option<String> {
+    let read = |path: &str| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|s| s.trim().to_string())
+    };
+
+    // DMI strings identify most hypervisors and cloud providers
+    let dmi = format!(
+        "{} {}",
+        read("/sys/class/dmi/id/sys_vendor").unwrap_or_default(),
+        read("/sys/class/dmi/id/product_name").unwrap_or_default()
+    );
+    let known = [
+        ("KVM", "kvm"),
+        ("QEMU", "qemu"),
+        ("VMware", "vmware"),
+        ("VirtualBox", "oracle"),
+        ("Xen", "xen"),
+        ("Microsoft Corporation", "microsoft"),
+        ("Amazon EC2", "amazon"),
+        ("Google Compute Engine", "google"),
+        ("Parallels", "parallels"),
+    ];
+    if let Some((_, name)) = known.iter().find(|(marker, _)| dmi.contains(marker)) {
+        return Some(name.to_string());
+    }
+
+    if let Some(kind) = read("/sys/hypervisor/type") {
+        return Some(kind);
+    }
+
+    // The CPU flag is set by every hypervisor, but does not say which one
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let flagged = cpuinfo
+        .lines()
+        .filter(|line| line.starts_with("flags"))
+        .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"));
+    if flagged {
+        Some("unknown".to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn detect_container() -> Option<String> {
+    if Path::new("/.dockerenv").exists() {
+        return Some("docker".to_string());
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("podman".to_string());
+    }
+    if let Ok(container) = std::env::var("container") {
+        return Some(container);
+    }
+
+    let cgroup = std::fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+    ["docker", "kubepods", "lxc", "containerd"]
+        .iter()
+        .find(|name| cgroup.contains(*name))
+        .map(|name| name.to_string())
+}
@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::*;
+use log::debug;
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::i18n;
+use crate::process::format_duration;
+use crate::theme::Themed;
+use crate::units;
+
+// wg and tailscale are external tools, so they are not run on every refresh
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunnelKind {
+    WireGuard,
+    Tailscale,
+    // OpenVPN and other tun/tap based tunnels
+    Tun,
+}
+
+impl TunnelKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TunnelKind::WireGuard => "WireGuard",
+            TunnelKind::Tailscale => "Tailscale",
+            TunnelKind::Tun => "tun/OpenVPN",
+        }
+    }
+}
+
+// The other end of a tunnel
+#[derive(Debug, Clone)]
+pub struct Peer {
+    pub name: String,
+    pub endpoint: Option<String>,
+    // Seconds since the last handshake; None if there never was one
+    pub handshake_age: Option<u64>,
+    pub rx: u64,
+    pub tx: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tunnel {
+    pub interface: String,
+    pub kind: TunnelKind,
+    pub up: bool,
+    pub rx: u64,
+    pub tx: u64,
+    pub peers: Vec<Peer>,
+    // Extra state from the tool, or why it could not be read
+    pub status: Option<String>,
+    // Seconds since the tunnel last received anything
+    pub idle_secs: u64,
+    pub stale: bool,
+}
+
+// Finds WireGuard, Tailscale and tun/tap interfaces and watches their handshakes
+// and traffic, alerting when a tunnel goes quiet for longer than `stale_secs`
+pub struct VpnMonitor {
+    stale_secs: u64,
+    tunnels: Vec<Tunnel>,
+    // Received bytes per interface and when they last changed
+    last_rx: HashMap<String, (u64, Instant)>,
+    last_check: Option<Instant>,
+}
+
+impl VpnMonitor {
+    pub fn new(stale_secs: u64) -> Self {
+        VpnMonitor {
+            stale_secs,
+            tunnels: Vec::new(),
+            last_rx: HashMap::new(),
+            last_check: None,
+        }
+    }
+
+    pub fn tunnels(&self) -> &[Tunnel] {
+        &self.tunnels
+    }
+
+    pub fn refresh(&mut self, alerts: &mut AlertEngine) {
+        if matches!(self.last_check, Some(last) if last.elapsed() < CHECK_INTERVAL) {
+            return;
+        }
+        self.last_check = Some(Instant::now());
+
+        let interfaces = tunnel_interfaces();
+        let needs = |kind| interfaces.iter().any(|(_, k)| *k == kind);
+        let mut wireguard = if needs(TunnelKind::WireGuard) {
+            wireguard_peers()
+        } else {
+            Ok(HashMap::new())
+        };
+        let tailscale = needs(TunnelKind::Tailscale).then(tailscale_status);
+
+        let mut tunnels = Vec::new();
+        for (interface, kind) in interfaces {
+            let (rx, tx) = counters(&interface);
+            let now = Instant::now();
+            let changed = match self.last_rx.get(&interface) {
+                Some(&(previous, at)) if previous == rx => at,
+                _ => now,
+            };
+            self.last_rx.insert(interface.clone(), (rx, changed));
+
+            let mut tunnel = Tunnel {
+                up: is_up(&interface),
+                rx,
+                tx,
+                peers: Vec::new(),
+                status: None,
+                idle_secs: now.duration_since(changed).as_secs(),
+                stale: false,
+                kind,
+                interface,
+            };
+
+            match kind {
+                TunnelKind::WireGuard => match wireguard {
+                    Ok(ref mut peers) => {
+                        tunnel.peers = peers.remove(&tunnel.interface).unwrap_or_default();
+                        // A peer that has not handshaken within the window is unreachable
+                        tunnel.stale = !tunnel.peers.is_empty()
+                            && tunnel.peers.iter().all(|peer| {
+                                peer.handshake_age.is_none_or(|age| age > self.stale_secs)
+                            });
+                    }
+                    // An idle WireGuard tunnel without keepalives sends nothing,
+                    // so without the handshakes there is no telling it is stale
+                    Err(ref reason) => tunnel.status = Some(reason.clone()),
+                },
+                TunnelKind::Tailscale => match tailscale {
+                    Some(Ok((ref state, ref peers))) => {
+                        tunnel.status = Some(state.clone());
+                        tunnel.peers = peers.clone();
+                        tunnel.stale = state != "Running";
+                    }
+                    Some(Err(ref reason)) => {
+                        tunnel.status = Some(reason.clone());
+                        tunnel.stale = tunnel.idle_secs > self.stale_secs;
+                    }
+                    None => {}
+                },
+                // OpenVPN sends keepalives, so a tunnel that receives nothing is dead
+                TunnelKind::Tun => tunnel.stale = tunnel.idle_secs > self.stale_secs,
+            }
+
+            let key = format!("vpn:{}", tunnel.interface);
+            if !tunnel.up {
+                alerts.raise(
+                    &key,
+                    AlertLevel::Critical,
+                    "vpn",
+                    format!(
+                        "{} tunnel {} is down",
+                        tunnel.kind.as_str(),
+                        tunnel.interface
+                    ),
+                );
+            } else if tunnel.stale {
+                alerts.raise(
+                    &key,
+                    AlertLevel::Warning,
+                    "vpn",
+                    format!(
+                        "{} tunnel {} is stale: {}",
+                        tunnel.kind.as_str(),
+                        tunnel.interface,
+                        stale_reason(&tunnel)
+                    ),
+                );
+            } else {
+                alerts.clear(&key);
+            }
+            tunnels.push(tunnel);
+        }
+
+        // Tunnels that disappeared, e.g. wg-quick down
+        for old in &self.tunnels {
+            if !tunnels.iter().any(|t| t.interface == old.interface) {
+                alerts.clear(&format!("vpn:{}", old.interface));
+            }
+        }
+        self.tunnels = tunnels;
+    }
+}
+
+fn stale_reason(tunnel: &Tunnel) -> String {
+    match tunnel.kind {
+        TunnelKind::WireGuard if !tunnel.peers.is_empty() => {
+            match tunnel
+                .peers
+                .iter()
+                .filter_map(|peer| peer.handshake_age)
+                .min()
+            {
+                Some(age) => format!("last handshake {} ago", format_duration(age)),
+                None => "no handshake yet".to_string(),
+            }
+        }
+        TunnelKind::Tailscale if tunnel.status.is_some() => {
+            format!("state {}", tunnel.status.as_deref().unwrap_or_default())
+        }
+        _ => format!("nothing received for {}", format_duration(tunnel.idle_secs)),
+    }
+}
+
+// Tunnel interfaces by kind, from sysfs
+#[cfg(target_os = "linux")]
+fn tunnel_interfaces() -> Vec<(String, TunnelKind)> {
+    let Ok(entries) = fs::read_dir("/sys/class/net") else {
+        return Vec::new();
+    };
+    let mut interfaces: Vec<(String, TunnelKind)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let path = entry.path();
+            let uevent = fs::read_to_string(path.join("uevent")).unwrap_or_default();
+            let kind = if uevent.contains("DEVTYPE=wireguard") {
+                TunnelKind::WireGuard
+            } else if name.starts_with("tailscale") {
+                TunnelKind::Tailscale
+            } else if path.join("tun_flags").exists() {
+                // wireguard-go runs as a tun device too
+                if name.starts_with("wg") {
+                    TunnelKind::WireGuard
+                } else {
+                    TunnelKind::Tun
+                }
+            } else {
+                return None;
+            };
+            Some((name, kind))
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.0.cmp(&b.0));
+    interfaces
+}
+
+#[cfg(not(target_os = "linux"))]
+fn tunnel_interfaces() -> Vec<(String, TunnelKind)> {
+    Vec::new()
+}
+
+fn read_sys(interface: &str, file: &str) -> Option<String> {
+    fs::read_to_string(Path::new("/sys/class/net").join(interface).join(file))
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+fn counters(interface: &str) -> (u64, u64) {
+    let read = |file| {
+        read_sys(interface, file)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0)
+    };
+    (read("statistics/rx_bytes"), read("statistics/tx_bytes"))
+}
+
+// Tunnels report "unknown" as their operstate, so the IFF_UP flag decides
+fn is_up(interface: &str) -> bool {
+    read_sys(interface, "flags")
+        .and_then(|flags| u32::from_str_radix(flags.trim_start_matches("0x"), 16).ok())
+        .is_some_and(|flags| flags & 0x1 != 0)
+}
+
+// Peers of every WireGuard interface from `wg show all dump`
+fn wireguard_peers() -> std::result::Result<HashMap<String, Vec<Peer>>, String> {
+    let output = Command::new("wg")
+        .args(["show", "all", "dump"])
+        .output()
+        .map_err(|_| "install wireguard-tools for peer details".to_string())?;
+    if !output.status.success() {
+        debug!(
+            "wg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Err("run as root for peer details".to_string());
+    }
+
+    let now = Utc::now().timestamp();
+    let mut peers: HashMap<String, Vec<Peer>> = HashMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        // Interface lines have 5 fields, peer lines 9:
+        // interface, public key, preshared key, endpoint, allowed ips,
+        // latest handshake, rx, tx, keepalive
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 9 {
+            continue;
+        }
+        let handshake: i64 = fields[5].parse().unwrap_or(0);
+        peers.entry(fields[0].to_string()).or_default().push(Peer {
+            name: short_key(fields[1]),
+            endpoint: (fields[3] != "(none)").then(|| fields[3].to_string()),
+            handshake_age: (handshake > 0).then(|| (now - handshake).max(0) as u64),
+            rx: fields[6].parse().unwrap_or(0),
+            tx: fields[7].parse().unwrap_or(0),
+        });
+    }
+    Ok(peers)
+}
+
+fn short_key(key: &str) -> String {
+    if key.len() > 8 {
+        format!("{}...", &key[..8])
+    } else {
+        key.to_string()
+    }
+}
+
+// Backend state and peers with a recent connection from `tailscale status --json`
+fn tailscale_status() -> std::result::Result<(String, Vec<Peer>), String> {
+    let output = Command::new("tailscale")
+        .args(["status", "--json"])
+        .output()
+        .map_err(|_| "tailscale CLI not found".to_string())?;
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|_| "tailscaled is not responding".to_string())?;
+    let state = status["BackendState"]
+        .as_str()
+        .unwrap_or("Unknown")
+        .to_string();
+
+    let now = Utc::now();
+    let mut peers: Vec<Peer> = status["Peer"]
+        .as_object()
+        .map(|peers| {
+            peers
+                .values()
+                .filter(|peer| peer["Online"].as_bool().unwrap_or(false))
+                .map(|peer| Peer {
+                    name: peer["HostName"].as_str().unwrap_or("?").to_string(),
+                    endpoint: peer["CurAddr"]
+                        .as_str()
+                        .filter(|addr| !addr.is_empty())
+                        .map(str::to_string)
+                        .or_else(|| {
+                            peer["Relay"]
+                                .as_str()
+                                .filter(|relay| !relay.is_empty())
+                                .map(|relay| format!("relay {}", relay))
+                        }),
+                    // The zero time "0001-01-01T00:00:00Z" means never
+                    handshake_age: peer["LastHandshake"]
+                        .as_str()
+                        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                        .filter(|time| time.timestamp() > 0)
+                        .map(|time| (now - time.with_timezone(&Utc)).num_seconds().max(0) as u64),
+                    rx: peer["RxBytes"].as_u64().unwrap_or(0),
+                    tx: peer["TxBytes"].as_u64().unwrap_or(0),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    peers.sort_by_key(|peer| peer.handshake_age.unwrap_or(u64::MAX));
+    Ok((state, peers))
+}
+
+// VPN panel: one line per tunnel, then its peers
+pub fn monitor_vpn(monitor: &VpnMonitor) -> Result<()> {
+    let heading = i18n::tr("heading-vpn");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    if monitor.tunnels().is_empty() {
+        println!("No WireGuard, Tailscale or tun/tap interfaces found");
+        return Ok(());
+    }
+
+    for tunnel in monitor.tunnels() {
+        let state = if !tunnel.up {
+            "DOWN".critical()
+        } else if tunnel.stale {
+            "STALE".warning()
+        } else {
+            "UP".good()
+        };
+        println!(
+            "{} ({}): {} - rx {}, tx {}{}",
+            tunnel.interface.warning(),
+            tunnel.kind.as_str(),
+            state,
+            units::format_bytes(tunnel.rx as f64).value(),
+            units::format_bytes(tunnel.tx as f64).value(),
+            tunnel
+                .status
+                .as_ref()
+                .map(|status| format!(" ({})", status))
+                .unwrap_or_default()
+        );
+        if tunnel.kind == TunnelKind::Tun || (tunnel.stale && tunnel.peers.is_empty()) {
+            println!("  Last received: {} ago", format_duration(tunnel.idle_secs));
+        }
+
+        for peer in &tunnel.peers {
+            let handshake = match peer.handshake_age {
+                Some(age) if age > monitor.stale_secs => {
+                    format!("{} ago", format_duration(age)).warning()
+                }
+                Some(age) => format!("{} ago", format_duration(age)).good(),
+                None => "never".warning(),
+            };
+            println!(
+                "  {} {} - handshake {}, rx {}, tx {}",
+                peer.name,
+                peer.endpoint.as_deref().unwrap_or("(no endpoint)"),
+                handshake,
+                units::format_bytes(peer.rx as f64),
+                units::format_bytes(peer.tx as f64)
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,351 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use colored::*;
+use log::{debug, info, warn};
+use sysinfo::{ProcessExt, System, SystemExt};
+
+use crate::alerts::{AlertEngine, AlertLevel};
+use crate::clock;
+use crate::history::HistoryStore;
+use crate::i18n;
+use crate::process::format_duration;
+use crate::theme::Themed;
+
+// systemctl is not asked on every refresh
+const UNIT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+// Wait after the first restart, doubled per attempt up to the maximum
+const INITIAL_BACKOFF: Duration = Duration::from_secs(10);
+const MAX_BACKOFF: Duration = Duration::from_secs(600);
+
+// A target that stays up this long starts again from the initial backoff
+const HEALTHY_RESET: Duration = Duration::from_secs(300);
+
+// How many events the panel lists
+const RECENT_EVENTS: usize = 5;
+const EVENT_LOG_LEN: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Health {
+    // Running processes with that name, or an active unit
+    Running(usize),
+    Missing,
+    // The unit's state from systemctl, e.g. "failed" or "inactive"
+    Down(String),
+}
+
+// A process name or systemd unit being supervised
+pub struct Target {
+    pub name: String,
+    restart: Option<String>,
+    pub health: Health,
+    // When the current outage or healthy period began
+    since: Instant,
+    pub restarts: u32,
+    next_restart: Option<Instant>,
+    last_unit_check: Option<Instant>,
+}
+
+impl Target {
+    fn is_unit(&self) -> bool {
+        is_unit(&self.name)
+    }
+
+    pub fn is_up(&self) -> bool {
+        matches!(self.health, Health::Running(_))
+    }
+
+    // How long the target has been in its current state
+    pub fn state_for(&self) -> Duration {
+        self.since.elapsed()
+    }
+
+    // Time until the next restart attempt, if one is pending
+    pub fn retry_in(&self) -> Option<Duration> {
+        self.next_restart
+            .map(|at| at.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn can_restart(&self) -> bool {
+        self.restart.is_some()
+    }
+}
+
+// Watches configured processes and systemd units and restarts them with
+// backoff when they disappear or fail
+pub struct Watchdog {
+    targets: Vec<Target>,
+    events: VecDeque<(DateTime<Local>, String)>,
+    store: Option<HistoryStore>,
+}
+
+impl Watchdog {
+    // Entries are "name" or "name=restart command". With `restart` set, units
+    // without a command are restarted through systemctl.
+    pub fn new(entries: &[String], restart: bool) -> Self {
+        let targets = entries
+            .iter()
+            .map(|entry| {
+                let (name, command) = match entry.split_once('=') {
+                    Some((name, command)) => (name.trim(), Some(command.trim().to_string())),
+                    None => (entry.trim(), None),
+                };
+                let restart = match command {
+                    Some(command) if restart && !command.is_empty() => Some(command),
+                    None if restart && is_unit(name) => Some(format!("systemctl restart {}", name)),
+                    _ => None,
+                };
+                Target {
+                    name: name.to_string(),
+                    restart,
+                    health: Health::Running(0),
+                    since: Instant::now(),
+                    restarts: 0,
+                    next_restart: None,
+                    last_unit_check: None,
+                }
+            })
+            .collect();
+
+        Watchdog {
+            targets,
+            events: VecDeque::with_capacity(EVENT_LOG_LEN),
+            store: HistoryStore::open()
+                .map_err(|e| debug!("Watchdog event history disabled: {}", e))
+                .ok(),
+        }
+    }
+
+    pub fn targets(&self) -> &[Target] {
+        &self.targets
+    }
+
+    // Newest first
+    pub fn recent_events(&self) -> impl Iterator<Item = &(DateTime<Local>, String)> {
+        self.events.iter().rev().take(RECENT_EVENTS)
+    }
+
+    pub fn refresh(&mut self, system: &System, alerts: &mut AlertEngine) {
+        let mut events = Vec::new();
+        for target in &mut self.targets {
+            let health = if target.is_unit() {
+                if matches!(target.last_unit_check, Some(last) if last.elapsed() < UNIT_CHECK_INTERVAL)
+                {
+                    target.health.clone()
+                } else {
+                    target.last_unit_check = Some(Instant::now());
+                    unit_health(&target.name)
+                }
+            } else {
+                process_health(system, &target.name)
+            };
+
+            let was_up = target.is_up();
+            let changed = was_up != matches!(health, Health::Running(_));
+            target.health = health;
+            if changed {
+                target.since = Instant::now();
+                if target.is_up() {
+                    target.next_restart = None;
+                    events.push(format!("{} is running again", target.name));
+                } else {
+                    events.push(format!("{} is {}", target.name, describe(&target.health)));
+                    // The first restart is tried straight away, repeat failures back off
+                    target.next_restart = target
+                        .restart
+                        .as_ref()
+                        .map(|_| Instant::now() + backoff(target.restarts));
+                }
+            }
+
+            let key = format!("watchdog:{}", target.name);
+            if target.is_up() {
+                alerts.clear(&key);
+                if target.restarts > 0 && target.state_for() >= HEALTHY_RESET {
+                    target.restarts = 0;
+                }
+                continue;
+            }
+            alerts.raise(
+                &key,
+                AlertLevel::Critical,
+                "watchdog",
+                format!("{} is {}", target.name, describe(&target.health)),
+            );
+
+            let (Some(command), Some(at)) = (target.restart.as_ref(), target.next_restart) else {
+                continue;
+            };
+            if Instant::now() < at {
+                continue;
+            }
+            target.restarts += 1;
+            match run_restart(command) {
+                Ok(()) => events.push(format!(
+                    "Restarting {} (attempt {}): {}",
+                    target.name, target.restarts, command
+                )),
+                Err(e) => events.push(format!("Could not restart {}: {}", target.name, e)),
+            }
+            target.next_restart = Some(Instant::now() + backoff(target.restarts));
+        }
+
+        for event in events {
+            self.log(event);
+        }
+    }
+
+    fn log(&mut self, message: String) {
+        info!("Watchdog: {}", message);
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.record_event("watchdog", &message) {
+                debug!("Failed to record watchdog event: {}", e);
+            }
+        }
+        if self.events.len() == EVENT_LOG_LEN {
+            self.events.pop_front();
+        }
+        self.events.push_back((Local::now(), message));
+    }
+}
+
+// Wait before the next restart after `restarts` attempts
+fn backoff(restarts: u32) -> Duration {
+    match restarts {
+        0 => Duration::ZERO,
+        n => INITIAL_BACKOFF
+            .saturating_mul(1 << (n - 1).min(16))
+            .min(MAX_BACKOFF),
+    }
+}
+
+// Anything with a systemd unit suffix is treated as a unit
+fn is_unit(name: &str) -> bool {
+    [".service", ".socket", ".timer", ".target", ".mount"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+// Matches the process name, or the executable's file name since the kernel
+// truncates process names to 15 characters
+fn process_health(system: &System, name: &str) -> Health {
+    let count = system
+        .processes()
+        .values()
+        .filter(|process| {
+            process.name() == name
+                || process.exe().file_name().is_some_and(|file| file == name)
+                || process
+                    .cmd()
+                    .first()
+                    .and_then(|arg| Path::new(arg).file_name())
+                    .is_some_and(|file| file == name)
+        })
+        .count();
+    if count == 0 {
+        Health::Missing
+    } else {
+        Health::Running(count)
+    }
+}
+
+fn unit_health(unit: &str) -> Health {
+    match Command::new("systemctl").args(["is-active", unit]).output() {
+        Ok(output) => {
+            let state = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            match state.as_str() {
+                "active" | "reloading" | "activating" => Health::Running(1),
+                "" => Health::Missing,
+                _ => Health::Down(state),
+            }
+        }
+        Err(e) => {
+            debug!("systemctl is-active {} failed: {}", unit, e);
+            Health::Missing
+        }
+    }
+}
+
+fn describe(health: &Health) -> String {
+    match health {
+        Health::Running(_) => "running".to_string(),
+        Health::Missing => "not running".to_string(),
+        Health::Down(state) => state.clone(),
+    }
+}
+
+// Starts the restart command through the shell without waiting for it, since
+// the command may be the long-running process itself
+fn run_restart(command: &str) -> Result<()> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut child = Command::new(shell)
+        .args([flag, command])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Reap the child so it does not linger as a zombie
+    let command = command.to_string();
+    thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            warn!("Watchdog restart '{}' exited with {}", command, status)
+        }
+        Err(e) => warn!("Watchdog restart '{}' failed: {}", command, e),
+        _ => {}
+    });
+    Ok(())
+}
+
+// Watchdog panel: one line per target, then the latest events
+pub fn monitor_watchdog(watchdog: &Watchdog) -> Result<()> {
+    let heading = i18n::tr("heading-watchdog");
+    println!("\n{}", heading.bold().good());
+    println!("{}", i18n::underline(heading).good());
+
+    for target in watchdog.targets() {
+        let state = match target.health {
+            Health::Running(count) if target.is_unit() || count == 1 => "running".good(),
+            Health::Running(count) => format!("running ({} processes)", count).good(),
+            ref health => describe(health).to_uppercase().critical().bold(),
+        };
+        let mut detail = format!("for {}", format_duration(target.state_for().as_secs()));
+        if target.restarts > 0 {
+            detail.push_str(&format!(
+                ", {} restart{}",
+                target.restarts,
+                if target.restarts == 1 { "" } else { "s" }
+            ));
+        }
+        if !target.is_up() {
+            match target.retry_in() {
+                Some(wait) => detail.push_str(&format!(
+                    ", next restart in {}",
+                    format_duration(wait.as_secs())
+                )),
+                None if !target.can_restart() => detail.push_str(", no restart command"),
+                None => {}
+            }
+        }
+        println!("{:<24} {:<24} {}", target.name, state, detail.value());
+    }
+
+    let mut events = watchdog.recent_events().peekable();
+    if events.peek().is_some() {
+        println!("Recent events:");
+        for (time, message) in events {
+            println!("  {} {}", clock::time(time).value(), message);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+#[cfg(any(target_os = "linux", windows))]
+use std::process::Command;
+
+// Link details for a wireless interface; fields are None when the driver or
+// tool does not report them (e.g. while disconnected)
+#[derive(Debug, Clone, Default)]
+pub struct WifiInfo {
+    pub ssid: Option<String>,
+    pub signal_dbm: Option<i32>,
+    pub link_quality: Option<u32>,
+    pub bitrate_mbps: Option<f64>,
+    pub channel: Option<u32>,
+    pub frequency_mhz: Option<u32>,
+}
+
+impl WifiInfo {
+    pub fn is_connected(&self) -> bool {
+        self.ssid.is_some()
+    }
+}
+
+// Wireless details for an interface, or None if it is not a Wi-Fi interface
+#[cfg(target_os = "linux")]
+pub fn wifi_info(interface: &str) -> Option<WifiInfo> {
+    let wireless_stats = proc_net_wireless(interface);
+    let is_wireless = std::path::Path::new("/sys/class/net")
+        .join(interface)
+        .join("wireless")
+        .exists();
+    if !is_wireless && wireless_stats.is_none() {
+        return None;
+    }
+
+    let mut info = WifiInfo::default();
+    if let Some((quality, level)) = wireless_stats {
+        // Most drivers report quality out of 70
+        info.link_quality = Some((quality * 100 / 70).min(100));
+        info.signal_dbm = Some(level);
+    }
+
+    // `iw` talks nl80211 and knows the SSID, bitrate and frequency
+    if let Ok(output) = Command::new("iw").args(["dev", interface, "link"]).output() {
+        parse_iw_link(&String::from_utf8_lossy(&output.stdout), &mut info);
+    }
+
+    if info.link_quality.is_none() {
+        info.link_quality = info.signal_dbm.map(quality_from_dbm);
+    }
+    info.channel = info.frequency_mhz.and_then(channel_from_frequency);
+
+    Some(info)
+}
+
+// Quality and signal level columns from /proc/net/wireless
+#[cfg(target_os = "linux")]
+fn proc_net_wireless(interface: &str) -> Option<(u32, i32)> {
+    let content = std::fs::read_to_string("/proc/net/wireless").ok()?;
+    content.lines().skip(2).find_map(|line| {
+        let (name, rest) = line.split_once(':')?;
+        if name.trim() != interface {
+            return None;
+        }
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        let quality = fields.get(1)?.trim_end_matches('.').parse::<f64>().ok()?;
+        let level = fields.get(2)?.trim_end_matches('.').parse::<f64>().ok()?;
+        Some((quality as u32, level as i32))
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn parse_iw_link(output: &str, info: &mut WifiInfo) {
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(ssid) = line.strip_prefix("SSID:") {
+            info.ssid = Some(ssid.trim().to_string());
+        } else if let Some(freq) = line.strip_prefix("freq:") {
+            info.frequency_mhz = freq.trim().parse::<f64>().ok().map(|freq| freq as u32);
+        } else if let Some(signal) = line.strip_prefix("signal:") {
+            info.signal_dbm = signal
+                .split_whitespace()
+                .next()
+                .and_then(|dbm| dbm.parse().ok())
+                .or(info.signal_dbm);
+        } else if let Some(bitrate) = line.strip_prefix("tx bitrate:") {
+            info.bitrate_mbps = bitrate
+                .split_whitespace()
+                .next()
+                .and_then(|rate| rate.parse().ok());
+        }
+    }
+}
+
+#[cfg(windows)]
+pub fn wifi_info(interface: &str) -> Option<WifiInfo> {
+    // netsh wraps the WLAN API and lists every wireless interface
+    let output = Command::new("netsh")
+        .args(["wlan", "show", "interfaces"])
+        .output()
+        .ok()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+
+    let mut info = WifiInfo::default();
+    let mut found = false;
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key == "Name" {
+            if found {
+                break;
+            }
+            found = value.eq_ignore_ascii_case(interface);
+            continue;
+        }
+        if !found {
+            continue;
+        }
+        match key {
+            "SSID" => info.ssid = Some(value.to_string()),
+            "Channel" => info.channel = value.parse().ok(),
+            "Receive rate (Mbps)" => info.bitrate_mbps = value.parse().ok(),
+            "Signal" => {
+                let percent = value.trim_end_matches('%').parse::<u32>().ok();
+                info.link_quality = percent;
+                // Windows maps -100..-50 dBm linearly onto 0..100%
+                info.signal_dbm = percent.map(|p| p as i32 / 2 - 100);
+            }
+            _ => {}
+        }
+    }
+
+    if found {
+        Some(info)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn wifi_info(_interface: &str) -> Option<WifiInfo> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn quality_from_dbm(dbm: i32) -> u32 {
+    ((dbm + 100) * 2).clamp(0, 100) as u32
+}
+
+#[cfg(target_os = "linux")]
+fn channel_from_frequency(mhz: u32) -> Option<u32> {
+    match mhz {
+        2484 => Some(14),
+        2412..=2472 => Some((mhz - 2407) / 5),
+        5000..=5895 => Some((mhz - 5000) / 5),
+        5955..=7115 => Some((mhz - 5950) / 5),
+        _ => None,
+    }
+}